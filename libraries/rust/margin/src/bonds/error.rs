@@ -8,6 +8,12 @@ pub enum BondsIxError {
     #[error("Failed to insert key: ({0})")]
     FailedInsert(String),
 
+    #[error("Invalid order rate: ({0})")]
+    InvalidOrderRate(String),
+
+    #[error("Failed to decode orderbook: ({0})")]
+    Orderbook(String),
+
     #[error("Client error: ({msg})")]
     Client { msg: String },
 }
@@ -13,15 +13,19 @@ use spl_associated_token_account::get_associated_token_address;
 pub use jet_bonds::{
     control::{
         instructions::{InitializeBondManagerParams, InitializeOrderbookParams},
-        state::BondManager,
+        state::{BondManager, CrankPolicy},
+    },
+    margin::instructions::MarginReplaceOrdersParams,
+    orderbook::{
+        instructions::ReplaceOrdersParams,
+        state::{event_queue_len, orderbook_slab_len, OrderParams},
     },
-    orderbook::state::{event_queue_len, orderbook_slab_len, OrderParams},
     ID,
 };
 
-use crate::ix_builder::get_metadata_address;
+use crate::ix_builder::{derive_permit, get_metadata_address};
 
-use super::event_builder::make_seed;
+use super::{event_builder::make_seed, market_order::MarketOrder, orderbook::Side};
 
 use super::error::{client_err, BondsIxError, Result};
 
@@ -145,6 +149,11 @@ impl BondsIxBuilder {
         self
     }
 
+    pub fn with_authority(mut self, authority: &Pubkey) -> Self {
+        self.authority = *authority;
+        self
+    }
+
     pub fn with_crank(mut self, crank: &Pubkey) -> Self {
         self.keys.insert("crank", *crank);
         self
@@ -235,7 +244,7 @@ impl BondsIxBuilder {
             underlying_token_vault: self.underlying_token_vault,
             orderbook_market_state: self.orderbook_market_state,
             event_queue: self.keys.unwrap("event_queue")?,
-            crank_authorization: crank_authorization(&self.keys.unwrap("crank")?),
+            crank_authorization: crank_authorization(&self.airspace, &self.keys.unwrap("crank")?),
             crank: self.keys.unwrap("crank")?,
             payer: self.keys.unwrap("payer")?,
             system_program: solana_sdk::system_program::ID,
@@ -345,7 +354,13 @@ impl BondsIxBuilder {
         Ok(Instruction::new_with_bytes(jet_bonds::ID, &data, accounts))
     }
 
-    pub fn initialize_margin_user(&self, owner: Pubkey) -> Result<Instruction> {
+    /// `owner` is the margin account, and `permit_owner` the owner of the margin account,
+    /// which must hold a permit for the market's airspace
+    pub fn initialize_margin_user(
+        &self,
+        owner: Pubkey,
+        permit_owner: Pubkey,
+    ) -> Result<Instruction> {
         let borrower_account = self.margin_user_account(owner);
         let accounts = jet_bonds::accounts::InitializeMarginUser {
             bond_manager: self.manager,
@@ -365,6 +380,7 @@ impl BondsIxBuilder {
             token_program: spl_token::ID,
             system_program: solana_sdk::system_program::ID,
             claims_metadata: get_metadata_address(&self.claims),
+            permit: derive_permit(&self.airspace, &permit_owner),
         }
         .to_account_metas(None);
         Ok(Instruction::new_with_bytes(
@@ -583,6 +599,41 @@ impl BondsIxBuilder {
         Ok(Instruction::new_with_bytes(jet_bonds::ID, &data, accounts))
     }
 
+    /// Lend at no less than the order's limit rate, cancelling anything that doesn't match immediately
+    pub fn lend_market_order(
+        &self,
+        user: Pubkey,
+        ticket_vault: Option<Pubkey>,
+        token_vault: Option<Pubkey>,
+        order: MarketOrder,
+        seed: Vec<u8>,
+    ) -> Result<Instruction> {
+        let params = order.params(Side::Bid)?;
+        self.lend_order(user, ticket_vault, token_vault, params, seed)
+    }
+
+    /// Sell tickets at no more than the order's limit rate, cancelling anything that doesn't match immediately
+    pub fn sell_tickets_market_order(
+        &self,
+        user: Pubkey,
+        ticket_vault: Option<Pubkey>,
+        token_vault: Option<Pubkey>,
+        order: MarketOrder,
+    ) -> Result<Instruction> {
+        let params = order.params(Side::Ask)?;
+        self.sell_tickets_order(user, ticket_vault, token_vault, params)
+    }
+
+    /// Borrow at no more than the order's limit rate, cancelling anything that doesn't match immediately
+    pub fn margin_borrow_market_order(
+        &self,
+        user: Pubkey,
+        order: MarketOrder,
+    ) -> Result<Instruction> {
+        let params = order.params(Side::Ask)?;
+        self.margin_borrow_order(user, params)
+    }
+
     pub fn cancel_order(&self, owner: Pubkey, order_id: u128) -> Result<Instruction> {
         let data = jet_bonds::instruction::CancelOrder { order_id }.data();
         let accounts = jet_bonds::accounts::CancelOrder {
@@ -594,6 +645,116 @@ impl BondsIxBuilder {
         Ok(Instruction::new_with_bytes(jet_bonds::ID, &data, accounts))
     }
 
+    /// Cancel all of an owner's orders, returning the posted funds
+    ///
+    /// `out_accounts` must contain the `out_account` of every order on the book owned by `owner`,
+    /// and the claims account of the `MarginUser` if it has borrow orders, see [`Self::margin_user_claims`]
+    pub fn cancel_all_orders(&self, owner: Pubkey, out_accounts: &[Pubkey]) -> Result<Instruction> {
+        let data = jet_bonds::instruction::CancelAllOrders {}.data();
+        let mut accounts = jet_bonds::accounts::CancelAllOrders {
+            owner,
+            orderbook_mut: self.orderbook_mut()?,
+            underlying_token_vault: self.underlying_token_vault,
+            bond_ticket_mint: self.bond_ticket_mint,
+            claims_mint: self.claims,
+            token_program: spl_token::ID,
+        }
+        .to_account_metas(None);
+        accounts.extend(
+            out_accounts
+                .iter()
+                .map(|out_account| AccountMeta::new(*out_account, false)),
+        );
+
+        Ok(Instruction::new_with_bytes(jet_bonds::ID, &data, accounts))
+    }
+
+    /// Cancel a set of orders and post a new set of post-only orders in a single instruction
+    pub fn replace_orders(
+        &self,
+        user: Pubkey,
+        ticket_vault: Option<Pubkey>,
+        token_vault: Option<Pubkey>,
+        params: ReplaceOrdersParams,
+    ) -> Result<Instruction> {
+        let user_ticket_vault = match ticket_vault {
+            Some(vault) => vault,
+            None => get_associated_token_address(&user, &self.bond_ticket_mint),
+        };
+        let user_token_vault = match token_vault {
+            Some(vault) => vault,
+            None => get_associated_token_address(&user, &self.keys.unwrap("underlying_mint")?),
+        };
+        let data = jet_bonds::instruction::ReplaceOrders { params }.data();
+        let accounts = jet_bonds::accounts::ReplaceOrders {
+            user,
+            user_ticket_vault,
+            user_token_vault,
+            orderbook_mut: self.orderbook_mut()?,
+            underlying_token_vault: self.underlying_token_vault,
+            bond_ticket_mint: self.bond_ticket_mint,
+            token_program: spl_token::ID,
+        }
+        .to_account_metas(None);
+
+        Ok(Instruction::new_with_bytes(jet_bonds::ID, &data, accounts))
+    }
+
+    /// Cancel a set of margin borrow orders and post a new set of post-only borrow orders
+    pub fn margin_replace_orders(
+        &self,
+        user: Pubkey,
+        params: MarginReplaceOrdersParams,
+    ) -> Result<Instruction> {
+        let borrower_account = bonds_pda(&[
+            jet_bonds::seeds::MARGIN_BORROWER,
+            self.manager.as_ref(),
+            user.as_ref(),
+        ]);
+        let data = jet_bonds::instruction::MarginReplaceOrders { params }.data();
+        let accounts = jet_bonds::accounts::MarginReplaceOrders {
+            borrower_account,
+            margin_account: user,
+            claims: bonds_pda(&[jet_bonds::seeds::CLAIM_NOTES, borrower_account.as_ref()]),
+            claims_mint: self.claims,
+            orderbook_mut: self.orderbook_mut()?,
+            token_program: spl_token::ID,
+        }
+        .to_account_metas(None);
+
+        Ok(Instruction::new_with_bytes(jet_bonds::ID, &data, accounts))
+    }
+
+    /// Remove orders past their good-til-time from the book
+    ///
+    /// `out_accounts` must contain the `out_account` of every pruned order, and the claims
+    /// account of each `MarginUser` with a pruned borrow order, see [`Self::margin_user_claims`]
+    pub fn prune_expired_orders(
+        &self,
+        order_ids: &[u128],
+        out_accounts: &[Pubkey],
+    ) -> Result<Instruction> {
+        let data = jet_bonds::instruction::PruneExpiredOrders {
+            order_ids: order_ids.to_vec(),
+        }
+        .data();
+        let mut accounts = jet_bonds::accounts::PruneExpiredOrders {
+            orderbook_mut: self.orderbook_mut()?,
+            underlying_token_vault: self.underlying_token_vault,
+            bond_ticket_mint: self.bond_ticket_mint,
+            claims_mint: self.claims,
+            token_program: spl_token::ID,
+        }
+        .to_account_metas(None);
+        accounts.extend(
+            out_accounts
+                .iter()
+                .map(|out_account| AccountMeta::new(*out_account, false)),
+        );
+
+        Ok(Instruction::new_with_bytes(jet_bonds::ID, &data, accounts))
+    }
+
     pub fn pause_order_matching(&self) -> Result<Instruction> {
         let data = jet_bonds::instruction::PauseOrderMatching {}.data();
         let accounts = jet_bonds::accounts::PauseOrderMatching {
@@ -624,28 +785,95 @@ impl BondsIxBuilder {
     }
 
     pub fn pause_ticket_redemption(&self) -> Result<Instruction> {
-        self.modify_manager([true as u8].into(), 8 + 32 * 13 + 2)
+        self.set_tickets_paused(true)
     }
     pub fn resume_ticket_redemption(&self) -> Result<Instruction> {
-        self.modify_manager([false as u8].into(), 8 + 32 * 13 + 2)
+        self.set_tickets_paused(false)
+    }
+
+    pub fn set_tickets_paused(&self, paused: bool) -> Result<Instruction> {
+        let data = jet_bonds::instruction::SetTicketsPaused { paused }.data();
+        let accounts = self.modify_manager_accounts().to_account_metas(None);
+        Ok(Instruction::new_with_bytes(jet_bonds::ID, &data, accounts))
     }
 
-    pub fn modify_manager(&self, data: Vec<u8>, offset: usize) -> Result<Instruction> {
-        let data = jet_bonds::instruction::ModifyBondManager { data, offset }.data();
-        let accounts = jet_bonds::accounts::ModifyBondManager {
+    pub fn set_underlying_oracle(&self, oracle: Pubkey) -> Result<Instruction> {
+        let data = jet_bonds::instruction::SetUnderlyingOracle {}.data();
+        let accounts = jet_bonds::accounts::SetOracle {
+            modify: self.modify_manager_accounts(),
+            oracle,
+        }
+        .to_account_metas(None);
+        Ok(Instruction::new_with_bytes(jet_bonds::ID, &data, accounts))
+    }
+
+    pub fn set_ticket_oracle(&self, oracle: Pubkey) -> Result<Instruction> {
+        let data = jet_bonds::instruction::SetTicketOracle {}.data();
+        let accounts = jet_bonds::accounts::SetOracle {
+            modify: self.modify_manager_accounts(),
+            oracle,
+        }
+        .to_account_metas(None);
+        Ok(Instruction::new_with_bytes(jet_bonds::ID, &data, accounts))
+    }
+
+    /// Change the tenor for orders filled after this instruction, in seconds
+    pub fn set_duration(&self, duration: i64) -> Result<Instruction> {
+        let data = jet_bonds::instruction::SetDuration { duration }.data();
+        let accounts = self.modify_manager_accounts().to_account_metas(None);
+        Ok(Instruction::new_with_bytes(jet_bonds::ID, &data, accounts))
+    }
+
+    pub fn set_crank_policy(&self, policy: CrankPolicy) -> Result<Instruction> {
+        let data = jet_bonds::instruction::SetCrankPolicy { policy }.data();
+        let accounts = self.modify_manager_accounts().to_account_metas(None);
+        Ok(Instruction::new_with_bytes(jet_bonds::ID, &data, accounts))
+    }
+
+    /// Require margin users in the market to hold an airspace permit of at least this tier
+    pub fn set_min_permit_tier(&self, tier: u8) -> Result<Instruction> {
+        let data = jet_bonds::instruction::SetMinPermitTier { tier }.data();
+        let accounts = self.modify_manager_accounts().to_account_metas(None);
+        Ok(Instruction::new_with_bytes(jet_bonds::ID, &data, accounts))
+    }
+
+    /// Get the best bid and ask on the orderbook, which is returned as an
+    /// [OrderbookQuote](jet_bonds::orderbook::instructions::OrderbookQuote) in the return data
+    pub fn orderbook_quote(&self) -> Result<Instruction> {
+        let data = jet_bonds::instruction::OrderbookQuote {}.data();
+        let accounts = jet_bonds::accounts::OrderbookQuoteView {
             bond_manager: self.manager,
-            authority: self.authority,
-            airspace: self.airspace,
+            bids: self.bids()?,
+            asks: self.asks()?,
         }
         .to_account_metas(None);
         Ok(Instruction::new_with_bytes(jet_bonds::ID, &data, accounts))
     }
 
+    /// Get the debt owed by the margin user for a margin account, which is returned as a
+    /// [MarginUserDebt](jet_bonds::margin::instructions::MarginUserDebt) in the return data
+    pub fn margin_user_debt(&self, margin_account: Pubkey) -> Result<Instruction> {
+        let data = jet_bonds::instruction::MarginUserDebt {}.data();
+        let accounts = jet_bonds::accounts::MarginUserDebtView {
+            margin_user: self.margin_user_account(margin_account),
+        }
+        .to_account_metas(None);
+        Ok(Instruction::new_with_bytes(jet_bonds::ID, &data, accounts))
+    }
+
+    fn modify_manager_accounts(&self) -> jet_bonds::accounts::ModifyBondManager {
+        jet_bonds::accounts::ModifyBondManager {
+            bond_manager: self.manager,
+            authority: self.authority,
+            airspace: self.airspace,
+        }
+    }
+
     pub fn authorize_crank(&self, payer: Pubkey, crank: Pubkey) -> Result<Instruction> {
         let data = jet_bonds::instruction::AuthorizeCrank {}.data();
         let accounts = jet_bonds::accounts::AuthorizeCrank {
             crank,
-            crank_authorization: crank_authorization(&crank),
+            crank_authorization: crank_authorization(&self.airspace, &crank),
             authority: self.authority,
             airspace: self.airspace,
             payer,
@@ -705,6 +933,11 @@ impl BondsIxBuilder {
         bonds_pda(&[jet_bonds::seeds::CLAIM_NOTES, manager_key.as_ref()])
     }
 
+    /// The token account tracking the claims of a `MarginUser`
+    pub fn margin_user_claims(borrower_account: &Pubkey) -> Pubkey {
+        bonds_pda(&[jet_bonds::seeds::CLAIM_NOTES, borrower_account.as_ref()])
+    }
+
     pub fn collateral_mint(manager_key: &Pubkey) -> Pubkey {
         bonds_pda(&[jet_bonds::seeds::DEPOSIT_NOTES, manager_key.as_ref()])
     }
@@ -742,9 +975,13 @@ pub fn bonds_pda(seeds: &[&[u8]]) -> Pubkey {
     Pubkey::find_program_address(seeds, &jet_bonds::ID).0
 }
 
-pub fn crank_authorization(crank: &Pubkey) -> Pubkey {
+pub fn crank_authorization(airspace: &Pubkey, crank: &Pubkey) -> Pubkey {
     Pubkey::find_program_address(
-        &[jet_bonds::seeds::CRANK_AUTHORIZATION, crank.as_ref()],
+        &[
+            jet_bonds::seeds::CRANK_AUTHORIZATION,
+            airspace.as_ref(),
+            crank.as_ref(),
+        ],
         &jet_bonds::ID,
     )
     .0
@@ -0,0 +1,111 @@
+use jet_bonds::orderbook::state::fp32_div;
+
+use super::{
+    error::{BondsIxError, Result},
+    interest_pricing::{bps_to_f64, f64_to_fp32, AprPricer, ApyPricer, InterestPricer},
+    orderbook::{OrderSize, Side},
+    OrderParams,
+};
+
+const SECONDS_PER_YEAR: f64 = 31_536_000.0;
+
+/// A yearly interest rate in basis points, tagged with the convention used to quote it
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum InterestRate {
+    Apr(u64),
+    Apy(u64),
+}
+
+impl InterestRate {
+    /// The fp32 ticket price implied by this rate for a bond with the given tenor in seconds
+    ///
+    /// Rates too large to be represented by a price give a price of zero.
+    pub fn to_price(&self, tenor: u64) -> u64 {
+        let yld = match *self {
+            InterestRate::Apr(bps) => {
+                AprPricer::interest_rate_to_yield(bps_to_f64(bps), SECONDS_PER_YEAR, tenor as f64)
+            }
+            InterestRate::Apy(bps) => {
+                ApyPricer::interest_rate_to_yield(bps_to_f64(bps), SECONDS_PER_YEAR, tenor as f64)
+            }
+        };
+
+        f64_to_fp32(1.0 / (1.0 + yld))
+    }
+}
+
+/// An order that is matched immediately against the book, and cancelled if it can't be
+/// matched within its rate limit.
+///
+/// For lend orders the rate is the minimum rate the lender will accept. For borrow orders
+/// and ticket sales, the rate is the maximum rate the borrower will pay.
+#[derive(Debug, Clone, Copy)]
+pub struct MarketOrder {
+    /// the amount to be traded
+    pub size: OrderSize,
+    /// the worst rate at which the order may be filled
+    pub limit_rate: InterestRate,
+    /// the duration of the bond market, in seconds
+    pub tenor: u64,
+    /// the maximum number of resting orders to match against
+    pub match_limit: u64,
+    /// stake purchased tickets automatically, only applies to lend orders
+    pub auto_stake: bool,
+}
+
+impl MarketOrder {
+    pub fn new(size: OrderSize, limit_rate: InterestRate, tenor: u64) -> Self {
+        Self {
+            size,
+            limit_rate,
+            tenor,
+            match_limit: 100,
+            auto_stake: false,
+        }
+    }
+
+    pub fn with_match_limit(mut self, match_limit: u64) -> Self {
+        self.match_limit = match_limit;
+        self
+    }
+
+    pub fn with_auto_stake(mut self, auto_stake: bool) -> Self {
+        self.auto_stake = auto_stake;
+        self
+    }
+
+    /// Convert into the order parameters accepted by the bonds program
+    ///
+    /// `side` is the side of the book the order is placed on, `Bid` for lending and `Ask` for
+    /// borrowing or selling tickets.
+    pub fn params(&self, side: Side) -> Result<OrderParams> {
+        let limit_price = self.limit_rate.to_price(self.tenor);
+
+        if limit_price == 0 {
+            return Err(BondsIxError::InvalidOrderRate(format!(
+                "{:?}",
+                self.limit_rate
+            )));
+        }
+
+        let (max_bond_ticket_qty, max_underlying_token_qty) = match (self.size, side) {
+            (OrderSize::Tickets(qty), _) => (qty, u64::MAX),
+            (OrderSize::Underlying(qty), Side::Bid) => (u64::MAX, qty),
+            // bound the tickets owed by a borrower to the amount implied by the limit rate
+            (OrderSize::Underlying(qty), Side::Ask) => {
+                (fp32_div(qty, limit_price).unwrap_or(u64::MAX), qty)
+            }
+        };
+
+        Ok(OrderParams {
+            max_bond_ticket_qty,
+            max_underlying_token_qty,
+            limit_price,
+            match_limit: self.match_limit,
+            post_only: false,
+            post_allowed: false,
+            auto_stake: matches!(side, Side::Bid) && self.auto_stake,
+            good_til_time: None,
+        })
+    }
+}
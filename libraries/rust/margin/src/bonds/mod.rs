@@ -3,5 +3,8 @@
 pub mod error;
 pub mod event_builder;
 mod ix_builder;
+pub mod market_order;
+pub mod orderbook;
 
 pub use ix_builder::*;
+pub use jet_interest_pricing as interest_pricing;
@@ -0,0 +1,482 @@
+use std::{collections::BTreeMap, sync::Arc};
+
+use agnostic_orderbook::state::{critbit::Slab, AccountTag};
+use anchor_lang::AccountDeserialize;
+use jet_bonds::orderbook::state::{fp32_div, fp32_mul, CallbackInfo};
+use jet_simulation::solana_rpc_api::SolanaRpcClient;
+use solana_sdk::pubkey::Pubkey;
+
+pub use agnostic_orderbook::state::Side;
+
+use super::{
+    error::{client_err, BondsIxError, Result},
+    interest_pricing::{InterestPricer, PricerImpl},
+    BondManager,
+};
+
+/// A resting order on one side of a bond market's book
+#[derive(Debug, Clone)]
+pub struct Order {
+    pub order_id: u128,
+    pub owner: Pubkey,
+    /// the account that receives the posted funds if the order is cancelled or pruned
+    pub out_account: Pubkey,
+    /// unix timestamp after which the order may be pruned from the book
+    pub expires_at: Option<i64>,
+    /// underlying per bond ticket, fixed point 32
+    pub limit_price: u64,
+    /// quantity of bond tickets
+    pub base_quantity: u64,
+    /// quantity of underlying tokens
+    pub quote_quantity: u64,
+}
+
+/// The complete set of resting orders in a bond market at a point in time
+#[derive(Debug, Clone)]
+pub struct OrderbookSnapshot {
+    /// the manager of the bond market
+    pub manager: Pubkey,
+    /// seconds until a filled order matures, used to convert prices into rates
+    pub duration: i64,
+    /// lend orders, best (highest price) first
+    pub bids: Vec<Order>,
+    /// borrow orders, best (lowest price) first
+    pub asks: Vec<Order>,
+}
+
+impl OrderbookSnapshot {
+    /// Fetch the bids and asks slabs of a bond market and decode the orders within
+    pub async fn load(rpc: &Arc<dyn SolanaRpcClient>, manager: &Pubkey) -> Result<Self> {
+        let manager_data = load_data(rpc, manager).await?;
+        let bond_manager =
+            BondManager::try_deserialize(&mut &manager_data[..]).map_err(client_err)?;
+
+        let mut bids_data = load_data(rpc, &bond_manager.bids).await?;
+        let mut asks_data = load_data(rpc, &bond_manager.asks).await?;
+
+        Self::from_slabs(
+            *manager,
+            bond_manager.duration,
+            &mut bids_data,
+            &mut asks_data,
+        )
+    }
+
+    /// Decode the orders from the raw data of the bids and asks slabs
+    pub fn from_slabs(
+        manager: Pubkey,
+        duration: i64,
+        bids_data: &mut [u8],
+        asks_data: &mut [u8],
+    ) -> Result<Self> {
+        let mut bids = read_slab(bids_data, AccountTag::Bids)?;
+        let mut asks = read_slab(asks_data, AccountTag::Asks)?;
+
+        bids.sort_by(|a, b| b.limit_price.cmp(&a.limit_price));
+        asks.sort_by(|a, b| a.limit_price.cmp(&b.limit_price));
+
+        Ok(Self {
+            manager,
+            duration,
+            bids,
+            asks,
+        })
+    }
+
+    /// The orders that are past their good-til-time and may be pruned from the book
+    pub fn expired_orders(&self, now: i64) -> impl Iterator<Item = &Order> {
+        self.bids
+            .iter()
+            .chain(self.asks.iter())
+            .filter(move |order| order.expires_at.map(|t| t <= now).unwrap_or(false))
+    }
+
+    /// Aggregate the resting orders into price levels
+    pub fn depth(&self) -> L2Depth {
+        let tenor = self.duration.max(0) as u64;
+
+        L2Depth {
+            tenor,
+            bids: aggregate(&self.bids, tenor, true),
+            asks: aggregate(&self.asks, tenor, false),
+        }
+    }
+}
+
+/// The aggregate quantity resting at a single price
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PriceLevel {
+    /// underlying per bond ticket, fixed point 32
+    pub price: u64,
+    /// yearly interest implied by the price, in basis points
+    pub rate: u64,
+    /// total quantity of bond tickets at this price
+    pub base_quantity: u64,
+    /// total quantity of underlying tokens at this price
+    pub quote_quantity: u64,
+    /// number of orders at this price
+    pub order_count: usize,
+}
+
+/// The expected result of a taker order crossing the book
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ExpectedFill {
+    /// bond tickets exchanged
+    pub base_filled: u64,
+    /// underlying tokens exchanged
+    pub quote_filled: u64,
+    /// the volume weighted price of the fill, fixed point 32
+    pub average_price: u64,
+    /// yearly interest implied by the average price, in basis points
+    pub average_rate: u64,
+    /// the price of the last level touched by the fill, fixed point 32
+    pub worst_price: u64,
+    /// true if the book has enough liquidity to fill the full size
+    pub complete: bool,
+}
+
+/// The size of a taker order
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OrderSize {
+    /// quantity of bond tickets
+    Tickets(u64),
+    /// quantity of underlying tokens
+    Underlying(u64),
+}
+
+/// An L2 view of a bond market, with orders aggregated per price
+#[derive(Debug, Clone, Default)]
+pub struct L2Depth {
+    /// seconds until a filled order matures, used to convert prices into rates
+    pub tenor: u64,
+    /// lend orders, best (highest price, lowest rate) first
+    pub bids: Vec<PriceLevel>,
+    /// borrow orders, best (lowest price, highest rate) first
+    pub asks: Vec<PriceLevel>,
+}
+
+impl L2Depth {
+    pub fn best_bid(&self) -> Option<&PriceLevel> {
+        self.bids.first()
+    }
+
+    pub fn best_ask(&self) -> Option<&PriceLevel> {
+        self.asks.first()
+    }
+
+    /// Estimate the fill for a taker order of the given size, assuming the book does not change.
+    ///
+    /// A taker on the `Bid` side lends, and fills against the asks. A taker on the `Ask` side
+    /// borrows or sells tickets, and fills against the bids.
+    pub fn expected_fill(&self, taker_side: Side, size: OrderSize) -> ExpectedFill {
+        let levels = match taker_side {
+            Side::Bid => &self.asks,
+            Side::Ask => &self.bids,
+        };
+        let mut fill = ExpectedFill::default();
+
+        for level in levels {
+            let (base, quote) = match size {
+                OrderSize::Tickets(qty) => {
+                    let base = qty
+                        .saturating_sub(fill.base_filled)
+                        .min(level.base_quantity);
+                    (base, fp32_mul(base, level.price).unwrap_or(u64::MAX))
+                }
+                OrderSize::Underlying(qty) => {
+                    let quote = qty
+                        .saturating_sub(fill.quote_filled)
+                        .min(level.quote_quantity);
+                    let base = fp32_div(quote, level.price).unwrap_or(u64::MAX);
+
+                    // the fill is limited by the tickets in the level, which then
+                    // determine the underlying exchanged for them
+                    if base > level.base_quantity {
+                        let base = level.base_quantity;
+                        (base, fp32_mul(base, level.price).unwrap_or(u64::MAX))
+                    } else {
+                        (base, quote)
+                    }
+                }
+            };
+
+            if base == 0 {
+                break;
+            }
+
+            // quantities that can't be represented saturate, rather than overflowing
+            fill.base_filled = fill.base_filled.saturating_add(base);
+            fill.quote_filled = fill.quote_filled.saturating_add(quote);
+            fill.worst_price = level.price;
+
+            let remaining = match size {
+                OrderSize::Tickets(qty) => qty.saturating_sub(fill.base_filled),
+                OrderSize::Underlying(qty) => qty.saturating_sub(fill.quote_filled),
+            };
+
+            if remaining == 0 {
+                fill.complete = true;
+                break;
+            }
+        }
+
+        if fill.base_filled > 0 {
+            fill.average_price = (((fill.quote_filled as u128) << 32) / fill.base_filled as u128)
+                .try_into()
+                .unwrap_or(u64::MAX);
+            fill.average_rate = price_to_rate(fill.average_price, self.tenor);
+        }
+
+        fill
+    }
+}
+
+fn read_slab(data: &mut [u8], tag: AccountTag) -> Result<Vec<Order>> {
+    // iterating consumes the slab, so callback info is looked up in a copy
+    let mut lookup_data = data.to_vec();
+    let lookup: Slab<CallbackInfo> = Slab::from_buffer(&mut lookup_data, tag)
+        .map_err(|e| BondsIxError::Orderbook(format!("{e:?}")))?;
+    let slab: Slab<CallbackInfo> =
+        Slab::from_buffer(data, tag).map_err(|e| BondsIxError::Orderbook(format!("{e:?}")))?;
+
+    slab.into_iter(true)
+        .map(|leaf| {
+            let handle = lookup.find_by_key(leaf.key).ok_or_else(|| {
+                BondsIxError::Orderbook(format!("order {} is missing from the slab", leaf.key))
+            })?;
+            let callback = lookup.get_callback_info(handle);
+
+            Ok(Order {
+                order_id: leaf.key,
+                owner: callback.owner,
+                out_account: callback.out_account,
+                expires_at: callback.expiry_timestamp(),
+                limit_price: leaf.price(),
+                base_quantity: leaf.base_quantity,
+                quote_quantity: fp32_mul(leaf.base_quantity, leaf.price()).unwrap_or(u64::MAX),
+            })
+        })
+        .collect()
+}
+
+fn aggregate(orders: &[Order], tenor: u64, descending: bool) -> Vec<PriceLevel> {
+    let mut levels = BTreeMap::<u64, PriceLevel>::new();
+
+    for order in orders {
+        let level = levels
+            .entry(order.limit_price)
+            .or_insert_with(|| PriceLevel {
+                price: order.limit_price,
+                rate: price_to_rate(order.limit_price, tenor),
+                base_quantity: 0,
+                quote_quantity: 0,
+                order_count: 0,
+            });
+
+        level.base_quantity = level.base_quantity.saturating_add(order.base_quantity);
+        level.quote_quantity = level.quote_quantity.saturating_add(order.quote_quantity);
+        level.order_count += 1;
+    }
+
+    match descending {
+        true => levels.into_values().rev().collect(),
+        false => levels.into_values().collect(),
+    }
+}
+
+/// The shared pricer panics outside of its domain, so prices at or above one, which
+/// imply no interest, and zero prices or tenors are handled here.
+fn price_to_rate(price: u64, tenor: u64) -> u64 {
+    if price >= 1 << 32 {
+        0
+    } else if price == 0 || tenor == 0 {
+        u64::MAX
+    } else {
+        PricerImpl::price_fp32_to_bps_yearly_interest(price, tenor)
+    }
+}
+
+async fn load_data(rpc: &Arc<dyn SolanaRpcClient>, address: &Pubkey) -> Result<Vec<u8>> {
+    Ok(rpc
+        .get_account(address)
+        .await
+        .map_err(client_err)?
+        .ok_or_else(|| BondsIxError::Client {
+            msg: format!("account {address} does not exist"),
+        })?
+        .data)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ONE: u64 = 1 << 32;
+    const TENOR: i64 = 31_536_000;
+
+    fn order(order_id: u128, limit_price: u64, base_quantity: u64) -> Order {
+        Order {
+            order_id,
+            owner: Pubkey::default(),
+            out_account: Pubkey::default(),
+            expires_at: None,
+            limit_price,
+            base_quantity,
+            quote_quantity: fp32_mul(base_quantity, limit_price).unwrap(),
+        }
+    }
+
+    fn snapshot() -> OrderbookSnapshot {
+        OrderbookSnapshot {
+            manager: Pubkey::default(),
+            duration: TENOR,
+            bids: vec![
+                order(1, ONE / 2, 1_000),
+                order(2, ONE / 2, 500),
+                order(3, ONE / 4, 2_000),
+            ],
+            asks: vec![order(4, ONE * 3 / 4, 1_000), order(5, ONE * 7 / 8, 1_000)],
+        }
+    }
+
+    #[test]
+    fn depth_aggregates_levels() {
+        let depth = snapshot().depth();
+
+        assert_eq!(2, depth.bids.len());
+        assert_eq!(ONE / 2, depth.best_bid().unwrap().price);
+        assert_eq!(1_500, depth.bids[0].base_quantity);
+        assert_eq!(750, depth.bids[0].quote_quantity);
+        assert_eq!(3, depth.bids[0].order_count);
+        assert_eq!(ONE / 4, depth.bids[1].price);
+
+        assert_eq!(2, depth.asks.len());
+        assert_eq!(ONE * 3 / 4, depth.best_ask().unwrap().price);
+        assert_eq!(750, depth.asks[0].quote_quantity);
+        assert_eq!(1, depth.asks[0].order_count);
+
+        // lower prices pay more interest
+        assert!(depth.bids[0].rate < depth.bids[1].rate);
+        assert!(depth.asks[0].rate > depth.asks[1].rate);
+        assert!(depth.bids[0].rate > depth.asks[0].rate);
+    }
+
+    #[test]
+    fn fill_by_tickets_walks_the_book() {
+        let depth = snapshot().depth();
+        let fill = depth.expected_fill(Side::Ask, OrderSize::Tickets(2_000));
+
+        assert!(fill.complete);
+        assert_eq!(2_000, fill.base_filled);
+        assert_eq!(750 + 125, fill.quote_filled);
+        assert_eq!(ONE / 4, fill.worst_price);
+        assert_eq!(((875u128 << 32) / 2_000) as u64, fill.average_price);
+        assert_eq!(
+            price_to_rate(fill.average_price, TENOR as u64),
+            fill.average_rate
+        );
+    }
+
+    #[test]
+    fn fill_by_underlying_walks_the_book() {
+        let depth = snapshot().depth();
+        let fill = depth.expected_fill(Side::Bid, OrderSize::Underlying(1_000));
+
+        assert!(fill.complete);
+        assert_eq!(1_000 + 285, fill.base_filled);
+        assert_eq!(1_000, fill.quote_filled);
+        assert_eq!(ONE * 7 / 8, fill.worst_price);
+    }
+
+    #[test]
+    fn fill_by_underlying_is_limited_by_level_tickets() {
+        let depth = L2Depth {
+            tenor: TENOR as u64,
+            bids: vec![],
+            asks: vec![PriceLevel {
+                price: ONE / 2,
+                rate: 0,
+                base_quantity: 10,
+                quote_quantity: 6,
+                order_count: 1,
+            }],
+        };
+        let fill = depth.expected_fill(Side::Bid, OrderSize::Underlying(6));
+
+        assert!(!fill.complete);
+        assert_eq!(10, fill.base_filled);
+        assert_eq!(5, fill.quote_filled);
+        assert_eq!(ONE / 2, fill.average_price);
+    }
+
+    #[test]
+    fn partial_fill_when_book_is_thin() {
+        let depth = snapshot().depth();
+        let fill = depth.expected_fill(Side::Bid, OrderSize::Tickets(5_000));
+
+        assert!(!fill.complete);
+        assert_eq!(2_000, fill.base_filled);
+        assert_eq!(750 + 875, fill.quote_filled);
+    }
+
+    #[test]
+    fn large_sizes_saturate() {
+        let level = |price| PriceLevel {
+            price,
+            rate: 0,
+            base_quantity: u64::MAX / 2,
+            quote_quantity: u64::MAX,
+            order_count: 1,
+        };
+        let depth = L2Depth {
+            tenor: TENOR as u64,
+            bids: vec![level(ONE * 3), level(ONE * 2)],
+            asks: vec![],
+        };
+        let fill = depth.expected_fill(Side::Ask, OrderSize::Tickets(u64::MAX));
+
+        assert!(!fill.complete);
+        assert_eq!(u64::MAX - 1, fill.base_filled);
+        assert_eq!(u64::MAX, fill.quote_filled);
+        assert_eq!(ONE * 2, fill.worst_price);
+
+        let fill = depth.expected_fill(Side::Ask, OrderSize::Underlying(u64::MAX));
+
+        assert!(fill.complete);
+        assert_eq!(u64::MAX, fill.quote_filled);
+    }
+
+    #[test]
+    fn depth_saturates_large_levels() {
+        let snapshot = OrderbookSnapshot {
+            manager: Pubkey::default(),
+            duration: TENOR,
+            bids: vec![
+                order(1, ONE / 2, u64::MAX),
+                order(2, ONE / 2, u64::MAX),
+                order(3, ONE / 2, u64::MAX),
+            ],
+            asks: vec![],
+        };
+        let depth = snapshot.depth();
+
+        assert_eq!(u64::MAX, depth.bids[0].base_quantity);
+        assert_eq!(u64::MAX, depth.bids[0].quote_quantity);
+        assert_eq!(3, depth.bids[0].order_count);
+    }
+
+    #[test]
+    fn empty_book_fills_nothing() {
+        let fill = L2Depth::default().expected_fill(Side::Bid, OrderSize::Tickets(1));
+
+        assert_eq!(ExpectedFill::default(), fill);
+    }
+
+    #[test]
+    fn rates_outside_the_pricer_domain() {
+        assert_eq!(0, price_to_rate(ONE, TENOR as u64));
+        assert_eq!(0, price_to_rate(ONE * 2, TENOR as u64));
+        assert_eq!(u64::MAX, price_to_rate(0, TENOR as u64));
+        assert_eq!(u64::MAX, price_to_rate(ONE / 2, 0));
+    }
+}
@@ -0,0 +1,164 @@
+// SPDX-License-Identifier: AGPL-3.0-or-later
+//
+// Copyright (C) 2022 JET PROTOCOL HOLDINGS, LLC.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+use std::str::FromStr;
+
+use anchor_lang::{AnchorDeserialize, Discriminator};
+use anyhow::Result;
+use solana_client::{
+    nonblocking::rpc_client::RpcClient, rpc_client::GetConfirmedSignaturesForAddress2Config,
+    rpc_config::RpcTransactionConfig,
+};
+use solana_sdk::{pubkey::Pubkey, signature::Signature};
+
+use jet_margin::events::HealthSnapshot;
+
+use crate::solana::logs::find_event_data;
+
+/// The maximum number of signatures the RPC will return in a single request
+const SIGNATURES_PAGE_SIZE: usize = 1000;
+
+/// Fetch the health history of a margin account, from the [HealthSnapshot]
+/// events emitted by the `record_health` instruction.
+///
+/// Only the most recent `max_transactions` transactions referencing the account
+/// are searched. Snapshots are returned in chronological order.
+pub async fn fetch_health_history(
+    rpc: &RpcClient,
+    margin_account: &Pubkey,
+    max_transactions: usize,
+) -> Result<Vec<HealthSnapshot>> {
+    let mut signatures = vec![];
+    let mut before = None;
+
+    while signatures.len() < max_transactions {
+        let limit = SIGNATURES_PAGE_SIZE.min(max_transactions - signatures.len());
+        let page = rpc
+            .get_signatures_for_address_with_config(
+                margin_account,
+                GetConfirmedSignaturesForAddress2Config {
+                    before,
+                    limit: Some(limit),
+                    ..Default::default()
+                },
+            )
+            .await?;
+
+        let page_len = page.len();
+        for status in page {
+            let signature = Signature::from_str(&status.signature)?;
+            before = Some(signature);
+
+            if status.err.is_none() {
+                signatures.push(signature);
+            }
+        }
+
+        if page_len < limit {
+            break;
+        }
+    }
+
+    let mut snapshots = vec![];
+
+    // signatures are returned newest first
+    for signature in signatures.iter().rev() {
+        let tx = rpc
+            .get_transaction_with_config(signature, RpcTransactionConfig::default())
+            .await?;
+        let logs = tx
+            .transaction
+            .meta
+            .and_then(|meta| meta.log_messages)
+            .unwrap_or_default();
+
+        snapshots.extend(
+            parse_health_snapshots(&logs)
+                .into_iter()
+                .filter(|snapshot| snapshot.margin_account == *margin_account),
+        );
+    }
+
+    Ok(snapshots)
+}
+
+/// Extract any [HealthSnapshot] events from a transaction's logs.
+///
+/// Only events logged by the margin program itself are returned, so other programs
+/// in the same transaction cannot forge a snapshot.
+pub fn parse_health_snapshots(logs: &[String]) -> Vec<HealthSnapshot> {
+    find_event_data(logs)
+        .into_iter()
+        .filter(|(program, _)| *program == jet_margin::ID)
+        .filter(|(_, data)| data.len() >= 8 && data[..8] == HealthSnapshot::discriminator())
+        .filter_map(|(_, data)| HealthSnapshot::try_from_slice(&data[8..]).ok())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use anchor_lang::AnchorSerialize;
+    use jet_margin::events::ValuationSummary;
+
+    use super::*;
+    use crate::solana::logs::PROGRAM_DATA_PREFIX;
+
+    fn snapshot_log(margin_account: Pubkey) -> String {
+        let snapshot = HealthSnapshot {
+            margin_account,
+            timestamp: 1,
+            health_ratio_bps: 12_000,
+            valuation_summary: ValuationSummary {
+                equity: 0,
+                liabilities: 0,
+                required_collateral: 0,
+                weighted_collateral: 0,
+                effective_collateral: 0,
+                available_collateral: 0,
+                past_due: false,
+            },
+        };
+        let mut data = HealthSnapshot::discriminator().to_vec();
+        data.extend(snapshot.try_to_vec().unwrap());
+
+        format!("{PROGRAM_DATA_PREFIX}{}", base64::encode(data))
+    }
+
+    #[test]
+    fn parses_snapshots_only_from_margin_program() {
+        let account = Pubkey::new_unique();
+        let other_program = Pubkey::new_unique();
+        let logs = vec![
+            format!("Program {} invoke [1]", jet_margin::ID),
+            "Program log: Instruction: RecordHealth".to_string(),
+            snapshot_log(account),
+            format!("Program {} success", jet_margin::ID),
+            format!("Program {other_program} invoke [1]"),
+            snapshot_log(account),
+            format!("Program {} invoke [2]", jet_margin::ID),
+            snapshot_log(account),
+            format!("Program {} success", jet_margin::ID),
+            format!("Program {other_program} success"),
+        ];
+
+        let snapshots = parse_health_snapshots(&logs);
+
+        assert_eq!(2, snapshots.len());
+        assert!(snapshots.iter().all(|s| s.margin_account == account));
+        assert_eq!(12_000, snapshots[0].health_ratio_bps);
+    }
+}
@@ -176,7 +176,9 @@ impl AirspaceIxBuilder {
     /// # Params
     ///
     /// `user` - The address authorized to use the airspace
-    pub fn permit_create(&self, user: Pubkey) -> Instruction {
+    /// `expires_at` - The unix timestamp when the permit expires, or zero for no expiry
+    /// `tier` - The level of access granted by the permit
+    pub fn permit_create(&self, user: Pubkey, expires_at: i64, tier: u8) -> Instruction {
         let accounts = jet_airspace::accounts::AirspacePermitCreate {
             airspace: self.address,
             authority: self.authority,
@@ -190,7 +192,42 @@ impl AirspaceIxBuilder {
         Instruction {
             accounts,
             program_id: jet_airspace::ID,
-            data: jet_airspace::instruction::AirspacePermitCreate { owner: user }.data(),
+            data: jet_airspace::instruction::AirspacePermitCreate {
+                owner: user,
+                expires_at,
+                tier,
+            }
+            .data(),
+        }
+    }
+
+    /// Change the expiry and tier of a previously issued permit
+    ///
+    /// # Params
+    ///
+    /// `user` - The address authorized to use the airspace
+    /// `issuer` - The address that originally issued the permit
+    /// `expires_at` - The unix timestamp when the permit expires, or zero for no expiry
+    /// `tier` - The level of access granted by the permit
+    pub fn permit_update(
+        &self,
+        user: Pubkey,
+        issuer: Pubkey,
+        expires_at: i64,
+        tier: u8,
+    ) -> Instruction {
+        let accounts = jet_airspace::accounts::AirspacePermitUpdate {
+            airspace: self.address,
+            authority: self.authority,
+            permit: self.derive_permit(&user),
+            issuer_id: self.derive_issuer_id(&issuer),
+        }
+        .to_account_metas(None);
+
+        Instruction {
+            accounts,
+            program_id: jet_airspace::ID,
+            data: jet_airspace::instruction::AirspacePermitUpdate { expires_at, tier }.data(),
         }
     }
 
@@ -213,7 +250,7 @@ impl AirspaceIxBuilder {
         Instruction {
             accounts,
             program_id: jet_airspace::ID,
-            data: jet_airspace::instruction::AirspacePermitCreate { owner: user }.data(),
+            data: jet_airspace::instruction::AirspacePermitRevoke {}.data(),
         }
     }
 
@@ -94,12 +94,13 @@ impl ControlIxBuilder {
     /// Instruction to register a margin pool.
     ///
     /// The margin pool is created with default settings, and must be configured
-    /// with `configure_margin_pool`
-    pub fn create_margin_pool(&self, token: &Pubkey) -> Instruction {
-        let pool_builder = MarginPoolIxBuilder::new(*token);
+    /// with `configure_margin_pool`. The requester must be the airspace authority.
+    pub fn create_margin_pool(&self, airspace: &Pubkey, token: &Pubkey) -> Instruction {
+        let pool_builder = MarginPoolIxBuilder::new(*airspace, *token);
         let accounts = jet_control::accounts::CreateMarginPool {
-            requester: self.payer,
+            requester: self.requester,
             authority: get_control_authority_address(),
+            airspace: *airspace,
 
             margin_pool: pool_builder.address,
             vault: pool_builder.vault,
@@ -129,16 +130,19 @@ impl ControlIxBuilder {
     /// Instruction to configure a margin pool.
     ///
     /// Configuration can update various parameters, enable or disable borrowing,
-    /// etc. See [MarginPoolConfiguration] for all parameters.
+    /// etc. See [MarginPoolConfiguration] for all parameters. The requester must
+    /// be the airspace authority.
     pub fn configure_margin_pool(
         &self,
+        airspace: &Pubkey,
         token: &Pubkey,
         config: &MarginPoolConfiguration,
     ) -> Instruction {
-        let pool_builder = MarginPoolIxBuilder::new(*token);
+        let pool_builder = MarginPoolIxBuilder::new(*airspace, *token);
         let accounts = jet_control::accounts::ConfigureMarginPool {
             requester: self.requester,
             authority: get_control_authority_address(),
+            airspace: *airspace,
 
             token_mint: *token,
             margin_pool: pool_builder.address,
@@ -16,8 +16,10 @@
 // along with this program.  If not, see <https://www.gnu.org/licenses/>.
 
 use anchor_spl::associated_token::{self, get_associated_token_address};
-use jet_margin::seeds::{ADAPTER_CONFIG_SEED, LIQUIDATOR_CONFIG_SEED, TOKEN_CONFIG_SEED};
-use solana_sdk::instruction::Instruction;
+use jet_margin::seeds::{
+    ADAPTER_CONFIG_SEED, LIQUIDATOR_CONFIG_SEED, TOKEN_CATEGORY_SEED, TOKEN_CONFIG_SEED,
+};
+use solana_sdk::instruction::{AccountMeta, Instruction};
 use solana_sdk::pubkey::Pubkey;
 use solana_sdk::system_program::ID as SYSTEM_PROGAM_ID;
 use solana_sdk::sysvar::{rent::Rent, SysvarId};
@@ -28,7 +30,11 @@ use anchor_spl::token::Token;
 
 use jet_margin::instruction as ix_data;
 use jet_margin::program::JetMargin;
-use jet_margin::{accounts as ix_account, TokenConfigUpdate};
+use jet_margin::{
+    accounts as ix_account, AdapterInstruction, TokenCategoryUpdate, TokenConfigUpdate,
+};
+
+use super::{derive_airspace, derive_permit};
 
 /// Utility for creating instructions to interact with the margin
 /// program for a specific account.
@@ -60,7 +66,8 @@ impl MarginIxBuilder {
         Self::new_with_payer(owner, seed, owner, None)
     }
 
-    /// Create a new [MarginIxBuilder] with a custom payer and authority.
+    /// Create a new [MarginIxBuilder] with a custom payer and authority, for an
+    /// account in the default airspace.
     /// The authority is expected to sign the instructions generated, and
     /// is normally the margin account or its registered liquidator.
     /// If the authority is not set, it defaults to the margin account.
@@ -70,7 +77,7 @@ impl MarginIxBuilder {
         payer: Pubkey,
         authority: Option<Pubkey>,
     ) -> Self {
-        Self::new_with_payer_and_airspace(owner, seed, payer, Pubkey::default(), authority)
+        Self::new_with_payer_and_airspace(owner, seed, payer, derive_airspace("default"), authority)
     }
 
     /// Create a new [MarginIxBuilder] with a custom payer and authority.
@@ -104,6 +111,8 @@ impl MarginIxBuilder {
             owner: self.owner,
             payer: self.payer,
             margin_account: self.address,
+            airspace: self.airspace,
+            permit: derive_permit(&self.airspace, &self.owner),
             system_program: SYSTEM_PROGAM_ID,
         };
 
@@ -114,6 +123,22 @@ impl MarginIxBuilder {
         }
     }
 
+    /// Get instruction to migrate a version 1 account into the default airspace
+    pub fn migrate_account(&self) -> Instruction {
+        let accounts = ix_account::MigrateAccount {
+            owner: self.owner,
+            payer: self.payer,
+            margin_account: self.address,
+            system_program: SYSTEM_PROGAM_ID,
+        };
+
+        Instruction {
+            program_id: JetMargin::id(),
+            data: ix_data::MigrateAccount.data(),
+            accounts: accounts.to_account_metas(None),
+        }
+    }
+
     /// Get instruction to close account
     pub fn close_account(&self) -> Instruction {
         let accounts = ix_account::CloseAccount {
@@ -152,20 +177,25 @@ impl MarginIxBuilder {
     ///
     /// # Params
     ///
-    /// `position_token_mint` - The mint for the relevant token for the position
-    /// `token_oracle` - The oracle account with price information on the token
+    /// `position_token_mint` - The mint for the relevant token for the position, which
+    ///   must have a token config in the account's airspace
+    /// `category` - The category in the token's config, or zero if it has none
     ///
     /// # Returns
     ///
     /// Returns the instruction, and the address of the token account to be
     /// created for the position.
-    pub fn register_position(&self, position_token_mint: Pubkey) -> (Pubkey, Instruction) {
+    pub fn register_position(
+        &self,
+        position_token_mint: Pubkey,
+        category: u16,
+    ) -> (Pubkey, Instruction) {
         let (token_account, _) = owned_position_token_account(&self.address, &position_token_mint);
 
-        let (metadata, _) =
-            Pubkey::find_program_address(&[position_token_mint.as_ref()], &jet_metadata::ID);
+        let metadata = MarginConfigIxBuilder::new(self.airspace, self.payer)
+            .derive_token_config(&position_token_mint);
 
-        let accounts = ix_account::RegisterPosition {
+        let mut accounts = ix_account::RegisterPosition {
             authority: self.authority(),
             payer: self.payer,
             margin_account: self.address,
@@ -175,12 +205,26 @@ impl MarginIxBuilder {
             token_program: Token::id(),
             system_program: System::id(),
             rent: Rent::id(),
-        };
+        }
+        .to_account_metas(None);
+
+        // the owner's permit, for tokens that require a minimum permit tier
+        accounts.push(AccountMeta::new_readonly(
+            derive_permit(&self.airspace, &self.owner),
+            false,
+        ));
+
+        if category != 0 {
+            accounts.push(AccountMeta::new_readonly(
+                derive_token_category(&self.airspace, category),
+                false,
+            ));
+        }
 
         let ix = Instruction {
             program_id: JetMargin::id(),
             data: ix_data::RegisterPosition {}.data(),
-            accounts: accounts.to_account_metas(None),
+            accounts,
         };
 
         (token_account, ix)
@@ -214,7 +258,8 @@ impl MarginIxBuilder {
         }
     }
 
-    /// Get instruction to refresh the metadata for a position
+    /// Get instruction to refresh the metadata for a position, from the legacy
+    /// metadata account for the token
     ///
     /// # Params
     ///
@@ -223,15 +268,22 @@ impl MarginIxBuilder {
         let (metadata, _) =
             Pubkey::find_program_address(&[position_token_mint.as_ref()], &jet_metadata::ID);
 
-        let accounts = ix_account::RefreshPositionMetadata {
+        let mut accounts = ix_account::RefreshPositionMetadata {
             metadata,
             margin_account: self.address,
-        };
+        }
+        .to_account_metas(None);
+
+        // the metadata is only accepted while the token has no config in the airspace
+        accounts.push(AccountMeta::new_readonly(
+            derive_token_config(&self.airspace, position_token_mint),
+            false,
+        ));
 
         Instruction {
             program_id: JetMargin::id(),
             data: ix_data::RefreshPositionMetadata.data(),
-            accounts: accounts.to_account_metas(None),
+            accounts,
         }
     }
 
@@ -240,19 +292,32 @@ impl MarginIxBuilder {
     /// # Params
     ///
     /// `position_token_mint` - The mint for the position to be refreshed
-    pub fn refresh_position_config(&self, position_token_mint: &Pubkey) -> Instruction {
+    /// `category` - The category in the token's config, or zero if it has none
+    pub fn refresh_position_config(
+        &self,
+        position_token_mint: &Pubkey,
+        category: u16,
+    ) -> Instruction {
         let config = MarginConfigIxBuilder::new(self.airspace, self.payer)
             .derive_token_config(position_token_mint);
 
-        let accounts = ix_account::RefreshPositionConfig {
+        let mut accounts = ix_account::RefreshPositionConfig {
             config,
             margin_account: self.address,
-        };
+        }
+        .to_account_metas(None);
+
+        if category != 0 {
+            accounts.push(AccountMeta::new_readonly(
+                derive_token_category(&self.airspace, category),
+                false,
+            ));
+        }
 
         Instruction {
             program_id: JetMargin::id(),
             data: ix_data::RefreshPositionConfig.data(),
-            accounts: accounts.to_account_metas(None),
+            accounts,
         }
     }
 
@@ -287,19 +352,64 @@ impl MarginIxBuilder {
     /// `adapter_ix` - The instruction to be invoked
     pub fn adapter_invoke(&self, adapter_ix: Instruction) -> Instruction {
         invoke!(
+            self.airspace,
             self.address,
             adapter_ix,
             AdapterInvoke { owner: self.owner }
         )
     }
 
+    /// Get instruction to invoke a sequence of adapter instructions, with a single
+    /// health check once they have all completed
+    ///
+    /// # Params
+    ///
+    /// `adapter_ixs` - The instructions to be invoked, in order
+    pub fn adapter_invoke_batch(&self, adapter_ixs: Vec<Instruction>) -> Instruction {
+        let mut accounts = ix_account::AdapterInvokeBatch {
+            owner: self.owner,
+            margin_account: self.address,
+        }
+        .to_account_metas(None);
+        let mut instructions = vec![];
+
+        for adapter_ix in adapter_ixs {
+            accounts.push(AccountMeta::new_readonly(adapter_ix.program_id, false));
+            accounts.push(AccountMeta::new_readonly(
+                derive_adapter_config(&self.airspace, &adapter_ix.program_id),
+                false,
+            ));
+            instructions.push(AdapterInstruction {
+                num_accounts: adapter_ix
+                    .accounts
+                    .len()
+                    .try_into()
+                    .expect("too many accounts for an adapter instruction"),
+                data: adapter_ix.data,
+            });
+
+            for acc in adapter_ix.accounts {
+                accounts.push(AccountMeta {
+                    is_signer: acc.is_signer && acc.pubkey != self.address,
+                    ..acc
+                });
+            }
+        }
+
+        Instruction {
+            program_id: JetMargin::id(),
+            data: ix_data::AdapterInvokeBatch { instructions }.data(),
+            accounts,
+        }
+    }
+
     /// Get instruction to invoke through an adapter for permissionless accounting instructions
     ///
     /// # Params
     ///
     /// `adapter_ix` - The instruction to be invoked
     pub fn accounting_invoke(&self, adapter_ix: Instruction) -> Instruction {
-        accounting_invoke(self.address, adapter_ix)
+        accounting_invoke(self.airspace, self.address, adapter_ix)
     }
 
     /// Begin liquidating a margin account
@@ -308,8 +418,7 @@ impl MarginIxBuilder {
     ///
     /// `liquidator` - The address of the liquidator
     pub fn liquidate_begin(&self, liquidator: Pubkey) -> Instruction {
-        let (liquidator_metadata, _) =
-            Pubkey::find_program_address(&[liquidator.as_ref()], &jet_metadata::id());
+        let liquidator_metadata = derive_liquidator_config(&self.airspace, &liquidator);
 
         let (liquidation, _) = Pubkey::find_program_address(
             &[b"liquidation", self.address.as_ref(), liquidator.as_ref()],
@@ -341,6 +450,7 @@ impl MarginIxBuilder {
         );
 
         invoke!(
+            self.airspace,
             self.address,
             adapter_ix,
             LiquidatorInvoke {
@@ -388,10 +498,11 @@ impl MarginIxBuilder {
     /// # Params
     ///
     /// `token_mint` - The mint for the token to be deposited
-    pub fn create_deposit_position(&self, token_mint: Pubkey) -> Instruction {
+    /// `category` - The category in the token's config, or zero if it has none
+    pub fn create_deposit_position(&self, token_mint: Pubkey, category: u16) -> Instruction {
         let config_ix = MarginConfigIxBuilder::new(self.airspace, self.payer);
         let token_account = get_associated_token_address(&self.address, &token_mint);
-        let accounts = ix_account::CreateDepositPosition {
+        let mut accounts = ix_account::CreateDepositPosition {
             margin_account: self.address,
             authority: self.authority(),
             payer: self.payer,
@@ -402,11 +513,25 @@ impl MarginIxBuilder {
             token_program: spl_token::ID,
             system_program: system_program::ID,
             rent: Rent::id(),
-        };
+        }
+        .to_account_metas(None);
+
+        // the owner's permit, for tokens that require a minimum permit tier
+        accounts.push(AccountMeta::new_readonly(
+            derive_permit(&self.airspace, &self.owner),
+            false,
+        ));
+
+        if category != 0 {
+            accounts.push(AccountMeta::new_readonly(
+                derive_token_category(&self.airspace, category),
+                false,
+            ));
+        }
 
         Instruction {
             program_id: jet_margin::ID,
-            accounts: accounts.to_account_metas(None),
+            accounts,
             data: ix_data::CreateDepositPosition.data(),
         }
     }
@@ -435,6 +560,36 @@ impl MarginIxBuilder {
         }
     }
 
+    /// Transfer tokens out of a deposit account associated with the margin account,
+    /// as the liquidator of the account
+    pub fn liquidator_transfer_deposit(
+        &self,
+        liquidator: Pubkey,
+        source: Pubkey,
+        destination: Pubkey,
+        amount: u64,
+    ) -> Instruction {
+        let (liquidation, _) = Pubkey::find_program_address(
+            &[b"liquidation", self.address.as_ref(), liquidator.as_ref()],
+            &jet_margin::id(),
+        );
+
+        let accounts = ix_account::LiquidatorTransferDeposit {
+            liquidator,
+            liquidation,
+            margin_account: self.address,
+            source,
+            destination,
+            token_program: spl_token::ID,
+        };
+
+        Instruction {
+            program_id: jet_margin::ID,
+            data: ix_data::LiquidatorTransferDeposit { amount }.data(),
+            accounts: accounts.to_account_metas(None),
+        }
+    }
+
     /// Verify that an account is healthy
     ///
     pub fn verify_healthy(&self) -> Instruction {
@@ -449,6 +604,33 @@ impl MarginIxBuilder {
         }
     }
 
+    /// Get instruction to get the health of the account, which is returned as an
+    /// [jet_margin::AccountHealth] in the return data
+    pub fn account_health(&self) -> Instruction {
+        let accounts = ix_account::AccountHealthView {
+            margin_account: self.address,
+        };
+
+        Instruction {
+            program_id: JetMargin::id(),
+            accounts: accounts.to_account_metas(None),
+            data: ix_data::AccountHealth.data(),
+        }
+    }
+
+    /// Get instruction to record the current health of the account
+    pub fn record_health(&self) -> Instruction {
+        let accounts = ix_account::RecordHealth {
+            margin_account: self.address,
+        };
+
+        Instruction {
+            program_id: JetMargin::id(),
+            accounts: accounts.to_account_metas(None),
+            data: ix_data::RecordHealth.data(),
+        }
+    }
+
     /// Helper function to get token account address for a position mint
     #[inline]
     pub fn get_token_account_address(&self, position_token_mint: &Pubkey) -> (Pubkey, u8) {
@@ -467,9 +649,15 @@ impl MarginIxBuilder {
 ///
 /// # Params
 ///
+/// `airspace` - The airspace the margin account belongs to
+/// `margin_account` - The margin account to invoke the adapter for
 /// `adapter_ix` - The instruction to be invoked
-pub fn accounting_invoke(margin_account: Pubkey, adapter_ix: Instruction) -> Instruction {
-    invoke!(margin_account, adapter_ix, AccountingInvoke)
+pub fn accounting_invoke(
+    airspace: Pubkey,
+    margin_account: Pubkey,
+    adapter_ix: Instruction,
+) -> Instruction {
+    invoke!(airspace, margin_account, adapter_ix, AccountingInvoke)
 }
 
 /// Utility for creating instructions that modify configuration for the margin program within
@@ -513,6 +701,27 @@ impl MarginConfigIxBuilder {
         }
     }
 
+    /// Set the configuration for a category of correlated tokens
+    pub fn configure_token_category(
+        &self,
+        category: u16,
+        update: Option<TokenCategoryUpdate>,
+    ) -> Instruction {
+        let accounts = ix_account::ConfigureTokenCategory {
+            authority: self.authority,
+            airspace: self.airspace,
+            payer: self.payer,
+            token_category: derive_token_category(&self.airspace, category),
+            system_program: system_program::ID,
+        };
+
+        Instruction {
+            program_id: jet_margin::ID,
+            data: ix_data::ConfigureTokenCategory { category, update }.data(),
+            accounts: accounts.to_account_metas(None),
+        }
+    }
+
     /// Set the configuration for an adapter program
     pub fn configure_adapter(&self, program_id: Pubkey, is_adapter: bool) -> Instruction {
         let accounts = ix_account::ConfigureAdapter {
@@ -586,6 +795,19 @@ pub fn derive_token_config(airspace: &Pubkey, token_mint: &Pubkey) -> Pubkey {
     .0
 }
 
+/// Derive address for the config account for a category of tokens
+pub fn derive_token_category(airspace: &Pubkey, category: u16) -> Pubkey {
+    Pubkey::find_program_address(
+        &[
+            TOKEN_CATEGORY_SEED,
+            airspace.as_ref(),
+            category.to_le_bytes().as_ref(),
+        ],
+        &jet_margin::ID,
+    )
+    .0
+}
+
 /// Derive address for the config account for a given adapter
 pub fn derive_adapter_config(airspace: &Pubkey, adapter_program_id: &Pubkey) -> Pubkey {
     Pubkey::find_program_address(
@@ -616,14 +838,14 @@ pub fn derive_liquidator_config(airspace: &Pubkey, liquidator: &Pubkey) -> Pubke
 /// instruction, such as adapter_invoke, liquidate_invoke, and accounting_invoke
 macro_rules! invoke {
     (
+        $airspace:expr,
         $margin_account:expr,
         $adapter_ix:ident,
         $Instruction:ident $({
             $($additional_field:ident: $value:expr),* $(,)?
         })?
     ) => {{
-        let (adapter_metadata, _) =
-            Pubkey::find_program_address(&[$adapter_ix.program_id.as_ref()], &jet_metadata::ID);
+        let adapter_metadata = derive_adapter_config(&$airspace, &$adapter_ix.program_id);
 
         let mut accounts = ix_account::$Instruction {
             margin_account: $margin_account,
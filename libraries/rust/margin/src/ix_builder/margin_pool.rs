@@ -24,11 +24,14 @@ use solana_sdk::sysvar::{rent::Rent, SysvarId};
 
 use jet_margin_pool::instruction as ix_data;
 use jet_margin_pool::program::JetMarginPool;
-use jet_margin_pool::{accounts as ix_accounts, TokenChange};
+use jet_margin_pool::{accounts as ix_accounts, pool_airspace_seed, TokenChange};
 
 /// Utility for creating instructions to interact with the margin
 /// pools program for a specific pool.
 pub struct MarginPoolIxBuilder {
+    /// The airspace the pool belongs to
+    pub airspace: Pubkey,
+
     /// The address of the mint for tokens stored in the pool
     pub token_mint: Pubkey,
 
@@ -52,10 +55,10 @@ impl MarginPoolIxBuilder {
     ///
     /// # Params
     ///
+    /// `airspace` - The airspace the pool belongs to
     /// `token_mint` - The token mint which whose tokens the pool stores
-    pub fn new(token_mint: Pubkey) -> Self {
-        let (address, _) =
-            Pubkey::find_program_address(&[token_mint.as_ref()], &JetMarginPool::id());
+    pub fn new(airspace: Pubkey, token_mint: Pubkey) -> Self {
+        let address = derive_margin_pool(&airspace, &token_mint);
 
         let (vault, _) = Pubkey::find_program_address(
             &[address.as_ref(), b"vault".as_ref()],
@@ -73,6 +76,7 @@ impl MarginPoolIxBuilder {
         );
 
         Self {
+            airspace,
             token_mint,
             address,
             vault,
@@ -85,13 +89,11 @@ impl MarginPoolIxBuilder {
     ///
     /// # Params
     ///
+    /// `authority` - The airspace authority
     /// `payer` - The address paying for the rent
-    pub fn create(&self, payer: Pubkey, fee_destination: Pubkey) -> Instruction {
-        let authority = match cfg!(feature = "devnet") {
-            true => payer,
-            false => jet_margin_pool::authority::ID,
-        };
+    pub fn create(&self, authority: Pubkey, payer: Pubkey, fee_destination: Pubkey) -> Instruction {
         let accounts = ix_accounts::CreatePool {
+            airspace: self.airspace,
             authority,
             token_mint: self.token_mint,
             margin_pool: self.address,
@@ -388,6 +390,102 @@ impl MarginPoolIxBuilder {
             accounts,
         }
     }
+
+    /// Instruction to migrate a pool created before airspaces into the default airspace
+    ///
+    /// # Params
+    ///
+    /// `payer` - The address paying for the rent of the extended pool account
+    pub fn migrate(&self, payer: Pubkey) -> Instruction {
+        let accounts = ix_accounts::MigratePool {
+            margin_pool: self.address,
+            payer,
+            system_program: System::id(),
+        }
+        .to_account_metas(None);
+
+        Instruction {
+            program_id: jet_margin_pool::ID,
+            data: ix_data::MigratePool.data(),
+            accounts,
+        }
+    }
+
+    /// Instruction to create token metadata for the pool's deposit notes
+    ///
+    /// # Params
+    ///
+    /// `authority` - The airspace authority, which becomes the metadata update authority
+    /// `payer` - The address paying for the rent
+    /// `name` - The name of the deposit note token
+    /// `symbol` - The symbol of the deposit note token
+    /// `uri` - The URI for the off-chain token metadata
+    pub fn create_deposit_note_metadata(
+        &self,
+        authority: Pubkey,
+        payer: Pubkey,
+        name: String,
+        symbol: String,
+        uri: String,
+    ) -> Instruction {
+        let accounts = ix_accounts::CreateDepositNoteMetadata {
+            margin_pool: self.address,
+            deposit_note_mint: self.deposit_note_mint,
+            deposit_note_metadata: derive_token_metadata(&self.deposit_note_mint),
+            airspace: self.airspace,
+            authority,
+            payer,
+            token_metadata_program: mpl_token_metadata::ID,
+            system_program: System::id(),
+            rent: Rent::id(),
+        }
+        .to_account_metas(None);
+
+        Instruction {
+            program_id: jet_margin_pool::ID,
+            data: ix_data::CreateDepositNoteMetadata { name, symbol, uri }.data(),
+            accounts,
+        }
+    }
+
+    /// Instruction to get the current deposit note exchange rate, which is
+    /// returned as an [jet_margin_pool::ExchangeRate] in the return data
+    pub fn deposit_note_exchange_rate(&self) -> Instruction {
+        let accounts = ix_accounts::DepositNoteExchangeRate {
+            margin_pool: self.address,
+        }
+        .to_account_metas(None);
+
+        Instruction {
+            program_id: jet_margin_pool::ID,
+            data: ix_data::DepositNoteExchangeRate.data(),
+            accounts,
+        }
+    }
+
+    /// Instruction to get the current rates for the pool, which are returned as
+    /// [jet_margin_pool::PoolRates] in the return data
+    pub fn pool_rates(&self) -> Instruction {
+        let accounts = ix_accounts::PoolRatesView {
+            margin_pool: self.address,
+        }
+        .to_account_metas(None);
+
+        Instruction {
+            program_id: jet_margin_pool::ID,
+            data: ix_data::PoolRates.data(),
+            accounts,
+        }
+    }
+}
+
+/// Derive the address of the margin pool for a token in an airspace
+pub fn derive_margin_pool(airspace: &Pubkey, token_mint: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(
+        &[pool_airspace_seed(airspace), token_mint.as_ref()],
+        &JetMarginPool::id(),
+    )
+    .0
 }
 
 /// Find a loan token account for a margin account and margin pool's loan note mint
@@ -397,3 +495,16 @@ pub fn loan_token_account(margin_account: &Pubkey, loan_note_mint: &Pubkey) -> (
         &jet_margin_pool::id(),
     )
 }
+
+/// Derive the address of the token metadata account for a mint
+pub fn derive_token_metadata(mint: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(
+        &[
+            b"metadata".as_ref(),
+            mpl_token_metadata::ID.as_ref(),
+            mint.as_ref(),
+        ],
+        &mpl_token_metadata::ID,
+    )
+    .0
+}
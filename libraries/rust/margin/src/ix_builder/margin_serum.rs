@@ -0,0 +1,402 @@
+// SPDX-License-Identifier: AGPL-3.0-or-later
+//
+// Copyright (C) 2022 JET PROTOCOL HOLDINGS, LLC.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+use solana_sdk::instruction::{AccountMeta, Instruction};
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::{system_program, sysvar::rent};
+
+use anchor_lang::{Id, InstructionData, ToAccountMetas};
+use anchor_spl::dex::serum_dex::{
+    instruction::SelfTradeBehavior,
+    matching::{OrderType, Side},
+};
+use anchor_spl::dex::Dex;
+use anchor_spl::token::Token;
+
+use jet_margin_serum::accounts as ix_accounts;
+use jet_margin_serum::instruction as ix_data;
+
+use super::derive_token_config;
+
+/// The addresses of a serum market and its related accounts
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub struct SerumMarketAccounts {
+    /// The address of the market
+    pub market: Pubkey,
+    /// The token bought and sold on the market
+    pub base_mint: Pubkey,
+    /// The token used to price the base token
+    pub quote_mint: Pubkey,
+    /// The vault holding the market's base tokens
+    pub base_vault: Pubkey,
+    /// The vault holding the market's quote tokens
+    pub quote_vault: Pubkey,
+    /// The queue of new orders
+    pub request_queue: Pubkey,
+    /// The queue of fills and cancellations to be consumed
+    pub event_queue: Pubkey,
+    /// The resting bids
+    pub bids: Pubkey,
+    /// The resting asks
+    pub asks: Pubkey,
+    /// The PDA with authority over the vaults
+    pub vault_signer: Pubkey,
+}
+
+/// Builder for creating instructions to interact with the margin serum program,
+/// for a single market.
+#[derive(Debug, Clone)]
+pub struct MarginSerumIxBuilder {
+    /// The airspace the market is registered in
+    pub airspace: Pubkey,
+    /// The accounts of the serum market
+    pub market: SerumMarketAccounts,
+    /// The registration of the market with the margin serum program
+    pub market_info: Pubkey,
+    /// The mint for notes representing base tokens held by open orders accounts
+    pub base_note_mint: Pubkey,
+    /// The mint for notes representing quote tokens held by open orders accounts
+    pub quote_note_mint: Pubkey,
+    /// The pyth price account for the base token
+    pub base_oracle: Pubkey,
+    /// The pyth price account for the quote token
+    pub quote_oracle: Pubkey,
+}
+
+impl MarginSerumIxBuilder {
+    /// Create a new instruction builder for a market
+    pub fn new(
+        airspace: Pubkey,
+        market: SerumMarketAccounts,
+        base_oracle: Pubkey,
+        quote_oracle: Pubkey,
+    ) -> Self {
+        let market_info = derive_market_info(&airspace, &market.market);
+
+        Self {
+            airspace,
+            market,
+            market_info,
+            base_note_mint: derive_note_mint(&market_info, b"base-notes"),
+            quote_note_mint: derive_note_mint(&market_info, b"quote-notes"),
+            base_oracle,
+            quote_oracle,
+        }
+    }
+
+    /// The open orders account used by a margin account on this market
+    pub fn open_orders(&self, margin_account: &Pubkey) -> Pubkey {
+        Pubkey::find_program_address(
+            &[
+                margin_account.as_ref(),
+                self.market.market.as_ref(),
+                b"open-orders".as_ref(),
+            ],
+            &jet_margin_serum::ID,
+        )
+        .0
+    }
+
+    /// The margin account's position for base tokens held by its open orders
+    pub fn base_notes(&self, margin_account: &Pubkey) -> Pubkey {
+        derive_note_account(margin_account, &self.base_note_mint)
+    }
+
+    /// The margin account's position for quote tokens held by its open orders
+    pub fn quote_notes(&self, margin_account: &Pubkey) -> Pubkey {
+        derive_note_account(margin_account, &self.quote_note_mint)
+    }
+
+    /// Register the market for trading by margin accounts
+    ///
+    /// # Params
+    ///
+    /// `authority` - The authority of the airspace
+    /// `payer` - The payer of rent for the new accounts
+    pub fn register_market(&self, authority: Pubkey, payer: Pubkey) -> Instruction {
+        let accounts = ix_accounts::RegisterMarket {
+            authority,
+            airspace: self.airspace,
+            payer,
+            market_info: self.market_info,
+            market: self.market.market,
+            base_mint: self.market.base_mint,
+            quote_mint: self.market.quote_mint,
+            base_note_mint: self.base_note_mint,
+            quote_note_mint: self.quote_note_mint,
+            base_oracle: self.base_oracle,
+            quote_oracle: self.quote_oracle,
+            dex_program: Dex::id(),
+            token_program: Token::id(),
+            system_program: system_program::ID,
+            rent: rent::ID,
+        }
+        .to_account_metas(None);
+
+        Instruction {
+            program_id: jet_margin_serum::ID,
+            data: ix_data::RegisterMarket {}.data(),
+            accounts,
+        }
+    }
+
+    /// Create the open orders account for a margin account, and register its positions
+    ///
+    /// # Params
+    ///
+    /// `margin_account` - The margin account that will trade on the market
+    /// `payer` - The payer of rent for the new accounts
+    pub fn init_open_orders(&self, margin_account: Pubkey, payer: Pubkey) -> Instruction {
+        let mut accounts = ix_accounts::InitOpenOrders {
+            margin_account,
+            payer,
+            market_info: self.market_info,
+            market: self.market.market,
+            open_orders: self.open_orders(&margin_account),
+            base_note_mint: self.base_note_mint,
+            quote_note_mint: self.quote_note_mint,
+            base_notes: self.base_notes(&margin_account),
+            quote_notes: self.quote_notes(&margin_account),
+            dex_program: Dex::id(),
+            token_program: Token::id(),
+            system_program: system_program::ID,
+            rent: rent::ID,
+        }
+        .to_account_metas(None);
+        accounts.extend([
+            AccountMeta::new_readonly(
+                derive_token_config(&self.airspace, &self.base_note_mint),
+                false,
+            ),
+            AccountMeta::new_readonly(
+                derive_token_config(&self.airspace, &self.quote_note_mint),
+                false,
+            ),
+        ]);
+
+        Instruction {
+            program_id: jet_margin_serum::ID,
+            data: ix_data::InitOpenOrders {}.data(),
+            accounts,
+        }
+    }
+
+    /// Place an order on the market
+    ///
+    /// # Params
+    ///
+    /// `margin_account` - The margin account placing the order
+    /// `payer` - The margin account's token account paying for the order. Base tokens
+    ///           for asks, and quote tokens for bids.
+    /// `limit_price` - The price in quote lots per base lot
+    /// `max_base_qty` - The most base lots to buy or sell
+    /// `max_native_quote_qty` - The most quote tokens to spend or receive, including fees
+    #[allow(clippy::too_many_arguments)]
+    pub fn new_order(
+        &self,
+        margin_account: Pubkey,
+        payer: Pubkey,
+        side: Side,
+        limit_price: u64,
+        max_base_qty: u64,
+        max_native_quote_qty: u64,
+        order_type: OrderType,
+        self_trade_behavior: SelfTradeBehavior,
+        client_order_id: u64,
+    ) -> Instruction {
+        let accounts = ix_accounts::NewOrderV3 {
+            margin_account,
+            positions: self.positions(&margin_account),
+            market: self.market.market,
+            open_orders: self.open_orders(&margin_account),
+            request_queue: self.market.request_queue,
+            event_queue: self.market.event_queue,
+            bids: self.market.bids,
+            asks: self.market.asks,
+            payer,
+            base_vault: self.market.base_vault,
+            quote_vault: self.market.quote_vault,
+            dex_program: Dex::id(),
+            rent: rent::ID,
+        }
+        .to_account_metas(None);
+
+        Instruction {
+            program_id: jet_margin_serum::ID,
+            data: ix_data::NewOrderV3 {
+                side: side as u8,
+                limit_price,
+                max_coin_qty: max_base_qty,
+                max_native_pc_qty_including_fees: max_native_quote_qty,
+                self_trade_behavior: self_trade_behavior as u8,
+                order_type: order_type as u8,
+                client_order_id,
+                limit: u16::MAX,
+            }
+            .data(),
+            accounts,
+        }
+    }
+
+    /// Cancel an order by its id
+    pub fn cancel_order(&self, margin_account: Pubkey, side: Side, order_id: u128) -> Instruction {
+        Instruction {
+            program_id: jet_margin_serum::ID,
+            data: ix_data::CancelOrderV2 {
+                side: side as u8,
+                order_id,
+            }
+            .data(),
+            accounts: self.cancel_order_accounts(margin_account),
+        }
+    }
+
+    /// Cancel an order by the id assigned to it when it was placed
+    pub fn cancel_order_by_client_id(
+        &self,
+        margin_account: Pubkey,
+        client_order_id: u64,
+    ) -> Instruction {
+        Instruction {
+            program_id: jet_margin_serum::ID,
+            data: ix_data::CancelOrderByClientIdV2 { client_order_id }.data(),
+            accounts: self.cancel_order_accounts(margin_account),
+        }
+    }
+
+    /// Move the free tokens of the open orders account into the margin account
+    ///
+    /// # Params
+    ///
+    /// `base_wallet` - The margin account's token account to receive base tokens
+    /// `quote_wallet` - The margin account's token account to receive quote tokens
+    pub fn settle_funds(
+        &self,
+        margin_account: Pubkey,
+        base_wallet: Pubkey,
+        quote_wallet: Pubkey,
+    ) -> Instruction {
+        let accounts = ix_accounts::SettleFunds {
+            margin_account,
+            positions: self.positions(&margin_account),
+            market: self.market.market,
+            open_orders: self.open_orders(&margin_account),
+            base_vault: self.market.base_vault,
+            quote_vault: self.market.quote_vault,
+            base_wallet,
+            quote_wallet,
+            vault_signer: self.market.vault_signer,
+            dex_program: Dex::id(),
+        }
+        .to_account_metas(None);
+
+        Instruction {
+            program_id: jet_margin_serum::ID,
+            data: ix_data::SettleFunds {}.data(),
+            accounts,
+        }
+    }
+
+    /// Close an empty open orders account and its positions
+    ///
+    /// # Params
+    ///
+    /// `destination` - The account to receive the rent of the closed accounts
+    pub fn close_open_orders(&self, margin_account: Pubkey, destination: Pubkey) -> Instruction {
+        let accounts = ix_accounts::CloseOpenOrders {
+            margin_account,
+            market_info: self.market_info,
+            market: self.market.market,
+            open_orders: self.open_orders(&margin_account),
+            destination,
+            base_note_mint: self.base_note_mint,
+            quote_note_mint: self.quote_note_mint,
+            base_notes: self.base_notes(&margin_account),
+            quote_notes: self.quote_notes(&margin_account),
+            dex_program: Dex::id(),
+            token_program: Token::id(),
+        }
+        .to_account_metas(None);
+
+        Instruction {
+            program_id: jet_margin_serum::ID,
+            data: ix_data::CloseOpenOrders {}.data(),
+            accounts,
+        }
+    }
+
+    /// Update the positions of a margin account after its orders were filled.
+    /// Should be wrapped in an accounting invoke.
+    pub fn refresh_open_orders(&self, margin_account: Pubkey) -> Instruction {
+        let accounts = ix_accounts::RefreshOpenOrders {
+            margin_account,
+            positions: self.positions(&margin_account),
+            open_orders: self.open_orders(&margin_account),
+        }
+        .to_account_metas(None);
+
+        Instruction {
+            program_id: jet_margin_serum::ID,
+            data: ix_data::RefreshOpenOrders {}.data(),
+            accounts,
+        }
+    }
+
+    fn positions(&self, margin_account: &Pubkey) -> ix_accounts::OpenOrdersPositions {
+        ix_accounts::OpenOrdersPositions {
+            market_info: self.market_info,
+            base_note_mint: self.base_note_mint,
+            quote_note_mint: self.quote_note_mint,
+            base_notes: self.base_notes(margin_account),
+            quote_notes: self.quote_notes(margin_account),
+            base_oracle: self.base_oracle,
+            quote_oracle: self.quote_oracle,
+            token_program: Token::id(),
+        }
+    }
+
+    fn cancel_order_accounts(&self, margin_account: Pubkey) -> Vec<AccountMeta> {
+        ix_accounts::CancelOrderV2 {
+            margin_account,
+            positions: self.positions(&margin_account),
+            market: self.market.market,
+            open_orders: self.open_orders(&margin_account),
+            bids: self.market.bids,
+            asks: self.market.asks,
+            event_queue: self.market.event_queue,
+            dex_program: Dex::id(),
+        }
+        .to_account_metas(None)
+    }
+}
+
+/// Derive the address of the registration of a serum market
+pub fn derive_market_info(airspace: &Pubkey, market: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(&[airspace.as_ref(), market.as_ref()], &jet_margin_serum::ID).0
+}
+
+fn derive_note_mint(market_info: &Pubkey, seed: &[u8]) -> Pubkey {
+    Pubkey::find_program_address(&[market_info.as_ref(), seed], &jet_margin_serum::ID).0
+}
+
+fn derive_note_account(margin_account: &Pubkey, note_mint: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(
+        &[margin_account.as_ref(), note_mint.as_ref()],
+        &jet_margin_serum::ID,
+    )
+    .0
+}
@@ -15,17 +15,75 @@
 // You should have received a copy of the GNU Affero General Public License
 // along with this program.  If not, see <https://www.gnu.org/licenses/>.
 
-use solana_sdk::instruction::Instruction;
+use solana_sdk::instruction::{AccountMeta, Instruction};
 use solana_sdk::pubkey::Pubkey;
 
 use anchor_lang::{Id, InstructionData, ToAccountMetas};
+use anchor_spl::associated_token::get_associated_token_address;
 use anchor_spl::token::Token;
 
 use jet_margin_swap::accounts as ix_accounts;
 use jet_margin_swap::instruction as ix_data;
+use jet_margin_swap::SwapEndpoint;
 
 use crate::ix_builder::MarginPoolIxBuilder;
 use crate::jet_margin_pool::TokenChange;
+use crate::spl_swap::SwapRoute;
+
+/// One side of a swap made with [MarginSwapIxBuilder::swap_positions]
+#[derive(Clone, Copy)]
+pub enum SwapPosition<'a> {
+    /// Deposit notes of a margin pool
+    MarginPool {
+        /// The margin pool of the token
+        pool: &'a MarginPoolIxBuilder,
+        /// The margin account's position holding the pool's deposit notes
+        position: Pubkey,
+        /// The margin account's token account that tokens move through
+        transit: Pubkey,
+    },
+    /// Tokens held directly in a deposit position of the margin account
+    Deposit {
+        /// The token account of the deposit position
+        account: Pubkey,
+    },
+}
+
+impl<'a> SwapPosition<'a> {
+    fn endpoint(&self) -> SwapEndpoint {
+        match self {
+            SwapPosition::MarginPool { .. } => SwapEndpoint::MarginPool,
+            SwapPosition::Deposit { .. } => SwapEndpoint::Deposit,
+        }
+    }
+
+    /// The position account and the transit account
+    fn accounts(&self) -> (Pubkey, Pubkey) {
+        match *self {
+            SwapPosition::MarginPool {
+                position, transit, ..
+            } => (position, transit),
+            SwapPosition::Deposit { account } => (account, account),
+        }
+    }
+
+    /// The margin pool accounts, which are unused for a deposit, so the deposit
+    /// account takes their place
+    fn pool_info(&self) -> ix_accounts::MarginPoolInfo {
+        match *self {
+            SwapPosition::MarginPool { pool, .. } => ix_accounts::MarginPoolInfo {
+                margin_pool: pool.address,
+                vault: pool.vault,
+                deposit_note_mint: pool.deposit_note_mint,
+            },
+            SwapPosition::Deposit { account } => ix_accounts::MarginPoolInfo {
+                margin_pool: account,
+                vault: account,
+                deposit_note_mint: account,
+            },
+        }
+    }
+}
 
 /// Builder for creating instructions to interact with the margin swap program.
 pub struct MarginSwapIxBuilder {
@@ -88,20 +146,66 @@ impl MarginSwapIxBuilder {
         change: TokenChange,
         minimum_amount_out: u64,
     ) -> Instruction {
-        let accounts = ix_accounts::MarginSplSwap {
+        let accounts = self
+            .swap_accounts(
+                margin_account,
+                transit_src_account,
+                transit_dst_account,
+                source_margin_position,
+                destination_margin_position,
+                source_token_account,
+                destination_token_account,
+                swap_program,
+                source_pool,
+                destination_pool,
+            )
+            .to_account_metas(None);
+
+        let TokenChange { kind, tokens } = change;
+        Instruction {
+            program_id: jet_margin_swap::id(),
+            data: ix_data::MarginSwap {
+                withdrawal_change_kind: kind,
+                withdrawal_amount: tokens,
+                minimum_amount_out,
+            }
+            .data(),
+            accounts,
+        }
+    }
+
+    /// Swap through every pool of a route, depositing the output of the final pool.
+    ///
+    /// The output of each intermediate pool is received by the margin account's
+    /// associated token account for that token, which must already exist.
+    #[allow(clippy::too_many_arguments)]
+    pub fn route_swap(
+        margin_account: Pubkey,
+        transit_src_account: Pubkey,
+        transit_dst_account: Pubkey,
+        source_margin_position: Pubkey,
+        destination_margin_position: Pubkey,
+        route: &SwapRoute,
+        source_pool: &MarginPoolIxBuilder,
+        destination_pool: &MarginPoolIxBuilder,
+        change: TokenChange,
+        minimum_amount_out: u64,
+    ) -> Instruction {
+        let first_hop = &route.hops[0];
+        let mut accounts = ix_accounts::MarginSplSwap {
             margin_account,
             source_account: source_margin_position,
             destination_account: destination_margin_position,
             transit_source_account: transit_src_account,
             transit_destination_account: transit_dst_account,
             swap_info: ix_accounts::SwapInfo {
-                swap_pool: self.swap_pool,
-                authority: self.swap_pool_authority,
-                vault_into: source_token_account,
-                vault_from: destination_token_account,
-                token_mint: self.pool_mint,
-                fee_account: self.fee_account,
-                swap_program,
+                swap_pool: first_hop.pool.pool,
+                authority: first_hop.pool.pool_authority,
+                vault_into: first_hop.vault_into(),
+                vault_from: first_hop.vault_from(),
+                token_mint: first_hop.pool.pool_mint,
+                fee_account: first_hop.pool.fee_account,
+                swap_program: first_hop.pool.program,
             },
             source_margin_pool: ix_accounts::MarginPoolInfo {
                 margin_pool: source_pool.address,
@@ -118,10 +222,26 @@ impl MarginSwapIxBuilder {
         }
         .to_account_metas(None);
 
+        for hop in &route.hops[1..] {
+            accounts.extend([
+                AccountMeta::new(
+                    get_associated_token_address(&margin_account, &hop.source_mint),
+                    false,
+                ),
+                AccountMeta::new_readonly(hop.pool.pool, false),
+                AccountMeta::new_readonly(hop.pool.pool_authority, false),
+                AccountMeta::new(hop.vault_into(), false),
+                AccountMeta::new(hop.vault_from(), false),
+                AccountMeta::new(hop.pool.pool_mint, false),
+                AccountMeta::new(hop.pool.fee_account, false),
+                AccountMeta::new_readonly(hop.pool.program, false),
+            ]);
+        }
+
         let TokenChange { kind, tokens } = change;
         Instruction {
             program_id: jet_margin_swap::id(),
-            data: ix_data::MarginSwap {
+            data: ix_data::RouteSwap {
                 withdrawal_change_kind: kind,
                 withdrawal_amount: tokens,
                 minimum_amount_out,
@@ -130,4 +250,212 @@ impl MarginSwapIxBuilder {
             accounts,
         }
     }
+
+    /// Swap from one token to another, where each side can be either a margin
+    /// pool deposit or a deposit position.
+    ///
+    /// The source token determines the direction of the swap.
+    #[allow(clippy::too_many_arguments)]
+    pub fn swap_positions(
+        &self,
+        margin_account: Pubkey,
+        source: SwapPosition,
+        destination: SwapPosition,
+        // swap pool token_a
+        source_token_account: Pubkey,
+        // swap pool token_b
+        destination_token_account: Pubkey,
+        swap_program: Pubkey,
+        change: TokenChange,
+        minimum_amount_out: u64,
+    ) -> Instruction {
+        let (source_account, transit_source_account) = source.accounts();
+        let (destination_account, transit_destination_account) = destination.accounts();
+
+        let accounts = ix_accounts::MarginSplSwap {
+            margin_account,
+            source_account,
+            destination_account,
+            transit_source_account,
+            transit_destination_account,
+            swap_info: ix_accounts::SwapInfo {
+                swap_pool: self.swap_pool,
+                authority: self.swap_pool_authority,
+                vault_into: source_token_account,
+                vault_from: destination_token_account,
+                token_mint: self.pool_mint,
+                fee_account: self.fee_account,
+                swap_program,
+            },
+            source_margin_pool: source.pool_info(),
+            destination_margin_pool: destination.pool_info(),
+            margin_pool_program: jet_margin_pool::id(),
+            token_program: Token::id(),
+        }
+        .to_account_metas(None);
+
+        let TokenChange { kind, tokens } = change;
+        Instruction {
+            program_id: jet_margin_swap::id(),
+            data: ix_data::MarginSwapPositions {
+                source: source.endpoint(),
+                destination: destination.endpoint(),
+                change_kind: kind,
+                amount: tokens,
+                minimum_amount_out,
+            }
+            .data(),
+            accounts,
+        }
+    }
+
+    /// Borrow from the source pool and swap the borrowed tokens into the
+    /// destination pool.
+    ///
+    /// `loan_account` is the margin account's loan position in the source pool.
+    #[allow(clippy::too_many_arguments)]
+    pub fn open_leveraged_position(
+        &self,
+        margin_account: Pubkey,
+        transit_src_account: Pubkey,
+        transit_dst_account: Pubkey,
+        source_margin_position: Pubkey,
+        destination_margin_position: Pubkey,
+        loan_account: Pubkey,
+        // swap pool token_a
+        source_token_account: Pubkey,
+        // swap pool token_b
+        destination_token_account: Pubkey,
+        swap_program: Pubkey,
+        source_pool: &MarginPoolIxBuilder,
+        destination_pool: &MarginPoolIxBuilder,
+        borrow_amount: u64,
+        minimum_amount_out: u64,
+    ) -> Instruction {
+        let accounts = ix_accounts::MarginLeveragedSwap {
+            swap: self.swap_accounts(
+                margin_account,
+                transit_src_account,
+                transit_dst_account,
+                source_margin_position,
+                destination_margin_position,
+                source_token_account,
+                destination_token_account,
+                swap_program,
+                source_pool,
+                destination_pool,
+            ),
+            loan_note_mint: source_pool.loan_note_mint,
+            loan_account,
+        }
+        .to_account_metas(None);
+
+        Instruction {
+            program_id: jet_margin_swap::id(),
+            data: ix_data::OpenLeveragedPosition {
+                borrow_amount,
+                minimum_amount_out,
+            }
+            .data(),
+            accounts,
+        }
+    }
+
+    /// Withdraw from the source pool and swap into the destination pool, using
+    /// the output to repay the loan in the destination pool.
+    ///
+    /// `loan_account` is the margin account's loan position in the destination pool.
+    #[allow(clippy::too_many_arguments)]
+    pub fn close_leveraged_position(
+        &self,
+        margin_account: Pubkey,
+        transit_src_account: Pubkey,
+        transit_dst_account: Pubkey,
+        source_margin_position: Pubkey,
+        destination_margin_position: Pubkey,
+        loan_account: Pubkey,
+        // swap pool token_a
+        source_token_account: Pubkey,
+        // swap pool token_b
+        destination_token_account: Pubkey,
+        swap_program: Pubkey,
+        source_pool: &MarginPoolIxBuilder,
+        destination_pool: &MarginPoolIxBuilder,
+        change: TokenChange,
+        minimum_amount_out: u64,
+    ) -> Instruction {
+        let accounts = ix_accounts::MarginLeveragedSwap {
+            swap: self.swap_accounts(
+                margin_account,
+                transit_src_account,
+                transit_dst_account,
+                source_margin_position,
+                destination_margin_position,
+                source_token_account,
+                destination_token_account,
+                swap_program,
+                source_pool,
+                destination_pool,
+            ),
+            loan_note_mint: destination_pool.loan_note_mint,
+            loan_account,
+        }
+        .to_account_metas(None);
+
+        let TokenChange { kind, tokens } = change;
+        Instruction {
+            program_id: jet_margin_swap::id(),
+            data: ix_data::CloseLeveragedPosition {
+                withdrawal_change_kind: kind,
+                withdrawal_amount: tokens,
+                minimum_amount_out,
+            }
+            .data(),
+            accounts,
+        }
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    fn swap_accounts(
+        &self,
+        margin_account: Pubkey,
+        transit_src_account: Pubkey,
+        transit_dst_account: Pubkey,
+        source_margin_position: Pubkey,
+        destination_margin_position: Pubkey,
+        source_token_account: Pubkey,
+        destination_token_account: Pubkey,
+        swap_program: Pubkey,
+        source_pool: &MarginPoolIxBuilder,
+        destination_pool: &MarginPoolIxBuilder,
+    ) -> ix_accounts::MarginSplSwap {
+        ix_accounts::MarginSplSwap {
+            margin_account,
+            source_account: source_margin_position,
+            destination_account: destination_margin_position,
+            transit_source_account: transit_src_account,
+            transit_destination_account: transit_dst_account,
+            swap_info: ix_accounts::SwapInfo {
+                swap_pool: self.swap_pool,
+                authority: self.swap_pool_authority,
+                vault_into: source_token_account,
+                vault_from: destination_token_account,
+                token_mint: self.pool_mint,
+                fee_account: self.fee_account,
+                swap_program,
+            },
+            source_margin_pool: ix_accounts::MarginPoolInfo {
+                margin_pool: source_pool.address,
+                vault: source_pool.vault,
+                deposit_note_mint: source_pool.deposit_note_mint,
+            },
+            destination_margin_pool: ix_accounts::MarginPoolInfo {
+                margin_pool: destination_pool.address,
+                vault: destination_pool.vault,
+                deposit_note_mint: destination_pool.deposit_note_mint,
+            },
+            margin_pool_program: jet_margin_pool::id(),
+            token_program: Token::id(),
+        }
+    }
 }
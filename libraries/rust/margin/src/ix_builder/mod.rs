@@ -21,6 +21,7 @@ mod airspace;
 mod control;
 mod margin;
 mod margin_pool;
+mod margin_serum;
 mod margin_swap;
 
 /// Instruction builder for the protocol test service
@@ -30,6 +31,7 @@ pub use airspace::*;
 pub use control::*;
 pub use margin::*;
 pub use margin_pool::*;
+pub use margin_serum::*;
 pub use margin_swap::*;
 
 /// Get the address of a [jet_metadata] account.
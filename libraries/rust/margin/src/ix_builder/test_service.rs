@@ -106,6 +106,8 @@ pub fn token_update_pyth_price(
     mint: &Pubkey,
     price: i64,
     conf: i64,
+    ema_price: i64,
+    ema_conf: i64,
     expo: i32,
 ) -> Instruction {
     let accounts = jet_test_service::accounts::TokenUpdatePythPrice {
@@ -118,7 +120,14 @@ pub fn token_update_pyth_price(
     Instruction {
         program_id: jet_test_service::ID,
         accounts,
-        data: jet_test_service::instruction::TokenUpdatePythPrice { price, conf, expo }.data(),
+        data: jet_test_service::instruction::TokenUpdatePythPrice {
+            price,
+            conf,
+            ema_price,
+            ema_conf,
+            expo,
+        }
+        .data(),
     }
 }
 
@@ -51,6 +51,8 @@
 
 #![deny(missing_docs)]
 
+/// Health history for margin accounts
+pub mod health;
 /// Instruction builders for programs and adapters supported by the SDK
 pub mod ix_builder;
 /// generic code to integrate adapters with margin
@@ -77,6 +79,7 @@ pub use jet_bonds;
 pub use jet_control;
 pub use jet_margin;
 pub use jet_margin_pool;
+pub use jet_margin_serum;
 pub use jet_margin_swap;
 pub use jet_metadata;
 pub use jet_test_service;
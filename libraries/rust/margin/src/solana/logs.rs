@@ -0,0 +1,95 @@
+use solana_sdk::pubkey::Pubkey;
+
+/// The prefix of the log lines that contain event data emitted by a program
+pub const PROGRAM_DATA_PREFIX: &str = "Program data: ";
+
+/// Find the data for all events emitted in a transaction, along with the program that
+/// emitted each one.
+///
+/// Programs are tracked from the invoke and completion lines of the logs, so an event
+/// is attributed to the innermost program that was executing when it was logged.
+pub fn find_event_data(logs: &[String]) -> Vec<(Pubkey, Vec<u8>)> {
+    let mut invoke_stack = vec![];
+    let mut events = vec![];
+
+    for line in logs {
+        let words = line.split_whitespace().collect::<Vec<_>>();
+
+        match words[..] {
+            ["Program", program, "invoke", _] => {
+                if let Ok(program) = program.parse::<Pubkey>() {
+                    invoke_stack.push(program);
+                }
+            }
+            ["Program", _, "success"] | ["Program", _, "failed:", ..] => {
+                invoke_stack.pop();
+            }
+            _ => (),
+        }
+
+        if let Some(encoded) = line.strip_prefix(PROGRAM_DATA_PREFIX) {
+            if let (Some(program), Ok(data)) = (invoke_stack.last(), base64::decode(encoded)) {
+                events.push((*program, data));
+            }
+        }
+    }
+
+    events
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn logs(lines: &[&str]) -> Vec<String> {
+        lines.iter().map(|line| line.to_string()).collect()
+    }
+
+    #[test]
+    fn events_are_attributed_to_the_invoked_program() {
+        let outer = Pubkey::new_unique();
+        let inner = Pubkey::new_unique();
+        let logs = logs(&[
+            &format!("Program {outer} invoke [1]"),
+            &format!("Program data: {}", base64::encode([1, 2])),
+            &format!("Program {inner} invoke [2]"),
+            &format!("Program data: {}", base64::encode([3])),
+            &format!("Program {inner} consumed 100 of 200000 compute units"),
+            &format!("Program {inner} success"),
+            &format!("Program data: {}", base64::encode([4])),
+            &format!("Program {outer} success"),
+        ]);
+
+        assert_eq!(
+            find_event_data(&logs),
+            vec![(outer, vec![1, 2]), (inner, vec![3]), (outer, vec![4])]
+        );
+    }
+
+    #[test]
+    fn events_after_a_failed_invoke_use_the_caller() {
+        let outer = Pubkey::new_unique();
+        let inner = Pubkey::new_unique();
+        let logs = logs(&[
+            &format!("Program {outer} invoke [1]"),
+            &format!("Program {inner} invoke [2]"),
+            &format!("Program {inner} failed: custom program error: 0x1"),
+            &format!("Program data: {}", base64::encode([5])),
+        ]);
+
+        assert_eq!(find_event_data(&logs), vec![(outer, vec![5])]);
+    }
+
+    #[test]
+    fn invalid_event_data_is_skipped() {
+        let program = Pubkey::new_unique();
+        let logs = logs(&[
+            "Program data: AQI=",
+            &format!("Program {program} invoke [1]"),
+            "Program data: not base64!",
+            "Program log: Program data: AQI=",
+        ]);
+
+        assert_eq!(find_event_data(&logs), vec![]);
+    }
+}
@@ -1,3 +1,5 @@
+/// find the events emitted in transaction logs
+pub mod logs;
 /// construct transactions out of instructions
 pub mod transaction;
 
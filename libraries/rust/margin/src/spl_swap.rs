@@ -159,6 +159,243 @@ impl SplSwapPool {
     }
 }
 
+/// One exchange along a [SwapRoute]
+#[derive(Debug, Clone, Copy)]
+pub struct SwapRouteHop {
+    /// The pool that the tokens are swapped through
+    pub pool: SplSwapPool,
+    /// The mint of the tokens that are sent to the pool
+    pub source_mint: Pubkey,
+    /// The mint of the tokens that are received from the pool
+    pub destination_mint: Pubkey,
+}
+
+impl SwapRouteHop {
+    /// The pool vault that receives the tokens being swapped
+    pub fn vault_into(&self) -> Pubkey {
+        if self.source_mint == self.pool.mint_a {
+            self.pool.token_a
+        } else {
+            self.pool.token_b
+        }
+    }
+
+    /// The pool vault that sends the swapped tokens
+    pub fn vault_from(&self) -> Pubkey {
+        if self.source_mint == self.pool.mint_a {
+            self.pool.token_b
+        } else {
+            self.pool.token_a
+        }
+    }
+}
+
+/// A sequence of swap pools that exchanges one token for another, used with
+/// the `route_swap` instruction of [`jet_margin_swap`]
+#[derive(Debug, Clone)]
+pub struct SwapRoute {
+    /// The pools to swap through, in order
+    pub hops: Vec<SwapRouteHop>,
+    /// The output of the route, estimated from the pool balances when it was found
+    pub expected_amount_out: u64,
+}
+
+impl SwapRoute {
+    /// The mint of the tokens going into the route
+    pub fn source_mint(&self) -> Pubkey {
+        self.hops[0].source_mint
+    }
+
+    /// The mint of the tokens coming out of the route
+    pub fn destination_mint(&self) -> Pubkey {
+        self.hops[self.hops.len() - 1].destination_mint
+    }
+}
+
+impl SplSwapPool {
+    /// Find the route through the supplied pools with the highest output for
+    /// swapping `amount_in` tokens, passing through at most `max_hops` pools.
+    /// Routes are never longer than [jet_margin_swap::MAX_ROUTE_HOPS], which is
+    /// the most that the swap program accepts.
+    ///
+    /// The pools would usually be found with [SplSwapPool::get_pools]. The output of
+    /// each pool is estimated from its current balances and trading fees.
+    pub async fn find_route(
+        rpc: &Arc<dyn SolanaRpcClient>,
+        pools: &HashMap<(Pubkey, Pubkey), Self>,
+        source_mint: Pubkey,
+        destination_mint: Pubkey,
+        amount_in: u64,
+        max_hops: usize,
+    ) -> anyhow::Result<Option<SwapRoute>> {
+        let mut quotes = HashMap::with_capacity(pools.len());
+        for pool in pools.values() {
+            quotes.insert(pool.pool, PoolQuote::load(rpc, pool).await?);
+        }
+
+        Ok(best_route(
+            pools,
+            &quotes,
+            source_mint,
+            destination_mint,
+            amount_in,
+            max_hops,
+        ))
+    }
+}
+
+/// The balances and fees of a swap pool, used to estimate the output of a swap
+struct PoolQuote {
+    balance_a: u64,
+    balance_b: u64,
+    fee_numerator: u64,
+    fee_denominator: u64,
+}
+
+impl PoolQuote {
+    async fn load(rpc: &Arc<dyn SolanaRpcClient>, pool: &SplSwapPool) -> Result<Self> {
+        let account = rpc
+            .get_account(&pool.pool)
+            .await?
+            .ok_or_else(|| anyhow::anyhow!("swap pool {} does not exist", pool.pool))?;
+        let swap = SwapV1::unpack(&account.data[1..])?;
+
+        // combine the trade and owner fees into a single rate
+        let fees = &swap.fees;
+        let fee_denominator =
+            fees.trade_fee_denominator.max(1) * fees.owner_trade_fee_denominator.max(1);
+        let fee_numerator = fees.trade_fee_numerator * fees.owner_trade_fee_denominator.max(1)
+            + fees.owner_trade_fee_numerator * fees.trade_fee_denominator.max(1);
+
+        Ok(Self {
+            balance_a: find_token(rpc, &pool.token_a).await?.amount,
+            balance_b: find_token(rpc, &pool.token_b).await?.amount,
+            fee_numerator,
+            fee_denominator,
+        })
+    }
+
+    /// Estimate the output of a constant product swap
+    fn amount_out(&self, pool: &SplSwapPool, source_mint: &Pubkey, amount_in: u64) -> u64 {
+        let (balance_in, balance_out) = if *source_mint == pool.mint_a {
+            (self.balance_a as u128, self.balance_b as u128)
+        } else {
+            (self.balance_b as u128, self.balance_a as u128)
+        };
+
+        let fee_denominator = self.fee_denominator as u128;
+        let fee = (amount_in as u128 * self.fee_numerator as u128 + fee_denominator - 1)
+            / fee_denominator;
+        let amount_in = (amount_in as u128).saturating_sub(fee);
+
+        if balance_in + amount_in == 0 {
+            return 0;
+        }
+
+        (balance_out * amount_in / (balance_in + amount_in)) as u64
+    }
+}
+
+/// The route with the highest output, out of all routes with at most `max_hops` pools
+fn best_route(
+    pools: &HashMap<(Pubkey, Pubkey), SplSwapPool>,
+    quotes: &HashMap<Pubkey, PoolQuote>,
+    source_mint: Pubkey,
+    destination_mint: Pubkey,
+    amount_in: u64,
+    max_hops: usize,
+) -> Option<SwapRoute> {
+    let mut best: Option<SwapRoute> = None;
+    let mut path = vec![];
+    let mut visited = HashSet::from([source_mint]);
+
+    find_routes(
+        pools,
+        quotes,
+        source_mint,
+        destination_mint,
+        amount_in,
+        max_hops.min(jet_margin_swap::MAX_ROUTE_HOPS),
+        &mut path,
+        &mut visited,
+        &mut |hops, amount_out| {
+            if best
+                .as_ref()
+                .map(|b| amount_out > b.expected_amount_out)
+                .unwrap_or(true)
+            {
+                best = Some(SwapRoute {
+                    hops: hops.to_vec(),
+                    expected_amount_out: amount_out,
+                });
+            }
+        },
+    );
+
+    best
+}
+
+#[allow(clippy::too_many_arguments)]
+fn find_routes(
+    pools: &HashMap<(Pubkey, Pubkey), SplSwapPool>,
+    quotes: &HashMap<Pubkey, PoolQuote>,
+    source_mint: Pubkey,
+    destination_mint: Pubkey,
+    amount_in: u64,
+    max_hops: usize,
+    path: &mut Vec<SwapRouteHop>,
+    visited: &mut HashSet<Pubkey>,
+    on_route: &mut dyn FnMut(&[SwapRouteHop], u64),
+) {
+    if path.len() == max_hops {
+        return;
+    }
+
+    for ((mint_a, mint_b), pool) in pools {
+        let next_mint = if *mint_a == source_mint {
+            *mint_b
+        } else if *mint_b == source_mint {
+            *mint_a
+        } else {
+            continue;
+        };
+        if visited.contains(&next_mint) {
+            continue;
+        }
+
+        let amount_out = quotes[&pool.pool].amount_out(pool, &source_mint, amount_in);
+        if amount_out == 0 {
+            continue;
+        }
+
+        path.push(SwapRouteHop {
+            pool: *pool,
+            source_mint,
+            destination_mint: next_mint,
+        });
+
+        if next_mint == destination_mint {
+            on_route(path, amount_out);
+        } else {
+            visited.insert(next_mint);
+            find_routes(
+                pools,
+                quotes,
+                next_mint,
+                destination_mint,
+                amount_out,
+                max_hops,
+                path,
+                visited,
+                on_route,
+            );
+            visited.remove(&next_mint);
+        }
+
+        path.pop();
+    }
+}
+
 // helper function to find token account
 async fn find_token(
     rpc: &Arc<dyn SolanaRpcClient>,
@@ -182,3 +419,165 @@ async fn find_mint(
 
     Ok(account)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A pool between two mints, with its balances and fee in basis points
+    fn pool(
+        pools: &mut HashMap<(Pubkey, Pubkey), SplSwapPool>,
+        quotes: &mut HashMap<Pubkey, PoolQuote>,
+        (mint_a, mint_b): (Pubkey, Pubkey),
+        (balance_a, balance_b): (u64, u64),
+        fee_bps: u64,
+    ) {
+        let pool = SplSwapPool {
+            pool: Pubkey::new_unique(),
+            pool_authority: Pubkey::new_unique(),
+            pool_mint: Pubkey::new_unique(),
+            mint_a,
+            mint_b,
+            token_a: Pubkey::new_unique(),
+            token_b: Pubkey::new_unique(),
+            fee_account: Pubkey::new_unique(),
+            program: spl_token_swap::id(),
+        };
+        quotes.insert(
+            pool.pool,
+            PoolQuote {
+                balance_a,
+                balance_b,
+                fee_numerator: fee_bps,
+                fee_denominator: 10_000,
+            },
+        );
+        pools.insert((mint_a, mint_b), pool);
+    }
+
+    fn mints<const N: usize>() -> [Pubkey; N] {
+        [(); N].map(|_| Pubkey::new_unique())
+    }
+
+    fn route_mints(route: &SwapRoute) -> Vec<Pubkey> {
+        std::iter::once(route.source_mint())
+            .chain(route.hops.iter().map(|hop| hop.destination_mint))
+            .collect()
+    }
+
+    #[test]
+    fn fees_reduce_the_output() {
+        let [a, b] = mints();
+        let (mut pools, mut quotes) = (HashMap::new(), HashMap::new());
+        pool(&mut pools, &mut quotes, (a, b), (1_000_000, 2_000_000), 30);
+        let swap = pools[&(a, b)];
+        let quote = &quotes[&swap.pool];
+
+        // 30 of the 10_000 are taken as fees
+        assert_eq!(19_743, quote.amount_out(&swap, &a, 10_000));
+        assert_eq!(4_960, quote.amount_out(&swap, &b, 10_000));
+
+        // fees are rounded up
+        assert_eq!(0, quote.amount_out(&swap, &a, 1));
+
+        let free = PoolQuote {
+            fee_numerator: 0,
+            ..*quote
+        };
+        assert_eq!(19_801, free.amount_out(&swap, &a, 10_000));
+    }
+
+    #[test]
+    fn best_route_has_the_highest_output() {
+        let [a, b, c] = mints();
+        let (mut pools, mut quotes) = (HashMap::new(), HashMap::new());
+        pool(&mut pools, &mut quotes, (a, b), (10_000, 10_000), 0);
+        pool(&mut pools, &mut quotes, (a, c), (1_000_000, 1_000_000), 0);
+        pool(&mut pools, &mut quotes, (c, b), (1_000_000, 1_000_000), 0);
+
+        // the direct pool is too shallow, so two hops through deeper pools are better
+        let route = best_route(&pools, &quotes, a, b, 1_000, 3).unwrap();
+        assert_eq!(vec![a, c, b], route_mints(&route));
+        assert_eq!(998, route.expected_amount_out);
+
+        // unless the route is limited to one hop
+        let route = best_route(&pools, &quotes, a, b, 1_000, 1).unwrap();
+        assert_eq!(vec![a, b], route_mints(&route));
+        assert_eq!(909, route.expected_amount_out);
+
+        // routes can be found in either direction of a pool
+        let route = best_route(&pools, &quotes, b, a, 1_000, 3).unwrap();
+        assert_eq!(vec![b, c, a], route_mints(&route));
+    }
+
+    #[test]
+    fn best_route_accounts_for_fees() {
+        let [a, b, c] = mints();
+        let (mut pools, mut quotes) = (HashMap::new(), HashMap::new());
+        pool(&mut pools, &mut quotes, (a, b), (1_000_000, 1_000_000), 100);
+        pool(&mut pools, &mut quotes, (a, c), (1_000_000, 1_000_000), 0);
+        pool(&mut pools, &mut quotes, (c, b), (1_000_000, 1_000_000), 0);
+
+        let route = best_route(&pools, &quotes, a, b, 1_000, 3).unwrap();
+        assert_eq!(vec![a, c, b], route_mints(&route));
+
+        // without the fee, the direct pool has less slippage
+        quotes.get_mut(&pools[&(a, b)].pool).unwrap().fee_numerator = 0;
+        let route = best_route(&pools, &quotes, a, b, 1_000, 3).unwrap();
+        assert_eq!(vec![a, b], route_mints(&route));
+    }
+
+    #[test]
+    fn routes_do_not_cycle() {
+        let [a, b, c, d] = mints();
+        let (mut pools, mut quotes) = (HashMap::new(), HashMap::new());
+        pool(&mut pools, &mut quotes, (a, b), (1_000_000, 1_000_000), 0);
+        pool(&mut pools, &mut quotes, (b, c), (1_000_000, 1_000_000), 0);
+        pool(&mut pools, &mut quotes, (c, a), (1_000_000, 1_000_000), 0);
+        pool(&mut pools, &mut quotes, (c, d), (1_000_000, 1_000_000), 0);
+
+        let mut routes = vec![];
+        find_routes(
+            &pools,
+            &quotes,
+            a,
+            d,
+            1_000,
+            usize::MAX,
+            &mut vec![],
+            &mut HashSet::from([a]),
+            &mut |hops, _| {
+                routes.push(
+                    std::iter::once(a)
+                        .chain(hops.iter().map(|hop| hop.destination_mint))
+                        .collect::<Vec<_>>(),
+                )
+            },
+        );
+        routes.sort_by_key(|route| route.len());
+
+        // the only routes are a, c, d and a, b, c, d, even without a hop limit
+        assert_eq!(vec![vec![a, c, d], vec![a, b, c, d]], routes);
+
+        // a route back to the source is never found
+        assert!(best_route(&pools, &quotes, a, a, 1_000, 3).is_none());
+    }
+
+    #[test]
+    fn hops_are_limited_by_the_swap_program() {
+        let [a, b, c, d, e] = mints();
+        let (mut pools, mut quotes) = (HashMap::new(), HashMap::new());
+        for pair in [(a, b), (b, c), (c, d), (d, e)] {
+            pool(&mut pools, &mut quotes, pair, (1_000_000, 1_000_000), 0);
+        }
+
+        assert_eq!(
+            jet_margin_swap::MAX_ROUTE_HOPS,
+            best_route(&pools, &quotes, a, d, 1_000, 10)
+                .unwrap()
+                .hops
+                .len()
+        );
+        assert!(best_route(&pools, &quotes, a, e, 1_000, 10).is_none());
+    }
+}
@@ -113,6 +113,10 @@ pub struct MarginPoolConfig {
 
     /// The fee rate applied to interest payments collected
     pub management_fee_rate: u16,
+
+    /// The maximum deviation of the oracle price from its EMA, in basis points
+    #[serde(default)]
+    pub max_price_deviation: u16,
 }
 
 impl From<MarginPoolConfig> for jet_margin_pool::MarginPoolConfig {
@@ -126,6 +130,7 @@ impl From<MarginPoolConfig> for jet_margin_pool::MarginPoolConfig {
             borrow_rate_2: config.borrow_rate_2,
             borrow_rate_3: config.borrow_rate_3,
             management_fee_rate: config.management_fee_rate,
+            max_price_deviation: config.max_price_deviation,
             ..Default::default()
         }
     }
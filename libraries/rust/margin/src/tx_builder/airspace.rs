@@ -21,10 +21,11 @@ use crate::{
     bonds::BondsIxBuilder,
     ix_builder::{
         derive_airspace, AirspaceIxBuilder, ControlIxBuilder, MarginConfigIxBuilder,
-        MarginPoolConfiguration,
+        MarginPoolConfiguration, MarginPoolIxBuilder, MarginSerumIxBuilder,
     },
     solana::transaction::TransactionBuilder,
 };
+use jet_airspace::state::AirspacePermit;
 use jet_margin::{TokenAdmin, TokenConfigUpdate, TokenKind, TokenOracle};
 
 /// Utility for constructing transactions for administrative functions on protocol
@@ -54,8 +55,21 @@ impl AirspaceAdmin {
     }
 
     /// Create a permit for a user to be allowed to use this airspace
-    pub fn issue_user_permit(&self, user: Pubkey) -> TransactionBuilder {
-        vec![self.as_ix.permit_create(user)].into()
+    ///
+    /// The permit expires at the `expires_at` unix timestamp, or never if zero.
+    pub fn issue_user_permit(&self, user: Pubkey, expires_at: i64, tier: u8) -> TransactionBuilder {
+        vec![self.as_ix.permit_create(user, expires_at, tier)].into()
+    }
+
+    /// Renew or change the tier of a permit previously issued for a user
+    pub fn update_user_permit(
+        &self,
+        user: Pubkey,
+        issuer: Pubkey,
+        expires_at: i64,
+        tier: u8,
+    ) -> TransactionBuilder {
+        vec![self.as_ix.permit_update(user, issuer, expires_at, tier)].into()
     }
 
     /// Revoke a previously issued permit for a user, preventing them from continuing to
@@ -67,7 +81,40 @@ impl AirspaceAdmin {
     /// Create a new margin pool for a given token
     pub fn create_margin_pool(&self, token_mint: Pubkey) -> TransactionBuilder {
         let ctrl_ix_builder = ControlIxBuilder::new_for_authority(self.authority, self.payer);
-        vec![ctrl_ix_builder.create_margin_pool(&token_mint)].into()
+        vec![ctrl_ix_builder.create_margin_pool(&self.airspace, &token_mint)].into()
+    }
+
+    /// Create a new margin pool for a given token, along with token metadata for its
+    /// deposit notes so that wallets can identify them.
+    pub fn create_margin_pool_with_metadata(
+        &self,
+        token_mint: Pubkey,
+        name: String,
+        symbol: String,
+        uri: String,
+    ) -> TransactionBuilder {
+        let ctrl_ix_builder = ControlIxBuilder::new_for_authority(self.authority, self.payer);
+        let pool = MarginPoolIxBuilder::new(self.airspace, token_mint);
+
+        vec![
+            ctrl_ix_builder.create_margin_pool(&self.airspace, &token_mint),
+            pool.create_deposit_note_metadata(self.authority, self.payer, name, symbol, uri),
+        ]
+        .into()
+    }
+
+    /// Create token metadata for the deposit notes of an existing margin pool for a
+    /// given token, so that wallets can identify them.
+    pub fn create_deposit_note_metadata(
+        &self,
+        token_mint: Pubkey,
+        name: String,
+        symbol: String,
+        uri: String,
+    ) -> TransactionBuilder {
+        let pool = MarginPoolIxBuilder::new(self.airspace, token_mint);
+        vec![pool.create_deposit_note_metadata(self.authority, self.payer, name, symbol, uri)]
+            .into()
     }
 
     /// Configure a margin pool for the given token.
@@ -82,37 +129,44 @@ impl AirspaceAdmin {
         // FIXME: remove control legacy
         let ctrl_ix_builder = ControlIxBuilder::new_for_authority(self.authority, self.payer);
 
-        instructions.push(ctrl_ix_builder.configure_margin_pool(&token_mint, config));
+        instructions.push(ctrl_ix_builder.configure_margin_pool(
+            &self.airspace,
+            &token_mint,
+            config,
+        ));
 
         if let Some(metadata) = &config.metadata {
-            let mut deposit_note_config_update = TokenConfigUpdate {
+            let pool = MarginPoolIxBuilder::new(self.airspace, token_mint);
+
+            let deposit_note_config_update = TokenConfigUpdate {
                 admin: TokenAdmin::Adapter(jet_margin_pool::ID),
                 underlying_mint: token_mint,
                 token_kind: metadata.token_kind.into(),
                 value_modifier: metadata.collateral_weight,
                 max_staleness: 0,
+                conservative_valuation: false,
+                category: 0,
+                min_permit_tier: AirspacePermit::TIER_RETAIL,
             };
 
-            let mut loan_note_config_update = TokenConfigUpdate {
+            let loan_note_config_update = TokenConfigUpdate {
                 admin: TokenAdmin::Adapter(jet_margin_pool::ID),
                 underlying_mint: token_mint,
                 token_kind: TokenKind::Claim,
                 value_modifier: metadata.max_leverage,
                 max_staleness: 0,
+                conservative_valuation: false,
+                category: 0,
+                min_permit_tier: AirspacePermit::TIER_RETAIL,
             };
 
-            if let Some(metadata) = &config.metadata {
-                deposit_note_config_update.token_kind = metadata.token_kind.into();
-                deposit_note_config_update.value_modifier = metadata.collateral_weight;
-                loan_note_config_update.value_modifier = metadata.max_leverage;
-            }
-
             instructions.push(
                 margin_config_ix_builder
-                    .configure_token(token_mint, Some(deposit_note_config_update)),
+                    .configure_token(pool.deposit_note_mint, Some(deposit_note_config_update)),
             );
             instructions.push(
-                margin_config_ix_builder.configure_token(token_mint, Some(loan_note_config_update)),
+                margin_config_ix_builder
+                    .configure_token(pool.loan_note_mint, Some(loan_note_config_update)),
             );
         }
 
@@ -131,6 +185,9 @@ impl AirspaceAdmin {
             token_kind: TokenKind::Collateral,
             value_modifier: config.collateral_weight,
             max_staleness: 0,
+            conservative_valuation: false,
+            category: 0,
+            min_permit_tier: AirspacePermit::TIER_RETAIL,
             admin: TokenAdmin::Margin {
                 oracle: config.oracle,
             },
@@ -187,6 +244,9 @@ impl AirspaceAdmin {
             token_kind: TokenKind::Claim,
             value_modifier: collateral_weight,
             max_staleness: 0,
+            conservative_valuation: false,
+            category: 0,
+            min_permit_tier: AirspacePermit::TIER_RETAIL,
         };
 
         let collateral_update = TokenConfigUpdate {
@@ -195,6 +255,9 @@ impl AirspaceAdmin {
             token_kind: TokenKind::AdapterCollateral,
             value_modifier: max_leverage,
             max_staleness: 0,
+            conservative_valuation: false,
+            category: 0,
+            min_permit_tier: AirspacePermit::TIER_RETAIL,
         };
 
         let claims_update_ix = margin_config_ix.configure_token(claims_mint, Some(claims_update));
@@ -206,6 +269,46 @@ impl AirspaceAdmin {
             signers: vec![],
         }
     }
+
+    /// Register a serum market for trading by margin accounts, with the tokens held
+    /// by open orders accounts counting as collateral
+    pub fn register_serum_market(
+        &self,
+        market: &MarginSerumIxBuilder,
+        base_collateral_weight: u16,
+        quote_collateral_weight: u16,
+    ) -> TransactionBuilder {
+        let margin_config_ix = MarginConfigIxBuilder::new(self.airspace, self.payer);
+
+        let base_notes_update = TokenConfigUpdate {
+            admin: TokenAdmin::Adapter(jet_margin_serum::ID),
+            underlying_mint: market.market.base_mint,
+            token_kind: TokenKind::AdapterCollateral,
+            value_modifier: base_collateral_weight,
+            max_staleness: 0,
+            conservative_valuation: false,
+            category: 0,
+            min_permit_tier: AirspacePermit::TIER_RETAIL,
+        };
+
+        let quote_notes_update = TokenConfigUpdate {
+            admin: TokenAdmin::Adapter(jet_margin_serum::ID),
+            underlying_mint: market.market.quote_mint,
+            token_kind: TokenKind::AdapterCollateral,
+            value_modifier: quote_collateral_weight,
+            max_staleness: 0,
+            conservative_valuation: false,
+            category: 0,
+            min_permit_tier: AirspacePermit::TIER_RETAIL,
+        };
+
+        vec![
+            market.register_market(self.authority, self.payer),
+            margin_config_ix.configure_token(market.base_note_mint, Some(base_notes_update)),
+            margin_config_ix.configure_token(market.quote_note_mint, Some(quote_notes_update)),
+        ]
+        .into()
+    }
 }
 
 /// Configuration for token deposits into margin accounts
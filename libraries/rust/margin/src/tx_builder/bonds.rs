@@ -17,18 +17,20 @@ use crate::{
 impl PositionRefresher for BondsPositionRefresher {
     async fn refresh_positions(&self) -> Result<Vec<TransactionBuilder>> {
         let mut ret = vec![];
+        let margin_account =
+            get_anchor_account::<MarginAccount>(self.rpc.clone(), &self.margin_account).await?;
+
         for bond_market in self.bond_markets.values() {
-            for position in
-                get_anchor_account::<MarginAccount>(self.rpc.clone(), &self.margin_account)
-                    .await?
-                    .positions()
-                    .filter(|p| p.adapter == jet_bonds::id())
+            for position in margin_account
+                .positions()
+                .filter(|p| p.adapter == jet_bonds::id())
             {
                 if position.token == bond_market.claims()
                     || position.token == bond_market.collateral()
                 {
                     ret.push(
                         accounting_invoke(
+                            margin_account.airspace,
                             self.margin_account,
                             bond_market.refresh_position(self.margin_account)?,
                         )
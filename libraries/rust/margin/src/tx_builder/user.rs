@@ -21,10 +21,10 @@ use std::sync::Arc;
 use anchor_spl::associated_token::get_associated_token_address;
 use async_trait::async_trait;
 use jet_margin_pool::program::JetMarginPool;
-use jet_metadata::{PositionTokenMetadata, TokenMetadata};
+use jet_metadata::PositionTokenMetadata;
 
 use anyhow::{bail, Result};
-use solana_sdk::instruction::Instruction;
+use solana_sdk::instruction::{AccountMeta, Instruction};
 use solana_sdk::pubkey::Pubkey;
 use solana_sdk::signature::Keypair;
 use solana_sdk::signer::Signer;
@@ -33,11 +33,13 @@ use solana_sdk::transaction::Transaction;
 use anchor_lang::{AccountDeserialize, Id};
 
 use jet_margin::{MarginAccount, TokenConfig, TokenKind, TokenOracle};
-use jet_margin_pool::TokenChange;
+use jet_margin_pool::{MarginPool, TokenChange};
+use jet_margin_swap::SwapEndpoint;
 use jet_simulation::solana_rpc_api::SolanaRpcClient;
 
 use crate::cat;
 use crate::margin_integrator::PositionRefresher;
+use crate::spl_swap::SwapRoute;
 use crate::util::data::Join;
 use crate::{
     ix_builder::*,
@@ -89,15 +91,15 @@ impl PositionRefresher for MarginTxBuilder {
 }
 
 impl MarginTxBuilder {
-    /// Create a [MarginTxBuilder] for an ordinary user. Liquidators should use
-    /// `Self::new_liquidator`.
+    /// Create a [MarginTxBuilder] for an ordinary user in the default airspace.
+    /// Liquidators should use `Self::new_liquidator`.
     pub fn new(
         rpc: Arc<dyn SolanaRpcClient>,
         signer: Option<Keypair>,
         owner: Pubkey,
         seed: u16,
     ) -> MarginTxBuilder {
-        Self::new_with_airspace(rpc, signer, owner, seed, Pubkey::default())
+        Self::new_with_airspace(rpc, signer, owner, seed, derive_airspace("default"))
     }
 
     /// Create a [MarginTxBuilder] for an ordinary user. Liquidators should use
@@ -144,7 +146,7 @@ impl MarginTxBuilder {
         let ix =
             MarginIxBuilder::new_with_payer(owner, seed, rpc.payer().pubkey(), Some(liquidator));
 
-        let config_ix = MarginConfigIxBuilder::new(Pubkey::default(), rpc.payer().pubkey());
+        let config_ix = MarginConfigIxBuilder::new(ix.airspace, rpc.payer().pubkey());
 
         Self {
             rpc,
@@ -214,6 +216,11 @@ impl MarginTxBuilder {
         self.create_transaction(&[self.ix.create_account()]).await
     }
 
+    /// Transaction to migrate a version 1 margin account into the default airspace
+    pub async fn migrate_account(&self) -> Result<Transaction> {
+        self.create_transaction(&[self.ix.migrate_account()]).await
+    }
+
     /// Transaction to close the user's margin account
     pub async fn close_account(&self) -> Result<Transaction> {
         self.create_transaction(&[self.ix.close_account()]).await
@@ -224,7 +231,7 @@ impl MarginTxBuilder {
     /// Both the deposit and loan position should be empty.
     /// Use [Self::close_empty_positions] to close all empty positions.
     pub async fn close_token_positions(&self, token_mint: &Pubkey) -> Result<Transaction> {
-        let pool = MarginPoolIxBuilder::new(*token_mint);
+        let pool = MarginPoolIxBuilder::new(self.ix.airspace, *token_mint);
         let (deposit_account, _) = self.ix.get_token_account_address(&pool.deposit_note_mint);
         let instructions = vec![
             self.ix
@@ -242,7 +249,7 @@ impl MarginTxBuilder {
         token_mint: &Pubkey,
         kind: TokenKind,
     ) -> Result<Transaction> {
-        let pool = MarginPoolIxBuilder::new(*token_mint);
+        let pool = MarginPoolIxBuilder::new(self.ix.airspace, *token_mint);
         let ix = match kind {
             TokenKind::Collateral => self.ix.close_position(
                 pool.deposit_note_mint,
@@ -269,7 +276,10 @@ impl MarginTxBuilder {
             .filter(|p| p.balance == 0)
             .map(|p| {
                 if p.adapter == JetMarginPool::id() && p.kind() == TokenKind::Claim {
-                    let pool = MarginPoolIxBuilder::new(*loan_to_token.get(&p.token).unwrap());
+                    let pool = MarginPoolIxBuilder::new(
+                        self.ix.airspace,
+                        *loan_to_token.get(&p.token).unwrap(),
+                    );
                     self.adapter_invoke_ix(pool.close_loan(*self.address(), self.ix.payer))
                 } else {
                     self.ix.close_position(p.token, p.address)
@@ -295,7 +305,7 @@ impl MarginTxBuilder {
     ) -> Result<Transaction> {
         let mut instructions = vec![];
 
-        let pool = MarginPoolIxBuilder::new(*token_mint);
+        let pool = MarginPoolIxBuilder::new(self.ix.airspace, *token_mint);
         let position = self
             .get_or_create_position(&mut instructions, &pool.deposit_note_mint)
             .await?;
@@ -319,8 +329,8 @@ impl MarginTxBuilder {
         change: TokenChange,
     ) -> Result<TransactionBuilder> {
         let mut instructions = vec![];
-        let pool = MarginPoolIxBuilder::new(*token_mint);
-        let token_metadata = self.get_token_metadata(token_mint).await?;
+        let pool = MarginPoolIxBuilder::new(self.ix.airspace, *token_mint);
+        let token_oracle = self.get_pool_oracle(token_mint).await?;
 
         let deposit_position = self
             .get_or_create_position(&mut instructions, &pool.deposit_note_mint)
@@ -329,14 +339,20 @@ impl MarginTxBuilder {
             .get_or_create_pool_loan_position(&mut instructions, &pool)
             .await?;
 
-        let inner_refresh_loan_ix =
-            pool.margin_refresh_position(self.ix.address, token_metadata.pyth_price);
-        instructions.push(self.adapter_invoke_ix(inner_refresh_loan_ix));
-
+        let inner_refresh_loan_ix = pool.margin_refresh_position(self.ix.address, token_oracle);
         let inner_borrow_ix =
             pool.margin_borrow(self.ix.address, deposit_position, loan_position, change);
 
-        instructions.push(self.adapter_invoke_ix(inner_borrow_ix));
+        if self.is_liquidator {
+            instructions.push(self.adapter_invoke_ix(inner_refresh_loan_ix));
+            instructions.push(self.adapter_invoke_ix(inner_borrow_ix));
+        } else {
+            instructions.push(
+                self.ix
+                    .adapter_invoke_batch(vec![inner_refresh_loan_ix, inner_borrow_ix]),
+            );
+        }
+
         self.create_transaction_builder(&instructions)
     }
 
@@ -352,7 +368,7 @@ impl MarginTxBuilder {
         change: TokenChange,
     ) -> Result<Transaction> {
         let mut instructions = vec![];
-        let pool = MarginPoolIxBuilder::new(*token_mint);
+        let pool = MarginPoolIxBuilder::new(self.ix.airspace, *token_mint);
 
         let deposit_position = self
             .get_or_create_position(&mut instructions, &pool.deposit_note_mint)
@@ -383,7 +399,7 @@ impl MarginTxBuilder {
     ) -> Result<Transaction> {
         let mut instructions = vec![];
 
-        let pool = MarginPoolIxBuilder::new(*token_mint);
+        let pool = MarginPoolIxBuilder::new(self.ix.airspace, *token_mint);
         let loan_position = self
             .get_or_create_pool_loan_position(&mut instructions, &pool)
             .await?;
@@ -409,7 +425,7 @@ impl MarginTxBuilder {
         change: TokenChange,
     ) -> Result<Transaction> {
         let mut instructions = vec![];
-        let pool = MarginPoolIxBuilder::new(*token_mint);
+        let pool = MarginPoolIxBuilder::new(self.ix.airspace, *token_mint);
 
         let deposit_position = self
             .get_or_create_position(&mut instructions, &pool.deposit_note_mint)
@@ -446,8 +462,8 @@ impl MarginTxBuilder {
         minimum_amount_out: u64,
     ) -> Result<Transaction> {
         let mut instructions = vec![];
-        let source_pool = MarginPoolIxBuilder::new(*source_token_mint);
-        let destination_pool = MarginPoolIxBuilder::new(*destination_token_mint);
+        let source_pool = MarginPoolIxBuilder::new(self.ix.airspace, *source_token_mint);
+        let destination_pool = MarginPoolIxBuilder::new(self.ix.airspace, *destination_token_mint);
 
         let source_position = self
             .get_or_create_position(&mut instructions, &source_pool.deposit_note_mint)
@@ -456,19 +472,16 @@ impl MarginTxBuilder {
             .get_or_create_position(&mut instructions, &destination_pool.deposit_note_mint)
             .await?;
 
-        let destination_metadata = self.get_token_metadata(destination_token_mint).await?;
+        let destination_oracle = self.get_pool_oracle(destination_token_mint).await?;
 
         // Only refreshing the destination due to transaction size.
         // The most common scenario would be that a new margin position is created
         // for the destination of the swap. If its position price is not set before
         // the swap, a liquidator would be accused of extracting too much value
         // as the destination becomes immediately stale after creation.
-        instructions.push(
-            self.ix.accounting_invoke(
-                destination_pool
-                    .margin_refresh_position(*self.address(), destination_metadata.pyth_price),
-            ),
-        );
+        instructions.push(self.ix.accounting_invoke(
+            destination_pool.margin_refresh_position(*self.address(), destination_oracle),
+        ));
 
         let (swap_authority, _) = Pubkey::find_program_address(&[swap_pool.as_ref()], swap_program);
         let swap_pool = MarginSwapIxBuilder::new(
@@ -501,6 +514,312 @@ impl MarginTxBuilder {
         self.create_transaction(&instructions).await
     }
 
+    /// Transaction to borrow one token and swap it for another, opening a
+    /// leveraged position in the destination token
+    ///
+    /// # Notes
+    ///
+    /// - `transit_source_account` and `transit_destination_account` should be
+    ///   created in a separate transaction to avoid packet size limits.
+    #[allow(clippy::too_many_arguments)]
+    pub async fn open_leveraged_position(
+        &self,
+        source_token_mint: &Pubkey,
+        destination_token_mint: &Pubkey,
+        transit_source_account: &Pubkey,
+        transit_destination_account: &Pubkey,
+        swap_pool: &Pubkey,
+        pool_mint: &Pubkey,
+        fee_account: &Pubkey,
+        source_token_account: &Pubkey,
+        destination_token_account: &Pubkey,
+        swap_program: &Pubkey,
+        borrow_amount: u64,
+        minimum_amount_out: u64,
+    ) -> Result<Transaction> {
+        let mut instructions = vec![];
+        let source_pool = MarginPoolIxBuilder::new(self.ix.airspace, *source_token_mint);
+        let destination_pool = MarginPoolIxBuilder::new(self.ix.airspace, *destination_token_mint);
+
+        let source_position = self
+            .get_or_create_position(&mut instructions, &source_pool.deposit_note_mint)
+            .await?;
+        let destination_position = self
+            .get_or_create_position(&mut instructions, &destination_pool.deposit_note_mint)
+            .await?;
+        let loan_position = self
+            .get_or_create_pool_loan_position(&mut instructions, &source_pool)
+            .await?;
+
+        // Both pools are refreshed, as the new loan and the new deposit both
+        // need a current price for the health check.
+        self.push_pool_refreshes(
+            &mut instructions,
+            &[
+                (&source_pool, source_token_mint),
+                (&destination_pool, destination_token_mint),
+            ],
+        )
+        .await?;
+
+        let swap_pool = self.swap_pool_builder(
+            source_token_mint,
+            destination_token_mint,
+            swap_pool,
+            pool_mint,
+            fee_account,
+            swap_program,
+        );
+        let inner_swap_ix = swap_pool.open_leveraged_position(
+            *self.address(),
+            *transit_source_account,
+            *transit_destination_account,
+            source_position,
+            destination_position,
+            loan_position,
+            *source_token_account,
+            *destination_token_account,
+            *swap_program,
+            &source_pool,
+            &destination_pool,
+            borrow_amount,
+            minimum_amount_out,
+        );
+
+        instructions.push(self.adapter_invoke_ix(inner_swap_ix));
+
+        self.create_transaction(&instructions).await
+    }
+
+    /// Transaction to swap a deposit back into a borrowed token and repay the
+    /// loan, closing a leveraged position
+    ///
+    /// # Notes
+    ///
+    /// - `transit_source_account` and `transit_destination_account` should be
+    ///   created in a separate transaction to avoid packet size limits.
+    #[allow(clippy::too_many_arguments)]
+    pub async fn close_leveraged_position(
+        &self,
+        source_token_mint: &Pubkey,
+        destination_token_mint: &Pubkey,
+        transit_source_account: &Pubkey,
+        transit_destination_account: &Pubkey,
+        swap_pool: &Pubkey,
+        pool_mint: &Pubkey,
+        fee_account: &Pubkey,
+        source_token_account: &Pubkey,
+        destination_token_account: &Pubkey,
+        swap_program: &Pubkey,
+        change: TokenChange,
+        minimum_amount_out: u64,
+    ) -> Result<Transaction> {
+        let mut instructions = vec![];
+        let source_pool = MarginPoolIxBuilder::new(self.ix.airspace, *source_token_mint);
+        let destination_pool = MarginPoolIxBuilder::new(self.ix.airspace, *destination_token_mint);
+
+        let source_position = self
+            .get_or_create_position(&mut instructions, &source_pool.deposit_note_mint)
+            .await?;
+        let destination_position = self
+            .get_or_create_position(&mut instructions, &destination_pool.deposit_note_mint)
+            .await?;
+        let loan_position = self
+            .get_or_create_pool_loan_position(&mut instructions, &destination_pool)
+            .await?;
+
+        self.push_pool_refreshes(
+            &mut instructions,
+            &[
+                (&source_pool, source_token_mint),
+                (&destination_pool, destination_token_mint),
+            ],
+        )
+        .await?;
+
+        let swap_pool = self.swap_pool_builder(
+            source_token_mint,
+            destination_token_mint,
+            swap_pool,
+            pool_mint,
+            fee_account,
+            swap_program,
+        );
+        let inner_swap_ix = swap_pool.close_leveraged_position(
+            *self.address(),
+            *transit_source_account,
+            *transit_destination_account,
+            source_position,
+            destination_position,
+            loan_position,
+            *source_token_account,
+            *destination_token_account,
+            *swap_program,
+            &source_pool,
+            &destination_pool,
+            change,
+            minimum_amount_out,
+        );
+
+        instructions.push(self.adapter_invoke_ix(inner_swap_ix));
+
+        self.create_transaction(&instructions).await
+    }
+
+    /// Transaction to swap one token for another, where either side of the swap
+    /// can be a margin pool deposit or a deposit position
+    ///
+    /// # Notes
+    ///
+    /// - A [SwapEndpoint::Deposit] side uses the deposit position created by
+    ///   [MarginTxBuilder::create_deposit_position], which must already exist.
+    /// - The transit account of a [SwapEndpoint::MarginPool] side should be
+    ///   created in a separate transaction to avoid packet size limits.
+    #[allow(clippy::too_many_arguments)]
+    pub async fn swap_positions(
+        &self,
+        source_token_mint: &Pubkey,
+        destination_token_mint: &Pubkey,
+        source: SwapEndpoint,
+        destination: SwapEndpoint,
+        swap_pool: &Pubkey,
+        pool_mint: &Pubkey,
+        fee_account: &Pubkey,
+        source_token_account: &Pubkey,
+        destination_token_account: &Pubkey,
+        swap_program: &Pubkey,
+        change: TokenChange,
+        minimum_amount_out: u64,
+    ) -> Result<Transaction> {
+        let mut instructions = vec![];
+        let source_pool = MarginPoolIxBuilder::new(self.ix.airspace, *source_token_mint);
+        let destination_pool = MarginPoolIxBuilder::new(self.ix.airspace, *destination_token_mint);
+        let source_transit = get_associated_token_address(self.address(), source_token_mint);
+        let destination_transit =
+            get_associated_token_address(self.address(), destination_token_mint);
+
+        let source_position = match source {
+            SwapEndpoint::MarginPool => SwapPosition::MarginPool {
+                pool: &source_pool,
+                position: self
+                    .get_or_create_position(&mut instructions, &source_pool.deposit_note_mint)
+                    .await?,
+                transit: source_transit,
+            },
+            SwapEndpoint::Deposit => SwapPosition::Deposit {
+                account: source_transit,
+            },
+        };
+
+        // Only refreshing the destination, for the same reasons as in `swap`
+        let destination_position = match destination {
+            SwapEndpoint::MarginPool => {
+                let position = self
+                    .get_or_create_position(&mut instructions, &destination_pool.deposit_note_mint)
+                    .await?;
+                let destination_oracle = self.get_pool_oracle(destination_token_mint).await?;
+                instructions.push(self.ix.accounting_invoke(
+                    destination_pool.margin_refresh_position(*self.address(), destination_oracle),
+                ));
+
+                SwapPosition::MarginPool {
+                    pool: &destination_pool,
+                    position,
+                    transit: destination_transit,
+                }
+            }
+            SwapEndpoint::Deposit => {
+                if let Some((config_address, config)) =
+                    self.get_position_config(destination_token_mint).await?
+                {
+                    if let Some(TokenOracle::Pyth { price, .. }) = config.oracle() {
+                        instructions
+                            .push(self.ix.refresh_deposit_position(&config_address, &price));
+                    }
+                }
+
+                SwapPosition::Deposit {
+                    account: destination_transit,
+                }
+            }
+        };
+
+        let (swap_authority, _) = Pubkey::find_program_address(&[swap_pool.as_ref()], swap_program);
+        let swap_pool = MarginSwapIxBuilder::new(
+            *source_token_mint,
+            *destination_token_mint,
+            *swap_pool,
+            swap_authority,
+            *pool_mint,
+            *fee_account,
+        );
+
+        let inner_swap_ix = swap_pool.swap_positions(
+            *self.address(),
+            source_position,
+            destination_position,
+            *source_token_account,
+            *destination_token_account,
+            *swap_program,
+            change,
+            minimum_amount_out,
+        );
+
+        instructions.push(self.adapter_invoke_ix(inner_swap_ix));
+
+        self.create_transaction(&instructions).await
+    }
+
+    /// Transaction to swap one token for another through a route of swap pools
+    ///
+    /// # Notes
+    ///
+    /// - The transit accounts for the source, destination and every intermediate
+    ///   token of the route should be created in a separate transaction, as
+    ///   associated token accounts of the margin account.
+    pub async fn route_swap(
+        &self,
+        route: &SwapRoute,
+        change: TokenChange,
+        minimum_amount_out: u64,
+    ) -> Result<Transaction> {
+        let mut instructions = vec![];
+        let source_token_mint = route.source_mint();
+        let destination_token_mint = route.destination_mint();
+        let source_pool = MarginPoolIxBuilder::new(self.ix.airspace, source_token_mint);
+        let destination_pool = MarginPoolIxBuilder::new(self.ix.airspace, destination_token_mint);
+
+        let source_position = self
+            .get_or_create_position(&mut instructions, &source_pool.deposit_note_mint)
+            .await?;
+        let destination_position = self
+            .get_or_create_position(&mut instructions, &destination_pool.deposit_note_mint)
+            .await?;
+
+        // Only refreshing the destination, for the same reasons as in `swap`
+        let destination_oracle = self.get_pool_oracle(&destination_token_mint).await?;
+        instructions.push(self.ix.accounting_invoke(
+            destination_pool.margin_refresh_position(*self.address(), destination_oracle),
+        ));
+
+        let inner_swap_ix = MarginSwapIxBuilder::route_swap(
+            *self.address(),
+            get_associated_token_address(self.address(), &source_token_mint),
+            get_associated_token_address(self.address(), &destination_token_mint),
+            source_position,
+            destination_position,
+            route,
+            &source_pool,
+            &destination_pool,
+            change,
+            minimum_amount_out,
+        );
+
+        instructions.push(self.adapter_invoke_ix(inner_swap_ix));
+
+        self.create_transaction(&instructions).await
+    }
+
     /// Transaction to begin liquidating user account.
     /// If `refresh_position` is provided, all the margin pools will be refreshed first.
     pub async fn liquidate_begin(&self, refresh_positions: bool) -> Result<Transaction> {
@@ -554,13 +873,20 @@ impl MarginTxBuilder {
             .await
     }
 
+    /// Record the current health of the margin account, so it can later be
+    /// retrieved with [crate::health::fetch_health_history]
+    pub async fn record_health(&self) -> Result<Transaction> {
+        self.create_unsigned_transaction(&[self.ix.record_health()])
+            .await
+    }
+
     /// Refresh a user's position in a margin pool
     pub async fn refresh_pool_position(&self, token_mint: &Pubkey) -> Result<Transaction> {
-        let metadata = self.get_token_metadata(token_mint).await?;
-        let ix_builder = MarginPoolIxBuilder::new(*token_mint);
-        let ix = self.ix.adapter_invoke(
-            ix_builder.margin_refresh_position(self.ix.address, metadata.pyth_price),
-        );
+        let oracle = self.get_pool_oracle(token_mint).await?;
+        let ix_builder = MarginPoolIxBuilder::new(self.ix.airspace, *token_mint);
+        let ix = self
+            .ix
+            .adapter_invoke(ix_builder.margin_refresh_position(self.ix.address, oracle));
 
         self.create_transaction(&[ix]).await
     }
@@ -585,33 +911,28 @@ impl MarginTxBuilder {
 
     /// Refresh metadata for all positions in the user account
     pub async fn refresh_all_position_metadata(&self) -> Result<Vec<Transaction>> {
-        let instructions = self
-            .get_account_state()
-            .await?
-            .positions()
-            .map(|position| {
-                let is_deposit_account = position.address
-                    == get_associated_token_address(self.address(), &position.token);
+        let state = self.get_account_state().await?;
+        let mut instructions = vec![];
 
-                match is_deposit_account {
-                    false => self.ix.refresh_position_metadata(&position.token),
-                    true => self.ix.refresh_position_config(&position.token),
-                }
-            })
-            .collect::<Vec<_>>();
+        for position in state.positions() {
+            let category = self.get_token_category(&position.token).await?;
+            instructions.push(self.ix.refresh_position_config(&position.token, category));
+        }
 
         self.get_chunk_transactions(12, instructions).await
     }
 
     /// Create a new token account that accepts deposits, registered as a position
     pub async fn create_deposit_position(&self, token_mint: &Pubkey) -> Result<Transaction> {
+        let category = self.get_token_category(token_mint).await?;
+
         self.create_transaction(&[
             spl_associated_token_account::instruction::create_associated_token_account(
                 &self.signer(),
                 self.address(),
                 token_mint,
             ),
-            self.ix.create_deposit_position(*token_mint),
+            self.ix.create_deposit_position(*token_mint, category),
         ])
         .await
     }
@@ -636,6 +957,8 @@ impl MarginTxBuilder {
         let mut instructions = vec![];
 
         if !state.positions().any(|p| p.token == token_mint) {
+            let category = self.get_token_category(&token_mint).await?;
+
             instructions.push(
                 spl_associated_token_account::instruction::create_associated_token_account(
                     &self.signer(),
@@ -643,7 +966,7 @@ impl MarginTxBuilder {
                     &token_mint,
                 ),
             );
-            instructions.push(self.ix.create_deposit_position(token_mint));
+            instructions.push(self.ix.create_deposit_position(token_mint, category));
         }
 
         instructions.push(
@@ -654,6 +977,26 @@ impl MarginTxBuilder {
         self.create_transaction(&instructions).await
     }
 
+    /// Transfer tokens out of a deposit account associated with the margin account, while
+    /// liquidating the account
+    pub async fn liquidator_transfer_deposit(
+        &self,
+        token_mint: Pubkey,
+        destination: Pubkey,
+        amount: u64,
+    ) -> Result<Transaction> {
+        if !self.is_liquidator {
+            bail!("only a liquidator can transfer deposits out of an account being liquidated");
+        }
+
+        let source = get_associated_token_address(self.address(), &token_mint);
+        let instruction =
+            self.ix
+                .liquidator_transfer_deposit(self.signer(), source, destination, amount);
+
+        self.create_transaction(&[instruction]).await
+    }
+
     /// Get the latest [MarginAccount] state
     pub async fn get_account_state(&self) -> Result<Box<MarginAccount>> {
         let account_data = self.rpc.get_account(&self.ix.address).await?;
@@ -700,12 +1043,13 @@ impl MarginTxBuilder {
             if txns.contains_key(&p_metadata.underlying_token_mint) {
                 continue;
             }
-            let t_metadata = self
-                .get_token_metadata(&p_metadata.underlying_token_mint)
+            let token_oracle = self
+                .get_pool_oracle(&p_metadata.underlying_token_mint)
                 .await?;
-            let ix_builder = MarginPoolIxBuilder::new(p_metadata.underlying_token_mint);
+            let ix_builder =
+                MarginPoolIxBuilder::new(self.ix.airspace, p_metadata.underlying_token_mint);
             let ix = self.ix.accounting_invoke(
-                ix_builder.margin_refresh_position(self.ix.address, t_metadata.pyth_price),
+                ix_builder.margin_refresh_position(self.ix.address, token_oracle),
             );
 
             txns.insert(p_metadata.underlying_token_mint, ix.into());
@@ -739,14 +1083,16 @@ impl MarginTxBuilder {
         Ok(instructions)
     }
 
-    async fn get_token_metadata(&self, token_mint: &Pubkey) -> Result<TokenMetadata> {
-        let (md_address, _) =
-            Pubkey::find_program_address(&[token_mint.as_ref()], &jet_metadata::ID);
-        let account_data = self.rpc.get_account(&md_address).await?;
+    /// The price oracle configured for the token's pool in this airspace
+    async fn get_pool_oracle(&self, token_mint: &Pubkey) -> Result<Pubkey> {
+        let pool_address = derive_margin_pool(&self.ix.airspace, token_mint);
+        let account_data = self.rpc.get_account(&pool_address).await?;
 
         match account_data {
-            None => bail!("no metadata {} found for token {}", md_address, token_mint),
-            Some(account) => Ok(TokenMetadata::try_deserialize(&mut &account.data[..])?),
+            None => bail!("no pool {} found for token {}", pool_address, token_mint),
+            Some(account) => {
+                Ok(MarginPool::try_deserialize(&mut &account.data[..])?.token_price_oracle)
+            }
         }
     }
 
@@ -771,6 +1117,14 @@ impl MarginTxBuilder {
         }
     }
 
+    async fn get_token_category(&self, token_mint: &Pubkey) -> Result<u16> {
+        Ok(self
+            .get_position_config(token_mint)
+            .await?
+            .map(|(_, config)| config.category)
+            .unwrap_or_default())
+    }
+
     async fn get_position_config(
         &self,
         token_mint: &Pubkey,
@@ -793,9 +1147,11 @@ impl MarginTxBuilder {
         token_mint: &Pubkey,
     ) -> Result<Pubkey> {
         let state = self.get_account_state().await?;
-        let (address, ix_register) = self.ix.register_position(*token_mint);
+        let (address, _) = owned_position_token_account(self.address(), token_mint);
 
         if !state.positions().any(|p| p.token == *token_mint) {
+            let category = self.get_token_category(token_mint).await?;
+            let (_, ix_register) = self.ix.register_position(*token_mint, category);
             instructions.push(ix_register);
         }
 
@@ -813,8 +1169,18 @@ impl MarginTxBuilder {
         Ok(if let Some(position) = search_result {
             position.address
         } else {
-            let (loan_note_token_account, pools_ix) =
+            let (loan_note_token_account, mut pools_ix) =
                 pool.register_loan(self.ix.address, self.ix.payer);
+            let category = self.get_token_category(&pool.loan_note_mint).await?;
+
+            // the margin program reads the loan's category from the adapter's accounts
+            if category != 0 {
+                pools_ix.accounts.push(AccountMeta::new_readonly(
+                    derive_token_category(&self.ix.airspace, category),
+                    false,
+                ));
+            }
+
             let wrapped_ix = self.adapter_invoke_ix(pools_ix);
             instructions.push(wrapped_ix);
 
@@ -822,6 +1188,43 @@ impl MarginTxBuilder {
         })
     }
 
+    async fn push_pool_refreshes(
+        &self,
+        instructions: &mut Vec<Instruction>,
+        pools: &[(&MarginPoolIxBuilder, &Pubkey)],
+    ) -> Result<()> {
+        for (pool, token_mint) in pools {
+            let oracle = self.get_pool_oracle(token_mint).await?;
+            instructions.push(
+                self.ix
+                    .accounting_invoke(pool.margin_refresh_position(*self.address(), oracle)),
+            );
+        }
+
+        Ok(())
+    }
+
+    fn swap_pool_builder(
+        &self,
+        source_token_mint: &Pubkey,
+        destination_token_mint: &Pubkey,
+        swap_pool: &Pubkey,
+        pool_mint: &Pubkey,
+        fee_account: &Pubkey,
+        swap_program: &Pubkey,
+    ) -> MarginSwapIxBuilder {
+        let (swap_authority, _) = Pubkey::find_program_address(&[swap_pool.as_ref()], swap_program);
+
+        MarginSwapIxBuilder::new(
+            *source_token_mint,
+            *destination_token_mint,
+            *swap_pool,
+            swap_authority,
+            *pool_mint,
+            *fee_account,
+        )
+    }
+
     fn adapter_invoke_ix(&self, inner: Instruction) -> Instruction {
         match self.is_liquidator {
             true => self
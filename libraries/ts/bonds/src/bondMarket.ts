@@ -16,13 +16,14 @@ export interface OrderParams {
   postOnly: boolean
   postAllowed: boolean
   autoStake: boolean
+  goodTilTime: BN | null
 }
 /**
  * The raw struct as found on chain
  */
 export interface BondManagerInfo {
   versionTag: BN
-  programAuthority: PublicKey
+  airspace: PublicKey
   orderbookMarketState: PublicKey
   eventQueue: PublicKey
   asks: PublicKey
@@ -38,6 +39,8 @@ export interface BondManagerInfo {
   bump: number[]
   orderbookPaused: boolean
   ticketsPaused: boolean
+  crankPolicy: number
+  minPermitTier: number
   reserved: number[]
   duration: BN
   nonce: BN
@@ -132,7 +135,7 @@ export class BondMarket {
     let data = await fetchData(program.provider.connection, bondManager)
     let info: BondManagerInfo = program.coder.accounts.decode("BondManager", data)
     const claimsMetadata = await findDerivedAccount(
-      ["token-config", info.programAuthority, info.claimsMint],
+      ["token-config", info.airspace, info.claimsMint],
       new PublicKey(jetMarginProgramId)
     )
     const marginAdapterMetadata = await findDerivedAccount([program.programId], new PublicKey(jetMarginProgramId))
@@ -159,7 +162,8 @@ export class BondMarket {
       matchLimit: new BN(U64_MAX.toString()),
       postOnly: false,
       postAllowed: true,
-      autoStake: true
+      autoStake: true,
+      goodTilTime: null
     }
     return await this.borrowIx(user, payer, params, seed)
   }
@@ -177,7 +181,8 @@ export class BondMarket {
       matchLimit: new BN(U64_MAX.toString()),
       postOnly: false,
       postAllowed: false,
-      autoStake: true
+      autoStake: true,
+      goodTilTime: null
     }
     return await this.borrowIx(user, payer, params, seed)
   }
@@ -221,7 +226,8 @@ export class BondMarket {
       matchLimit: new BN(U64_MAX.toString()),
       postOnly: false,
       postAllowed: true,
-      autoStake: true
+      autoStake: true,
+      goodTilTime: null
     }
     return await this.lendIx(user.address, userTicketVault, userTokenVault, payer, params, seed)
   }
@@ -235,7 +241,8 @@ export class BondMarket {
       matchLimit: new BN(U64_MAX.toString()),
       postOnly: false,
       postAllowed: false,
-      autoStake: true
+      autoStake: true,
+      goodTilTime: null
     }
     return await this.lendIx(user.address, userTicketVault, userTokenVault, payer, params, seed)
   }
@@ -268,7 +275,7 @@ export class BondMarket {
       .cancelOrder(bnOrderId)
       .accounts({
         ...this.addresses,
-        user: user.address
+        owner: user.address
       })
       .instruction()
   }
@@ -30,6 +30,13 @@ export type JetBonds = {
       }
       value: 'b"claim_ticket"'
     },
+    {
+      name: "CRANK_AUTHORIZATION"
+      type: {
+        defined: "&[u8]"
+      }
+      value: 'b"crank_authorization"'
+    },
     {
       name: "DEPOSIT_NOTES"
       type: {
@@ -88,6 +95,78 @@ export type JetBonds = {
     }
   ]
   instructions: [
+    {
+      name: "authorizeCrank"
+      docs: ["authorize an address to run orderbook consume_event instructions"]
+      accounts: [
+        {
+          name: "crank"
+          isMut: false
+          isSigner: false
+          docs: ["The crank signer pubkey"]
+        },
+        {
+          name: "authority"
+          isMut: false
+          isSigner: true
+          docs: ["The authority that must sign to make this change"]
+        },
+        {
+          name: "airspace"
+          isMut: false
+          isSigner: false
+          docs: ["The airspace being modified"]
+        },
+        {
+          name: "crankAuthorization"
+          isMut: true
+          isSigner: false
+          docs: ["The account containing the metadata for the key"]
+        },
+        {
+          name: "payer"
+          isMut: true
+          isSigner: true
+          docs: ["The address paying the rent for the account"]
+        },
+        {
+          name: "systemProgram"
+          isMut: false
+          isSigner: false
+        }
+      ]
+      args: []
+    },
+    {
+      name: "revokeCrank"
+      docs: ["unauthorize an address to run orderbook consume_event instructions"]
+      accounts: [
+        {
+          name: "metadataAccount"
+          isMut: true
+          isSigner: false
+          docs: ["The account containing the metadata for the key"]
+        },
+        {
+          name: "authority"
+          isMut: false
+          isSigner: true
+          docs: ["The authority that must sign to make this change"]
+        },
+        {
+          name: "airspace"
+          isMut: false
+          isSigner: false
+          docs: ["The airspace being modified"]
+        },
+        {
+          name: "receiver"
+          isMut: true
+          isSigner: false
+        }
+      ]
+      args: []
+    },
     {
       name: "initializeBondManager"
       docs: ["Initializes a BondManager for a bond ticket market"]
@@ -123,16 +202,22 @@ export type JetBonds = {
           docs: ["Mints tokens to a margin account to represent debt that must be collateralized"]
         },
         {
-          name: "deposits"
+          name: "collateral"
           isMut: true
           isSigner: false
           docs: ["Mints tokens to a margin account to represent debt that must be collateralized"]
         },
         {
-          name: "programAuthority"
+          name: "authority"
           isMut: false
           isSigner: true
-          docs: ["The controlling signer for this program"]
+          docs: ["The authority that must sign to make this change"]
+        },
+        {
+          name: "airspace"
+          isMut: false
+          isSigner: false
+          docs: ["The airspace being modified"]
         },
         {
           name: "underlyingOracle"
@@ -194,31 +279,37 @@ export type JetBonds = {
           name: "orderbookMarketState"
           isMut: true
           isSigner: false
-          docs: [
-            "Accounts for `agnostic-orderbook`",
-            "Should be uninitialized, used for invoking create_account and sent to the agnostic orderbook program"
-          ]
+          docs: ["AOB market state"]
         },
         {
           name: "eventQueue"
           isMut: true
           isSigner: false
+          docs: ["AOB market event queue", "", "Must be initialized"]
         },
         {
           name: "bids"
           isMut: true
           isSigner: false
+          docs: ["AOB market bids"]
         },
         {
           name: "asks"
           isMut: true
           isSigner: false
+          docs: ["AOB market asks"]
         },
         {
-          name: "programAuthority"
+          name: "authority"
           isMut: false
           isSigner: true
-          docs: ["Signing account responsible for changes to the bond market"]
+          docs: ["The authority that must sign to make this change"]
+        },
+        {
+          name: "airspace"
+          isMut: false
+          isSigner: false
+          docs: ["The airspace being modified"]
         },
         {
           name: "payer"
@@ -243,8 +334,8 @@ export type JetBonds = {
       ]
     },
     {
-      name: "modifyBondManager"
-      docs: ["Modify a `BondManager` account", "Authority use only"]
+      name: "setTicketsPaused"
+      docs: ["Pause or resume the redemption of staked tickets", "Authority use only"]
       accounts: [
         {
           name: "bondManager"
@@ -253,20 +344,176 @@ export type JetBonds = {
           docs: ["The `BondManager` manages asset tokens for a particular bond duration"]
         },
         {
-          name: "programAuthority"
+          name: "authority"
           isMut: false
           isSigner: true
-          docs: ["The controlling signer for this program"]
+          docs: ["The authority that must sign to make this change"]
+        },
+        {
+          name: "airspace"
+          isMut: false
+          isSigner: false
+          docs: ["The airspace being modified"]
         }
       ]
       args: [
         {
-          name: "data"
-          type: "bytes"
+          name: "paused"
+          type: "bool"
+        }
+      ]
+    },
+    {
+      name: "setUnderlyingOracle"
+      docs: ["Set the oracle that values the underlying token", "Authority use only"]
+      accounts: [
+        {
+          name: "bondManager"
+          isMut: true
+          isSigner: false
+          docs: ["The `BondManager` manages asset tokens for a particular bond duration"]
         },
         {
-          name: "offset"
-          type: "u32"
+          name: "authority"
+          isMut: false
+          isSigner: true
+          docs: ["The authority that must sign to make this change"]
+        },
+        {
+          name: "airspace"
+          isMut: false
+          isSigner: false
+          docs: ["The airspace being modified"]
+        },
+        {
+          name: "oracle"
+          isMut: false
+          isSigner: false
+          docs: ["The new oracle"]
+        }
+      ]
+      args: []
+    },
+    {
+      name: "setTicketOracle"
+      docs: ["Set the oracle that values the bond tickets", "Authority use only"]
+      accounts: [
+        {
+          name: "bondManager"
+          isMut: true
+          isSigner: false
+          docs: ["The `BondManager` manages asset tokens for a particular bond duration"]
+        },
+        {
+          name: "authority"
+          isMut: false
+          isSigner: true
+          docs: ["The authority that must sign to make this change"]
+        },
+        {
+          name: "airspace"
+          isMut: false
+          isSigner: false
+          docs: ["The airspace being modified"]
+        },
+        {
+          name: "oracle"
+          isMut: false
+          isSigner: false
+          docs: ["The new oracle"]
+        }
+      ]
+      args: []
+    },
+    {
+      name: "setDuration"
+      docs: ["Set the tenor applied to orders filled from now on", "Authority use only"]
+      accounts: [
+        {
+          name: "bondManager"
+          isMut: true
+          isSigner: false
+          docs: ["The `BondManager` manages asset tokens for a particular bond duration"]
+        },
+        {
+          name: "authority"
+          isMut: false
+          isSigner: true
+          docs: ["The authority that must sign to make this change"]
+        },
+        {
+          name: "airspace"
+          isMut: false
+          isSigner: false
+          docs: ["The airspace being modified"]
+        }
+      ]
+      args: [
+        {
+          name: "duration"
+          type: "i64"
+        }
+      ]
+    },
+    {
+      name: "setCrankPolicy"
+      docs: ["Set who may consume events from the orderbook", "Authority use only"]
+      accounts: [
+        {
+          name: "bondManager"
+          isMut: true
+          isSigner: false
+          docs: ["The `BondManager` manages asset tokens for a particular bond duration"]
+        },
+        {
+          name: "authority"
+          isMut: false
+          isSigner: true
+          docs: ["The authority that must sign to make this change"]
+        },
+        {
+          name: "airspace"
+          isMut: false
+          isSigner: false
+          docs: ["The airspace being modified"]
+        }
+      ]
+      args: [
+        {
+          name: "policy"
+          type: {
+            defined: "CrankPolicy"
+          }
+        }
+      ]
+    },
+    {
+      name: "setMinPermitTier"
+      docs: ["Set the minimum airspace permit tier needed to create a margin user in the market", "Authority use only"]
+      accounts: [
+        {
+          name: "bondManager"
+          isMut: true
+          isSigner: false
+          docs: ["The `BondManager` manages asset tokens for a particular bond duration"]
+        },
+        {
+          name: "authority"
+          isMut: false
+          isSigner: true
+          docs: ["The authority that must sign to make this change"]
+        },
+        {
+          name: "airspace"
+          isMut: false
+          isSigner: false
+          docs: ["The airspace being modified"]
+        }
+      ]
+      args: [
+        {
+          name: "tier"
+          type: "u8"
         }
       ]
     },
@@ -286,10 +533,16 @@ export type JetBonds = {
           isSigner: false
         },
         {
-          name: "programAuthority"
+          name: "authority"
           isMut: false
           isSigner: true
-          docs: ["The controlling signer for this program"]
+          docs: ["The authority that must sign to make this change"]
+        },
+        {
+          name: "airspace"
+          isMut: false
+          isSigner: false
+          docs: ["The airspace being modified"]
         }
       ]
       args: []
@@ -330,10 +583,16 @@ export type JetBonds = {
           isSigner: false
         },
         {
-          name: "programAuthority"
+          name: "authority"
           isMut: false
           isSigner: true
-          docs: ["The controlling signer for this program"]
+          docs: ["The authority that must sign to make this change"]
+        },
+        {
+          name: "airspace"
+          isMut: false
+          isSigner: false
+          docs: ["The airspace being modified"]
         }
       ]
       args: []
@@ -416,7 +675,13 @@ export type JetBonds = {
           name: "claimsMetadata"
           isMut: false
           isSigner: false
-          docs: ["Token metadata account used by the margin program to register claims positions"]
+          docs: ["Token metadata account needed by the margin program to register the claim position"]
+        },
+        {
+          name: "permit"
+          isMut: false
+          isSigner: false
+          docs: ["The permit allowing the owner of the margin account to use the market's airspace"]
         }
       ]
       args: []
@@ -513,68 +778,137 @@ export type JetBonds = {
       ]
     },
     {
-      name: "refreshPosition"
-      docs: ["Refresh the associated margin account `claims` for a given `MarginUser` account"]
+      name: "marginReplaceOrders"
+      docs: ["Atomically cancels a set of margin borrow orders and posts a new set of post-only borrow orders"]
       accounts: [
         {
           name: "borrowerAccount"
-          isMut: false
+          isMut: true
           isSigner: false
-          docs: ["The account tracking information related to this particular user"]
+          docs: ["The account tracking borrower debts"]
         },
         {
           name: "marginAccount"
           isMut: false
+          isSigner: true
+          docs: ["The margin account that owns the orders"]
+        },
+        {
+          name: "claims"
+          isMut: true
           isSigner: false
+          docs: ["Token account used by the margin program to track the debt that must be collateralized"]
         },
         {
           name: "claimsMint"
-          isMut: false
+          isMut: true
           isSigner: false
+          docs: ["Token mint used by the margin program to track the debt that must be collateralized"]
         },
         {
           name: "bondManager"
-          isMut: false
+          isMut: true
           isSigner: false
           docs: ["The `BondManager` account tracks global information related to this particular bond market"]
         },
         {
-          name: "underlyingOracle"
-          isMut: false
+          name: "orderbookMarketState"
+          isMut: true
+          isSigner: false
+        },
+        {
+          name: "eventQueue"
+          isMut: true
+          isSigner: false
+        },
+        {
+          name: "bids"
+          isMut: true
+          isSigner: false
+        },
+        {
+          name: "asks"
+          isMut: true
           isSigner: false
-          docs: ["The pyth price account"]
         },
         {
           name: "tokenProgram"
           isMut: false
           isSigner: false
-          docs: ["SPL token program"]
         }
       ]
       args: [
         {
-          name: "expectPrice"
-          type: "bool"
+          name: "params"
+          type: {
+            defined: "MarginReplaceOrdersParams"
+          }
         }
       ]
     },
     {
-      name: "repay"
-      docs: ["Repay debt on an Obligation"]
+      name: "refreshPosition"
+      docs: ["Refresh the associated margin account `claims` for a given `MarginUser` account"]
       accounts: [
         {
-          name: "borrowerAccount"
+          name: "marginUser"
           isMut: false
           isSigner: false
           docs: ["The account tracking information related to this particular user"]
         },
         {
-          name: "obligation"
-          isMut: true
+          name: "marginAccount"
+          isMut: false
           isSigner: false
         },
         {
-          name: "nextObligation"
+          name: "claimsMint"
+          isMut: false
+          isSigner: false
+        },
+        {
+          name: "bondManager"
+          isMut: false
+          isSigner: false
+          docs: ["The `BondManager` account tracks global information related to this particular bond market"]
+        },
+        {
+          name: "underlyingOracle"
+          isMut: false
+          isSigner: false
+          docs: ["The pyth price account"]
+        },
+        {
+          name: "tokenProgram"
+          isMut: false
+          isSigner: false
+          docs: ["SPL token program"]
+        }
+      ]
+      args: [
+        {
+          name: "expectPrice"
+          type: "bool"
+        }
+      ]
+    },
+    {
+      name: "repay"
+      docs: ["Repay debt on an Obligation"]
+      accounts: [
+        {
+          name: "borrowerAccount"
+          isMut: false
+          isSigner: false
+          docs: ["The account tracking information related to this particular user"]
+        },
+        {
+          name: "obligation"
+          isMut: true
+          isSigner: false
+        },
+        {
+          name: "nextObligation"
           isMut: false
           isSigner: false
           docs: [
@@ -694,7 +1028,6 @@ export type JetBonds = {
           isSigner: false
           docs: ["The account to recieve the matched tokens"]
         },
-
         {
           name: "bondManager"
           isMut: true
@@ -747,10 +1080,10 @@ export type JetBonds = {
       docs: ["Cancels an order on the book"]
       accounts: [
         {
-          name: "user"
+          name: "owner"
           isMut: false
           isSigner: true
-          docs: ["The signing authority for this user account"]
+          docs: ["The owner of the order"]
         },
         {
           name: "bondManager"
@@ -786,6 +1119,143 @@ export type JetBonds = {
         }
       ]
     },
+    {
+      name: "cancelAllOrders"
+      docs: ["Cancels all of a user's orders on the book, returning the posted funds"]
+      accounts: [
+        {
+          name: "owner"
+          isMut: false
+          isSigner: true
+          docs: ["The owner of the orders. For margin orders, this is the margin account"]
+        },
+        {
+          name: "bondManager"
+          isMut: true
+          isSigner: false
+          docs: ["The `BondManager` account tracks global information related to this particular bond market"]
+        },
+        {
+          name: "orderbookMarketState"
+          isMut: true
+          isSigner: false
+        },
+        {
+          name: "eventQueue"
+          isMut: true
+          isSigner: false
+        },
+        {
+          name: "bids"
+          isMut: true
+          isSigner: false
+        },
+        {
+          name: "asks"
+          isMut: true
+          isSigner: false
+        },
+        {
+          name: "underlyingTokenVault"
+          isMut: true
+          isSigner: false
+          docs: ["The market token vault"]
+        },
+        {
+          name: "bondTicketMint"
+          isMut: true
+          isSigner: false
+          docs: ["The market ticket mint"]
+        },
+        {
+          name: "claimsMint"
+          isMut: true
+          isSigner: false
+          docs: ["Token mint used by the margin program to track the debt that must be collateralized"]
+        },
+        {
+          name: "tokenProgram"
+          isMut: false
+          isSigner: false
+        }
+      ]
+      args: []
+    },
+    {
+      name: "replaceOrders"
+      docs: ["Atomically cancels a set of orders and posts a new set of post-only orders"]
+      accounts: [
+        {
+          name: "user"
+          isMut: false
+          isSigner: true
+          docs: ["The owner of the orders, and signing authority over the token and ticket vaults"]
+        },
+        {
+          name: "userTicketVault"
+          isMut: true
+          isSigner: false
+          docs: ["Receives refunds for cancelled asks, and pays for new asks"]
+        },
+        {
+          name: "userTokenVault"
+          isMut: true
+          isSigner: false
+          docs: ["Receives refunds for cancelled bids, and pays for new bids"]
+        },
+        {
+          name: "bondManager"
+          isMut: true
+          isSigner: false
+          docs: ["The `BondManager` account tracks global information related to this particular bond market"]
+        },
+        {
+          name: "orderbookMarketState"
+          isMut: true
+          isSigner: false
+        },
+        {
+          name: "eventQueue"
+          isMut: true
+          isSigner: false
+        },
+        {
+          name: "bids"
+          isMut: true
+          isSigner: false
+        },
+        {
+          name: "asks"
+          isMut: true
+          isSigner: false
+        },
+        {
+          name: "underlyingTokenVault"
+          isMut: true
+          isSigner: false
+          docs: ["The market token vault"]
+        },
+        {
+          name: "bondTicketMint"
+          isMut: true
+          isSigner: false
+          docs: ["The market ticket mint"]
+        },
+        {
+          name: "tokenProgram"
+          isMut: false
+          isSigner: false
+        }
+      ]
+      args: [
+        {
+          name: "params"
+          type: {
+            defined: "ReplaceOrdersParams"
+          }
+        }
+      ]
+    },
     {
       name: "lendOrder"
       docs: ["Place a `Lend` order to the book by depositing tokens"]
@@ -875,6 +1345,75 @@ export type JetBonds = {
         }
       ]
     },
+    {
+      name: "pruneExpiredOrders"
+      docs: [
+        "Remove orders that are past their good-til-time from the book",
+        "Permissionless, the posted funds are returned to each order's owner",
+        "",
+        "Expired orders are also removed when an order would be matched against them, in which",
+        "case the funds are returned when the crank consumes the resulting out events."
+      ]
+      accounts: [
+        {
+          name: "bondManager"
+          isMut: true
+          isSigner: false
+          docs: ["The `BondManager` account tracks global information related to this particular bond market"]
+        },
+        {
+          name: "orderbookMarketState"
+          isMut: true
+          isSigner: false
+        },
+        {
+          name: "eventQueue"
+          isMut: true
+          isSigner: false
+        },
+        {
+          name: "bids"
+          isMut: true
+          isSigner: false
+        },
+        {
+          name: "asks"
+          isMut: true
+          isSigner: false
+        },
+        {
+          name: "underlyingTokenVault"
+          isMut: true
+          isSigner: false
+          docs: ["The market token vault"]
+        },
+        {
+          name: "bondTicketMint"
+          isMut: true
+          isSigner: false
+          docs: ["The market ticket mint"]
+        },
+        {
+          name: "claimsMint"
+          isMut: true
+          isSigner: false
+          docs: ["Token mint used by the margin program to track the debt that must be collateralized"]
+        },
+        {
+          name: "tokenProgram"
+          isMut: false
+          isSigner: false
+        }
+      ]
+      args: [
+        {
+          name: "orderIds"
+          type: {
+            vec: "u128"
+          }
+        }
+      ]
+    },
     {
       name: "consumeEvents"
       docs: ["Crank specific instruction, processes the event queue"]
@@ -908,12 +1447,13 @@ export type JetBonds = {
           isSigner: false
         },
         {
-          name: "crankMetadata"
+          name: "crankAuthorization"
           isMut: false
           isSigner: false
+          docs: ["The `CrankAuthorization` of the crank, if required by the crank policy of the market"]
         },
         {
-          name: "crankSigner"
+          name: "crank"
           isMut: false
           isSigner: true
         },
@@ -1203,28 +1743,70 @@ export type JetBonds = {
           type: "u32"
         }
       ]
-    }
-  ]
-  accounts: [
+    },
     {
-      name: "BondManager"
+      name: "orderbookQuote"
       docs: [
-        "The `BondManager` contains all the information necessary to run the bond market",
-        "",
-        "Utilized by program instructions to verify given transaction accounts are correct. Contains data",
-        "about the bond market including the tenor and ticket<->token conversion rate"
+        "Get the best bid and ask on the orderbook",
+        "Does not modify any accounts, the `OrderbookQuote` is set as the return data"
       ]
-      type: {
-        kind: "struct"
-        fields: [
-          {
-            name: "versionTag"
-            docs: ["Versioning and tag information"]
-            type: "u64"
+      accounts: [
+        {
+          name: "bondManager"
+          isMut: false
+          isSigner: false
+          docs: ["The `BondManager` account tracks global information related to this particular bond market"]
+        },
+        {
+          name: "bids"
+          isMut: false
+          isSigner: false
+        },
+        {
+          name: "asks"
+          isMut: false
+          isSigner: false
+        }
+      ]
+      args: []
+    },
+    {
+      name: "marginUserDebt"
+      docs: [
+        "Get the debt and obligations due for a margin user",
+        "Does not modify any accounts, the `MarginUserDebt` is set as the return data"
+      ]
+      accounts: [
+        {
+          name: "marginUser"
+          isMut: false
+          isSigner: false
+          docs: ["The account tracking information related to this particular user"]
+        }
+      ]
+      args: []
+    }
+  ]
+  accounts: [
+    {
+      name: "BondManager"
+      docs: [
+        "The `BondManager` contains all the information necessary to run the bond market",
+        "",
+        "Utilized by program instructions to verify given transaction accounts are correct. Contains data",
+        "about the bond market including the tenor and ticket<->token conversion rate"
+      ]
+      type: {
+        kind: "struct"
+        fields: [
+          {
+            name: "versionTag"
+            docs: ["Versioning and tag information"]
+            type: "u64"
           },
           {
-            name: "programAuthority"
-            docs: ["The address allowed to make changes to this program state"]
+            name: "airspace"
+            docs: ["The airspace the market is a part of"]
             type: "publicKey"
           },
           {
@@ -1274,7 +1856,7 @@ export type JetBonds = {
             name: "collateralMint"
             docs: [
               "Mint owned by bonds to issue collateral value to a user",
-              "The deposit notes are monitored by the margin program to track value"
+              "The collateral notes are monitored by the margin program to track value"
             ]
             type: "publicKey"
           },
@@ -1312,11 +1894,21 @@ export type JetBonds = {
             docs: ["Can tickets be redeemed"]
             type: "bool"
           },
+          {
+            name: "crankPolicy"
+            docs: ["Who may consume events from the orderbook, see [CrankPolicy]"]
+            type: "u8"
+          },
+          {
+            name: "minPermitTier"
+            docs: ["The minimum `AirspacePermit` tier required for margin accounts to borrow in this market"]
+            type: "u8"
+          },
           {
             name: "reserved"
             docs: ["reserved for future use"]
             type: {
-              array: ["u8", 28]
+              array: ["u8", 26]
             }
           },
           {
@@ -1332,6 +1924,23 @@ export type JetBonds = {
         ]
       }
     },
+    {
+      name: "CrankAuthorization"
+      docs: ["This authorizes a crank to act on any orderbook within the airspace"]
+      type: {
+        kind: "struct"
+        fields: [
+          {
+            name: "crank"
+            type: "publicKey"
+          },
+          {
+            name: "airspace"
+            type: "publicKey"
+          }
+        ]
+      }
+    },
     {
       name: "MarginUser"
       docs: ["An acocunt used to track margin users of the market"]
@@ -1424,7 +2033,7 @@ export type JetBonds = {
           {
             name: "maturationTimestamp"
             docs: ["The time that the obligation must be repaid"]
-            type: "u64"
+            type: "i64"
           },
           {
             name: "balance"
@@ -1600,6 +2209,178 @@ export type JetBonds = {
         ]
       }
     },
+    {
+      name: "CrankPolicy"
+      docs: ["Decides who may run the `consume_events` instruction for a market"]
+      type: {
+        kind: "enum"
+        variants: [
+          {
+            name: "Authorized"
+          },
+          {
+            name: "Permissionless"
+          }
+        ]
+      }
+    },
+    {
+      name: "BondManagerChange"
+      docs: ["A configuration value of a `BondManager` that was changed, with its old and new values"]
+      type: {
+        kind: "enum"
+        variants: [
+          {
+            name: "TicketsPaused"
+            fields: [
+              {
+                name: "old"
+                type: "bool"
+              },
+              {
+                name: "new"
+                type: "bool"
+              }
+            ]
+          },
+          {
+            name: "UnderlyingOracle"
+            fields: [
+              {
+                name: "old"
+                type: "publicKey"
+              },
+              {
+                name: "new"
+                type: "publicKey"
+              }
+            ]
+          },
+          {
+            name: "TicketOracle"
+            fields: [
+              {
+                name: "old"
+                type: "publicKey"
+              },
+              {
+                name: "new"
+                type: "publicKey"
+              }
+            ]
+          },
+          {
+            name: "Duration"
+            fields: [
+              {
+                name: "old"
+                type: "i64"
+              },
+              {
+                name: "new"
+                type: "i64"
+              }
+            ]
+          },
+          {
+            name: "CrankPolicy"
+            fields: [
+              {
+                name: "old"
+                type: {
+                  defined: "CrankPolicy"
+                }
+              },
+              {
+                name: "new"
+                type: {
+                  defined: "CrankPolicy"
+                }
+              }
+            ]
+          },
+          {
+            name: "MinPermitTier"
+            fields: [
+              {
+                name: "old"
+                type: "u8"
+              },
+              {
+                name: "new"
+                type: "u8"
+              }
+            ]
+          }
+        ]
+      }
+    },
+    {
+      name: "MarginReplaceOrdersParams"
+      docs: ["Parameters for an atomic replacement of a margin account's borrow orders"]
+      type: {
+        kind: "struct"
+        fields: [
+          {
+            name: "cancelOrderIds"
+            docs: ["The borrow orders to remove from the book before posting the new orders"]
+            type: {
+              vec: "u128"
+            }
+          },
+          {
+            name: "asks"
+            docs: ["New borrow orders, must be `post_only`"]
+            type: {
+              vec: {
+                defined: "OrderParams"
+              }
+            }
+          }
+        ]
+      }
+    },
+    {
+      name: "MarginUserDebt"
+      docs: ["The debt owed by a margin user, as returned by the `margin_user_debt` instruction"]
+      type: {
+        kind: "struct"
+        fields: [
+          {
+            name: "pending"
+            docs: ["Amount of underlying tokens posted in borrow orders that have not been filled"]
+            type: "u64"
+          },
+          {
+            name: "committed"
+            docs: ["Amount of underlying tokens owed for filled borrow orders, including past due debt"]
+            type: "u64"
+          },
+          {
+            name: "outstandingObligations"
+            docs: ["The number of obligations that have not been fully repaid"]
+            type: "u64"
+          },
+          {
+            name: "nextObligationToRepay"
+            docs: ["The sequence number of the next obligation that must be repaid, if any"]
+            type: {
+              option: "u64"
+            }
+          },
+          {
+            name: "nextObligationMaturity"
+            docs: ["The maturation timestamp of the next obligation that must be repaid"]
+            type: "i64"
+          },
+          {
+            name: "isPastDue"
+            docs: ["Whether the next obligation is past its maturity"]
+            type: "bool"
+          }
+        ]
+      }
+    },
     {
       name: "Debt"
       type: {
@@ -1618,7 +2399,7 @@ export type JetBonds = {
           {
             name: "nextObligationMaturity"
             docs: ["The maturation timestamp of the next obligation that is unpaid"]
-            type: "u64"
+            type: "i64"
           },
           {
             name: "pending"
@@ -1683,6 +2464,89 @@ export type JetBonds = {
         ]
       }
     },
+    {
+      name: "QuoteLevel"
+      docs: ["The total size of all orders at a price on one side of the book"]
+      type: {
+        kind: "struct"
+        fields: [
+          {
+            name: "price"
+            docs: ["The limit price of the orders, as an fp32 number of underlying tokens per ticket"]
+            type: "u64"
+          },
+          {
+            name: "quantity"
+            docs: ["The total number of tickets in all orders at this price"]
+            type: "u64"
+          }
+        ]
+      }
+    },
+    {
+      name: "OrderbookQuote"
+      docs: [
+        "The best prices currently available in a bond market, as returned by the",
+        "`orderbook_quote` instruction"
+      ]
+      type: {
+        kind: "struct"
+        fields: [
+          {
+            name: "bestBid"
+            docs: ["The highest price any lender is willing to pay for tickets"]
+            type: {
+              option: {
+                defined: "QuoteLevel"
+              }
+            }
+          },
+          {
+            name: "bestAsk"
+            docs: ["The lowest price any borrower is willing to sell tickets for"]
+            type: {
+              option: {
+                defined: "QuoteLevel"
+              }
+            }
+          }
+        ]
+      }
+    },
+    {
+      name: "ReplaceOrdersParams"
+      docs: ["Parameters for an atomic replacement of a user's resting orders"]
+      type: {
+        kind: "struct"
+        fields: [
+          {
+            name: "cancelOrderIds"
+            docs: ["The orders to remove from the book before posting the new orders"]
+            type: {
+              vec: "u128"
+            }
+          },
+          {
+            name: "bids"
+            docs: ["New lend orders, must be `post_only`"]
+            type: {
+              vec: {
+                defined: "OrderParams"
+              }
+            }
+          },
+          {
+            name: "asks"
+            docs: ["New ticket sale orders, must be `post_only`"]
+            type: {
+              vec: {
+                defined: "OrderParams"
+              }
+            }
+          }
+        ]
+      }
+    },
     {
       name: "OrderParams"
       docs: ["Parameters needed for order placement"]
@@ -1727,6 +2591,17 @@ export type JetBonds = {
             name: "autoStake"
             docs: ["Should the purchased tickets be automatically staked with the ticket program"]
             type: "bool"
+          },
+          {
+            name: "goodTilTime"
+            docs: [
+              "Good-til-time: the unix timestamp after which any posted remainder of the order is no",
+              "longer filled, and may be pruned from the book. `None` keeps the order until it is",
+              "filled or cancelled."
+            ]
+            type: {
+              option: "i64"
+            }
           }
         ]
       }
@@ -1766,7 +2641,22 @@ export type JetBonds = {
           index: false
         },
         {
-          name: "underlyingToken"
+          name: "airspace"
+          type: "publicKey"
+          index: false
+        },
+        {
+          name: "underlyingTokenMint"
+          type: "publicKey"
+          index: false
+        },
+        {
+          name: "underlyingOracle"
+          type: "publicKey"
+          index: false
+        },
+        {
+          name: "ticketOracle"
           type: "publicKey"
           index: false
         },
@@ -1804,6 +2694,16 @@ export type JetBonds = {
           name: "asks"
           type: "publicKey"
           index: false
+        },
+        {
+          name: "minBaseOrderSize"
+          type: "u64"
+          index: false
+        },
+        {
+          name: "tickSize"
+          type: "u64"
+          index: false
         }
       ]
     },
@@ -1818,17 +2718,22 @@ export type JetBonds = {
       ]
     },
     {
-      name: "SkippedError"
+      name: "ToggleOrderMatching"
       fields: [
         {
-          name: "message"
-          type: "string"
+          name: "bondManager"
+          type: "publicKey"
+          index: false
+        },
+        {
+          name: "isOrderbookPaused"
+          type: "bool"
           index: false
         }
       ]
     },
     {
-      name: "MarginUserInitialized"
+      name: "BondManagerModified"
       fields: [
         {
           name: "bondManager"
@@ -1836,19 +2741,16 @@ export type JetBonds = {
           index: false
         },
         {
-          name: "borrowerAccount"
-          type: "publicKey"
-          index: false
-        },
-        {
-          name: "marginAccount"
-          type: "publicKey"
+          name: "change"
+          type: {
+            defined: "BondManagerChange"
+          }
           index: false
         }
       ]
     },
     {
-      name: "MarginBorrow"
+      name: "MarginUserInitialized"
       fields: [
         {
           name: "bondManager"
@@ -1856,18 +2758,48 @@ export type JetBonds = {
           index: false
         },
         {
-          name: "marginAccount"
+          name: "borrowerAccount"
           type: "publicKey"
           index: false
         },
         {
-          name: "borrowerAccount"
+          name: "marginAccount"
           type: "publicKey"
           index: false
         },
         {
-          name: "orderSummary"
-          type: {
+          name: "underlyingSettlement"
+          type: "publicKey"
+          index: false
+        },
+        {
+          name: "ticketSettlement"
+          type: "publicKey"
+          index: false
+        }
+      ]
+    },
+    {
+      name: "MarginBorrow"
+      fields: [
+        {
+          name: "bondManager"
+          type: "publicKey"
+          index: false
+        },
+        {
+          name: "marginAccount"
+          type: "publicKey"
+          index: false
+        },
+        {
+          name: "borrowerAccount"
+          type: "publicKey"
+          index: false
+        },
+        {
+          name: "orderSummary"
+          type: {
             array: ["u8", 48]
           }
           index: false
@@ -1902,6 +2834,16 @@ export type JetBonds = {
     {
       name: "ObligationFulfilled"
       fields: [
+        {
+          name: "obligation"
+          type: "publicKey"
+          index: false
+        },
+        {
+          name: "orderbookUser"
+          type: "publicKey"
+          index: false
+        },
         {
           name: "borrower"
           type: "publicKey"
@@ -1934,6 +2876,31 @@ export type JetBonds = {
         }
       ]
     },
+    {
+      name: "OrderExpired"
+      fields: [
+        {
+          name: "bondManager"
+          type: "publicKey"
+          index: false
+        },
+        {
+          name: "user"
+          type: "publicKey"
+          index: false
+        },
+        {
+          name: "orderId"
+          type: "u128"
+          index: false
+        },
+        {
+          name: "expiredAt"
+          type: "i64"
+          index: false
+        }
+      ]
+    },
     {
       name: "LendOrder"
       fields: [
@@ -2087,6 +3054,16 @@ export type JetBonds = {
           index: false
         }
       ]
+    },
+    {
+      name: "SkippedError"
+      fields: [
+        {
+          name: "message"
+          type: "string"
+          index: false
+        }
+      ]
     }
   ]
   errors: [
@@ -2212,133 +3189,183 @@ export type JetBonds = {
     },
     {
       code: 6024
+      name: "TicketsPaused"
+      msg: "tickets are paused"
+    },
+    {
+      code: 6025
       name: "UnauthorizedCaller"
       msg: "this signer is not authorized to place a permissioned order"
     },
     {
-      code: 6025
+      code: 6026
       name: "UserDoesNotOwnAccount"
       msg: "this user does not own the user account"
     },
     {
-      code: 6026
+      code: 6027
       name: "UserDoesNotOwnAdapter"
       msg: "this adapter does not belong to the user"
     },
     {
-      code: 6027
+      code: 6028
       name: "UserNotInMarket"
       msg: "this user account is not associated with this bond market"
     },
     {
-      code: 6028
+      code: 6029
       name: "WrongAdapter"
       msg: "the wrong adapter account was passed to this instruction"
     },
     {
-      code: 6029
+      code: 6030
       name: "WrongAsks"
       msg: "asks account does not belong to this market"
     },
     {
-      code: 6030
+      code: 6031
+      name: "WrongAirspace"
+      msg: "the market is configured for a different airspace"
+    },
+    {
+      code: 6032
+      name: "WrongAirspaceAuthorization"
+      msg: "the signer is not authorized to perform this action in the current airspace"
+    },
+    {
+      code: 6033
       name: "WrongBids"
       msg: "bids account does not belong to this market"
     },
     {
-      code: 6031
+      code: 6034
       name: "WrongBondManager"
       msg: "adapter does not belong to given bond manager"
     },
     {
-      code: 6032
+      code: 6035
       name: "WrongCrankAuthority"
       msg: "wrong authority for this crank instruction"
     },
     {
-      code: 6033
+      code: 6036
       name: "WrongEventQueue"
       msg: "event queue account does not belong to this market"
     },
     {
-      code: 6034
+      code: 6037
       name: "WrongMarketState"
       msg: "this market state is not associated with this market"
     },
     {
-      code: 6035
+      code: 6038
       name: "WrongTicketManager"
       msg: "wrong TicketManager account provided"
     },
     {
-      code: 6036
+      code: 6039
       name: "DoesNotOwnMarket"
       msg: "this market owner does not own this market"
     },
     {
-      code: 6037
+      code: 6040
       name: "WrongClaimAccount"
       msg: "the wrong account was provided for the token account that represents a user's claims"
     },
     {
-      code: 6038
+      code: 6041
       name: "WrongClaimMint"
       msg: "the wrong account was provided for the claims token mint"
     },
     {
-      code: 6039
+      code: 6042
       name: "WrongDepositsMint"
       msg: "the wrong account was provided for the claims token mint"
     },
     {
-      code: 6040
+      code: 6043
       name: "WrongOracle"
       msg: "wrong oracle address was sent to instruction"
     },
     {
-      code: 6041
+      code: 6044
       name: "WrongMarginUser"
       msg: "wrong margin borrower account address was sent to instruction"
     },
     {
-      code: 6042
+      code: 6045
       name: "WrongProgramAuthority"
       msg: "incorrect authority account"
     },
     {
-      code: 6043
+      code: 6046
       name: "WrongTicketMint"
       msg: "not the ticket mint for this bond market"
     },
     {
-      code: 6044
+      code: 6047
       name: "WrongTicketSettlementAccount"
       msg: "wrong ticket settlement account"
     },
     {
-      code: 6045
+      code: 6048
       name: "WrongUnderlyingSettlementAccount"
       msg: "wrong underlying settlement account"
     },
     {
-      code: 6046
+      code: 6049
       name: "WrongUnderlyingTokenMint"
       msg: "wrong underlying token mint for this bond market"
     },
     {
-      code: 6047
+      code: 6050
       name: "WrongUserAccount"
       msg: "wrong user account address was sent to instruction"
     },
     {
-      code: 6048
+      code: 6051
       name: "WrongVault"
       msg: "wrong vault address was sent to instruction"
     },
     {
-      code: 6049
+      code: 6052
       name: "ZeroDivision"
       msg: "attempted to divide with zero"
+    },
+    {
+      code: 6053
+      name: "SlippageExceeded"
+      msg: "immediate-or-cancel order could not be matched within its limit price"
+    },
+    {
+      code: 6054
+      name: "InvalidOrderExpiry"
+      msg: "the good-til-time of an order must be in the future"
+    },
+    {
+      code: 6055
+      name: "OrderNotExpired"
+      msg: "the order has not reached its good-til-time and cannot be pruned"
+    },
+    {
+      code: 6056
+      name: "ReplacementNotPostOnly"
+      msg: "orders posted by a replacement must be post-only"
+    },
+    {
+      code: 6057
+      name: "InvalidDuration"
+      msg: "the duration of a bond market must be positive"
+    },
+    {
+      code: 6058
+      name: "WrongPermit"
+      msg: "the airspace permit does not belong to the owner of the margin account"
+    },
+    {
+      code: 6059
+      name: "TooManyExpiredOrders"
+      msg: "too many expired orders would be matched, they must be pruned first"
     }
   ]
 }
@@ -2375,6 +3402,13 @@ export const IDL: JetBonds = {
       },
       value: 'b"claim_ticket"'
     },
+    {
+      name: "CRANK_AUTHORIZATION",
+      type: {
+        defined: "&[u8]"
+      },
+      value: 'b"crank_authorization"'
+    },
     {
       name: "DEPOSIT_NOTES",
       type: {
@@ -2433,6 +3467,78 @@ export const IDL: JetBonds = {
     }
   ],
   instructions: [
+    {
+      name: "authorizeCrank",
+      docs: ["authorize an address to run orderbook consume_event instructions"],
+      accounts: [
+        {
+          name: "crank",
+          isMut: false,
+          isSigner: false,
+          docs: ["The crank signer pubkey"]
+        },
+        {
+          name: "authority",
+          isMut: false,
+          isSigner: true,
+          docs: ["The authority that must sign to make this change"]
+        },
+        {
+          name: "airspace",
+          isMut: false,
+          isSigner: false,
+          docs: ["The airspace being modified"]
+        },
+        {
+          name: "crankAuthorization",
+          isMut: true,
+          isSigner: false,
+          docs: ["The account containing the metadata for the key"]
+        },
+        {
+          name: "payer",
+          isMut: true,
+          isSigner: true,
+          docs: ["The address paying the rent for the account"]
+        },
+        {
+          name: "systemProgram",
+          isMut: false,
+          isSigner: false
+        }
+      ],
+      args: []
+    },
+    {
+      name: "revokeCrank",
+      docs: ["unauthorize an address to run orderbook consume_event instructions"],
+      accounts: [
+        {
+          name: "metadataAccount",
+          isMut: true,
+          isSigner: false,
+          docs: ["The account containing the metadata for the key"]
+        },
+        {
+          name: "authority",
+          isMut: false,
+          isSigner: true,
+          docs: ["The authority that must sign to make this change"]
+        },
+        {
+          name: "airspace",
+          isMut: false,
+          isSigner: false,
+          docs: ["The airspace being modified"]
+        },
+        {
+          name: "receiver",
+          isMut: true,
+          isSigner: false
+        }
+      ],
+      args: []
+    },
     {
       name: "initializeBondManager",
       docs: ["Initializes a BondManager for a bond ticket market"],
@@ -2468,16 +3574,22 @@ export const IDL: JetBonds = {
           docs: ["Mints tokens to a margin account to represent debt that must be collateralized"]
         },
         {
-          name: "deposits",
+          name: "collateral",
           isMut: true,
           isSigner: false,
           docs: ["Mints tokens to a margin account to represent debt that must be collateralized"]
         },
         {
-          name: "programAuthority",
+          name: "authority",
           isMut: false,
           isSigner: true,
-          docs: ["The controlling signer for this program"]
+          docs: ["The authority that must sign to make this change"]
+        },
+        {
+          name: "airspace",
+          isMut: false,
+          isSigner: false,
+          docs: ["The airspace being modified"]
         },
         {
           name: "underlyingOracle",
@@ -2539,31 +3651,37 @@ export const IDL: JetBonds = {
           name: "orderbookMarketState",
           isMut: true,
           isSigner: false,
-          docs: [
-            "Accounts for `agnostic-orderbook`",
-            "Should be uninitialized, used for invoking create_account and sent to the agnostic orderbook program"
-          ]
+          docs: ["AOB market state"]
         },
         {
           name: "eventQueue",
           isMut: true,
-          isSigner: false
+          isSigner: false,
+          docs: ["AOB market event queue", "", "Must be initialized"]
         },
         {
           name: "bids",
           isMut: true,
-          isSigner: false
+          isSigner: false,
+          docs: ["AOB market bids"]
         },
         {
           name: "asks",
           isMut: true,
-          isSigner: false
+          isSigner: false,
+          docs: ["AOB market asks"]
         },
         {
-          name: "programAuthority",
+          name: "authority",
           isMut: false,
           isSigner: true,
-          docs: ["Signing account responsible for changes to the bond market"]
+          docs: ["The authority that must sign to make this change"]
+        },
+        {
+          name: "airspace",
+          isMut: false,
+          isSigner: false,
+          docs: ["The airspace being modified"]
         },
         {
           name: "payer",
@@ -2588,8 +3706,8 @@ export const IDL: JetBonds = {
       ]
     },
     {
-      name: "modifyBondManager",
-      docs: ["Modify a `BondManager` account", "Authority use only"],
+      name: "setTicketsPaused",
+      docs: ["Pause or resume the redemption of staked tickets", "Authority use only"],
       accounts: [
         {
           name: "bondManager",
@@ -2598,67 +3716,229 @@ export const IDL: JetBonds = {
           docs: ["The `BondManager` manages asset tokens for a particular bond duration"]
         },
         {
-          name: "programAuthority",
+          name: "authority",
           isMut: false,
           isSigner: true,
-          docs: ["The controlling signer for this program"]
+          docs: ["The authority that must sign to make this change"]
+        },
+        {
+          name: "airspace",
+          isMut: false,
+          isSigner: false,
+          docs: ["The airspace being modified"]
         }
       ],
       args: [
         {
-          name: "data",
-          type: "bytes"
-        },
-        {
-          name: "offset",
-          type: "u32"
+          name: "paused",
+          type: "bool"
         }
       ]
     },
     {
-      name: "pauseOrderMatching",
-      docs: ["Pause matching of orders placed in the orderbook"],
+      name: "setUnderlyingOracle",
+      docs: ["Set the oracle that values the underlying token", "Authority use only"],
       accounts: [
         {
           name: "bondManager",
-          isMut: false,
+          isMut: true,
           isSigner: false,
           docs: ["The `BondManager` manages asset tokens for a particular bond duration"]
         },
         {
-          name: "orderbookMarketState",
-          isMut: true,
-          isSigner: false
+          name: "authority",
+          isMut: false,
+          isSigner: true,
+          docs: ["The authority that must sign to make this change"]
         },
         {
-          name: "programAuthority",
+          name: "airspace",
           isMut: false,
-          isSigner: true,
-          docs: ["The controlling signer for this program"]
+          isSigner: false,
+          docs: ["The airspace being modified"]
+        },
+        {
+          name: "oracle",
+          isMut: false,
+          isSigner: false,
+          docs: ["The new oracle"]
         }
       ],
       args: []
     },
     {
-      name: "resumeOrderMatching",
-      docs: [
-        "Resume matching of orders placed in the orderbook",
-        "NOTE: This instruction may have to be run several times to clear the",
-        "existing matches. Check the `orderbook_market_state.pause_matching` variable",
-        "to determine success"
-      ],
+      name: "setTicketOracle",
+      docs: ["Set the oracle that values the bond tickets", "Authority use only"],
       accounts: [
         {
           name: "bondManager",
-          isMut: false,
+          isMut: true,
           isSigner: false,
           docs: ["The `BondManager` manages asset tokens for a particular bond duration"]
         },
         {
-          name: "orderbookMarketState",
-          isMut: true,
-          isSigner: false
-        },
+          name: "authority",
+          isMut: false,
+          isSigner: true,
+          docs: ["The authority that must sign to make this change"]
+        },
+        {
+          name: "airspace",
+          isMut: false,
+          isSigner: false,
+          docs: ["The airspace being modified"]
+        },
+        {
+          name: "oracle",
+          isMut: false,
+          isSigner: false,
+          docs: ["The new oracle"]
+        }
+      ],
+      args: []
+    },
+    {
+      name: "setDuration",
+      docs: ["Set the tenor applied to orders filled from now on", "Authority use only"],
+      accounts: [
+        {
+          name: "bondManager",
+          isMut: true,
+          isSigner: false,
+          docs: ["The `BondManager` manages asset tokens for a particular bond duration"]
+        },
+        {
+          name: "authority",
+          isMut: false,
+          isSigner: true,
+          docs: ["The authority that must sign to make this change"]
+        },
+        {
+          name: "airspace",
+          isMut: false,
+          isSigner: false,
+          docs: ["The airspace being modified"]
+        }
+      ],
+      args: [
+        {
+          name: "duration",
+          type: "i64"
+        }
+      ]
+    },
+    {
+      name: "setCrankPolicy",
+      docs: ["Set who may consume events from the orderbook", "Authority use only"],
+      accounts: [
+        {
+          name: "bondManager",
+          isMut: true,
+          isSigner: false,
+          docs: ["The `BondManager` manages asset tokens for a particular bond duration"]
+        },
+        {
+          name: "authority",
+          isMut: false,
+          isSigner: true,
+          docs: ["The authority that must sign to make this change"]
+        },
+        {
+          name: "airspace",
+          isMut: false,
+          isSigner: false,
+          docs: ["The airspace being modified"]
+        }
+      ],
+      args: [
+        {
+          name: "policy",
+          type: {
+            defined: "CrankPolicy"
+          }
+        }
+      ]
+    },
+    {
+      name: "setMinPermitTier",
+      docs: ["Set the minimum airspace permit tier needed to create a margin user in the market", "Authority use only"],
+      accounts: [
+        {
+          name: "bondManager",
+          isMut: true,
+          isSigner: false,
+          docs: ["The `BondManager` manages asset tokens for a particular bond duration"]
+        },
+        {
+          name: "authority",
+          isMut: false,
+          isSigner: true,
+          docs: ["The authority that must sign to make this change"]
+        },
+        {
+          name: "airspace",
+          isMut: false,
+          isSigner: false,
+          docs: ["The airspace being modified"]
+        }
+      ],
+      args: [
+        {
+          name: "tier",
+          type: "u8"
+        }
+      ]
+    },
+    {
+      name: "pauseOrderMatching",
+      docs: ["Pause matching of orders placed in the orderbook"],
+      accounts: [
+        {
+          name: "bondManager",
+          isMut: false,
+          isSigner: false,
+          docs: ["The `BondManager` manages asset tokens for a particular bond duration"]
+        },
+        {
+          name: "orderbookMarketState",
+          isMut: true,
+          isSigner: false
+        },
+        {
+          name: "authority",
+          isMut: false,
+          isSigner: true,
+          docs: ["The authority that must sign to make this change"]
+        },
+        {
+          name: "airspace",
+          isMut: false,
+          isSigner: false,
+          docs: ["The airspace being modified"]
+        }
+      ],
+      args: []
+    },
+    {
+      name: "resumeOrderMatching",
+      docs: [
+        "Resume matching of orders placed in the orderbook",
+        "NOTE: This instruction may have to be run several times to clear the",
+        "existing matches. Check the `orderbook_market_state.pause_matching` variable",
+        "to determine success"
+      ],
+      accounts: [
+        {
+          name: "bondManager",
+          isMut: false,
+          isSigner: false,
+          docs: ["The `BondManager` manages asset tokens for a particular bond duration"]
+        },
+        {
+          name: "orderbookMarketState",
+          isMut: true,
+          isSigner: false
+        },
         {
           name: "eventQueue",
           isMut: true,
@@ -2675,10 +3955,16 @@ export const IDL: JetBonds = {
           isSigner: false
         },
         {
-          name: "programAuthority",
+          name: "authority",
           isMut: false,
           isSigner: true,
-          docs: ["The controlling signer for this program"]
+          docs: ["The authority that must sign to make this change"]
+        },
+        {
+          name: "airspace",
+          isMut: false,
+          isSigner: false,
+          docs: ["The airspace being modified"]
         }
       ],
       args: []
@@ -2761,7 +4047,13 @@ export const IDL: JetBonds = {
           name: "claimsMetadata",
           isMut: false,
           isSigner: false,
-          docs: ["Token metadata account used by the margin program to register claims positions"]
+          docs: ["Token metadata account needed by the margin program to register the claim position"]
+        },
+        {
+          name: "permit",
+          isMut: false,
+          isSigner: false,
+          docs: ["The permit allowing the owner of the margin account to use the market's airspace"]
         }
       ],
       args: []
@@ -2857,12 +4149,81 @@ export const IDL: JetBonds = {
         }
       ]
     },
+    {
+      name: "marginReplaceOrders",
+      docs: ["Atomically cancels a set of margin borrow orders and posts a new set of post-only borrow orders"],
+      accounts: [
+        {
+          name: "borrowerAccount",
+          isMut: true,
+          isSigner: false,
+          docs: ["The account tracking borrower debts"]
+        },
+        {
+          name: "marginAccount",
+          isMut: false,
+          isSigner: true,
+          docs: ["The margin account that owns the orders"]
+        },
+        {
+          name: "claims",
+          isMut: true,
+          isSigner: false,
+          docs: ["Token account used by the margin program to track the debt that must be collateralized"]
+        },
+        {
+          name: "claimsMint",
+          isMut: true,
+          isSigner: false,
+          docs: ["Token mint used by the margin program to track the debt that must be collateralized"]
+        },
+        {
+          name: "bondManager",
+          isMut: true,
+          isSigner: false,
+          docs: ["The `BondManager` account tracks global information related to this particular bond market"]
+        },
+        {
+          name: "orderbookMarketState",
+          isMut: true,
+          isSigner: false
+        },
+        {
+          name: "eventQueue",
+          isMut: true,
+          isSigner: false
+        },
+        {
+          name: "bids",
+          isMut: true,
+          isSigner: false
+        },
+        {
+          name: "asks",
+          isMut: true,
+          isSigner: false
+        },
+        {
+          name: "tokenProgram",
+          isMut: false,
+          isSigner: false
+        }
+      ],
+      args: [
+        {
+          name: "params",
+          type: {
+            defined: "MarginReplaceOrdersParams"
+          }
+        }
+      ]
+    },
     {
       name: "refreshPosition",
       docs: ["Refresh the associated margin account `claims` for a given `MarginUser` account"],
       accounts: [
         {
-          name: "borrowerAccount",
+          name: "marginUser",
           isMut: false,
           isSigner: false,
           docs: ["The account tracking information related to this particular user"]
@@ -2989,28 +4350,203 @@ export const IDL: JetBonds = {
           docs: ["Token account used by the margin program to track the debt that must be collateralized"]
         },
         {
-          name: "claimsMint",
+          name: "claimsMint",
+          isMut: true,
+          isSigner: false,
+          docs: ["Token mint used by the margin program to track the debt that must be collateralized"]
+        },
+        {
+          name: "underlyingTokenVault",
+          isMut: false,
+          isSigner: false
+        },
+        {
+          name: "bondTicketMint",
+          isMut: false,
+          isSigner: false
+        },
+        {
+          name: "underlyingSettlement",
+          isMut: false,
+          isSigner: false
+        },
+        {
+          name: "ticketSettlement",
+          isMut: false,
+          isSigner: false
+        }
+      ],
+      args: []
+    },
+    {
+      name: "sellTicketsOrder",
+      docs: ["Place an order to the book to sell tickets, which will burn them"],
+      accounts: [
+        {
+          name: "user",
+          isMut: false,
+          isSigner: true,
+          docs: ["Signing authority over the ticket vault transferring for a borrow order"]
+        },
+        {
+          name: "userTicketVault",
+          isMut: true,
+          isSigner: false,
+          docs: ["Account containing the bond tickets being sold"]
+        },
+        {
+          name: "userTokenVault",
+          isMut: true,
+          isSigner: false,
+          docs: ["The account to recieve the matched tokens"]
+        },
+        {
+          name: "bondManager",
+          isMut: true,
+          isSigner: false,
+          docs: ["The `BondManager` account tracks global information related to this particular bond market"]
+        },
+        {
+          name: "orderbookMarketState",
+          isMut: true,
+          isSigner: false
+        },
+        {
+          name: "eventQueue",
+          isMut: true,
+          isSigner: false
+        },
+        {
+          name: "bids",
+          isMut: true,
+          isSigner: false
+        },
+        {
+          name: "asks",
+          isMut: true,
+          isSigner: false
+        },
+        {
+          name: "bondTicketMint",
+          isMut: true,
+          isSigner: false,
+          docs: ["The market ticket mint"]
+        },
+        {
+          name: "tokenProgram",
+          isMut: false,
+          isSigner: false
+        }
+      ],
+      args: [
+        {
+          name: "params",
+          type: {
+            defined: "OrderParams"
+          }
+        }
+      ]
+    },
+    {
+      name: "cancelOrder",
+      docs: ["Cancels an order on the book"],
+      accounts: [
+        {
+          name: "owner",
+          isMut: false,
+          isSigner: true,
+          docs: ["The owner of the order"]
+        },
+        {
+          name: "bondManager",
+          isMut: true,
+          isSigner: false,
+          docs: ["The `BondManager` account tracks global information related to this particular bond market"]
+        },
+        {
+          name: "orderbookMarketState",
+          isMut: true,
+          isSigner: false
+        },
+        {
+          name: "eventQueue",
+          isMut: true,
+          isSigner: false
+        },
+        {
+          name: "bids",
+          isMut: true,
+          isSigner: false
+        },
+        {
+          name: "asks",
+          isMut: true,
+          isSigner: false
+        }
+      ],
+      args: [
+        {
+          name: "orderId",
+          type: "u128"
+        }
+      ]
+    },
+    {
+      name: "cancelAllOrders",
+      docs: ["Cancels all of a user's orders on the book, returning the posted funds"],
+      accounts: [
+        {
+          name: "owner",
+          isMut: false,
+          isSigner: true,
+          docs: ["The owner of the orders. For margin orders, this is the margin account"]
+        },
+        {
+          name: "bondManager",
+          isMut: true,
+          isSigner: false,
+          docs: ["The `BondManager` account tracks global information related to this particular bond market"]
+        },
+        {
+          name: "orderbookMarketState",
+          isMut: true,
+          isSigner: false
+        },
+        {
+          name: "eventQueue",
+          isMut: true,
+          isSigner: false
+        },
+        {
+          name: "bids",
+          isMut: true,
+          isSigner: false
+        },
+        {
+          name: "asks",
           isMut: true,
-          isSigner: false,
-          docs: ["Token mint used by the margin program to track the debt that must be collateralized"]
+          isSigner: false
         },
         {
           name: "underlyingTokenVault",
-          isMut: false,
-          isSigner: false
+          isMut: true,
+          isSigner: false,
+          docs: ["The market token vault"]
         },
         {
           name: "bondTicketMint",
-          isMut: false,
-          isSigner: false
+          isMut: true,
+          isSigner: false,
+          docs: ["The market ticket mint"]
         },
         {
-          name: "underlyingSettlement",
-          isMut: false,
-          isSigner: false
+          name: "claimsMint",
+          isMut: true,
+          isSigner: false,
+          docs: ["Token mint used by the margin program to track the debt that must be collateralized"]
         },
         {
-          name: "ticketSettlement",
+          name: "tokenProgram",
           isMut: false,
           isSigner: false
         }
@@ -3018,26 +4554,26 @@ export const IDL: JetBonds = {
       args: []
     },
     {
-      name: "sellTicketsOrder",
-      docs: ["Place an order to the book to sell tickets, which will burn them"],
+      name: "replaceOrders",
+      docs: ["Atomically cancels a set of orders and posts a new set of post-only orders"],
       accounts: [
         {
           name: "user",
           isMut: false,
           isSigner: true,
-          docs: ["Signing authority over the ticket vault transferring for a borrow order"]
+          docs: ["The owner of the orders, and signing authority over the token and ticket vaults"]
         },
         {
           name: "userTicketVault",
           isMut: true,
           isSigner: false,
-          docs: ["Account containing the bond tickets being sold"]
+          docs: ["Receives refunds for cancelled asks, and pays for new asks"]
         },
         {
           name: "userTokenVault",
           isMut: true,
           isSigner: false,
-          docs: ["The account to recieve the matched tokens"]
+          docs: ["Receives refunds for cancelled bids, and pays for new bids"]
         },
         {
           name: "bondManager",
@@ -3065,6 +4601,12 @@ export const IDL: JetBonds = {
           isMut: true,
           isSigner: false
         },
+        {
+          name: "underlyingTokenVault",
+          isMut: true,
+          isSigner: false,
+          docs: ["The market token vault"]
+        },
         {
           name: "bondTicketMint",
           isMut: true,
@@ -3081,20 +4623,37 @@ export const IDL: JetBonds = {
         {
           name: "params",
           type: {
-            defined: "OrderParams"
+            defined: "ReplaceOrdersParams"
           }
         }
       ]
     },
     {
-      name: "cancelOrder",
-      docs: ["Cancels an order on the book"],
+      name: "lendOrder",
+      docs: ["Place a `Lend` order to the book by depositing tokens"],
       accounts: [
         {
           name: "user",
           isMut: false,
           isSigner: true,
-          docs: ["The signing authority for this user account"]
+          docs: ["Signing authority over the token vault transferring for a lend order"]
+        },
+        {
+          name: "userTicketVault",
+          isMut: true,
+          isSigner: false,
+          docs: ["If auto stake is not enabled, the ticket account that will recieve the bond tickets"]
+        },
+        {
+          name: "userTokenVault",
+          isMut: true,
+          isSigner: false
+        },
+        {
+          name: "splitTicket",
+          isMut: true,
+          isSigner: false,
+          docs: ["SplitTicket that will be created if the order is filled as a taker and `auto_stake` is enabled"]
         },
         {
           name: "bondManager",
@@ -3121,42 +4680,53 @@ export const IDL: JetBonds = {
           name: "asks",
           isMut: true,
           isSigner: false
-        }
-      ],
-      args: [
-        {
-          name: "orderId",
-          type: "u128"
-        }
-      ]
-    },
-    {
-      name: "lendOrder",
-      docs: ["Place a `Lend` order to the book by depositing tokens"],
-      accounts: [
-        {
-          name: "user",
-          isMut: false,
-          isSigner: true,
-          docs: ["Signing authority over the token vault transferring for a lend order"]
         },
         {
-          name: "userTicketVault",
+          name: "underlyingTokenVault",
           isMut: true,
           isSigner: false,
-          docs: ["If auto stake is not enabled, the ticket account that will recieve the bond tickets"]
+          docs: ["The market token vault"]
         },
         {
-          name: "userTokenVault",
+          name: "payer",
           isMut: true,
+          isSigner: true,
+          docs: ["payer for `Obligation` initialization"]
+        },
+        {
+          name: "systemProgram",
+          isMut: false,
           isSigner: false
         },
         {
-          name: "splitTicket",
-          isMut: true,
-          isSigner: false,
-          docs: ["SplitTicket that will be created if the order is filled as a taker and `auto_stake` is enabled"]
+          name: "tokenProgram",
+          isMut: false,
+          isSigner: false
+        }
+      ],
+      args: [
+        {
+          name: "params",
+          type: {
+            defined: "OrderParams"
+          }
         },
+        {
+          name: "seed",
+          type: "bytes"
+        }
+      ]
+    },
+    {
+      name: "pruneExpiredOrders",
+      docs: [
+        "Remove orders that are past their good-til-time from the book",
+        "Permissionless, the posted funds are returned to each order's owner",
+        "",
+        "Expired orders are also removed when an order would be matched against them, in which",
+        "case the funds are returned when the crank consumes the resulting out events."
+      ],
+      accounts: [
         {
           name: "bondManager",
           isMut: true,
@@ -3190,15 +4760,16 @@ export const IDL: JetBonds = {
           docs: ["The market token vault"]
         },
         {
-          name: "payer",
+          name: "bondTicketMint",
           isMut: true,
-          isSigner: true,
-          docs: ["payer for `Obligation` initialization"]
+          isSigner: false,
+          docs: ["The market ticket mint"]
         },
         {
-          name: "systemProgram",
-          isMut: false,
-          isSigner: false
+          name: "claimsMint",
+          isMut: true,
+          isSigner: false,
+          docs: ["Token mint used by the margin program to track the debt that must be collateralized"]
         },
         {
           name: "tokenProgram",
@@ -3208,14 +4779,10 @@ export const IDL: JetBonds = {
       ],
       args: [
         {
-          name: "params",
+          name: "orderIds",
           type: {
-            defined: "OrderParams"
+            vec: "u128"
           }
-        },
-        {
-          name: "seed",
-          type: "bytes"
         }
       ]
     },
@@ -3252,12 +4819,13 @@ export const IDL: JetBonds = {
           isSigner: false
         },
         {
-          name: "crankMetadata",
+          name: "crankAuthorization",
           isMut: false,
-          isSigner: false
+          isSigner: false,
+          docs: ["The `CrankAuthorization` of the crank, if required by the crank policy of the market"]
         },
         {
-          name: "crankSigner",
+          name: "crank",
           isMut: false,
           isSigner: true
         },
@@ -3547,6 +5115,48 @@ export const IDL: JetBonds = {
           type: "u32"
         }
       ]
+    },
+    {
+      name: "orderbookQuote",
+      docs: [
+        "Get the best bid and ask on the orderbook",
+        "Does not modify any accounts, the `OrderbookQuote` is set as the return data"
+      ],
+      accounts: [
+        {
+          name: "bondManager",
+          isMut: false,
+          isSigner: false,
+          docs: ["The `BondManager` account tracks global information related to this particular bond market"]
+        },
+        {
+          name: "bids",
+          isMut: false,
+          isSigner: false
+        },
+        {
+          name: "asks",
+          isMut: false,
+          isSigner: false
+        }
+      ],
+      args: []
+    },
+    {
+      name: "marginUserDebt",
+      docs: [
+        "Get the debt and obligations due for a margin user",
+        "Does not modify any accounts, the `MarginUserDebt` is set as the return data"
+      ],
+      accounts: [
+        {
+          name: "marginUser",
+          isMut: false,
+          isSigner: false,
+          docs: ["The account tracking information related to this particular user"]
+        }
+      ],
+      args: []
     }
   ],
   accounts: [
@@ -3567,8 +5177,8 @@ export const IDL: JetBonds = {
             type: "u64"
           },
           {
-            name: "programAuthority",
-            docs: ["The address allowed to make changes to this program state"],
+            name: "airspace",
+            docs: ["The airspace the market is a part of"],
             type: "publicKey"
           },
           {
@@ -3618,7 +5228,7 @@ export const IDL: JetBonds = {
             name: "collateralMint",
             docs: [
               "Mint owned by bonds to issue collateral value to a user",
-              "The deposit notes are monitored by the margin program to track value"
+              "The collateral notes are monitored by the margin program to track value"
             ],
             type: "publicKey"
           },
@@ -3656,11 +5266,21 @@ export const IDL: JetBonds = {
             docs: ["Can tickets be redeemed"],
             type: "bool"
           },
+          {
+            name: "crankPolicy",
+            docs: ["Who may consume events from the orderbook, see [CrankPolicy]"],
+            type: "u8"
+          },
+          {
+            name: "minPermitTier",
+            docs: ["The minimum `AirspacePermit` tier required for margin accounts to borrow in this market"],
+            type: "u8"
+          },
           {
             name: "reserved",
             docs: ["reserved for future use"],
             type: {
-              array: ["u8", 28]
+              array: ["u8", 26]
             }
           },
           {
@@ -3676,6 +5296,23 @@ export const IDL: JetBonds = {
         ]
       }
     },
+    {
+      name: "CrankAuthorization",
+      docs: ["This authorizes a crank to act on any orderbook within the airspace"],
+      type: {
+        kind: "struct",
+        fields: [
+          {
+            name: "crank",
+            type: "publicKey"
+          },
+          {
+            name: "airspace",
+            type: "publicKey"
+          }
+        ]
+      }
+    },
     {
       name: "MarginUser",
       docs: ["An acocunt used to track margin users of the market"],
@@ -3768,7 +5405,7 @@ export const IDL: JetBonds = {
           {
             name: "maturationTimestamp",
             docs: ["The time that the obligation must be repaid"],
-            type: "u64"
+            type: "i64"
           },
           {
             name: "balance",
@@ -3923,23 +5560,195 @@ export const IDL: JetBonds = {
             }
           },
           {
-            name: "duration",
-            docs: ["Units added to the initial stake timestamp to determine claim maturity"],
-            type: "i64"
+            name: "duration",
+            docs: ["Units added to the initial stake timestamp to determine claim maturity"],
+            type: "i64"
+          }
+        ]
+      }
+    },
+    {
+      name: "InitializeOrderbookParams",
+      docs: ["Parameters necessary for orderbook initialization"],
+      type: {
+        kind: "struct",
+        fields: [
+          {
+            name: "minBaseOrderSize",
+            docs: ["The minimum order size that can be inserted into the orderbook after matching."],
+            type: "u64"
+          }
+        ]
+      }
+    },
+    {
+      name: "CrankPolicy",
+      docs: ["Decides who may run the `consume_events` instruction for a market"],
+      type: {
+        kind: "enum",
+        variants: [
+          {
+            name: "Authorized"
+          },
+          {
+            name: "Permissionless"
+          }
+        ]
+      }
+    },
+    {
+      name: "BondManagerChange",
+      docs: ["A configuration value of a `BondManager` that was changed, with its old and new values"],
+      type: {
+        kind: "enum",
+        variants: [
+          {
+            name: "TicketsPaused",
+            fields: [
+              {
+                name: "old",
+                type: "bool"
+              },
+              {
+                name: "new",
+                type: "bool"
+              }
+            ]
+          },
+          {
+            name: "UnderlyingOracle",
+            fields: [
+              {
+                name: "old",
+                type: "publicKey"
+              },
+              {
+                name: "new",
+                type: "publicKey"
+              }
+            ]
+          },
+          {
+            name: "TicketOracle",
+            fields: [
+              {
+                name: "old",
+                type: "publicKey"
+              },
+              {
+                name: "new",
+                type: "publicKey"
+              }
+            ]
+          },
+          {
+            name: "Duration",
+            fields: [
+              {
+                name: "old",
+                type: "i64"
+              },
+              {
+                name: "new",
+                type: "i64"
+              }
+            ]
+          },
+          {
+            name: "CrankPolicy",
+            fields: [
+              {
+                name: "old",
+                type: {
+                  defined: "CrankPolicy"
+                }
+              },
+              {
+                name: "new",
+                type: {
+                  defined: "CrankPolicy"
+                }
+              }
+            ]
+          },
+          {
+            name: "MinPermitTier",
+            fields: [
+              {
+                name: "old",
+                type: "u8"
+              },
+              {
+                name: "new",
+                type: "u8"
+              }
+            ]
+          }
+        ]
+      }
+    },
+    {
+      name: "MarginReplaceOrdersParams",
+      docs: ["Parameters for an atomic replacement of a margin account's borrow orders"],
+      type: {
+        kind: "struct",
+        fields: [
+          {
+            name: "cancelOrderIds",
+            docs: ["The borrow orders to remove from the book before posting the new orders"],
+            type: {
+              vec: "u128"
+            }
+          },
+          {
+            name: "asks",
+            docs: ["New borrow orders, must be `post_only`"],
+            type: {
+              vec: {
+                defined: "OrderParams"
+              }
+            }
           }
         ]
       }
     },
     {
-      name: "InitializeOrderbookParams",
-      docs: ["Parameters necessary for orderbook initialization"],
+      name: "MarginUserDebt",
+      docs: ["The debt owed by a margin user, as returned by the `margin_user_debt` instruction"],
       type: {
         kind: "struct",
         fields: [
           {
-            name: "minBaseOrderSize",
-            docs: ["The minimum order size that can be inserted into the orderbook after matching."],
+            name: "pending",
+            docs: ["Amount of underlying tokens posted in borrow orders that have not been filled"],
+            type: "u64"
+          },
+          {
+            name: "committed",
+            docs: ["Amount of underlying tokens owed for filled borrow orders, including past due debt"],
+            type: "u64"
+          },
+          {
+            name: "outstandingObligations",
+            docs: ["The number of obligations that have not been fully repaid"],
             type: "u64"
+          },
+          {
+            name: "nextObligationToRepay",
+            docs: ["The sequence number of the next obligation that must be repaid, if any"],
+            type: {
+              option: "u64"
+            }
+          },
+          {
+            name: "nextObligationMaturity",
+            docs: ["The maturation timestamp of the next obligation that must be repaid"],
+            type: "i64"
+          },
+          {
+            name: "isPastDue",
+            docs: ["Whether the next obligation is past its maturity"],
+            type: "bool"
           }
         ]
       }
@@ -3962,7 +5771,7 @@ export const IDL: JetBonds = {
           {
             name: "nextObligationMaturity",
             docs: ["The maturation timestamp of the next obligation that is unpaid"],
-            type: "u64"
+            type: "i64"
           },
           {
             name: "pending",
@@ -4027,6 +5836,89 @@ export const IDL: JetBonds = {
         ]
       }
     },
+    {
+      name: "QuoteLevel",
+      docs: ["The total size of all orders at a price on one side of the book"],
+      type: {
+        kind: "struct",
+        fields: [
+          {
+            name: "price",
+            docs: ["The limit price of the orders, as an fp32 number of underlying tokens per ticket"],
+            type: "u64"
+          },
+          {
+            name: "quantity",
+            docs: ["The total number of tickets in all orders at this price"],
+            type: "u64"
+          }
+        ]
+      }
+    },
+    {
+      name: "OrderbookQuote",
+      docs: [
+        "The best prices currently available in a bond market, as returned by the",
+        "`orderbook_quote` instruction"
+      ],
+      type: {
+        kind: "struct",
+        fields: [
+          {
+            name: "bestBid",
+            docs: ["The highest price any lender is willing to pay for tickets"],
+            type: {
+              option: {
+                defined: "QuoteLevel"
+              }
+            }
+          },
+          {
+            name: "bestAsk",
+            docs: ["The lowest price any borrower is willing to sell tickets for"],
+            type: {
+              option: {
+                defined: "QuoteLevel"
+              }
+            }
+          }
+        ]
+      }
+    },
+    {
+      name: "ReplaceOrdersParams",
+      docs: ["Parameters for an atomic replacement of a user's resting orders"],
+      type: {
+        kind: "struct",
+        fields: [
+          {
+            name: "cancelOrderIds",
+            docs: ["The orders to remove from the book before posting the new orders"],
+            type: {
+              vec: "u128"
+            }
+          },
+          {
+            name: "bids",
+            docs: ["New lend orders, must be `post_only`"],
+            type: {
+              vec: {
+                defined: "OrderParams"
+              }
+            }
+          },
+          {
+            name: "asks",
+            docs: ["New ticket sale orders, must be `post_only`"],
+            type: {
+              vec: {
+                defined: "OrderParams"
+              }
+            }
+          }
+        ]
+      }
+    },
     {
       name: "OrderParams",
       docs: ["Parameters needed for order placement"],
@@ -4071,6 +5963,17 @@ export const IDL: JetBonds = {
             name: "autoStake",
             docs: ["Should the purchased tickets be automatically staked with the ticket program"],
             type: "bool"
+          },
+          {
+            name: "goodTilTime",
+            docs: [
+              "Good-til-time: the unix timestamp after which any posted remainder of the order is no",
+              "longer filled, and may be pruned from the book. `None` keeps the order until it is",
+              "filled or cancelled."
+            ],
+            type: {
+              option: "i64"
+            }
           }
         ]
       }
@@ -4110,7 +6013,22 @@ export const IDL: JetBonds = {
           index: false
         },
         {
-          name: "underlyingToken",
+          name: "airspace",
+          type: "publicKey",
+          index: false
+        },
+        {
+          name: "underlyingTokenMint",
+          type: "publicKey",
+          index: false
+        },
+        {
+          name: "underlyingOracle",
+          type: "publicKey",
+          index: false
+        },
+        {
+          name: "ticketOracle",
           type: "publicKey",
           index: false
         },
@@ -4148,6 +6066,16 @@ export const IDL: JetBonds = {
           name: "asks",
           type: "publicKey",
           index: false
+        },
+        {
+          name: "minBaseOrderSize",
+          type: "u64",
+          index: false
+        },
+        {
+          name: "tickSize",
+          type: "u64",
+          index: false
         }
       ]
     },
@@ -4162,11 +6090,33 @@ export const IDL: JetBonds = {
       ]
     },
     {
-      name: "SkippedError",
+      name: "ToggleOrderMatching",
       fields: [
         {
-          name: "message",
-          type: "string",
+          name: "bondManager",
+          type: "publicKey",
+          index: false
+        },
+        {
+          name: "isOrderbookPaused",
+          type: "bool",
+          index: false
+        }
+      ]
+    },
+    {
+      name: "BondManagerModified",
+      fields: [
+        {
+          name: "bondManager",
+          type: "publicKey",
+          index: false
+        },
+        {
+          name: "change",
+          type: {
+            defined: "BondManagerChange"
+          },
           index: false
         }
       ]
@@ -4188,6 +6138,16 @@ export const IDL: JetBonds = {
           name: "marginAccount",
           type: "publicKey",
           index: false
+        },
+        {
+          name: "underlyingSettlement",
+          type: "publicKey",
+          index: false
+        },
+        {
+          name: "ticketSettlement",
+          type: "publicKey",
+          index: false
         }
       ]
     },
@@ -4246,6 +6206,16 @@ export const IDL: JetBonds = {
     {
       name: "ObligationFulfilled",
       fields: [
+        {
+          name: "obligation",
+          type: "publicKey",
+          index: false
+        },
+        {
+          name: "orderbookUser",
+          type: "publicKey",
+          index: false
+        },
         {
           name: "borrower",
           type: "publicKey",
@@ -4278,6 +6248,31 @@ export const IDL: JetBonds = {
         }
       ]
     },
+    {
+      name: "OrderExpired",
+      fields: [
+        {
+          name: "bondManager",
+          type: "publicKey",
+          index: false
+        },
+        {
+          name: "user",
+          type: "publicKey",
+          index: false
+        },
+        {
+          name: "orderId",
+          type: "u128",
+          index: false
+        },
+        {
+          name: "expiredAt",
+          type: "i64",
+          index: false
+        }
+      ]
+    },
     {
       name: "LendOrder",
       fields: [
@@ -4431,6 +6426,16 @@ export const IDL: JetBonds = {
           index: false
         }
       ]
+    },
+    {
+      name: "SkippedError",
+      fields: [
+        {
+          name: "message",
+          type: "string",
+          index: false
+        }
+      ]
     }
   ],
   errors: [
@@ -4556,133 +6561,183 @@ export const IDL: JetBonds = {
     },
     {
       code: 6024,
+      name: "TicketsPaused",
+      msg: "tickets are paused"
+    },
+    {
+      code: 6025,
       name: "UnauthorizedCaller",
       msg: "this signer is not authorized to place a permissioned order"
     },
     {
-      code: 6025,
+      code: 6026,
       name: "UserDoesNotOwnAccount",
       msg: "this user does not own the user account"
     },
     {
-      code: 6026,
+      code: 6027,
       name: "UserDoesNotOwnAdapter",
       msg: "this adapter does not belong to the user"
     },
     {
-      code: 6027,
+      code: 6028,
       name: "UserNotInMarket",
       msg: "this user account is not associated with this bond market"
     },
     {
-      code: 6028,
+      code: 6029,
       name: "WrongAdapter",
       msg: "the wrong adapter account was passed to this instruction"
     },
     {
-      code: 6029,
+      code: 6030,
       name: "WrongAsks",
       msg: "asks account does not belong to this market"
     },
     {
-      code: 6030,
+      code: 6031,
+      name: "WrongAirspace",
+      msg: "the market is configured for a different airspace"
+    },
+    {
+      code: 6032,
+      name: "WrongAirspaceAuthorization",
+      msg: "the signer is not authorized to perform this action in the current airspace"
+    },
+    {
+      code: 6033,
       name: "WrongBids",
       msg: "bids account does not belong to this market"
     },
     {
-      code: 6031,
+      code: 6034,
       name: "WrongBondManager",
       msg: "adapter does not belong to given bond manager"
     },
     {
-      code: 6032,
+      code: 6035,
       name: "WrongCrankAuthority",
       msg: "wrong authority for this crank instruction"
     },
     {
-      code: 6033,
+      code: 6036,
       name: "WrongEventQueue",
       msg: "event queue account does not belong to this market"
     },
     {
-      code: 6034,
+      code: 6037,
       name: "WrongMarketState",
       msg: "this market state is not associated with this market"
     },
     {
-      code: 6035,
+      code: 6038,
       name: "WrongTicketManager",
       msg: "wrong TicketManager account provided"
     },
     {
-      code: 6036,
+      code: 6039,
       name: "DoesNotOwnMarket",
       msg: "this market owner does not own this market"
     },
     {
-      code: 6037,
+      code: 6040,
       name: "WrongClaimAccount",
       msg: "the wrong account was provided for the token account that represents a user's claims"
     },
     {
-      code: 6038,
+      code: 6041,
       name: "WrongClaimMint",
       msg: "the wrong account was provided for the claims token mint"
     },
     {
-      code: 6039,
+      code: 6042,
       name: "WrongDepositsMint",
       msg: "the wrong account was provided for the claims token mint"
     },
     {
-      code: 6040,
+      code: 6043,
       name: "WrongOracle",
       msg: "wrong oracle address was sent to instruction"
     },
     {
-      code: 6041,
+      code: 6044,
       name: "WrongMarginUser",
       msg: "wrong margin borrower account address was sent to instruction"
     },
     {
-      code: 6042,
+      code: 6045,
       name: "WrongProgramAuthority",
       msg: "incorrect authority account"
     },
     {
-      code: 6043,
+      code: 6046,
       name: "WrongTicketMint",
       msg: "not the ticket mint for this bond market"
     },
     {
-      code: 6044,
+      code: 6047,
       name: "WrongTicketSettlementAccount",
       msg: "wrong ticket settlement account"
     },
     {
-      code: 6045,
+      code: 6048,
       name: "WrongUnderlyingSettlementAccount",
       msg: "wrong underlying settlement account"
     },
     {
-      code: 6046,
+      code: 6049,
       name: "WrongUnderlyingTokenMint",
       msg: "wrong underlying token mint for this bond market"
     },
     {
-      code: 6047,
+      code: 6050,
       name: "WrongUserAccount",
       msg: "wrong user account address was sent to instruction"
     },
     {
-      code: 6048,
+      code: 6051,
       name: "WrongVault",
       msg: "wrong vault address was sent to instruction"
     },
     {
-      code: 6049,
+      code: 6052,
       name: "ZeroDivision",
       msg: "attempted to divide with zero"
+    },
+    {
+      code: 6053,
+      name: "SlippageExceeded",
+      msg: "immediate-or-cancel order could not be matched within its limit price"
+    },
+    {
+      code: 6054,
+      name: "InvalidOrderExpiry",
+      msg: "the good-til-time of an order must be in the future"
+    },
+    {
+      code: 6055,
+      name: "OrderNotExpired",
+      msg: "the order has not reached its good-til-time and cannot be pruned"
+    },
+    {
+      code: 6056,
+      name: "ReplacementNotPostOnly",
+      msg: "orders posted by a replacement must be post-only"
+    },
+    {
+      code: 6057,
+      name: "InvalidDuration",
+      msg: "the duration of a bond market must be positive"
+    },
+    {
+      code: 6058,
+      name: "WrongPermit",
+      msg: "the airspace permit does not belong to the owner of the margin account"
+    },
+    {
+      code: 6059,
+      name: "TooManyExpiredOrders",
+      msg: "too many expired orders would be matched, they must be pruned first"
     }
   ]
 }
@@ -1,5 +1,5 @@
 mod critbit;
-mod interest_pricing;
+pub mod interest_pricing;
 pub mod methods;
 pub mod types;
 
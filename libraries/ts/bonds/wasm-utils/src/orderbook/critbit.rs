@@ -25,7 +25,9 @@ pub struct CallbackInfo {
     pub order_submitted: [u8; 8],
     /// configuration used by callback execution
     pub flags: u8,
-    _reserved: [u8; 14],
+    /// The unix timestamp after which the order may be pruned from the book, zero if it never expires
+    pub expires_at: [u8; 8],
+    _reserved: [u8; 6],
 }
 
 /// Multiply a `u64` with a fixed point 32 number
@@ -13,6 +13,7 @@ import {
   TransactionSignature
 } from "@solana/web3.js"
 import { feesBuffer, Pool, PoolAction } from "./pool/pool"
+import { AIRSPACE_PROGRAM_ID, DEFAULT_AIRSPACE } from "./pool/poolManager"
 import {
   AccountPositionList,
   AccountPositionListLayout,
@@ -214,7 +215,7 @@ export class MarginAccount {
   ) {
     this.owner = translateAddress(owner)
     this.address = MarginAccount.derive(programs, owner, seed)
-    this.airspace = PublicKey.default // TODO: populate from on-chain state
+    this.airspace = DEFAULT_AIRSPACE // TODO: populate from on-chain state
     this.pools = pools
     this.walletTokens = walletTokens
     this.positions = this.getPositions()
@@ -299,6 +300,13 @@ export class MarginAccount {
     return findDerivedAccount(this.programs.config.marginProgramId, "token-config", this.airspace, tokenMint)
   }
 
+  /**
+   * Derive the address of the permit allowing the owner to use the account's airspace.
+   */
+  findPermitAddress(): PublicKey {
+    return findDerivedAccount(AIRSPACE_PROGRAM_ID, "airspace-permit", this.airspace, this.owner)
+  }
+
   /**
    *
    * @param args
@@ -992,6 +1000,8 @@ export class MarginAccount {
           owner: this.owner,
           payer: this.provider.wallet.publicKey,
           marginAccount: this.address,
+          airspace: this.airspace,
+          permit: this.findPermitAddress(),
           systemProgram: SystemProgram.programId
         })
         .instruction()
@@ -1147,6 +1157,10 @@ export class MarginAccount {
         marginAccount: this.address,
         metadata
       })
+      // the metadata is only accepted while the token has no config in the airspace
+      .remainingAccounts([
+        { pubkey: this.findTokenConfigAddress(positionMint), isSigner: false, isWritable: false }
+      ])
       .instruction()
     instructions.push(ix)
   }
@@ -1325,6 +1339,10 @@ export class MarginAccount {
         rent: SYSVAR_RENT_PUBKEY,
         systemProgram: SystemProgram.programId
       })
+      // the metadata is only accepted while the token has no config in the airspace
+      .remainingAccounts([
+        { pubkey: this.findTokenConfigAddress(positionTokenMint), isSigner: false, isWritable: false }
+      ])
       .instruction()
     instructions.push(ix)
     return tokenAccount
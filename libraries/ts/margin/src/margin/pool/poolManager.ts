@@ -8,6 +8,23 @@ import { TokenKind } from "../metadata"
 import { PoolAddresses, Pool } from "./pool"
 import { MarginPoolConfigData } from "./state"
 
+/** The address of the airspace that pools created before airspaces were migrated into */
+export const DEFAULT_AIRSPACE = new PublicKey("BwQhHumhyyyRBtCsiSrdnFCinJDCaaMBbbyRhqJ5p81d")
+
+/** The program that manages airspaces and the permits to use them */
+export const AIRSPACE_PROGRAM_ID = new PublicKey("JPASMkxARMmbeahk37H8PAAP1UzPNC4wGhvwLnBsfHi")
+
+/**
+ * The seed an airspace contributes to the addresses of its pools. Pools in the default
+ * airspace use an empty seed, so they keep the addresses derived from just the token mint.
+ * @param {Address} airspace
+ * @returns {Buffer}
+ */
+export function poolAirspaceSeed(airspace: Address): Buffer {
+  const airspaceAddress = translateAddress(airspace)
+  return airspaceAddress.equals(DEFAULT_AIRSPACE) ? Buffer.alloc(0) : airspaceAddress.toBuffer()
+}
+
 interface TokenMetadataParams {
   tokenKind: TokenKind
   collateralWeight: number
@@ -21,6 +38,7 @@ interface IPoolCreationParams {
   pythProduct: Address
   pythPrice: Address
   marginPoolConfig: MarginPoolConfigData
+  airspace?: Address
   provider?: AnchorProvider
   programs?: MarginPrograms
 }
@@ -42,6 +60,7 @@ export class PoolManager {
    *     tokenMint: Address
    *     poolConfig?: MarginPoolConfig
    *     tokenConfig?: MarginTokenConfig
+   *     airspace?: Address
    *     programs?: MarginPrograms
    *   }}
    * @return {Promise<Pool>}
@@ -50,13 +69,15 @@ export class PoolManager {
   async load({
     tokenMint,
     tokenConfig,
+    airspace = DEFAULT_AIRSPACE,
     programs = this.programs
   }: {
     tokenMint: Address
     tokenConfig: MarginTokenConfig
+    airspace?: Address
     programs?: MarginPrograms
   }): Promise<Pool> {
-    const addresses = this._derive({ programs, tokenMint })
+    const addresses = this._derive({ programs, tokenMint, airspace })
     const marginPool = new Pool(programs, addresses, tokenConfig)
     await marginPool.refresh()
     return marginPool
@@ -102,10 +123,11 @@ export class PoolManager {
     pythProduct,
     pythPrice,
     marginPoolConfig,
+    airspace = DEFAULT_AIRSPACE,
     provider = this.provider,
     programs = this.programs
   }: IPoolCreationParams) {
-    const addresses = this._derive({ programs: programs, tokenMint })
+    const addresses = this._derive({ programs: programs, tokenMint, airspace })
     const address = addresses.marginPool
     const ix1: TransactionInstruction[] = []
     if (this.owner) {
@@ -263,12 +285,21 @@ export class PoolManager {
    * Derive accounts from tokenMint
    * @param {MarginPrograms} programs
    * @param {Address} tokenMint
+   * @param {Address} airspace
    * @returns {PublicKey} Margin Pool Address
    */
-  private _derive({ programs, tokenMint }: { programs: MarginPrograms; tokenMint: Address }): PoolAddresses {
+  private _derive({
+    programs,
+    tokenMint,
+    airspace
+  }: {
+    programs: MarginPrograms
+    tokenMint: Address
+    airspace: Address
+  }): PoolAddresses {
     const tokenMintAddress = translateAddress(tokenMint)
     const programId = translateAddress(programs.config.marginPoolProgramId)
-    const marginPool = findDerivedAccount(programId, tokenMintAddress)
+    const marginPool = findDerivedAccount(programId, poolAirspaceSeed(airspace), tokenMintAddress)
     const vault = findDerivedAccount(programId, marginPool, "vault")
     const depositNoteMint = findDerivedAccount(programId, marginPool, "deposit-notes")
     const loanNoteMint = findDerivedAccount(programId, marginPool, "loan-notes")
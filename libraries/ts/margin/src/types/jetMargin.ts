@@ -4,10 +4,12 @@ export type JetMargin = {
   docs: [
     "This crate documents the instructions used in the `margin` program of the",
     "[jet-v2 repo](https://github.com/jet-lab/jet-v2/).",
+    "",
     "Handler functions are described for each instruction well as struct parameters",
     "(and their types and descriptions are listed) and any handler function",
     "parameters aside from parameters that exist in every instruction handler function.",
-    "Parameters of events emitted for the purposes of data logging are also included."
+    "",
+    "Accounts associated with events emitted for the purposes of data logging are also included."
   ]
   constants: [
     {
@@ -31,6 +33,13 @@ export type JetMargin = {
       }
       value: 'b"liquidator-config"'
     },
+    {
+      name: "TOKEN_CATEGORY_SEED"
+      type: {
+        defined: "&[u8]"
+      }
+      value: 'b"token-category"'
+    },
     {
       name: "MAX_ORACLE_CONFIDENCE"
       type: "u16"
@@ -60,35 +69,30 @@ export type JetMargin = {
       docs: [
         "Create a new margin account for a user",
         "",
-        "This instruction does the following:",
-        "",
-        "1.  Let `account` be a mutable reference to the margin account.",
-        "",
-        "2.  Initialize the margin account by setting the margin account version, owner,",
-        "bump seed, user seed, and setting liquidator pubkey field to the default",
-        "(if an account is being liquidated, the liquidator pubkey will be set here).",
-        "",
-        "3.  Emit the `AccountCreated` event for data logging (see table below):",
+        "# Parameters",
         "",
-        "4.  Return `Ok(())`.",
+        "* `seed` - An abritrary integer used to derive the new account address. This allows",
+        "           a user to own multiple margin accounts, by creating new accounts with different",
+        "           seed values.",
         "",
+        "# [Accounts](jet_margin::accounts::CreateAccount)",
+        "    ",
+        "|     |     |     |",
+        "| --- | --- | --- |",
+        "| **Name** | **Type** | **Description** |",
+        "| `owner` | `signer` | The owner of the new margin account. |",
+        "| `payer` | `signer` | The pubkey paying rent for the new margin account opening. |",
+        "| `margin_account` | `writable` | The margin account to initialize for the owner. |",
+        "| `airspace` | `read_only` | The airspace the new account will belong to. |",
+        "| `permit` | `read_only` | The owner's permit for the airspace, required only if the airspace is restricted. |",
+        "| `system_program` | `read_only` | The [system native program](https://docs.solana.com/developing/runtime-facilities/programs#system-program). |",
         "",
-        "**Parameters of create\\_account.rs:**",
-        "",
-        "|     |     |",
-        "| --- | --- |",
-        "| **Name** | **Description** |",
-        "| `owner` | The owner of the new margin account. |",
-        "| `payer` | The pubkey paying rent for the new margin account opening. |",
-        "| `margin_account` | The margin account to initialize for the owner. |",
-        "| `system_program` | The system program. |",
-        "",
-        "**Events emitted by create\\_account.rs:**",
+        "# Events",
         "",
         "|     |     |",
         "| --- | --- |",
         "| **Event Name** | **Description** |",
-        "| [`events::AccountCreated`] | The created account (includes the margin account pubkey, the owner of margin account’s the pubkey, and the seed). |"
+        "| [`events::AccountCreated`] | The created account. |"
       ]
       accounts: [
         {
@@ -108,6 +112,22 @@ export type JetMargin = {
           isSigner: false
           docs: ["The margin account to initialize for the owner"]
         },
+        {
+          name: "airspace"
+          isMut: false
+          isSigner: false
+          docs: ["The airspace the new account will belong to"]
+        },
+        {
+          name: "permit"
+          isMut: false
+          isSigner: false
+          docs: [
+            "The permit allowing the owner to use the airspace, which is only",
+            "required when the airspace is restricted.",
+            ""
+          ]
+        },
         {
           name: "systemProgram"
           isMut: false
@@ -122,40 +142,80 @@ export type JetMargin = {
       ]
     },
     {
-      name: "closeAccount"
+      name: "migrateAccount"
       docs: [
-        "Close a user's margin account",
-        "",
-        "This instruction does the following:",
-        "",
-        "1.  Let `account`be a reference to the margin account being closed.",
+        "Migrate a version 1 margin account, which was created before accounts were bound",
+        "to an airspace.",
         "",
-        "2.  Check if the loaded margin account has any open positions.",
+        "The account is resized to fit the current layout and assigned to the default",
+        "airspace, which is where the pools and configs it already uses belong.",
         "",
-        "a.  If open positions exist, then return `ErrorCode::AccountNotEmpty`.",
+        "# [Accounts](jet_margin::accounts::MigrateAccount)",
         "",
-        "3.  Emit the `AccountClosed` event for data logging (see table below).",
+        "|     |     |     |",
+        "| --- | --- | --- |",
+        "| **Name** | **Type** | **Description** |",
+        "| `owner` | `signer` | The owner of the margin account. |",
+        "| `payer` | `signer` | The pubkey paying rent for the additional account space. |",
+        "| `margin_account` | `writable` | The margin account to migrate. |",
+        "| `system_program` | `read_only` | The [system native program](https://docs.solana.com/developing/runtime-facilities/programs#system-program). |",
         "",
-        "4.  Load the margin account.",
+        "# Events",
         "",
-        "5.  Return `Ok(())`.",
+        "|     |     |",
+        "| --- | --- |",
+        "| **Event Name** | **Description** |",
+        "| [`events::AccountMigrated`] | The migrated account. |"
+      ]
+      accounts: [
+        {
+          name: "owner"
+          isMut: false
+          isSigner: true
+          docs: ["The owner of the margin account"]
+        },
+        {
+          name: "payer"
+          isMut: true
+          isSigner: true
+          docs: ["The address paying for the additional rent"]
+        },
+        {
+          name: "marginAccount"
+          isMut: true
+          isSigner: false
+          docs: ["The version 1 margin account to migrate", ""]
+        },
+        {
+          name: "systemProgram"
+          isMut: false
+          isSigner: false
+        }
+      ]
+      args: []
+    },
+    {
+      name: "closeAccount"
+      docs: [
+        "Close a user's margin account",
         "",
+        "The margin account must have zero positions remaining to be closed.",
         "",
-        "**Parameters of close\\_account.rs:**",
+        "# [Accounts](jet_margin::accounts::CloseAccount)",
         "",
-        "|     |     |",
-        "| --- | --- |",
-        "| **Name** | **Description** |",
-        "| `owner` | The owner of the account being closed. |",
-        "| `receiver` | The account to get any returned rent. |",
-        "| `margin_account` | The account being closed. |",
+        "|     |     |     |",
+        "| --- | --- | --- |",
+        "| **Name** | **Type** | **Description** |",
+        "| `owner` | `signer` | The owner of the account being closed. |",
+        "| `receiver` | `writable` | The account to get any returned rent. |",
+        "| `margin_account` | `writable` | The account being closed. |",
         "",
-        "**Events emitted by close\\_account.rs:**",
+        "# Events",
         "",
         "|     |     |",
         "| --- | --- |",
         "| **Event Name** | **Description** |",
-        "| [`events::AccountClosed`] | The closed account (includes the margin account pubkey). |"
+        "| [`events::AccountClosed`] | The closed account. |"
       ]
       accounts: [
         {
@@ -182,39 +242,39 @@ export type JetMargin = {
     {
       name: "registerPosition"
       docs: [
-        "Register a position for some token that will be custodied by margin.",
-        "Currently this applies to anything other than a claim.",
-        "",
-        "This instruction does the following:",
-        "",
-        "1.  Register a new position that belongs to the individual margin account, allocate account space for it, and set the parameters for that asset type.",
-        "",
-        "2.  Emit the `PositionRegistered` event for data logging (see table below).",
-        "",
-        "3.  Return `Ok(())`.",
-        "",
-        "",
-        "**Parameters of register\\_position.rs:**",
-        "",
-        "|     |     |",
-        "| --- | --- |",
-        "| **Name** | **Description** |",
-        "| `authority` | The authority that can change the margin account. |",
-        "| `payer` | The address paying for rent. |",
-        "| `margin_account` | The margin account to register position type with. |",
-        "| `position_token_mint` | The mint for the position token being registered. |",
-        "| `metadata` | The metadata account that references the correct oracle for the token. |",
-        "| `token_account` | The token account to store hold the position assets in the custody of the margin account. |",
-        "| `token_program` | The token program of the token accounts to store for this margin account. |",
-        "| `rent` | The rent to open the account. |",
-        "| `system_program` | The system program. |",
-        "",
-        "**Events emitted by register\\_position.rs:**",
+        "Register a position for deposits of tokens returned by adapter programs (e.g. margin-pool).",
+        "    ",
+        "This will create a token account to hold the adapter provided tokens which represent",
+        "a user's deposit with that adapter.",
+        "",
+        "This instruction may fail if the account has reached it's maximum number of positions.",
+        "",
+        "If the token requires a minimum permit tier, the owner's permit must be passed as a",
+        "remaining account.",
+        "Likewise, if the token belongs to a category, the category's config must be passed",
+        "as a remaining account.",
+        "",
+        "# [Accounts](jet_margin::accounts::RegisterPosition)",
+        "",
+        "|     |     |     |",
+        "| --- | --- | --- |",
+        "| **Name** | **Type** | **Description** |",
+        "| `authority` | `signer` | The authority that can change the margin account. |",
+        "| `payer` | `signer` | The address paying for rent. |",
+        "| `margin_account` | `writable` |  The margin account to register position type with. |",
+        "| `position_token_mint` | `read_only` | The mint for the position token being registered. |",
+        "| `metadata` | `read_only` | The token config for the position token, or its legacy metadata account. |",
+        "| `token_account` | `writable` | The token account to store hold the position assets in the custody of the margin account. |",
+        "| `token_program` | `read_only` | The [spl token program](https://spl.solana.com/token). |",
+        "| `rent` | `read_only` | The [rent sysvar](https://docs.solana.com/developing/runtime-facilities/sysvars#rent). The rent to open the account. |",
+        "| `system_program` | `read_only` | The [system native program](https://docs.solana.com/developing/runtime-facilities/programs#system-program). |",
+        "",
+        "# Events",
         "",
         "|     |     |",
         "| --- | --- |",
         "| **Event Name** | **Description** |",
-        "| [`events::PositionRegistered`] | The position registered (includes the margin account pubkey, the authority pubkey of that margin account, and the position itself). |"
+        "| [`events::PositionRegistered`] | The position registered. |"
       ]
       accounts: [
         {
@@ -245,7 +305,11 @@ export type JetMargin = {
           name: "metadata"
           isMut: false
           isSigner: false
-          docs: ["The metadata account that references the correct oracle for the token"]
+          docs: [
+            "The config account for the token, or the legacy metadata account while",
+            "compatibility with metadata is enabled.",
+            ""
+          ]
         },
         {
           name: "tokenAccount"
@@ -274,36 +338,28 @@ export type JetMargin = {
     {
       name: "updatePositionBalance"
       docs: [
-        "Update the balance of a position stored in the margin account to",
-        "match the actual balance stored by the SPL token acount.",
-        "",
-        "This instruction does the following:",
-        "",
-        "1.  Let `margin_account` be a mutable reference to the margin account.",
-        "",
-        "2.  Let `token_account` be a reference to the token account.",
-        "",
-        "3.  Load a margin account position and update it with `token_account`, `account`, and `balance`.",
-        "",
-        "4.  Emit the `PositionBalanceUpdated` event for data logging (see table below).",
+        "Update the balance of a position stored in the margin account to match the actual",
+        "stored by the SPL token account.",
         "",
-        "5.  Return `Ok(())`.",
+        "When a user deposits tokens directly (without invoking this program), there's no",
+        "update within the user's margin account to account for the new token balance. This",
+        "instruction allows udating the margin account state to reflect the current available",
+        "balance of collateral.",
         "",
+        "# [Accounts](jet_margin::accounts::UpdatePositionBalance)",
         "",
-        "**Parameters of update\\_position\\_balance.rs:**",
+        "|     |     |     |",
+        "| --- | --- | --- |",
+        "| **Name** | **Type** | **Description** |",
+        "| `margin_account` | `writable` | The margin account to update. |",
+        "| `token_account` | `read_only` | The token account to update the balance for. |",
         "",
-        "|     |     |",
-        "| --- | --- |",
-        "| **Name** | **Description** |",
-        "| `margin_account` | The margin account to update. |",
-        "| `token_account` | The token account to update the balance for. |",
-        "",
-        "**Events emitted by update\\_position\\_balance.rs:**",
+        "# Events",
         "",
         "|     |     |",
         "| --- | --- |",
         "| **Event Name** | **Description** |",
-        "| [`events::PositionBalanceUpdated`] | The updated position (includes the token account, margin account pubkey, and token balance). |",
+        "| [`events::PositionBalanceUpdated`] | The updated position. |",
         ""
       ]
       accounts: [
@@ -328,33 +384,25 @@ export type JetMargin = {
         "Update the metadata for a position stored in the margin account,",
         "in the case where the metadata has changed after the position was",
         "created.",
-        "This instruction does the following:",
-        "",
-        "1.  Read account token metadata.",
-        "",
-        "2.  Load the margin account.",
-        "",
-        "3.  Update the position with refreshed metadata.",
-        "",
-        "4.  Emit the `PositionMetadataRefreshed` event for data logging (see table below).",
         "",
-        "5.  Return `Ok(())`.",
+        "Accepts either the token config or the legacy metadata for the token. New",
+        "clients should prefer `refresh_position_config`.",
         "",
+        "# [Accounts](jet_margin::accounts::RefreshPositionMetadata)",
         "",
-        "**Parameters of refresh\\_position\\_metadata.rs:**",
+        "|     |     |     |",
+        "| --- | --- | --- |",
+        "| **Name** | **Type** | **Description** |",
+        "| `margin_account` | `writable` | The margin account with the position to be refreshed. |",
+        "| `metadata` | `read_only` | The token config or legacy metadata account for the token, which has been updated. |",
+        "| `token_category` | `read_only` | _(optional remaining account)_ The category config, required if the token belongs to a category. |",
         "",
-        "|     |     |",
-        "| --- | --- |",
-        "| **Name** | **Description** |",
-        "| `margin_account` | The margin account with the position to be refreshed. |",
-        "| `metadata` | The metadata account for the token, which has been updated. |",
-        "",
-        "**Events emitted by refresh\\_position\\_metadata.rs:**",
+        "# Events",
         "",
         "|     |     |",
         "| --- | --- |",
         "| **Event Name** | **Description** |",
-        "| [`events::PositionMetadataRefreshed`] | The position of which metadata was refreshed (including the margin account pubkey and the `position` itself). |"
+        "| [`events::PositionMetadataRefreshed`] | The position of which metadata was refreshed. |"
       ]
       accounts: [
         {
@@ -367,7 +415,11 @@ export type JetMargin = {
           name: "metadata"
           isMut: false
           isSigner: false
-          docs: ["The metadata account for the token, which has been updated"]
+          docs: [
+            "The config account for the token, or the legacy metadata account while",
+            "compatibility with metadata is enabled.",
+            ""
+          ]
         }
       ]
       args: []
@@ -375,43 +427,29 @@ export type JetMargin = {
     {
       name: "closePosition"
       docs: [
-        "Close out a position, freeing up space in the account.",
-        "",
-        "This instruction does the following:",
-        "",
-        "1.  Let `account` be a mutable reference to the margin account.",
+        "Close out a position, removing it from the account.",
         "",
-        "2.  Verify the authority of `account`.",
+        "Since there is a finite number of positions a single account can maintain it may be",
+        "necessary for a user to close out old positions to take new ones.",
         "",
-        "3.  Record unregistering (closing) the position in question of `account`, which involves passing the token mint account, token account, and margin account authority.",
+        "# [Accounts](jet_margin::accounts::ClosePosition)",
         "",
-        "4.  If the token account authority of the account is the same as the authority.",
+        "|     |     |     |",
+        "| --- | --- | --- |",
+        "| **Name** | **Type** | **Description** |",
+        "| `authority` | `signer` | The authority that can change the margin account. |",
+        "| `receiver` | `writable` | The receiver for the rent released. |",
+        "| `margin_account` | `writable` | The margin account with the position to close. |",
+        "| `position_token_mint` | `read_only` | The mint for the position token being deregistered. |",
+        "| `token_account` | `writable` | The token account for the position being closed. |",
+        "| `token_program` | `read_only` | The [spl token program](https://spl.solana.com/token). |",
         "",
-        "a.  Return the token account.",
-        "",
-        "5.  Emit the `PositionClosed` event for data logging (see table below):",
-        "",
-        "6.  Return `Ok(())`.",
-        "",
-        "",
-        "**Parameters of close\\_position.rs:**",
-        "",
-        "|     |     |",
-        "| --- | --- |",
-        "| **Name** | **Description** |",
-        "| `authority` | The authority that can change the margin account. |",
-        "| `receiver` | The receiver for the rent released. |",
-        "| `margin_account` | The margin account with the position to close. |",
-        "| `position_token_mint` | The mint for the position token being deregistered. |",
-        "| `token_account` | The token account for the position being closed. |",
-        "| `token_program` | The token program for the position being closed. |",
-        "",
-        "**Events emitted by close\\_position.rs:**",
+        "# Events",
         "",
         "|     |     |",
         "| --- | --- |",
         "| **Event Name** | **Description** |",
-        "| [`events::PositionClosed`] | The closed position (includes the margin account authority’s pubkey and the relevant token pool’s note mint pubkey). |"
+        "| [`events::PositionClosed`] | The closed position. |"
       ]
       accounts: [
         {
@@ -458,39 +496,85 @@ export type JetMargin = {
         "Verify that the account is healthy, by validating the collateralization",
         "ration is above the minimum.",
         "",
-        "This instruction does the following:",
+        "There's no real reason to call this instruction, outside of wanting to simulate",
+        "the health check for a margin account.",
         "",
-        "1.  Let `account` be the loaded margin account.",
         "",
-        "2.  Check if all positions for that margin account are healthy.",
+        "# [Accounts](jet_margin::accounts::VerifyHealthy)",
         "",
-        "a.  If there are unhealthy positions exist for this margin account, return `False`.",
+        "|     |     |     |",
+        "| --- | --- | --- |",
+        "| **Name** | **Type** | **Description** |",
+        "| `margin_account` | `read_only` | The account to verify the health of. |",
+        ""
+      ]
+      accounts: [
+        {
+          name: "marginAccount"
+          isMut: false
+          isSigner: false
+          docs: ["The account verify the health of"]
+        }
+      ]
+      args: []
+    },
+    {
+      name: "recordHealth"
+      docs: [
+        "Record the current health of a margin account.",
         "",
-        "3.  Emit the `VerifiedHealthy` event for data logging (see table below).",
+        "This instruction is permissionless, and can be used to build a history of",
+        "an account's valuation over time. The account does not need to be healthy,",
+        "but its claim positions must not be stale.",
         "",
-        "4.  Return `Ok(())`.",
+        "# [Accounts](jet_margin::accounts::RecordHealth)",
         "",
+        "|     |     |     |",
+        "| --- | --- | --- |",
+        "| **Name** | **Type** | **Description** |",
+        "| `margin_account` | `read_only` | The account to record the health of. |",
         "",
-        "**Parameters of verify\\_healthy.rs:**",
+        "# Events",
         "",
         "|     |     |",
         "| --- | --- |",
-        "| **Name** | **Description** |",
-        "| `margin_account` | The account to verify the health of. |",
+        "| **Event Name** | **Description** |",
+        "| [`events::HealthSnapshot`] | The valuation and health ratio of the account. |"
+      ]
+      accounts: [
+        {
+          name: "marginAccount"
+          isMut: false
+          isSigner: false
+          docs: ["The account to record the health of"]
+        }
+      ]
+      args: []
+    },
+    {
+      name: "accountHealth"
+      docs: [
+        "Get the current health of a margin account.",
         "",
-        "**Events emitted by verify\\_healthy.rs:**",
+        "This instruction does not modify any accounts, and returns an [AccountHealth]",
+        "through the return data, so other programs can check an account's health",
+        "through CPI. See [views::account_health]. The account's claim positions",
+        "must not be stale.",
         "",
-        "|     |     |",
-        "| --- | --- |",
-        "| **Event Name** | **Description** |",
-        "|[`events::VerifiedHealthy`] | The margin account pubkeys of verified healthy accounts. |"
+        "# [Accounts](jet_margin::accounts::AccountHealthView)",
+        "",
+        "|     |     |     |",
+        "| --- | --- | --- |",
+        "| **Name** | **Type** | **Description** |",
+        "| `margin_account` | `read_only` | The account to get the health of. |",
+        ""
       ]
       accounts: [
         {
           name: "marginAccount"
           isMut: false
           isSigner: false
-          docs: ["The account verify the health of"]
+          docs: ["The account to get the health of"]
         }
       ]
       args: []
@@ -501,44 +585,38 @@ export type JetMargin = {
         "Perform an action by invoking other programs, allowing them to alter",
         "the balances of the token accounts belonging to this margin account.",
         "",
-        "This instruction does the following:",
-        "",
-        "1.  If a read account has the `liquidation` parameter set to a pubkey:",
-        "",
-        "a.  This means that that margin account is already under liquidation by the liquidator at that pubkey.",
-        "",
-        "b.  Return `ErrorCode::Liquidating`.",
-        "",
-        "2.  Emit the `AdapterInvokeBegin` event for data logging (see table below).",
-        "",
-        "3.  Check if any positions that have changed via adapters.",
+        "This provides the margin account as a signer to any invoked instruction, and therefore",
+        "grants the adapter authority over any tokens held by the margin account.",
         "",
-        "a.  For each changed position, emit each existing adapter position as an `event` (see table below).",
+        "This validates the invoked program by expecting an `adapter_metadata` account,",
+        "which must be the adapter's config in the margin account's airspace for the",
+        "instruction to be considered valid. The configuration for allowing adapter",
+        "programs is controlled by the airspace authority.",
         "",
-        "4.  Emit the `AdapterInvokeEnd` event for data logging (see table below).",
+        "All extra accounts passed in are used as the input accounts when invoking",
+        "the provided adapter porgram.",
         "",
-        "5.  Verify that margin accounts positions via adapter are healthy.",
+        "# Parameters",
         "",
-        "6.  Return `Ok(())`.",
+        "* `data` - The instruction data to pass to the adapter program",
         "",
+        "# [Accounts](jet_margin::accounts::AdapterInvoke)",
         "",
-        "**Parameters of adapter\\_invoke.rs:**",
+        "|     |     |     |",
+        "| --- | --- | --- |",
+        "| **Name** | **Type** | **Description** |",
+        "| `owner` | `signer` | The authority that owns the margin account. |",
+        "| `margin_account` | `writable` | The margin account to proxy an action for. |",
+        "| `adapter_program` | `read_only` | The program to be invoked. |",
+        "| `adapter_metadata` | `read_only` | The config for the proxy program in the account's airspace. |",
         "",
-        "|     |     |",
-        "| --- | --- |",
-        "| **Name** | **Description** |",
-        "| `owner` | The authority that owns the margin account. |",
-        "| `margin_account` | The margin account to proxy an action for. |",
-        "| `adapter_program` | The program to be invoked. |",
-        "| `adapter_metadata` | The metadata about the proxy program. |",
-        "",
-        "**Events emitted by adapter\\_invoke.rs:**",
+        "# Events",
         "",
         "|     |     |",
         "| --- | --- |",
         "| **Event Name** | **Description** |",
         "| [`events::AdapterInvokeBegin`] | Marks the start of the adapter invocation (includes the margin account pubkey and the adapter program pubkey). |",
-        "| [`events::PositionEvent`] _(Note that each single event represents a different adapter position)_ | Each adapter position is emitted as an event (includes the margin account, the adapter program, the accounts, and a value of `true` for the field `signed`. |",
+        "| [`events::PositionEvent`] _(Note that each single event represents a different adapter position)_ | The [PositionEvent](events::PositionEvent) describing the change in position. |",
         "| [`events::AdapterInvokeEnd`] | Marks the ending of the adapter invocation (includes no data except for the event itself being emitted). |"
       ]
       accounts: [
@@ -564,7 +642,11 @@ export type JetMargin = {
           name: "adapterMetadata"
           isMut: false
           isSigner: false
-          docs: ["The metadata about the proxy program"]
+          docs: [
+            "The config for the proxy program in the margin account's airspace, or the legacy",
+            "metadata while compatibility with metadata is enabled.",
+            ""
+          ]
         }
       ]
       args: [
@@ -575,42 +657,102 @@ export type JetMargin = {
       ]
     },
     {
-      name: "accountingInvoke"
+      name: "adapterInvokeBatch"
       docs: [
-        "Perform an action by invoking other programs, allowing them only to",
-        "refresh the state of the margin account to be consistent with the actual",
-        "underlying prices or positions, but not permitting new position changes.",
-        "",
-        "This instruction does the following:",
+        "Perform a sequence of actions by invoking other programs, allowing them to alter",
+        "the balances of the token accounts belonging to this margin account.",
         "",
-        "1.  Emit `AccountingInvokeBegin` events for data logging (see table below).",
+        "This behaves like a series of `adapter_invoke` instructions, except that the",
+        "account health is only verified once, after the results from all the adapters have",
+        "been applied. If any of the invocations fail, the entire batch fails.",
         "",
-        "2.  Check if any positions that have changed via adapters.",
+        "The remaining accounts are split up between the instructions in order. Each",
+        "instruction uses the adapter program, then the adapter's config for the account's",
+        "airspace, and then the number of accounts given by `num_accounts` to be passed",
+        "through to the adapter.",
         "",
-        "a.  For each changed position, emit each existing adapter position as an `event` (see table below).",
+        "# Parameters",
         "",
-        "3.  Emit `AccountingInvokeEnd` event for data logging (see table below).",
+        "* `instructions` - The instructions to invoke, in order",
         "",
-        "4.  Return `Ok(())`.",
+        "# [Accounts](jet_margin::accounts::AdapterInvokeBatch)",
         "",
+        "|     |     |     |",
+        "| --- | --- | --- |",
+        "| **Name** | **Type** | **Description** |",
+        "| `owner` | `signer` | The authority that owns the margin account. |",
+        "| `margin_account` | `writable` | The margin account to proxy actions for. |",
         "",
-        "**Parameters of accounting\\_invoke.rs:**",
+        "# Events",
         "",
         "|     |     |",
         "| --- | --- |",
-        "| **Name** | **Description** |",
-        "| `margin_account` | The margin account to proxy an action for. |",
-        "| `adapter_program` | The program to be invoked. |",
-        "| `adapter_metadata` | The metadata about the proxy program. |",
+        "| **Event Name** | **Description** |",
+        "| [`events::AdapterInvokeBegin`] | Marks the start of each adapter invocation. |",
+        "| [`events::PositionEvent`] _(Note that each single event represents a different adapter position)_ | The [PositionEvent](events::PositionEvent) describing the change in position. |",
+        "| [`events::AdapterInvokeEnd`] | Marks the ending of each adapter invocation. |"
+      ]
+      accounts: [
+        {
+          name: "owner"
+          isMut: false
+          isSigner: true
+          docs: ["The authority that owns the margin account"]
+        },
+        {
+          name: "marginAccount"
+          isMut: true
+          isSigner: false
+          docs: ["The margin account to proxy actions for"]
+        }
+      ]
+      args: [
+        {
+          name: "instructions"
+          type: {
+            vec: {
+              defined: "AdapterInstruction"
+            }
+          }
+        }
+      ]
+    },
+    {
+      name: "accountingInvoke"
+      docs: [
+        "Perform an action by invoking other programs, allowing them only to",
+        "refresh the state of the margin account to be consistent with the actual",
+        "underlying prices or positions, but not permitting new position changes.",
+        "",
+        "This is a permissionless way of updating the value of positions on a margin",
+        "account which require some adapter to provide the update. Unlike `adapter_invoke`,",
+        "this instruction will not provider the margin account as a signer to invoked programs,",
+        "and they thefore do not have authority to modify any token balances held by the account.",
+        "    ",
+        "All extra accounts passed in are used as the input accounts when invoking",
+        "the provided adapter porgram.",
+        "",
+        "# Parameters",
         "",
-        "**Events emitted by accounting\\_invoke.rs:**",
+        "* `data` - The instruction data to pass to the adapter program",
+        "",
+        "# [Accounts](jet_margin::accounts::AccountingInvoke)",
+        "",
+        "|     |     |     |",
+        "| --- | --- | --- |",
+        "| **Name** | **Type** |  **Description** |",
+        "| `margin_account` | `writable` | The margin account to proxy an action for. |",
+        "| `adapter_program` | `read_only` | The program to be invoked. |",
+        "| `adapter_metadata` | `read_only` | The config for the proxy program in the account's airspace. |",
+        "",
+        "# Events",
         "",
         "|     |     |",
         "| --- | --- |",
         "| **Name** | **Description** |",
         "| [`events::AccountingInvokeBegin`] | Signify that the accounting invocation process has begun. |",
-        "| [`events::PositionEvent`] _(Note that each single event represents an different adapter position)_ | Each adapter position is emitted as an event (includes the margin account, the adapter program, the remaining accounts, and a value of `false` for the field `signed`. |",
-        "| [`events::AccountingInvokeEnd`] | The margin account to proxy an action for. |"
+        "| [`events::PositionEvent`] _(Note that each single event represents an different adapter position)_ | The [PositionEvent](events::PositionEvent) describing the change in position. |",
+        "| [`events::AccountingInvokeEnd`] | Signify that the accounting invocation process has ended. |"
       ]
       accounts: [
         {
@@ -629,7 +771,11 @@ export type JetMargin = {
           name: "adapterMetadata"
           isMut: false
           isSigner: false
-          docs: ["The metadata about the proxy program"]
+          docs: [
+            "The config for the proxy program in the margin account's airspace, or the legacy",
+            "metadata while compatibility with metadata is enabled.",
+            ""
+          ]
         }
       ]
       args: [
@@ -644,49 +790,30 @@ export type JetMargin = {
       docs: [
         "Begin liquidating an account",
         "",
-        "This instruction does the following:",
-        "",
-        "1.  Read `liquidation` and `liquidator` from the account.",
-        "",
-        "2.  Let `account` be a mutable reference to the margin account.",
-        "",
-        "3.  Verify that the account is subject to liquidation, return `False` if not.",
-        "",
-        "4.  Verify that the account is not already being liquidated.",
-        "",
-        "a.  If the liquidator is already assigned to this margin account, do nothing.",
-        "",
-        "b.  Else if there is no liquidator assigned to the unhealthy account, the liquidator can claim this unhealthy account and begin the process of liquidation.",
-        "",
-        "c.  Otherwise return `ErrorCode::Liquidating` because it is already claimed by some other liquidator.",
+        "The account will enter a state preventing the owner from taking any action,",
+        "until the liquidator process is complete.",
         "",
-        "5.  Record the valuation of the account.",
+        "Requires the `liquidator_metadata` account, which restricts the signer to",
+        "those approved by the governance of the account's airspace.",
         "",
-        "6.  Record the minimum valuation change of the account.",
+        "# [Accounts](jet_margin::accounts::LiquidateBegin)",
         "",
-        "7.  Emit the `LiquidationBegun` event for data logging (see table below).",
+        "|     |     |     |",
+        "| --- | --- | --- |",
+        "| **Name** | **Type** | **Description** |",
+        "| `margin_account` | `writable` | The account in need of liquidation. |",
+        "| `payer` | `signer` | The address paying rent. |",
+        "| `liquidator` | `signer` | The liquidator account performing the liquidation. |",
+        "| `liquidator_metadata` | `read_only` | The config for the liquidator in the account's airspace, or the legacy metadata. |",
+        "| `liquidation` | `writable` | The account to persist the state of liquidation. |",
+        "| `system_program` | `read_only` | The [system native program](https://docs.solana.com/developing/runtime-facilities/programs#system-program). |",
         "",
-        "8.  Return `Ok(())`.",
-        "",
-        "",
-        "**Parameters of liquidate\\_begin.rs:**",
-        "",
-        "|     |     |",
-        "| --- | --- |",
-        "| **Name** | **Description** |",
-        "| `margin_account` | The account in need of liquidation. |",
-        "| `payer` | The address paying rent. |",
-        "| `liquidator` | The liquidator account performing the liquidation. |",
-        "| `liquidator_metadata` | The metadata describing the liquidator. |",
-        "| `liquidation` | The account to persist the state of liquidation. |",
-        "| `system_program` | The system program. |",
-        "",
-        "**Events emitted by liquidate\\_begin.rs:**",
+        "# Events",
         "",
         "|     |     |",
         "| --- | --- |",
         "| **Event Name** | **Description** |",
-        "| [`events::LiquidationBegun`] | The event marking the beginning of liquidation (Includes the margin account pubkey, the liquidator pubkey, the liquidation pubkey, the liquidation data, and the valuation of the margin account to be liquidated). |"
+        "| [`events::LiquidationBegun`] | The event marking the beginning of liquidation. |"
       ]
       accounts: [
         {
@@ -711,7 +838,11 @@ export type JetMargin = {
           name: "liquidatorMetadata"
           isMut: false
           isSigner: false
-          docs: ["The metadata describing the liquidator"]
+          docs: [
+            "The config for the liquidator in the account's airspace, or the legacy",
+            "metadata describing the liquidator while compatibility with metadata is enabled.",
+            ""
+          ]
         },
         {
           name: "liquidation"
@@ -730,46 +861,26 @@ export type JetMargin = {
     {
       name: "liquidateEnd"
       docs: [
-        "Stop liquidating an account",
-        "",
-        "This instruction does the following:",
-        "",
-        "1.  Let `account` be a mutable reference to the margin account.",
-        "",
-        "2.  Let `start_time` be the time that the liquidation on this margin account began, if it exists",
-        "",
-        "3.  Let `timed_out` be the boolean representing the type of account:",
-        "",
-        "a.  If the liquidation is timed out, then this can be any account.",
-        "",
-        "b.  If the liquidation is not timed out, then this must be the liquidator, and it must be a signer.",
-        "",
-        "4.  Check if the entity trying to end the liquidation is not the liquidator.",
+        "End the liquidation state for an account",
         "",
-        "a.  If not, return `ErrorCode::UnauthorizedLiquidator`.",
+        "Normally must be signed by the liquidator that started the liquidation state. Can be",
+        "signed by anyone after the [timeout period](jet_margin::LIQUIDATION_TIMEOUT) has elapsed.",
         "",
-        "5.  Record the end of the liquidation.",
+        "# [Accounts](jet_margin::accounts::LiquidateEnd)",
         "",
-        "6.  Emit the `LiquidationEnded` event for data logging (see table below).",
+        "|     |     |     |",
+        "| --- | --- | --- |",
+        "| **Name** | **Type** | **Description** |",
+        "| `authority` | `signer` | The pubkey calling the instruction to end liquidation. |",
+        "| `margin_account` | `writable` | The account in need of liquidation. |",
+        "| `liquidation` | `writable` | The account to persist the state of liquidation. |",
         "",
-        "7.  Return `Ok(())`.",
-        "",
-        "",
-        "**Parameters of liquidate\\_end.rs:**",
-        "",
-        "|     |     |",
-        "| --- | --- |",
-        "| **Name** | **Description** |",
-        "| `authority` | The pubkey calling the instruction to end liquidation. |",
-        "| `margin_account` | The account in need of liquidation. |",
-        "| `liquidation` | The account to persist the state of liquidation. |",
-        "",
-        "**Events emitted by liquidate\\_end.rs:**",
+        "# Events",
         "",
         "|     |     |",
         "| --- | --- |",
         "| **Event Name** | **Description** |",
-        "| [`events::LiquidationEnded`] | The event marking the end of liquidation (Includes the margin account pubkey, the authority of the margin account pubkey, and the timed\\_out boolean that is true if the liquidation has timed out). |"
+        "| [`events::LiquidationEnded`] | The event marking the end of liquidation. |"
       ]
       accounts: [
         {
@@ -802,46 +913,28 @@ export type JetMargin = {
         "Perform an action by invoking another program, for the purposes of",
         "liquidating a margin account.",
         "",
-        "This instruction does the following:",
-        "",
-        "1.  Load the margin account.",
+        "Requires the account already be in the liquidation state, and the signer must",
+        "be the same liquidator that started the liquidation state.      ",
         "",
-        "2.  Let `start_value` be the valuation of the margin account before invoking the liquidator.",
+        "# [Accounts](jet_margin::accounts::LiquidatorInvoke)",
         "",
-        "3.  Emit the `LiquidatorInvokeBegin` event for data logging (see table below).",
+        "|     |     |     |",
+        "| --- | --- | --- |",
+        "| **Name** | **Type** | **Description** |",
+        "| `liquidator` | `signer` | The liquidator processing the margin account. |",
+        "| `liquidation` | `writable` | The account to persist the state of liquidation. |",
+        "| `margin_account` | `writable` | The margin account to proxy an action for. |",
+        "| `adapter_program` | `read_only` | The program to be invoked. |",
+        "| `adapter_metadata` | `read_only` | The config for the proxy program in the account's airspace. |",
         "",
-        "4.  Loop through adapter and store positions, getting and storing as `margin_account`, `adapter_program`, `accounts` and `signed`.",
-        "",
-        "5.  Emit each adapter position as an `event` (see table below).",
-        "",
-        "6.  Let`liquidation` be a mutable copy of the liquidated account.",
-        "",
-        "7.  Let `end_value` be the valuation of the margin account after the liquidation attempt, after verifying that a liquidation did occur.",
-        "",
-        "8.  Emit the `LiquidatorInvokeEnd` event for data logging (see table below).",
-        "",
-        "9.  Return `Ok(())`.",
-        "",
-        "",
-        "**Parameters of liquidator\\_invoke.rs:**",
-        "",
-        "|     |     |",
-        "| --- | --- |",
-        "| **Name** | **Description** |",
-        "| `liquidator` | The liquidator processing the margin account. |",
-        "| `liquidation` | The account to persist the state of liquidation. |",
-        "| `margin_account` | The margin account to proxy an action for. |",
-        "| `adapter_program` | The program to be invoked. |",
-        "| `adapter_metadata` | The metadata about the proxy program. |",
-        "",
-        "**Events emitted by liquidator\\_invoke.rs:**",
+        "# Events",
         "",
         "|     |     |",
         "| --- | --- |",
         "| **Event Name** | **Description** |",
-        "| [`events::LiquidatorInvokeBegin`] | Marks the beginning of this liquidation event (includes the margin account pubkey, the adapter program pubkey, and the liquidator pubkey that is liquidating that margin account or adapter position). |",
-        "| [`events::PositionEvent`] _(Note that each single event represents an different adapter position)_ | Each adapter position is emitted as an event (includes the margin account, the adapter program, the accounts, and a value of `true` for the `signed` field. |",
-        "| [`events::LiquidatorInvokeEnd`] | Marks the ending of this liquidator event (includes the liquidation data and the valuation of the account after liquidation has been performed). |"
+        "| [`events::LiquidatorInvokeBegin`] | Marks the beginning of this liquidation event. |",
+        "| [`events::PositionEvent`] _(Note that each single event represents an different adapter position)_ | The [PositionEvent](events::PositionEvent) describing the change in position. |",
+        "| [`events::LiquidatorInvokeEnd`] | Marks the ending of this liquidator event. |"
       ]
       accounts: [
         {
@@ -872,7 +965,11 @@ export type JetMargin = {
           name: "adapterMetadata"
           isMut: false
           isSigner: false
-          docs: ["The metadata about the proxy program"]
+          docs: [
+            "The config for the proxy program in the margin account's airspace, or the legacy",
+            "metadata while compatibility with metadata is enabled.",
+            ""
+          ]
         }
       ]
       args: [
@@ -887,7 +984,10 @@ export type JetMargin = {
       docs: [
         "Update the config for a token position stored in the margin account,",
         "in the case where the token config has changed after the position was",
-        "created."
+        "created.",
+        "",
+        "If the token belongs to a category, the category's config must be passed as",
+        "a remaining account."
       ]
       accounts: [
         {
@@ -932,7 +1032,14 @@ export type JetMargin = {
     },
     {
       name: "createDepositPosition"
-      docs: ["Create a new account for holding SPL token deposits directly by a margin account."]
+      docs: [
+        "Create a new account for holding SPL token deposits directly by a margin account.",
+        "",
+        "If the token requires a minimum permit tier, the owner's permit must be passed as a",
+        "remaining account.",
+        "Likewise, if the token belongs to a category, the category's config must be passed",
+        "as a remaining account."
+      ]
       accounts: [
         {
           name: "authority"
@@ -1041,26 +1148,80 @@ export type JetMargin = {
       ]
     },
     {
-      name: "configureToken"
+      name: "liquidatorTransferDeposit"
       docs: [
-        "Set the configuration for a token, which allows it to be used as a position in a margin",
-        "account.",
-        "",
-        "The configuration for a token only applies for the associated airspace, and changing any",
-        "configuration requires the airspace authority to sign.",
+        "Transfer tokens out of a deposit position in an account being liquidated, into an",
+        "account chosen by the liquidator.",
         "",
-        "The account storing the configuration will be funded if not already. If a `None` is provided as",
-        "the updated configuration, then the account will be defunded."
+        "The loss of equity is recorded on the liquidation, and the instruction fails if the",
+        "total change in equity during the liquidation falls below the allowed minimum. Only",
+        "positions not managed by an adapter can be transferred this way."
       ]
       accounts: [
         {
-          name: "authority"
+          name: "liquidator"
           isMut: false
           isSigner: true
-          docs: ["The authority allowed to make changes to configuration"]
+          docs: ["The liquidator processing the margin account"]
         },
         {
-          name: "airspace"
+          name: "liquidation"
+          isMut: true
+          isSigner: false
+          docs: ["Account to persist the state of the liquidation"]
+        },
+        {
+          name: "marginAccount"
+          isMut: true
+          isSigner: false
+          docs: ["The margin account being liquidated"]
+        },
+        {
+          name: "source"
+          isMut: true
+          isSigner: false
+          docs: ["The deposit account to transfer tokens out of"]
+        },
+        {
+          name: "destination"
+          isMut: true
+          isSigner: false
+          docs: ["The account to receive the tokens"]
+        },
+        {
+          name: "tokenProgram"
+          isMut: false
+          isSigner: false
+        }
+      ]
+      args: [
+        {
+          name: "amount"
+          type: "u64"
+        }
+      ]
+    },
+    {
+      name: "configureToken"
+      docs: [
+        "Set the configuration for a token, which allows it to be used as a position in a margin",
+        "account.",
+        "",
+        "The configuration for a token only applies for the associated airspace, and changing any",
+        "configuration requires the airspace authority to sign.",
+        "",
+        "The account storing the configuration will be funded if not already. If a `None` is provided as",
+        "the updated configuration, then the account will be defunded."
+      ]
+      accounts: [
+        {
+          name: "authority"
+          isMut: false
+          isSigner: true
+          docs: ["The authority allowed to make changes to configuration"]
+        },
+        {
+          name: "airspace"
           isMut: false
           isSigner: false
           docs: ["The airspace being modified"]
@@ -1100,6 +1261,65 @@ export type JetMargin = {
         }
       ]
     },
+    {
+      name: "configureTokenCategory"
+      docs: [
+        "Set the configuration for a category of correlated tokens.",
+        "",
+        "When all the claims in a margin account are in the same category, positions in that",
+        "category are valued with the category's collateral weight or max leverage, if higher",
+        "than the ones in their token configs. Positions pick up the category's values when",
+        "refreshed with `refresh_position_config`.",
+        "",
+        "The account storing the configuration will be funded if not already. If a `None` is provided as",
+        "the updated configuration, then the account will be defunded."
+      ]
+      accounts: [
+        {
+          name: "authority"
+          isMut: false
+          isSigner: true
+          docs: ["The authority allowed to make changes to configuration"]
+        },
+        {
+          name: "airspace"
+          isMut: false
+          isSigner: false
+          docs: ["The airspace being modified"]
+        },
+        {
+          name: "payer"
+          isMut: true
+          isSigner: true
+          docs: ["The payer for any rent costs, if required"]
+        },
+        {
+          name: "tokenCategory"
+          isMut: true
+          isSigner: false
+          docs: ["The category account to be modified"]
+        },
+        {
+          name: "systemProgram"
+          isMut: false
+          isSigner: false
+        }
+      ]
+      args: [
+        {
+          name: "category"
+          type: "u16"
+        },
+        {
+          name: "update"
+          type: {
+            option: {
+              defined: "TokenCategoryUpdate"
+            }
+          }
+        }
+      ]
+    },
     {
       name: "configureAdapter"
       docs: [
@@ -1270,6 +1490,15 @@ export type JetMargin = {
             type: {
               array: ["u8", 7432]
             }
+          },
+          {
+            name: "airspace"
+            docs: [
+              "The airspace this account belongs to",
+              "",
+              "Not present in version 1 accounts, which must be migrated before they can be used."
+            ]
+            type: "publicKey"
           }
         ]
       }
@@ -1315,30 +1544,13 @@ export type JetMargin = {
             type: "publicKey"
           },
           {
-            name: "adapterProgram"
+            name: "admin"
             docs: [
-              "The adapter program in control of positions of this token",
-              "",
-              "If this is `None`, then the margin program is in control of this asset, and",
-              "thus determining its price. The `oracle` field must be set to allow the margin",
-              "program to price the asset."
+              "The administrator of this token, which has the authority to provide information",
+              "about (e.g. prices) and otherwise modify position states for these tokens."
             ]
             type: {
-              option: "publicKey"
-            }
-          },
-          {
-            name: "oracle"
-            docs: [
-              "The oracle for the token",
-              "",
-              "This only has effect in the margin program when the price for the token is not",
-              "being managed by an adapter."
-            ]
-            type: {
-              option: {
-                defined: "TokenOracle"
-              }
+              defined: "TokenAdmin"
             }
           },
           {
@@ -1362,6 +1574,60 @@ export type JetMargin = {
             name: "maxStaleness"
             docs: ["The maximum staleness (seconds) that's acceptable for balances of this token"]
             type: "u64"
+          },
+          {
+            name: "conservativeValuation"
+            docs: ["Value positions using the less favorable of the spot and average prices"]
+            type: "bool"
+          },
+          {
+            name: "category"
+            docs: ["The [TokenCategory] this token belongs to, or zero if it has none"]
+            type: "u16"
+          },
+          {
+            name: "minPermitTier"
+            docs: [
+              "The minimum [AirspacePermit] tier the owner of a margin account needs to register",
+              "a position for this token"
+            ]
+            type: "u8"
+          }
+        ]
+      }
+    },
+    {
+      name: "tokenCategory"
+      docs: [
+        "Configuration for a category of correlated tokens within an airspace, such as",
+        "stablecoins, or a token and its staked derivatives.",
+        "",
+        "When every claim in a margin account belongs to the same category, positions in",
+        "that category are valued with the modifiers configured here instead of the ones",
+        "in their [TokenConfig], if these are higher."
+      ]
+      type: {
+        kind: "struct"
+        fields: [
+          {
+            name: "airspace"
+            docs: ["The airspace the category is defined within"]
+            type: "publicKey"
+          },
+          {
+            name: "category"
+            docs: ["The identifier for the category, referenced by [TokenConfig::category]"]
+            type: "u16"
+          },
+          {
+            name: "collateralWeight"
+            docs: ["The collateral weight for collateral tokens in the category"]
+            type: "u16"
+          },
+          {
+            name: "maxLeverage"
+            docs: ["The maximum leverage for claims in the category"]
+            type: "u16"
           }
         ]
       }
@@ -1406,6 +1672,42 @@ export type JetMargin = {
     }
   ]
   types: [
+    {
+      name: "ValuationSummary"
+      type: {
+        kind: "struct"
+        fields: [
+          {
+            name: "equity"
+            type: "i128"
+          },
+          {
+            name: "liabilities"
+            type: "i128"
+          },
+          {
+            name: "requiredCollateral"
+            type: "i128"
+          },
+          {
+            name: "weightedCollateral"
+            type: "i128"
+          },
+          {
+            name: "effectiveCollateral"
+            type: "i128"
+          },
+          {
+            name: "availableCollateral"
+            type: "i128"
+          },
+          {
+            name: "pastDue"
+            type: "bool"
+          }
+        ]
+      }
+    },
     {
       name: "AdapterResult"
       type: {
@@ -1423,6 +1725,39 @@ export type JetMargin = {
         ]
       }
     },
+    {
+      name: "PositionChange"
+      type: {
+        kind: "enum"
+        variants: [
+          {
+            name: "Price"
+            fields: [
+              {
+                defined: "PriceChangeInfo"
+              }
+            ]
+          },
+          {
+            name: "Flags"
+            fields: [
+              {
+                defined: "AdapterPositionFlags"
+              },
+              "bool"
+            ]
+          },
+          {
+            name: "Register"
+            fields: ["publicKey"]
+          },
+          {
+            name: "Close"
+            fields: ["publicKey"]
+          }
+        ]
+      }
+    },
     {
       name: "PriceChangeInfo"
       type: {
@@ -1457,37 +1792,49 @@ export type JetMargin = {
       }
     },
     {
-      name: "ValuationSummary"
+      name: "AccountHealth"
+      docs: ["The health of a margin account, as returned by the `account_health` instruction"]
       type: {
         kind: "struct"
         fields: [
           {
-            name: "equity"
-            type: "i128"
-          },
-          {
-            name: "liabilities"
-            type: "i128"
-          },
-          {
-            name: "requiredCollateral"
-            type: "i128"
+            name: "timestamp"
+            docs: ["The time the valuation was calculated at"]
+            type: "u64"
           },
           {
-            name: "weightedCollateral"
-            type: "i128"
+            name: "healthRatioBps"
+            docs: ["The ratio of effective collateral to required collateral, in basis points"]
+            type: "u64"
           },
           {
-            name: "effectiveCollateral"
-            type: "i128"
-          },
+            name: "valuationSummary"
+            docs: ["The valuation of the account"]
+            type: {
+              defined: "ValuationSummary"
+            }
+          }
+        ]
+      }
+    },
+    {
+      name: "AdapterInstruction"
+      docs: ["An instruction for an adapter, to be invoked as part of a batch"]
+      type: {
+        kind: "struct"
+        fields: [
           {
-            name: "availableCollateral"
-            type: "i128"
+            name: "numAccounts"
+            docs: [
+              "The number of accounts to pass through to the adapter, which follow the",
+              "adapter program and its config in the remaining accounts"
+            ]
+            type: "u8"
           },
           {
-            name: "pastDue"
-            type: "bool"
+            name: "data"
+            docs: ["The instruction data to pass to the adapter program"]
+            type: "bytes"
           }
         ]
       }
@@ -1503,19 +1850,10 @@ export type JetMargin = {
             type: "publicKey"
           },
           {
-            name: "adapterProgram"
-            docs: ["The adapter program in control of positions of this token"]
-            type: {
-              option: "publicKey"
-            }
-          },
-          {
-            name: "oracle"
-            docs: ["The oracle for the token"]
+            name: "admin"
+            docs: ["The administration authority for the token"]
             type: {
-              option: {
-                defined: "TokenOracle"
-              }
+              defined: "TokenAdmin"
             }
           },
           {
@@ -1534,18 +1872,39 @@ export type JetMargin = {
             name: "maxStaleness"
             docs: ["The maximum staleness (seconds) that's acceptable for balances of this token"]
             type: "u64"
+          },
+          {
+            name: "conservativeValuation"
+            docs: ["Value positions using the less favorable of the spot and average prices"]
+            type: "bool"
+          },
+          {
+            name: "category"
+            docs: ["The category this token belongs to, or zero if it has none"]
+            type: "u16"
+          },
+          {
+            name: "minPermitTier"
+            docs: ["The minimum permit tier needed to register a position for this token"]
+            type: "u8"
           }
         ]
       }
     },
     {
-      name: "AdapterPositionFlags"
+      name: "TokenCategoryUpdate"
       type: {
         kind: "struct"
         fields: [
           {
-            name: "flags"
-            type: "u8"
+            name: "collateralWeight"
+            docs: ["The collateral weight for collateral tokens in the category"]
+            type: "u16"
+          },
+          {
+            name: "maxLeverage"
+            docs: ["The maximum leverage for claims in the category"]
+            type: "u16"
           }
         ]
       }
@@ -1655,16 +2014,55 @@ export type JetMargin = {
               defined: "AdapterPositionFlags"
             }
           },
+          {
+            name: "conservativeValuation"
+            docs: ["Non-zero if the position is valued using the less favorable of its spot", "and average prices"]
+            type: "u8"
+          },
+          {
+            name: "reserved0"
+            docs: ["Unused"]
+            type: {
+              array: ["u8", 6]
+            }
+          },
+          {
+            name: "twap"
+            docs: ["The average price reported with the current price, using the same exponent"]
+            type: "i64"
+          },
+          {
+            name: "category"
+            docs: ["The category of correlated tokens this position belongs to, or zero if none"]
+            type: "u16"
+          },
+          {
+            name: "categoryValueModifier"
+            docs: ["The value modifier used when all claims in the account are in the same category"]
+            type: "u16"
+          },
           {
             name: "reserved"
             docs: ["Unused"]
             type: {
-              array: ["u8", 23]
+              array: ["u8", 4]
             }
           }
         ]
       }
     },
+    {
+      name: "AdapterPositionFlags"
+      type: {
+        kind: "struct"
+        fields: [
+          {
+            name: "flags"
+            type: "u8"
+          }
+        ]
+      }
+    },
     {
       name: "AccountPositionKey"
       type: {
@@ -1752,60 +2150,13 @@ export type JetMargin = {
       }
     },
     {
-      name: "Invocation"
-      type: {
-        kind: "struct"
-        fields: [
-          {
-            name: "flags"
-            type: "u8"
-          }
-        ]
-      }
-    },
-    {
-      name: "PositionChange"
-      type: {
-        kind: "enum"
-        variants: [
-          {
-            name: "Price"
-            fields: [
-              {
-                defined: "PriceChangeInfo"
-              }
-            ]
-          },
-          {
-            name: "Flags"
-            fields: [
-              {
-                defined: "AdapterPositionFlags"
-              },
-              "bool"
-            ]
-          },
-          {
-            name: "Register"
-            fields: ["publicKey"]
-          },
-          {
-            name: "Close"
-            fields: ["publicKey"]
-          }
-        ]
-      }
-    },
-    {
-      name: "PositionKind"
+      name: "TokenKind"
+      docs: ["Description of the token's usage"]
       type: {
         kind: "enum"
         variants: [
           {
-            name: "NoValue"
-          },
-          {
-            name: "Deposit"
+            name: "Collateral"
           },
           {
             name: "Claim"
@@ -1817,61 +2168,59 @@ export type JetMargin = {
       }
     },
     {
-      name: "Approver"
+      name: "TokenOracle"
+      docs: ["Information about where to find the oracle data for a token"]
       type: {
         kind: "enum"
         variants: [
           {
-            name: "MarginAccountAuthority"
-          },
-          {
-            name: "Adapter"
-            fields: ["publicKey"]
+            name: "Pyth"
+            fields: [
+              {
+                name: "price"
+                type: "publicKey"
+              },
+              {
+                name: "product"
+                type: "publicKey"
+              }
+            ]
           }
         ]
       }
     },
     {
-      name: "TokenKind"
-      docs: ["Description of the token's usage"]
+      name: "TokenAdmin"
+      docs: ["Description of which program administers a token"]
       type: {
         kind: "enum"
         variants: [
           {
-            name: "NoValue"
-          },
-          {
-            name: "Collateral"
-          },
-          {
-            name: "Claim"
+            name: "Margin"
+            fields: [
+              {
+                name: "oracle"
+                type: {
+                  defined: "TokenOracle"
+                }
+              }
+            ]
           },
           {
-            name: "AdapterCollateral"
+            name: "Adapter"
+            fields: ["publicKey"]
           }
         ]
       }
     },
     {
-      name: "TokenOracle"
-      docs: ["Information about where to find the oracle data for a token"]
+      name: "Invocation"
       type: {
-        kind: "enum"
-        variants: [
+        kind: "struct"
+        fields: [
           {
-            name: "Pyth"
-            fields: [
-              {
-                name: "price"
-                docs: ["The pyth address containing price information for a token."]
-                type: "publicKey"
-              },
-              {
-                name: "product"
-                docs: ["The pyth address with product information for a token"]
-                type: "publicKey"
-              }
-            ]
+            name: "flags"
+            type: "u8"
           }
         ]
       }
@@ -1891,6 +2240,11 @@ export type JetMargin = {
           type: "publicKey"
           index: false
         },
+        {
+          name: "airspace"
+          type: "publicKey"
+          index: false
+        },
         {
           name: "seed"
           type: "u16"
@@ -1898,6 +2252,26 @@ export type JetMargin = {
         }
       ]
     },
+    {
+      name: "AccountMigrated"
+      fields: [
+        {
+          name: "marginAccount"
+          type: "publicKey"
+          index: false
+        },
+        {
+          name: "airspace"
+          type: "publicKey"
+          index: false
+        },
+        {
+          name: "version"
+          type: "u8"
+          index: false
+        }
+      ]
+    },
     {
       name: "AccountClosed"
       fields: [
@@ -1918,6 +2292,33 @@ export type JetMargin = {
         }
       ]
     },
+    {
+      name: "HealthSnapshot"
+      fields: [
+        {
+          name: "marginAccount"
+          type: "publicKey"
+          index: false
+        },
+        {
+          name: "timestamp"
+          type: "u64"
+          index: false
+        },
+        {
+          name: "healthRatioBps"
+          type: "u64"
+          index: false
+        },
+        {
+          name: "valuationSummary"
+          type: {
+            defined: "ValuationSummary"
+          }
+          index: false
+        }
+      ]
+    },
     {
       name: "PositionRegistered"
       fields: [
@@ -2090,12 +2491,51 @@ export type JetMargin = {
           name: "liquidator"
           type: "publicKey"
           index: false
-        }
-      ]
-    },
-    {
-      name: "LiquidatorInvokeEnd"
-      fields: [
+        }
+      ]
+    },
+    {
+      name: "LiquidatorInvokeEnd"
+      fields: [
+        {
+          name: "liquidationData"
+          type: {
+            defined: "Liquidation"
+          }
+          index: false
+        },
+        {
+          name: "valuationSummary"
+          type: {
+            defined: "ValuationSummary"
+          }
+          index: false
+        }
+      ]
+    },
+    {
+      name: "LiquidatorDepositTransferred"
+      fields: [
+        {
+          name: "marginAccount"
+          type: "publicKey"
+          index: false
+        },
+        {
+          name: "liquidator"
+          type: "publicKey"
+          index: false
+        },
+        {
+          name: "destination"
+          type: "publicKey"
+          index: false
+        },
+        {
+          name: "amount"
+          type: "u64"
+          index: false
+        },
         {
           name: "liquidationData"
           type: {
@@ -2153,6 +2593,16 @@ export type JetMargin = {
       name: "IndirectInvocation"
       msg: "the current instruction was not directly invoked by the margin program"
     },
+    {
+      code: 141004
+      name: "NoReturnData"
+      msg: "the program did not return a result"
+    },
+    {
+      code: 141005
+      name: "WrongProgramReturnData"
+      msg: "the program that set the return data was not the one invoked"
+    },
     {
       code: 141010
       name: "MaxPositions"
@@ -2266,6 +2716,16 @@ export type JetMargin = {
       code: 141052
       name: "InvalidOracle"
       msg: "attempting to use or set invalid configuration"
+    },
+    {
+      code: 141053
+      name: "WrongPermit"
+      msg: "the permit does not allow the owner to use the airspace"
+    },
+    {
+      code: 141054
+      name: "WrongAccountVersion"
+      msg: "the account does not have the expected version"
     }
   ]
 }
@@ -2276,10 +2736,12 @@ export const IDL: JetMargin = {
   docs: [
     "This crate documents the instructions used in the `margin` program of the",
     "[jet-v2 repo](https://github.com/jet-lab/jet-v2/).",
+    "",
     "Handler functions are described for each instruction well as struct parameters",
     "(and their types and descriptions are listed) and any handler function",
     "parameters aside from parameters that exist in every instruction handler function.",
-    "Parameters of events emitted for the purposes of data logging are also included."
+    "",
+    "Accounts associated with events emitted for the purposes of data logging are also included."
   ],
   constants: [
     {
@@ -2303,6 +2765,13 @@ export const IDL: JetMargin = {
       },
       value: 'b"liquidator-config"'
     },
+    {
+      name: "TOKEN_CATEGORY_SEED",
+      type: {
+        defined: "&[u8]"
+      },
+      value: 'b"token-category"'
+    },
     {
       name: "MAX_ORACLE_CONFIDENCE",
       type: "u16",
@@ -2332,35 +2801,30 @@ export const IDL: JetMargin = {
       docs: [
         "Create a new margin account for a user",
         "",
-        "This instruction does the following:",
-        "",
-        "1.  Let `account` be a mutable reference to the margin account.",
-        "",
-        "2.  Initialize the margin account by setting the margin account version, owner,",
-        "bump seed, user seed, and setting liquidator pubkey field to the default",
-        "(if an account is being liquidated, the liquidator pubkey will be set here).",
-        "",
-        "3.  Emit the `AccountCreated` event for data logging (see table below):",
-        "",
-        "4.  Return `Ok(())`.",
+        "# Parameters",
         "",
+        "* `seed` - An abritrary integer used to derive the new account address. This allows",
+        "           a user to own multiple margin accounts, by creating new accounts with different",
+        "           seed values.",
         "",
-        "**Parameters of create\\_account.rs:**",
+        "# [Accounts](jet_margin::accounts::CreateAccount)",
+        "    ",
+        "|     |     |     |",
+        "| --- | --- | --- |",
+        "| **Name** | **Type** | **Description** |",
+        "| `owner` | `signer` | The owner of the new margin account. |",
+        "| `payer` | `signer` | The pubkey paying rent for the new margin account opening. |",
+        "| `margin_account` | `writable` | The margin account to initialize for the owner. |",
+        "| `airspace` | `read_only` | The airspace the new account will belong to. |",
+        "| `permit` | `read_only` | The owner's permit for the airspace, required only if the airspace is restricted. |",
+        "| `system_program` | `read_only` | The [system native program](https://docs.solana.com/developing/runtime-facilities/programs#system-program). |",
         "",
-        "|     |     |",
-        "| --- | --- |",
-        "| **Name** | **Description** |",
-        "| `owner` | The owner of the new margin account. |",
-        "| `payer` | The pubkey paying rent for the new margin account opening. |",
-        "| `margin_account` | The margin account to initialize for the owner. |",
-        "| `system_program` | The system program. |",
-        "",
-        "**Events emitted by create\\_account.rs:**",
+        "# Events",
         "",
         "|     |     |",
         "| --- | --- |",
         "| **Event Name** | **Description** |",
-        "| [`events::AccountCreated`] | The created account (includes the margin account pubkey, the owner of margin account’s the pubkey, and the seed). |"
+        "| [`events::AccountCreated`] | The created account. |"
       ],
       accounts: [
         {
@@ -2380,6 +2844,22 @@ export const IDL: JetMargin = {
           isSigner: false,
           docs: ["The margin account to initialize for the owner"]
         },
+        {
+          name: "airspace",
+          isMut: false,
+          isSigner: false,
+          docs: ["The airspace the new account will belong to"]
+        },
+        {
+          name: "permit",
+          isMut: false,
+          isSigner: false,
+          docs: [
+            "The permit allowing the owner to use the airspace, which is only",
+            "required when the airspace is restricted.",
+            ""
+          ]
+        },
         {
           name: "systemProgram",
           isMut: false,
@@ -2394,40 +2874,80 @@ export const IDL: JetMargin = {
       ]
     },
     {
-      name: "closeAccount",
+      name: "migrateAccount",
       docs: [
-        "Close a user's margin account",
-        "",
-        "This instruction does the following:",
+        "Migrate a version 1 margin account, which was created before accounts were bound",
+        "to an airspace.",
         "",
-        "1.  Let `account`be a reference to the margin account being closed.",
+        "The account is resized to fit the current layout and assigned to the default",
+        "airspace, which is where the pools and configs it already uses belong.",
         "",
-        "2.  Check if the loaded margin account has any open positions.",
+        "# [Accounts](jet_margin::accounts::MigrateAccount)",
         "",
-        "a.  If open positions exist, then return `ErrorCode::AccountNotEmpty`.",
+        "|     |     |     |",
+        "| --- | --- | --- |",
+        "| **Name** | **Type** | **Description** |",
+        "| `owner` | `signer` | The owner of the margin account. |",
+        "| `payer` | `signer` | The pubkey paying rent for the additional account space. |",
+        "| `margin_account` | `writable` | The margin account to migrate. |",
+        "| `system_program` | `read_only` | The [system native program](https://docs.solana.com/developing/runtime-facilities/programs#system-program). |",
         "",
-        "3.  Emit the `AccountClosed` event for data logging (see table below).",
+        "# Events",
         "",
-        "4.  Load the margin account.",
-        "",
-        "5.  Return `Ok(())`.",
+        "|     |     |",
+        "| --- | --- |",
+        "| **Event Name** | **Description** |",
+        "| [`events::AccountMigrated`] | The migrated account. |"
+      ],
+      accounts: [
+        {
+          name: "owner",
+          isMut: false,
+          isSigner: true,
+          docs: ["The owner of the margin account"]
+        },
+        {
+          name: "payer",
+          isMut: true,
+          isSigner: true,
+          docs: ["The address paying for the additional rent"]
+        },
+        {
+          name: "marginAccount",
+          isMut: true,
+          isSigner: false,
+          docs: ["The version 1 margin account to migrate", ""]
+        },
+        {
+          name: "systemProgram",
+          isMut: false,
+          isSigner: false
+        }
+      ],
+      args: []
+    },
+    {
+      name: "closeAccount",
+      docs: [
+        "Close a user's margin account",
         "",
+        "The margin account must have zero positions remaining to be closed.",
         "",
-        "**Parameters of close\\_account.rs:**",
+        "# [Accounts](jet_margin::accounts::CloseAccount)",
         "",
-        "|     |     |",
-        "| --- | --- |",
-        "| **Name** | **Description** |",
-        "| `owner` | The owner of the account being closed. |",
-        "| `receiver` | The account to get any returned rent. |",
-        "| `margin_account` | The account being closed. |",
+        "|     |     |     |",
+        "| --- | --- | --- |",
+        "| **Name** | **Type** | **Description** |",
+        "| `owner` | `signer` | The owner of the account being closed. |",
+        "| `receiver` | `writable` | The account to get any returned rent. |",
+        "| `margin_account` | `writable` | The account being closed. |",
         "",
-        "**Events emitted by close\\_account.rs:**",
+        "# Events",
         "",
         "|     |     |",
         "| --- | --- |",
         "| **Event Name** | **Description** |",
-        "| [`events::AccountClosed`] | The closed account (includes the margin account pubkey). |"
+        "| [`events::AccountClosed`] | The closed account. |"
       ],
       accounts: [
         {
@@ -2454,39 +2974,39 @@ export const IDL: JetMargin = {
     {
       name: "registerPosition",
       docs: [
-        "Register a position for some token that will be custodied by margin.",
-        "Currently this applies to anything other than a claim.",
-        "",
-        "This instruction does the following:",
-        "",
-        "1.  Register a new position that belongs to the individual margin account, allocate account space for it, and set the parameters for that asset type.",
-        "",
-        "2.  Emit the `PositionRegistered` event for data logging (see table below).",
-        "",
-        "3.  Return `Ok(())`.",
-        "",
-        "",
-        "**Parameters of register\\_position.rs:**",
-        "",
-        "|     |     |",
-        "| --- | --- |",
-        "| **Name** | **Description** |",
-        "| `authority` | The authority that can change the margin account. |",
-        "| `payer` | The address paying for rent. |",
-        "| `margin_account` | The margin account to register position type with. |",
-        "| `position_token_mint` | The mint for the position token being registered. |",
-        "| `metadata` | The metadata account that references the correct oracle for the token. |",
-        "| `token_account` | The token account to store hold the position assets in the custody of the margin account. |",
-        "| `token_program` | The token program of the token accounts to store for this margin account. |",
-        "| `rent` | The rent to open the account. |",
-        "| `system_program` | The system program. |",
-        "",
-        "**Events emitted by register\\_position.rs:**",
+        "Register a position for deposits of tokens returned by adapter programs (e.g. margin-pool).",
+        "    ",
+        "This will create a token account to hold the adapter provided tokens which represent",
+        "a user's deposit with that adapter.",
+        "",
+        "This instruction may fail if the account has reached it's maximum number of positions.",
+        "",
+        "If the token requires a minimum permit tier, the owner's permit must be passed as a",
+        "remaining account.",
+        "Likewise, if the token belongs to a category, the category's config must be passed",
+        "as a remaining account.",
+        "",
+        "# [Accounts](jet_margin::accounts::RegisterPosition)",
+        "",
+        "|     |     |     |",
+        "| --- | --- | --- |",
+        "| **Name** | **Type** | **Description** |",
+        "| `authority` | `signer` | The authority that can change the margin account. |",
+        "| `payer` | `signer` | The address paying for rent. |",
+        "| `margin_account` | `writable` |  The margin account to register position type with. |",
+        "| `position_token_mint` | `read_only` | The mint for the position token being registered. |",
+        "| `metadata` | `read_only` | The token config for the position token, or its legacy metadata account. |",
+        "| `token_account` | `writable` | The token account to store hold the position assets in the custody of the margin account. |",
+        "| `token_program` | `read_only` | The [spl token program](https://spl.solana.com/token). |",
+        "| `rent` | `read_only` | The [rent sysvar](https://docs.solana.com/developing/runtime-facilities/sysvars#rent). The rent to open the account. |",
+        "| `system_program` | `read_only` | The [system native program](https://docs.solana.com/developing/runtime-facilities/programs#system-program). |",
+        "",
+        "# Events",
         "",
         "|     |     |",
         "| --- | --- |",
         "| **Event Name** | **Description** |",
-        "| [`events::PositionRegistered`] | The position registered (includes the margin account pubkey, the authority pubkey of that margin account, and the position itself). |"
+        "| [`events::PositionRegistered`] | The position registered. |"
       ],
       accounts: [
         {
@@ -2517,7 +3037,11 @@ export const IDL: JetMargin = {
           name: "metadata",
           isMut: false,
           isSigner: false,
-          docs: ["The metadata account that references the correct oracle for the token"]
+          docs: [
+            "The config account for the token, or the legacy metadata account while",
+            "compatibility with metadata is enabled.",
+            ""
+          ]
         },
         {
           name: "tokenAccount",
@@ -2546,36 +3070,28 @@ export const IDL: JetMargin = {
     {
       name: "updatePositionBalance",
       docs: [
-        "Update the balance of a position stored in the margin account to",
-        "match the actual balance stored by the SPL token acount.",
-        "",
-        "This instruction does the following:",
-        "",
-        "1.  Let `margin_account` be a mutable reference to the margin account.",
-        "",
-        "2.  Let `token_account` be a reference to the token account.",
-        "",
-        "3.  Load a margin account position and update it with `token_account`, `account`, and `balance`.",
-        "",
-        "4.  Emit the `PositionBalanceUpdated` event for data logging (see table below).",
-        "",
-        "5.  Return `Ok(())`.",
+        "Update the balance of a position stored in the margin account to match the actual",
+        "stored by the SPL token account.",
         "",
+        "When a user deposits tokens directly (without invoking this program), there's no",
+        "update within the user's margin account to account for the new token balance. This",
+        "instruction allows udating the margin account state to reflect the current available",
+        "balance of collateral.",
         "",
-        "**Parameters of update\\_position\\_balance.rs:**",
+        "# [Accounts](jet_margin::accounts::UpdatePositionBalance)",
         "",
-        "|     |     |",
-        "| --- | --- |",
-        "| **Name** | **Description** |",
-        "| `margin_account` | The margin account to update. |",
-        "| `token_account` | The token account to update the balance for. |",
+        "|     |     |     |",
+        "| --- | --- | --- |",
+        "| **Name** | **Type** | **Description** |",
+        "| `margin_account` | `writable` | The margin account to update. |",
+        "| `token_account` | `read_only` | The token account to update the balance for. |",
         "",
-        "**Events emitted by update\\_position\\_balance.rs:**",
+        "# Events",
         "",
         "|     |     |",
         "| --- | --- |",
         "| **Event Name** | **Description** |",
-        "| [`events::PositionBalanceUpdated`] | The updated position (includes the token account, margin account pubkey, and token balance). |",
+        "| [`events::PositionBalanceUpdated`] | The updated position. |",
         ""
       ],
       accounts: [
@@ -2600,33 +3116,25 @@ export const IDL: JetMargin = {
         "Update the metadata for a position stored in the margin account,",
         "in the case where the metadata has changed after the position was",
         "created.",
-        "This instruction does the following:",
-        "",
-        "1.  Read account token metadata.",
-        "",
-        "2.  Load the margin account.",
-        "",
-        "3.  Update the position with refreshed metadata.",
-        "",
-        "4.  Emit the `PositionMetadataRefreshed` event for data logging (see table below).",
-        "",
-        "5.  Return `Ok(())`.",
         "",
+        "Accepts either the token config or the legacy metadata for the token. New",
+        "clients should prefer `refresh_position_config`.",
         "",
-        "**Parameters of refresh\\_position\\_metadata.rs:**",
+        "# [Accounts](jet_margin::accounts::RefreshPositionMetadata)",
         "",
-        "|     |     |",
-        "| --- | --- |",
-        "| **Name** | **Description** |",
-        "| `margin_account` | The margin account with the position to be refreshed. |",
-        "| `metadata` | The metadata account for the token, which has been updated. |",
+        "|     |     |     |",
+        "| --- | --- | --- |",
+        "| **Name** | **Type** | **Description** |",
+        "| `margin_account` | `writable` | The margin account with the position to be refreshed. |",
+        "| `metadata` | `read_only` | The token config or legacy metadata account for the token, which has been updated. |",
+        "| `token_category` | `read_only` | _(optional remaining account)_ The category config, required if the token belongs to a category. |",
         "",
-        "**Events emitted by refresh\\_position\\_metadata.rs:**",
+        "# Events",
         "",
         "|     |     |",
         "| --- | --- |",
         "| **Event Name** | **Description** |",
-        "| [`events::PositionMetadataRefreshed`] | The position of which metadata was refreshed (including the margin account pubkey and the `position` itself). |"
+        "| [`events::PositionMetadataRefreshed`] | The position of which metadata was refreshed. |"
       ],
       accounts: [
         {
@@ -2639,7 +3147,11 @@ export const IDL: JetMargin = {
           name: "metadata",
           isMut: false,
           isSigner: false,
-          docs: ["The metadata account for the token, which has been updated"]
+          docs: [
+            "The config account for the token, or the legacy metadata account while",
+            "compatibility with metadata is enabled.",
+            ""
+          ]
         }
       ],
       args: []
@@ -2647,43 +3159,29 @@ export const IDL: JetMargin = {
     {
       name: "closePosition",
       docs: [
-        "Close out a position, freeing up space in the account.",
-        "",
-        "This instruction does the following:",
-        "",
-        "1.  Let `account` be a mutable reference to the margin account.",
-        "",
-        "2.  Verify the authority of `account`.",
+        "Close out a position, removing it from the account.",
         "",
-        "3.  Record unregistering (closing) the position in question of `account`, which involves passing the token mint account, token account, and margin account authority.",
+        "Since there is a finite number of positions a single account can maintain it may be",
+        "necessary for a user to close out old positions to take new ones.",
         "",
-        "4.  If the token account authority of the account is the same as the authority.",
+        "# [Accounts](jet_margin::accounts::ClosePosition)",
         "",
-        "a.  Return the token account.",
+        "|     |     |     |",
+        "| --- | --- | --- |",
+        "| **Name** | **Type** | **Description** |",
+        "| `authority` | `signer` | The authority that can change the margin account. |",
+        "| `receiver` | `writable` | The receiver for the rent released. |",
+        "| `margin_account` | `writable` | The margin account with the position to close. |",
+        "| `position_token_mint` | `read_only` | The mint for the position token being deregistered. |",
+        "| `token_account` | `writable` | The token account for the position being closed. |",
+        "| `token_program` | `read_only` | The [spl token program](https://spl.solana.com/token). |",
         "",
-        "5.  Emit the `PositionClosed` event for data logging (see table below):",
-        "",
-        "6.  Return `Ok(())`.",
-        "",
-        "",
-        "**Parameters of close\\_position.rs:**",
-        "",
-        "|     |     |",
-        "| --- | --- |",
-        "| **Name** | **Description** |",
-        "| `authority` | The authority that can change the margin account. |",
-        "| `receiver` | The receiver for the rent released. |",
-        "| `margin_account` | The margin account with the position to close. |",
-        "| `position_token_mint` | The mint for the position token being deregistered. |",
-        "| `token_account` | The token account for the position being closed. |",
-        "| `token_program` | The token program for the position being closed. |",
-        "",
-        "**Events emitted by close\\_position.rs:**",
+        "# Events",
         "",
         "|     |     |",
         "| --- | --- |",
         "| **Event Name** | **Description** |",
-        "| [`events::PositionClosed`] | The closed position (includes the margin account authority’s pubkey and the relevant token pool’s note mint pubkey). |"
+        "| [`events::PositionClosed`] | The closed position. |"
       ],
       accounts: [
         {
@@ -2730,39 +3228,85 @@ export const IDL: JetMargin = {
         "Verify that the account is healthy, by validating the collateralization",
         "ration is above the minimum.",
         "",
-        "This instruction does the following:",
+        "There's no real reason to call this instruction, outside of wanting to simulate",
+        "the health check for a margin account.",
         "",
-        "1.  Let `account` be the loaded margin account.",
         "",
-        "2.  Check if all positions for that margin account are healthy.",
+        "# [Accounts](jet_margin::accounts::VerifyHealthy)",
         "",
-        "a.  If there are unhealthy positions exist for this margin account, return `False`.",
+        "|     |     |     |",
+        "| --- | --- | --- |",
+        "| **Name** | **Type** | **Description** |",
+        "| `margin_account` | `read_only` | The account to verify the health of. |",
+        ""
+      ],
+      accounts: [
+        {
+          name: "marginAccount",
+          isMut: false,
+          isSigner: false,
+          docs: ["The account verify the health of"]
+        }
+      ],
+      args: []
+    },
+    {
+      name: "recordHealth",
+      docs: [
+        "Record the current health of a margin account.",
         "",
-        "3.  Emit the `VerifiedHealthy` event for data logging (see table below).",
+        "This instruction is permissionless, and can be used to build a history of",
+        "an account's valuation over time. The account does not need to be healthy,",
+        "but its claim positions must not be stale.",
         "",
-        "4.  Return `Ok(())`.",
+        "# [Accounts](jet_margin::accounts::RecordHealth)",
         "",
+        "|     |     |     |",
+        "| --- | --- | --- |",
+        "| **Name** | **Type** | **Description** |",
+        "| `margin_account` | `read_only` | The account to record the health of. |",
         "",
-        "**Parameters of verify\\_healthy.rs:**",
+        "# Events",
         "",
         "|     |     |",
         "| --- | --- |",
-        "| **Name** | **Description** |",
-        "| `margin_account` | The account to verify the health of. |",
+        "| **Event Name** | **Description** |",
+        "| [`events::HealthSnapshot`] | The valuation and health ratio of the account. |"
+      ],
+      accounts: [
+        {
+          name: "marginAccount",
+          isMut: false,
+          isSigner: false,
+          docs: ["The account to record the health of"]
+        }
+      ],
+      args: []
+    },
+    {
+      name: "accountHealth",
+      docs: [
+        "Get the current health of a margin account.",
         "",
-        "**Events emitted by verify\\_healthy.rs:**",
+        "This instruction does not modify any accounts, and returns an [AccountHealth]",
+        "through the return data, so other programs can check an account's health",
+        "through CPI. See [views::account_health]. The account's claim positions",
+        "must not be stale.",
         "",
-        "|     |     |",
-        "| --- | --- |",
-        "| **Event Name** | **Description** |",
-        "|[`events::VerifiedHealthy`] | The margin account pubkeys of verified healthy accounts. |"
+        "# [Accounts](jet_margin::accounts::AccountHealthView)",
+        "",
+        "|     |     |     |",
+        "| --- | --- | --- |",
+        "| **Name** | **Type** | **Description** |",
+        "| `margin_account` | `read_only` | The account to get the health of. |",
+        ""
       ],
       accounts: [
         {
           name: "marginAccount",
           isMut: false,
           isSigner: false,
-          docs: ["The account verify the health of"]
+          docs: ["The account to get the health of"]
         }
       ],
       args: []
@@ -2773,44 +3317,38 @@ export const IDL: JetMargin = {
         "Perform an action by invoking other programs, allowing them to alter",
         "the balances of the token accounts belonging to this margin account.",
         "",
-        "This instruction does the following:",
-        "",
-        "1.  If a read account has the `liquidation` parameter set to a pubkey:",
-        "",
-        "a.  This means that that margin account is already under liquidation by the liquidator at that pubkey.",
-        "",
-        "b.  Return `ErrorCode::Liquidating`.",
-        "",
-        "2.  Emit the `AdapterInvokeBegin` event for data logging (see table below).",
-        "",
-        "3.  Check if any positions that have changed via adapters.",
-        "",
-        "a.  For each changed position, emit each existing adapter position as an `event` (see table below).",
+        "This provides the margin account as a signer to any invoked instruction, and therefore",
+        "grants the adapter authority over any tokens held by the margin account.",
         "",
-        "4.  Emit the `AdapterInvokeEnd` event for data logging (see table below).",
+        "This validates the invoked program by expecting an `adapter_metadata` account,",
+        "which must be the adapter's config in the margin account's airspace for the",
+        "instruction to be considered valid. The configuration for allowing adapter",
+        "programs is controlled by the airspace authority.",
         "",
-        "5.  Verify that margin accounts positions via adapter are healthy.",
+        "All extra accounts passed in are used as the input accounts when invoking",
+        "the provided adapter porgram.",
         "",
-        "6.  Return `Ok(())`.",
+        "# Parameters",
         "",
+        "* `data` - The instruction data to pass to the adapter program",
         "",
-        "**Parameters of adapter\\_invoke.rs:**",
+        "# [Accounts](jet_margin::accounts::AdapterInvoke)",
         "",
-        "|     |     |",
-        "| --- | --- |",
-        "| **Name** | **Description** |",
-        "| `owner` | The authority that owns the margin account. |",
-        "| `margin_account` | The margin account to proxy an action for. |",
-        "| `adapter_program` | The program to be invoked. |",
-        "| `adapter_metadata` | The metadata about the proxy program. |",
+        "|     |     |     |",
+        "| --- | --- | --- |",
+        "| **Name** | **Type** | **Description** |",
+        "| `owner` | `signer` | The authority that owns the margin account. |",
+        "| `margin_account` | `writable` | The margin account to proxy an action for. |",
+        "| `adapter_program` | `read_only` | The program to be invoked. |",
+        "| `adapter_metadata` | `read_only` | The config for the proxy program in the account's airspace. |",
         "",
-        "**Events emitted by adapter\\_invoke.rs:**",
+        "# Events",
         "",
         "|     |     |",
         "| --- | --- |",
         "| **Event Name** | **Description** |",
         "| [`events::AdapterInvokeBegin`] | Marks the start of the adapter invocation (includes the margin account pubkey and the adapter program pubkey). |",
-        "| [`events::PositionEvent`] _(Note that each single event represents a different adapter position)_ | Each adapter position is emitted as an event (includes the margin account, the adapter program, the accounts, and a value of `true` for the field `signed`. |",
+        "| [`events::PositionEvent`] _(Note that each single event represents a different adapter position)_ | The [PositionEvent](events::PositionEvent) describing the change in position. |",
         "| [`events::AdapterInvokeEnd`] | Marks the ending of the adapter invocation (includes no data except for the event itself being emitted). |"
       ],
       accounts: [
@@ -2836,7 +3374,11 @@ export const IDL: JetMargin = {
           name: "adapterMetadata",
           isMut: false,
           isSigner: false,
-          docs: ["The metadata about the proxy program"]
+          docs: [
+            "The config for the proxy program in the margin account's airspace, or the legacy",
+            "metadata while compatibility with metadata is enabled.",
+            ""
+          ]
         }
       ],
       args: [
@@ -2847,42 +3389,102 @@ export const IDL: JetMargin = {
       ]
     },
     {
-      name: "accountingInvoke",
+      name: "adapterInvokeBatch",
       docs: [
-        "Perform an action by invoking other programs, allowing them only to",
-        "refresh the state of the margin account to be consistent with the actual",
-        "underlying prices or positions, but not permitting new position changes.",
-        "",
-        "This instruction does the following:",
+        "Perform a sequence of actions by invoking other programs, allowing them to alter",
+        "the balances of the token accounts belonging to this margin account.",
         "",
-        "1.  Emit `AccountingInvokeBegin` events for data logging (see table below).",
+        "This behaves like a series of `adapter_invoke` instructions, except that the",
+        "account health is only verified once, after the results from all the adapters have",
+        "been applied. If any of the invocations fail, the entire batch fails.",
         "",
-        "2.  Check if any positions that have changed via adapters.",
+        "The remaining accounts are split up between the instructions in order. Each",
+        "instruction uses the adapter program, then the adapter's config for the account's",
+        "airspace, and then the number of accounts given by `num_accounts` to be passed",
+        "through to the adapter.",
         "",
-        "a.  For each changed position, emit each existing adapter position as an `event` (see table below).",
+        "# Parameters",
         "",
-        "3.  Emit `AccountingInvokeEnd` event for data logging (see table below).",
+        "* `instructions` - The instructions to invoke, in order",
         "",
-        "4.  Return `Ok(())`.",
+        "# [Accounts](jet_margin::accounts::AdapterInvokeBatch)",
         "",
+        "|     |     |     |",
+        "| --- | --- | --- |",
+        "| **Name** | **Type** | **Description** |",
+        "| `owner` | `signer` | The authority that owns the margin account. |",
+        "| `margin_account` | `writable` | The margin account to proxy actions for. |",
         "",
-        "**Parameters of accounting\\_invoke.rs:**",
+        "# Events",
         "",
         "|     |     |",
         "| --- | --- |",
-        "| **Name** | **Description** |",
-        "| `margin_account` | The margin account to proxy an action for. |",
-        "| `adapter_program` | The program to be invoked. |",
-        "| `adapter_metadata` | The metadata about the proxy program. |",
+        "| **Event Name** | **Description** |",
+        "| [`events::AdapterInvokeBegin`] | Marks the start of each adapter invocation. |",
+        "| [`events::PositionEvent`] _(Note that each single event represents a different adapter position)_ | The [PositionEvent](events::PositionEvent) describing the change in position. |",
+        "| [`events::AdapterInvokeEnd`] | Marks the ending of each adapter invocation. |"
+      ],
+      accounts: [
+        {
+          name: "owner",
+          isMut: false,
+          isSigner: true,
+          docs: ["The authority that owns the margin account"]
+        },
+        {
+          name: "marginAccount",
+          isMut: true,
+          isSigner: false,
+          docs: ["The margin account to proxy actions for"]
+        }
+      ],
+      args: [
+        {
+          name: "instructions",
+          type: {
+            vec: {
+              defined: "AdapterInstruction"
+            }
+          }
+        }
+      ]
+    },
+    {
+      name: "accountingInvoke",
+      docs: [
+        "Perform an action by invoking other programs, allowing them only to",
+        "refresh the state of the margin account to be consistent with the actual",
+        "underlying prices or positions, but not permitting new position changes.",
+        "",
+        "This is a permissionless way of updating the value of positions on a margin",
+        "account which require some adapter to provide the update. Unlike `adapter_invoke`,",
+        "this instruction will not provider the margin account as a signer to invoked programs,",
+        "and they thefore do not have authority to modify any token balances held by the account.",
+        "    ",
+        "All extra accounts passed in are used as the input accounts when invoking",
+        "the provided adapter porgram.",
+        "",
+        "# Parameters",
+        "",
+        "* `data` - The instruction data to pass to the adapter program",
         "",
-        "**Events emitted by accounting\\_invoke.rs:**",
+        "# [Accounts](jet_margin::accounts::AccountingInvoke)",
+        "",
+        "|     |     |     |",
+        "| --- | --- | --- |",
+        "| **Name** | **Type** |  **Description** |",
+        "| `margin_account` | `writable` | The margin account to proxy an action for. |",
+        "| `adapter_program` | `read_only` | The program to be invoked. |",
+        "| `adapter_metadata` | `read_only` | The config for the proxy program in the account's airspace. |",
+        "",
+        "# Events",
         "",
         "|     |     |",
         "| --- | --- |",
         "| **Name** | **Description** |",
         "| [`events::AccountingInvokeBegin`] | Signify that the accounting invocation process has begun. |",
-        "| [`events::PositionEvent`] _(Note that each single event represents an different adapter position)_ | Each adapter position is emitted as an event (includes the margin account, the adapter program, the remaining accounts, and a value of `false` for the field `signed`. |",
-        "| [`events::AccountingInvokeEnd`] | The margin account to proxy an action for. |"
+        "| [`events::PositionEvent`] _(Note that each single event represents an different adapter position)_ | The [PositionEvent](events::PositionEvent) describing the change in position. |",
+        "| [`events::AccountingInvokeEnd`] | Signify that the accounting invocation process has ended. |"
       ],
       accounts: [
         {
@@ -2901,7 +3503,11 @@ export const IDL: JetMargin = {
           name: "adapterMetadata",
           isMut: false,
           isSigner: false,
-          docs: ["The metadata about the proxy program"]
+          docs: [
+            "The config for the proxy program in the margin account's airspace, or the legacy",
+            "metadata while compatibility with metadata is enabled.",
+            ""
+          ]
         }
       ],
       args: [
@@ -2916,49 +3522,30 @@ export const IDL: JetMargin = {
       docs: [
         "Begin liquidating an account",
         "",
-        "This instruction does the following:",
+        "The account will enter a state preventing the owner from taking any action,",
+        "until the liquidator process is complete.",
         "",
-        "1.  Read `liquidation` and `liquidator` from the account.",
+        "Requires the `liquidator_metadata` account, which restricts the signer to",
+        "those approved by the governance of the account's airspace.",
         "",
-        "2.  Let `account` be a mutable reference to the margin account.",
+        "# [Accounts](jet_margin::accounts::LiquidateBegin)",
         "",
-        "3.  Verify that the account is subject to liquidation, return `False` if not.",
+        "|     |     |     |",
+        "| --- | --- | --- |",
+        "| **Name** | **Type** | **Description** |",
+        "| `margin_account` | `writable` | The account in need of liquidation. |",
+        "| `payer` | `signer` | The address paying rent. |",
+        "| `liquidator` | `signer` | The liquidator account performing the liquidation. |",
+        "| `liquidator_metadata` | `read_only` | The config for the liquidator in the account's airspace, or the legacy metadata. |",
+        "| `liquidation` | `writable` | The account to persist the state of liquidation. |",
+        "| `system_program` | `read_only` | The [system native program](https://docs.solana.com/developing/runtime-facilities/programs#system-program). |",
         "",
-        "4.  Verify that the account is not already being liquidated.",
-        "",
-        "a.  If the liquidator is already assigned to this margin account, do nothing.",
-        "",
-        "b.  Else if there is no liquidator assigned to the unhealthy account, the liquidator can claim this unhealthy account and begin the process of liquidation.",
-        "",
-        "c.  Otherwise return `ErrorCode::Liquidating` because it is already claimed by some other liquidator.",
-        "",
-        "5.  Record the valuation of the account.",
-        "",
-        "6.  Record the minimum valuation change of the account.",
-        "",
-        "7.  Emit the `LiquidationBegun` event for data logging (see table below).",
-        "",
-        "8.  Return `Ok(())`.",
-        "",
-        "",
-        "**Parameters of liquidate\\_begin.rs:**",
-        "",
-        "|     |     |",
-        "| --- | --- |",
-        "| **Name** | **Description** |",
-        "| `margin_account` | The account in need of liquidation. |",
-        "| `payer` | The address paying rent. |",
-        "| `liquidator` | The liquidator account performing the liquidation. |",
-        "| `liquidator_metadata` | The metadata describing the liquidator. |",
-        "| `liquidation` | The account to persist the state of liquidation. |",
-        "| `system_program` | The system program. |",
-        "",
-        "**Events emitted by liquidate\\_begin.rs:**",
+        "# Events",
         "",
         "|     |     |",
         "| --- | --- |",
         "| **Event Name** | **Description** |",
-        "| [`events::LiquidationBegun`] | The event marking the beginning of liquidation (Includes the margin account pubkey, the liquidator pubkey, the liquidation pubkey, the liquidation data, and the valuation of the margin account to be liquidated). |"
+        "| [`events::LiquidationBegun`] | The event marking the beginning of liquidation. |"
       ],
       accounts: [
         {
@@ -2983,7 +3570,11 @@ export const IDL: JetMargin = {
           name: "liquidatorMetadata",
           isMut: false,
           isSigner: false,
-          docs: ["The metadata describing the liquidator"]
+          docs: [
+            "The config for the liquidator in the account's airspace, or the legacy",
+            "metadata describing the liquidator while compatibility with metadata is enabled.",
+            ""
+          ]
         },
         {
           name: "liquidation",
@@ -3002,46 +3593,26 @@ export const IDL: JetMargin = {
     {
       name: "liquidateEnd",
       docs: [
-        "Stop liquidating an account",
+        "End the liquidation state for an account",
         "",
-        "This instruction does the following:",
+        "Normally must be signed by the liquidator that started the liquidation state. Can be",
+        "signed by anyone after the [timeout period](jet_margin::LIQUIDATION_TIMEOUT) has elapsed.",
         "",
-        "1.  Let `account` be a mutable reference to the margin account.",
+        "# [Accounts](jet_margin::accounts::LiquidateEnd)",
         "",
-        "2.  Let `start_time` be the time that the liquidation on this margin account began, if it exists",
+        "|     |     |     |",
+        "| --- | --- | --- |",
+        "| **Name** | **Type** | **Description** |",
+        "| `authority` | `signer` | The pubkey calling the instruction to end liquidation. |",
+        "| `margin_account` | `writable` | The account in need of liquidation. |",
+        "| `liquidation` | `writable` | The account to persist the state of liquidation. |",
         "",
-        "3.  Let `timed_out` be the boolean representing the type of account:",
-        "",
-        "a.  If the liquidation is timed out, then this can be any account.",
-        "",
-        "b.  If the liquidation is not timed out, then this must be the liquidator, and it must be a signer.",
-        "",
-        "4.  Check if the entity trying to end the liquidation is not the liquidator.",
-        "",
-        "a.  If not, return `ErrorCode::UnauthorizedLiquidator`.",
-        "",
-        "5.  Record the end of the liquidation.",
-        "",
-        "6.  Emit the `LiquidationEnded` event for data logging (see table below).",
-        "",
-        "7.  Return `Ok(())`.",
-        "",
-        "",
-        "**Parameters of liquidate\\_end.rs:**",
-        "",
-        "|     |     |",
-        "| --- | --- |",
-        "| **Name** | **Description** |",
-        "| `authority` | The pubkey calling the instruction to end liquidation. |",
-        "| `margin_account` | The account in need of liquidation. |",
-        "| `liquidation` | The account to persist the state of liquidation. |",
-        "",
-        "**Events emitted by liquidate\\_end.rs:**",
+        "# Events",
         "",
         "|     |     |",
         "| --- | --- |",
         "| **Event Name** | **Description** |",
-        "| [`events::LiquidationEnded`] | The event marking the end of liquidation (Includes the margin account pubkey, the authority of the margin account pubkey, and the timed\\_out boolean that is true if the liquidation has timed out). |"
+        "| [`events::LiquidationEnded`] | The event marking the end of liquidation. |"
       ],
       accounts: [
         {
@@ -3065,55 +3636,37 @@ export const IDL: JetMargin = {
           isSigner: false,
           docs: ["Account to persist the state of the liquidation"]
         }
-      ],
-      args: []
-    },
-    {
-      name: "liquidatorInvoke",
-      docs: [
-        "Perform an action by invoking another program, for the purposes of",
-        "liquidating a margin account.",
-        "",
-        "This instruction does the following:",
-        "",
-        "1.  Load the margin account.",
-        "",
-        "2.  Let `start_value` be the valuation of the margin account before invoking the liquidator.",
-        "",
-        "3.  Emit the `LiquidatorInvokeBegin` event for data logging (see table below).",
-        "",
-        "4.  Loop through adapter and store positions, getting and storing as `margin_account`, `adapter_program`, `accounts` and `signed`.",
-        "",
-        "5.  Emit each adapter position as an `event` (see table below).",
-        "",
-        "6.  Let`liquidation` be a mutable copy of the liquidated account.",
-        "",
-        "7.  Let `end_value` be the valuation of the margin account after the liquidation attempt, after verifying that a liquidation did occur.",
-        "",
-        "8.  Emit the `LiquidatorInvokeEnd` event for data logging (see table below).",
-        "",
-        "9.  Return `Ok(())`.",
+      ],
+      args: []
+    },
+    {
+      name: "liquidatorInvoke",
+      docs: [
+        "Perform an action by invoking another program, for the purposes of",
+        "liquidating a margin account.",
         "",
+        "Requires the account already be in the liquidation state, and the signer must",
+        "be the same liquidator that started the liquidation state.      ",
         "",
-        "**Parameters of liquidator\\_invoke.rs:**",
+        "# [Accounts](jet_margin::accounts::LiquidatorInvoke)",
         "",
-        "|     |     |",
-        "| --- | --- |",
-        "| **Name** | **Description** |",
-        "| `liquidator` | The liquidator processing the margin account. |",
-        "| `liquidation` | The account to persist the state of liquidation. |",
-        "| `margin_account` | The margin account to proxy an action for. |",
-        "| `adapter_program` | The program to be invoked. |",
-        "| `adapter_metadata` | The metadata about the proxy program. |",
+        "|     |     |     |",
+        "| --- | --- | --- |",
+        "| **Name** | **Type** | **Description** |",
+        "| `liquidator` | `signer` | The liquidator processing the margin account. |",
+        "| `liquidation` | `writable` | The account to persist the state of liquidation. |",
+        "| `margin_account` | `writable` | The margin account to proxy an action for. |",
+        "| `adapter_program` | `read_only` | The program to be invoked. |",
+        "| `adapter_metadata` | `read_only` | The config for the proxy program in the account's airspace. |",
         "",
-        "**Events emitted by liquidator\\_invoke.rs:**",
+        "# Events",
         "",
         "|     |     |",
         "| --- | --- |",
         "| **Event Name** | **Description** |",
-        "| [`events::LiquidatorInvokeBegin`] | Marks the beginning of this liquidation event (includes the margin account pubkey, the adapter program pubkey, and the liquidator pubkey that is liquidating that margin account or adapter position). |",
-        "| [`events::PositionEvent`] _(Note that each single event represents an different adapter position)_ | Each adapter position is emitted as an event (includes the margin account, the adapter program, the accounts, and a value of `true` for the `signed` field. |",
-        "| [`events::LiquidatorInvokeEnd`] | Marks the ending of this liquidator event (includes the liquidation data and the valuation of the account after liquidation has been performed). |"
+        "| [`events::LiquidatorInvokeBegin`] | Marks the beginning of this liquidation event. |",
+        "| [`events::PositionEvent`] _(Note that each single event represents an different adapter position)_ | The [PositionEvent](events::PositionEvent) describing the change in position. |",
+        "| [`events::LiquidatorInvokeEnd`] | Marks the ending of this liquidator event. |"
       ],
       accounts: [
         {
@@ -3144,7 +3697,11 @@ export const IDL: JetMargin = {
           name: "adapterMetadata",
           isMut: false,
           isSigner: false,
-          docs: ["The metadata about the proxy program"]
+          docs: [
+            "The config for the proxy program in the margin account's airspace, or the legacy",
+            "metadata while compatibility with metadata is enabled.",
+            ""
+          ]
         }
       ],
       args: [
@@ -3159,7 +3716,10 @@ export const IDL: JetMargin = {
       docs: [
         "Update the config for a token position stored in the margin account,",
         "in the case where the token config has changed after the position was",
-        "created."
+        "created.",
+        "",
+        "If the token belongs to a category, the category's config must be passed as",
+        "a remaining account."
       ],
       accounts: [
         {
@@ -3204,7 +3764,14 @@ export const IDL: JetMargin = {
     },
     {
       name: "createDepositPosition",
-      docs: ["Create a new account for holding SPL token deposits directly by a margin account."],
+      docs: [
+        "Create a new account for holding SPL token deposits directly by a margin account.",
+        "",
+        "If the token requires a minimum permit tier, the owner's permit must be passed as a",
+        "remaining account.",
+        "Likewise, if the token belongs to a category, the category's config must be passed",
+        "as a remaining account."
+      ],
       accounts: [
         {
           name: "authority",
@@ -3312,6 +3879,60 @@ export const IDL: JetMargin = {
         }
       ]
     },
+    {
+      name: "liquidatorTransferDeposit",
+      docs: [
+        "Transfer tokens out of a deposit position in an account being liquidated, into an",
+        "account chosen by the liquidator.",
+        "",
+        "The loss of equity is recorded on the liquidation, and the instruction fails if the",
+        "total change in equity during the liquidation falls below the allowed minimum. Only",
+        "positions not managed by an adapter can be transferred this way."
+      ],
+      accounts: [
+        {
+          name: "liquidator",
+          isMut: false,
+          isSigner: true,
+          docs: ["The liquidator processing the margin account"]
+        },
+        {
+          name: "liquidation",
+          isMut: true,
+          isSigner: false,
+          docs: ["Account to persist the state of the liquidation"]
+        },
+        {
+          name: "marginAccount",
+          isMut: true,
+          isSigner: false,
+          docs: ["The margin account being liquidated"]
+        },
+        {
+          name: "source",
+          isMut: true,
+          isSigner: false,
+          docs: ["The deposit account to transfer tokens out of"]
+        },
+        {
+          name: "destination",
+          isMut: true,
+          isSigner: false,
+          docs: ["The account to receive the tokens"]
+        },
+        {
+          name: "tokenProgram",
+          isMut: false,
+          isSigner: false
+        }
+      ],
+      args: [
+        {
+          name: "amount",
+          type: "u64"
+        }
+      ]
+    },
     {
       name: "configureToken",
       docs: [
@@ -3372,6 +3993,65 @@ export const IDL: JetMargin = {
         }
       ]
     },
+    {
+      name: "configureTokenCategory",
+      docs: [
+        "Set the configuration for a category of correlated tokens.",
+        "",
+        "When all the claims in a margin account are in the same category, positions in that",
+        "category are valued with the category's collateral weight or max leverage, if higher",
+        "than the ones in their token configs. Positions pick up the category's values when",
+        "refreshed with `refresh_position_config`.",
+        "",
+        "The account storing the configuration will be funded if not already. If a `None` is provided as",
+        "the updated configuration, then the account will be defunded."
+      ],
+      accounts: [
+        {
+          name: "authority",
+          isMut: false,
+          isSigner: true,
+          docs: ["The authority allowed to make changes to configuration"]
+        },
+        {
+          name: "airspace",
+          isMut: false,
+          isSigner: false,
+          docs: ["The airspace being modified"]
+        },
+        {
+          name: "payer",
+          isMut: true,
+          isSigner: true,
+          docs: ["The payer for any rent costs, if required"]
+        },
+        {
+          name: "tokenCategory",
+          isMut: true,
+          isSigner: false,
+          docs: ["The category account to be modified"]
+        },
+        {
+          name: "systemProgram",
+          isMut: false,
+          isSigner: false
+        }
+      ],
+      args: [
+        {
+          name: "category",
+          type: "u16"
+        },
+        {
+          name: "update",
+          type: {
+            option: {
+              defined: "TokenCategoryUpdate"
+            }
+          }
+        }
+      ]
+    },
     {
       name: "configureAdapter",
       docs: [
@@ -3542,6 +4222,15 @@ export const IDL: JetMargin = {
             type: {
               array: ["u8", 7432]
             }
+          },
+          {
+            name: "airspace",
+            docs: [
+              "The airspace this account belongs to",
+              "",
+              "Not present in version 1 accounts, which must be migrated before they can be used."
+            ],
+            type: "publicKey"
           }
         ]
       }
@@ -3587,30 +4276,13 @@ export const IDL: JetMargin = {
             type: "publicKey"
           },
           {
-            name: "adapterProgram",
-            docs: [
-              "The adapter program in control of positions of this token",
-              "",
-              "If this is `None`, then the margin program is in control of this asset, and",
-              "thus determining its price. The `oracle` field must be set to allow the margin",
-              "program to price the asset."
-            ],
-            type: {
-              option: "publicKey"
-            }
-          },
-          {
-            name: "oracle",
+            name: "admin",
             docs: [
-              "The oracle for the token",
-              "",
-              "This only has effect in the margin program when the price for the token is not",
-              "being managed by an adapter."
+              "The administrator of this token, which has the authority to provide information",
+              "about (e.g. prices) and otherwise modify position states for these tokens."
             ],
             type: {
-              option: {
-                defined: "TokenOracle"
-              }
+              defined: "TokenAdmin"
             }
           },
           {
@@ -3634,6 +4306,60 @@ export const IDL: JetMargin = {
             name: "maxStaleness",
             docs: ["The maximum staleness (seconds) that's acceptable for balances of this token"],
             type: "u64"
+          },
+          {
+            name: "conservativeValuation",
+            docs: ["Value positions using the less favorable of the spot and average prices"],
+            type: "bool"
+          },
+          {
+            name: "category",
+            docs: ["The [TokenCategory] this token belongs to, or zero if it has none"],
+            type: "u16"
+          },
+          {
+            name: "minPermitTier",
+            docs: [
+              "The minimum [AirspacePermit] tier the owner of a margin account needs to register",
+              "a position for this token"
+            ],
+            type: "u8"
+          }
+        ]
+      }
+    },
+    {
+      name: "tokenCategory",
+      docs: [
+        "Configuration for a category of correlated tokens within an airspace, such as",
+        "stablecoins, or a token and its staked derivatives.",
+        "",
+        "When every claim in a margin account belongs to the same category, positions in",
+        "that category are valued with the modifiers configured here instead of the ones",
+        "in their [TokenConfig], if these are higher."
+      ],
+      type: {
+        kind: "struct",
+        fields: [
+          {
+            name: "airspace",
+            docs: ["The airspace the category is defined within"],
+            type: "publicKey"
+          },
+          {
+            name: "category",
+            docs: ["The identifier for the category, referenced by [TokenConfig::category]"],
+            type: "u16"
+          },
+          {
+            name: "collateralWeight",
+            docs: ["The collateral weight for collateral tokens in the category"],
+            type: "u16"
+          },
+          {
+            name: "maxLeverage",
+            docs: ["The maximum leverage for claims in the category"],
+            type: "u16"
           }
         ]
       }
@@ -3678,6 +4404,42 @@ export const IDL: JetMargin = {
     }
   ],
   types: [
+    {
+      name: "ValuationSummary",
+      type: {
+        kind: "struct",
+        fields: [
+          {
+            name: "equity",
+            type: "i128"
+          },
+          {
+            name: "liabilities",
+            type: "i128"
+          },
+          {
+            name: "requiredCollateral",
+            type: "i128"
+          },
+          {
+            name: "weightedCollateral",
+            type: "i128"
+          },
+          {
+            name: "effectiveCollateral",
+            type: "i128"
+          },
+          {
+            name: "availableCollateral",
+            type: "i128"
+          },
+          {
+            name: "pastDue",
+            type: "bool"
+          }
+        ]
+      }
+    },
     {
       name: "AdapterResult",
       type: {
@@ -3695,6 +4457,39 @@ export const IDL: JetMargin = {
         ]
       }
     },
+    {
+      name: "PositionChange",
+      type: {
+        kind: "enum",
+        variants: [
+          {
+            name: "Price",
+            fields: [
+              {
+                defined: "PriceChangeInfo"
+              }
+            ]
+          },
+          {
+            name: "Flags",
+            fields: [
+              {
+                defined: "AdapterPositionFlags"
+              },
+              "bool"
+            ]
+          },
+          {
+            name: "Register",
+            fields: ["publicKey"]
+          },
+          {
+            name: "Close",
+            fields: ["publicKey"]
+          }
+        ]
+      }
+    },
     {
       name: "PriceChangeInfo",
       type: {
@@ -3729,37 +4524,49 @@ export const IDL: JetMargin = {
       }
     },
     {
-      name: "ValuationSummary",
+      name: "AccountHealth",
+      docs: ["The health of a margin account, as returned by the `account_health` instruction"],
       type: {
         kind: "struct",
         fields: [
           {
-            name: "equity",
-            type: "i128"
-          },
-          {
-            name: "liabilities",
-            type: "i128"
-          },
-          {
-            name: "requiredCollateral",
-            type: "i128"
+            name: "timestamp",
+            docs: ["The time the valuation was calculated at"],
+            type: "u64"
           },
           {
-            name: "weightedCollateral",
-            type: "i128"
+            name: "healthRatioBps",
+            docs: ["The ratio of effective collateral to required collateral, in basis points"],
+            type: "u64"
           },
           {
-            name: "effectiveCollateral",
-            type: "i128"
-          },
+            name: "valuationSummary",
+            docs: ["The valuation of the account"],
+            type: {
+              defined: "ValuationSummary"
+            }
+          }
+        ]
+      }
+    },
+    {
+      name: "AdapterInstruction",
+      docs: ["An instruction for an adapter, to be invoked as part of a batch"],
+      type: {
+        kind: "struct",
+        fields: [
           {
-            name: "availableCollateral",
-            type: "i128"
+            name: "numAccounts",
+            docs: [
+              "The number of accounts to pass through to the adapter, which follow the",
+              "adapter program and its config in the remaining accounts"
+            ],
+            type: "u8"
           },
           {
-            name: "pastDue",
-            type: "bool"
+            name: "data",
+            docs: ["The instruction data to pass to the adapter program"],
+            type: "bytes"
           }
         ]
       }
@@ -3775,19 +4582,10 @@ export const IDL: JetMargin = {
             type: "publicKey"
           },
           {
-            name: "adapterProgram",
-            docs: ["The adapter program in control of positions of this token"],
-            type: {
-              option: "publicKey"
-            }
-          },
-          {
-            name: "oracle",
-            docs: ["The oracle for the token"],
+            name: "admin",
+            docs: ["The administration authority for the token"],
             type: {
-              option: {
-                defined: "TokenOracle"
-              }
+              defined: "TokenAdmin"
             }
           },
           {
@@ -3806,18 +4604,39 @@ export const IDL: JetMargin = {
             name: "maxStaleness",
             docs: ["The maximum staleness (seconds) that's acceptable for balances of this token"],
             type: "u64"
+          },
+          {
+            name: "conservativeValuation",
+            docs: ["Value positions using the less favorable of the spot and average prices"],
+            type: "bool"
+          },
+          {
+            name: "category",
+            docs: ["The category this token belongs to, or zero if it has none"],
+            type: "u16"
+          },
+          {
+            name: "minPermitTier",
+            docs: ["The minimum permit tier needed to register a position for this token"],
+            type: "u8"
           }
         ]
       }
     },
     {
-      name: "AdapterPositionFlags",
+      name: "TokenCategoryUpdate",
       type: {
         kind: "struct",
         fields: [
           {
-            name: "flags",
-            type: "u8"
+            name: "collateralWeight",
+            docs: ["The collateral weight for collateral tokens in the category"],
+            type: "u16"
+          },
+          {
+            name: "maxLeverage",
+            docs: ["The maximum leverage for claims in the category"],
+            type: "u16"
           }
         ]
       }
@@ -3927,16 +4746,55 @@ export const IDL: JetMargin = {
               defined: "AdapterPositionFlags"
             }
           },
+          {
+            name: "conservativeValuation",
+            docs: ["Non-zero if the position is valued using the less favorable of its spot", "and average prices"],
+            type: "u8"
+          },
+          {
+            name: "reserved0",
+            docs: ["Unused"],
+            type: {
+              array: ["u8", 6]
+            }
+          },
+          {
+            name: "twap",
+            docs: ["The average price reported with the current price, using the same exponent"],
+            type: "i64"
+          },
+          {
+            name: "category",
+            docs: ["The category of correlated tokens this position belongs to, or zero if none"],
+            type: "u16"
+          },
+          {
+            name: "categoryValueModifier",
+            docs: ["The value modifier used when all claims in the account are in the same category"],
+            type: "u16"
+          },
           {
             name: "reserved",
             docs: ["Unused"],
             type: {
-              array: ["u8", 23]
+              array: ["u8", 4]
             }
           }
         ]
       }
     },
+    {
+      name: "AdapterPositionFlags",
+      type: {
+        kind: "struct",
+        fields: [
+          {
+            name: "flags",
+            type: "u8"
+          }
+        ]
+      }
+    },
     {
       name: "AccountPositionKey",
       type: {
@@ -4024,60 +4882,13 @@ export const IDL: JetMargin = {
       }
     },
     {
-      name: "Invocation",
-      type: {
-        kind: "struct",
-        fields: [
-          {
-            name: "flags",
-            type: "u8"
-          }
-        ]
-      }
-    },
-    {
-      name: "PositionChange",
-      type: {
-        kind: "enum",
-        variants: [
-          {
-            name: "Price",
-            fields: [
-              {
-                defined: "PriceChangeInfo"
-              }
-            ]
-          },
-          {
-            name: "Flags",
-            fields: [
-              {
-                defined: "AdapterPositionFlags"
-              },
-              "bool"
-            ]
-          },
-          {
-            name: "Register",
-            fields: ["publicKey"]
-          },
-          {
-            name: "Close",
-            fields: ["publicKey"]
-          }
-        ]
-      }
-    },
-    {
-      name: "PositionKind",
+      name: "TokenKind",
+      docs: ["Description of the token's usage"],
       type: {
         kind: "enum",
         variants: [
           {
-            name: "NoValue"
-          },
-          {
-            name: "Deposit"
+            name: "Collateral"
           },
           {
             name: "Claim"
@@ -4089,61 +4900,59 @@ export const IDL: JetMargin = {
       }
     },
     {
-      name: "Approver",
+      name: "TokenOracle",
+      docs: ["Information about where to find the oracle data for a token"],
       type: {
         kind: "enum",
         variants: [
           {
-            name: "MarginAccountAuthority"
-          },
-          {
-            name: "Adapter",
-            fields: ["publicKey"]
+            name: "Pyth",
+            fields: [
+              {
+                name: "price",
+                type: "publicKey"
+              },
+              {
+                name: "product",
+                type: "publicKey"
+              }
+            ]
           }
         ]
       }
     },
     {
-      name: "TokenKind",
-      docs: ["Description of the token's usage"],
+      name: "TokenAdmin",
+      docs: ["Description of which program administers a token"],
       type: {
         kind: "enum",
         variants: [
           {
-            name: "NoValue"
-          },
-          {
-            name: "Collateral"
-          },
-          {
-            name: "Claim"
+            name: "Margin",
+            fields: [
+              {
+                name: "oracle",
+                type: {
+                  defined: "TokenOracle"
+                }
+              }
+            ]
           },
           {
-            name: "AdapterCollateral"
+            name: "Adapter",
+            fields: ["publicKey"]
           }
         ]
       }
     },
     {
-      name: "TokenOracle",
-      docs: ["Information about where to find the oracle data for a token"],
+      name: "Invocation",
       type: {
-        kind: "enum",
-        variants: [
+        kind: "struct",
+        fields: [
           {
-            name: "Pyth",
-            fields: [
-              {
-                name: "price",
-                docs: ["The pyth address containing price information for a token."],
-                type: "publicKey"
-              },
-              {
-                name: "product",
-                docs: ["The pyth address with product information for a token"],
-                type: "publicKey"
-              }
-            ]
+            name: "flags",
+            type: "u8"
           }
         ]
       }
@@ -4163,6 +4972,11 @@ export const IDL: JetMargin = {
           type: "publicKey",
           index: false
         },
+        {
+          name: "airspace",
+          type: "publicKey",
+          index: false
+        },
         {
           name: "seed",
           type: "u16",
@@ -4170,6 +4984,26 @@ export const IDL: JetMargin = {
         }
       ]
     },
+    {
+      name: "AccountMigrated",
+      fields: [
+        {
+          name: "marginAccount",
+          type: "publicKey",
+          index: false
+        },
+        {
+          name: "airspace",
+          type: "publicKey",
+          index: false
+        },
+        {
+          name: "version",
+          type: "u8",
+          index: false
+        }
+      ]
+    },
     {
       name: "AccountClosed",
       fields: [
@@ -4190,6 +5024,33 @@ export const IDL: JetMargin = {
         }
       ]
     },
+    {
+      name: "HealthSnapshot",
+      fields: [
+        {
+          name: "marginAccount",
+          type: "publicKey",
+          index: false
+        },
+        {
+          name: "timestamp",
+          type: "u64",
+          index: false
+        },
+        {
+          name: "healthRatioBps",
+          type: "u64",
+          index: false
+        },
+        {
+          name: "valuationSummary",
+          type: {
+            defined: "ValuationSummary"
+          },
+          index: false
+        }
+      ]
+    },
     {
       name: "PositionRegistered",
       fields: [
@@ -4384,6 +5245,45 @@ export const IDL: JetMargin = {
         }
       ]
     },
+    {
+      name: "LiquidatorDepositTransferred",
+      fields: [
+        {
+          name: "marginAccount",
+          type: "publicKey",
+          index: false
+        },
+        {
+          name: "liquidator",
+          type: "publicKey",
+          index: false
+        },
+        {
+          name: "destination",
+          type: "publicKey",
+          index: false
+        },
+        {
+          name: "amount",
+          type: "u64",
+          index: false
+        },
+        {
+          name: "liquidationData",
+          type: {
+            defined: "Liquidation"
+          },
+          index: false
+        },
+        {
+          name: "valuationSummary",
+          type: {
+            defined: "ValuationSummary"
+          },
+          index: false
+        }
+      ]
+    },
     {
       name: "LiquidationEnded",
       fields: [
@@ -4425,6 +5325,16 @@ export const IDL: JetMargin = {
       name: "IndirectInvocation",
       msg: "the current instruction was not directly invoked by the margin program"
     },
+    {
+      code: 141004,
+      name: "NoReturnData",
+      msg: "the program did not return a result"
+    },
+    {
+      code: 141005,
+      name: "WrongProgramReturnData",
+      msg: "the program that set the return data was not the one invoked"
+    },
     {
       code: 141010,
       name: "MaxPositions",
@@ -4538,6 +5448,16 @@ export const IDL: JetMargin = {
       code: 141052,
       name: "InvalidOracle",
       msg: "attempting to use or set invalid configuration"
+    },
+    {
+      code: 141053,
+      name: "WrongPermit",
+      msg: "the permit does not allow the owner to use the airspace"
+    },
+    {
+      code: 141054,
+      name: "WrongAccountVersion",
+      msg: "the account does not have the expected version"
     }
   ]
 }
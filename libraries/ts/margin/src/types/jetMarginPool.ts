@@ -30,6 +30,11 @@ export type JetMarginPool = {
           isMut: false
           isSigner: false
         },
+        {
+          name: "airspace"
+          isMut: false
+          isSigner: false
+        },
         {
           name: "authority"
           isMut: false
@@ -64,18 +69,130 @@ export type JetMarginPool = {
       ]
     },
     {
-      name: "configure"
+      name: "createDepositNoteMetadata"
       accounts: [
         {
           name: "marginPool"
+          isMut: false
+          isSigner: false
+        },
+        {
+          name: "depositNoteMint"
+          isMut: false
+          isSigner: false
+        },
+        {
+          name: "depositNoteMetadata"
           isMut: true
           isSigner: false
         },
+        {
+          name: "airspace"
+          isMut: false
+          isSigner: false
+        },
         {
           name: "authority"
           isMut: false
+          isSigner: true
+        },
+        {
+          name: "payer"
+          isMut: true
+          isSigner: true
+        },
+        {
+          name: "tokenMetadataProgram"
+          isMut: false
+          isSigner: false
+        },
+        {
+          name: "systemProgram"
+          isMut: false
+          isSigner: false
+        },
+        {
+          name: "rent"
+          isMut: false
+          isSigner: false
+        }
+      ]
+      args: [
+        {
+          name: "name"
+          type: "string"
+        },
+        {
+          name: "symbol"
+          type: "string"
+        },
+        {
+          name: "uri"
+          type: "string"
+        }
+      ]
+    },
+    {
+      name: "depositNoteExchangeRate"
+      accounts: [
+        {
+          name: "marginPool"
+          isMut: false
+          isSigner: false
+        }
+      ]
+      args: []
+    },
+    {
+      name: "poolRates"
+      accounts: [
+        {
+          name: "marginPool"
+          isMut: false
+          isSigner: false
+        }
+      ]
+      args: []
+    },
+    {
+      name: "migratePool"
+      accounts: [
+        {
+          name: "marginPool"
+          isMut: true
+          isSigner: false
+        },
+        {
+          name: "payer"
+          isMut: true
+          isSigner: true
+        },
+        {
+          name: "systemProgram"
+          isMut: false
+          isSigner: false
+        }
+      ]
+      args: []
+    },
+    {
+      name: "configure"
+      accounts: [
+        {
+          name: "marginPool"
+          isMut: true
+          isSigner: false
+        },
+        {
+          name: "airspace"
+          isMut: false
           isSigner: false
         },
+        {
+          name: "authority"
+          isMut: false
+          isSigner: true
+        },
         {
           name: "pythProduct"
           isMut: false
@@ -399,7 +516,7 @@ export type JetMarginPool = {
         },
         {
           name: "marginPool"
-          isMut: false
+          isMut: true
           isSigner: false
         },
         {
@@ -575,6 +692,14 @@ export type JetMarginPool = {
           {
             name: "accruedUntil"
             type: "i64"
+          },
+          {
+            name: "airspace"
+            type: "publicKey"
+          },
+          {
+            name: "priceBandExceeded"
+            type: "bool"
           }
         ]
       }
@@ -582,32 +707,48 @@ export type JetMarginPool = {
   ]
   types: [
     {
-      name: "MarginPoolSummary"
+      name: "ExchangeRate"
       type: {
         kind: "struct"
         fields: [
           {
-            name: "borrowedTokens"
+            name: "tokensPerNote"
             type: "u64"
           },
           {
-            name: "uncollectedFees"
+            name: "timestamp"
+            type: "i64"
+          }
+        ]
+      }
+    },
+    {
+      name: "PoolRates"
+      type: {
+        kind: "struct"
+        fields: [
+          {
+            name: "depositNoteExchangeRate"
             type: "u64"
           },
           {
-            name: "depositTokens"
+            name: "loanNoteExchangeRate"
             type: "u64"
           },
           {
-            name: "depositNotes"
+            name: "borrowRate"
             type: "u64"
           },
           {
-            name: "loanNotes"
+            name: "depositRate"
             type: "u64"
           },
           {
-            name: "accruedUntil"
+            name: "utilizationRate"
+            type: "u64"
+          },
+          {
+            name: "timestamp"
             type: "i64"
           }
         ]
@@ -651,78 +792,64 @@ export type JetMarginPool = {
             type: "u16"
           },
           {
-            name: "managementFeeCollectThreshold"
-            type: "u64"
-          }
-        ]
-      }
-    },
-    {
-      name: "TokenChange"
-      type: {
-        kind: "struct"
-        fields: [
+            name: "maxPriceDeviation"
+            type: "u16"
+          },
           {
-            name: "kind"
+            name: "reserved"
             type: {
-              defined: "ChangeKind"
+              array: ["u8", 6]
             }
-          },
-          {
-            name: "tokens"
-            type: "u64"
           }
         ]
       }
     },
     {
-      name: "Amount"
+      name: "MarginPoolSummary"
       type: {
         kind: "struct"
         fields: [
           {
-            name: "kind"
-            type: {
-              defined: "AmountKind"
-            }
+            name: "borrowedTokens"
+            type: "u64"
           },
           {
-            name: "value"
+            name: "uncollectedFees"
             type: "u64"
-          }
-        ]
-      }
-    },
-    {
-      name: "PoolAction"
-      type: {
-        kind: "enum"
-        variants: [
+          },
           {
-            name: "Borrow"
+            name: "depositTokens"
+            type: "u64"
           },
           {
-            name: "Deposit"
+            name: "depositNotes"
+            type: "u64"
           },
           {
-            name: "Repay"
+            name: "loanNotes"
+            type: "u64"
           },
           {
-            name: "Withdraw"
+            name: "accruedUntil"
+            type: "i64"
           }
         ]
       }
     },
     {
-      name: "RoundingDirection"
+      name: "TokenChange"
       type: {
-        kind: "enum"
-        variants: [
+        kind: "struct"
+        fields: [
           {
-            name: "Down"
+            name: "kind"
+            type: {
+              defined: "ChangeKind"
+            }
           },
           {
-            name: "Up"
+            name: "tokens"
+            type: "u64"
           }
         ]
       }
@@ -754,6 +881,24 @@ export type JetMarginPool = {
           }
         ]
       }
+    },
+    {
+      name: "Amount"
+      type: {
+        kind: "struct"
+        fields: [
+          {
+            name: "kind"
+            type: {
+              defined: "AmountKind"
+            }
+          },
+          {
+            name: "value"
+            type: "u64"
+          }
+        ]
+      }
     }
   ]
   events: [
@@ -765,6 +910,11 @@ export type JetMarginPool = {
           type: "publicKey"
           index: false
         },
+        {
+          name: "airspace"
+          type: "publicKey"
+          index: false
+        },
         {
           name: "vault"
           type: "publicKey"
@@ -806,6 +956,31 @@ export type JetMarginPool = {
             defined: "MarginPoolSummary"
           }
           index: false
+        },
+        {
+          name: "version"
+          type: "u8"
+          index: false
+        }
+      ]
+    },
+    {
+      name: "PoolMigrated"
+      fields: [
+        {
+          name: "marginPool"
+          type: "publicKey"
+          index: false
+        },
+        {
+          name: "airspace"
+          type: "publicKey"
+          index: false
+        },
+        {
+          name: "version"
+          type: "u8"
+          index: false
         }
       ]
     },
@@ -836,6 +1011,36 @@ export type JetMarginPool = {
         }
       ]
     },
+    {
+      name: "PriceBandChanged"
+      fields: [
+        {
+          name: "marginPool"
+          type: "publicKey"
+          index: false
+        },
+        {
+          name: "exceeded"
+          type: "bool"
+          index: false
+        },
+        {
+          name: "price"
+          type: "i64"
+          index: false
+        },
+        {
+          name: "emaPrice"
+          type: "i64"
+          index: false
+        },
+        {
+          name: "exponent"
+          type: "i32"
+          index: false
+        }
+      ]
+    },
     {
       name: "Deposit"
       fields: [
@@ -1122,11 +1327,11 @@ export type JetMarginPool = {
     },
     {
       code: 141105
-      name: "InvalidPrice"
+      name: "InvalidPoolPrice"
     },
     {
       code: 141106
-      name: "InvalidOracle"
+      name: "InvalidPoolOracle"
     },
     {
       code: 141107
@@ -1136,6 +1341,26 @@ export type JetMarginPool = {
     {
       code: 141108
       name: "RepaymentExceedsTotalOutstanding"
+    },
+    {
+      code: 141109
+      name: "InvalidPoolVersion"
+      msg: "The pool account has the wrong version, it may need to be migrated"
+    },
+    {
+      code: 141110
+      name: "PriceBandExceeded"
+      msg: "The pool is paused while the token price is outside its expected band"
+    },
+    {
+      code: 141111
+      name: "WrongAirspace"
+      msg: "The margin account is not in the pool's airspace"
+    },
+    {
+      code: 141112
+      name: "RateOverflow"
+      msg: "The rate is too large to be represented"
     }
   ]
 }
@@ -1153,22 +1378,83 @@ export const IDL: JetMarginPool = {
           isSigner: false
         },
         {
-          name: "vault",
+          name: "vault",
+          isMut: true,
+          isSigner: false
+        },
+        {
+          name: "depositNoteMint",
+          isMut: true,
+          isSigner: false
+        },
+        {
+          name: "loanNoteMint",
+          isMut: true,
+          isSigner: false
+        },
+        {
+          name: "tokenMint",
+          isMut: false,
+          isSigner: false
+        },
+        {
+          name: "airspace",
+          isMut: false,
+          isSigner: false
+        },
+        {
+          name: "authority",
+          isMut: false,
+          isSigner: true
+        },
+        {
+          name: "payer",
           isMut: true,
+          isSigner: true
+        },
+        {
+          name: "tokenProgram",
+          isMut: false,
+          isSigner: false
+        },
+        {
+          name: "systemProgram",
+          isMut: false,
+          isSigner: false
+        },
+        {
+          name: "rent",
+          isMut: false,
+          isSigner: false
+        }
+      ],
+      args: [
+        {
+          name: "feeDestination",
+          type: "publicKey"
+        }
+      ]
+    },
+    {
+      name: "createDepositNoteMetadata",
+      accounts: [
+        {
+          name: "marginPool",
+          isMut: false,
           isSigner: false
         },
         {
           name: "depositNoteMint",
-          isMut: true,
+          isMut: false,
           isSigner: false
         },
         {
-          name: "loanNoteMint",
+          name: "depositNoteMetadata",
           isMut: true,
           isSigner: false
         },
         {
-          name: "tokenMint",
+          name: "airspace",
           isMut: false,
           isSigner: false
         },
@@ -1183,7 +1469,7 @@ export const IDL: JetMarginPool = {
           isSigner: true
         },
         {
-          name: "tokenProgram",
+          name: "tokenMetadataProgram",
           isMut: false,
           isSigner: false
         },
@@ -1200,11 +1486,62 @@ export const IDL: JetMarginPool = {
       ],
       args: [
         {
-          name: "feeDestination",
-          type: "publicKey"
+          name: "name",
+          type: "string"
+        },
+        {
+          name: "symbol",
+          type: "string"
+        },
+        {
+          name: "uri",
+          type: "string"
         }
       ]
     },
+    {
+      name: "depositNoteExchangeRate",
+      accounts: [
+        {
+          name: "marginPool",
+          isMut: false,
+          isSigner: false
+        }
+      ],
+      args: []
+    },
+    {
+      name: "poolRates",
+      accounts: [
+        {
+          name: "marginPool",
+          isMut: false,
+          isSigner: false
+        }
+      ],
+      args: []
+    },
+    {
+      name: "migratePool",
+      accounts: [
+        {
+          name: "marginPool",
+          isMut: true,
+          isSigner: false
+        },
+        {
+          name: "payer",
+          isMut: true,
+          isSigner: true
+        },
+        {
+          name: "systemProgram",
+          isMut: false,
+          isSigner: false
+        }
+      ],
+      args: []
+    },
     {
       name: "configure",
       accounts: [
@@ -1214,10 +1551,15 @@ export const IDL: JetMarginPool = {
           isSigner: false
         },
         {
-          name: "authority",
+          name: "airspace",
           isMut: false,
           isSigner: false
         },
+        {
+          name: "authority",
+          isMut: false,
+          isSigner: true
+        },
         {
           name: "pythProduct",
           isMut: false,
@@ -1541,7 +1883,7 @@ export const IDL: JetMarginPool = {
         },
         {
           name: "marginPool",
-          isMut: false,
+          isMut: true,
           isSigner: false
         },
         {
@@ -1717,6 +2059,14 @@ export const IDL: JetMarginPool = {
           {
             name: "accruedUntil",
             type: "i64"
+          },
+          {
+            name: "airspace",
+            type: "publicKey"
+          },
+          {
+            name: "priceBandExceeded",
+            type: "bool"
           }
         ]
       }
@@ -1724,32 +2074,48 @@ export const IDL: JetMarginPool = {
   ],
   types: [
     {
-      name: "MarginPoolSummary",
+      name: "ExchangeRate",
       type: {
         kind: "struct",
         fields: [
           {
-            name: "borrowedTokens",
+            name: "tokensPerNote",
             type: "u64"
           },
           {
-            name: "uncollectedFees",
+            name: "timestamp",
+            type: "i64"
+          }
+        ]
+      }
+    },
+    {
+      name: "PoolRates",
+      type: {
+        kind: "struct",
+        fields: [
+          {
+            name: "depositNoteExchangeRate",
             type: "u64"
           },
           {
-            name: "depositTokens",
+            name: "loanNoteExchangeRate",
             type: "u64"
           },
           {
-            name: "depositNotes",
+            name: "borrowRate",
             type: "u64"
           },
           {
-            name: "loanNotes",
+            name: "depositRate",
             type: "u64"
           },
           {
-            name: "accruedUntil",
+            name: "utilizationRate",
+            type: "u64"
+          },
+          {
+            name: "timestamp",
             type: "i64"
           }
         ]
@@ -1793,78 +2159,64 @@ export const IDL: JetMarginPool = {
             type: "u16"
           },
           {
-            name: "managementFeeCollectThreshold",
-            type: "u64"
-          }
-        ]
-      }
-    },
-    {
-      name: "TokenChange",
-      type: {
-        kind: "struct",
-        fields: [
+            name: "maxPriceDeviation",
+            type: "u16"
+          },
           {
-            name: "kind",
+            name: "reserved",
             type: {
-              defined: "ChangeKind"
+              array: ["u8", 6]
             }
-          },
-          {
-            name: "tokens",
-            type: "u64"
           }
         ]
       }
     },
     {
-      name: "Amount",
+      name: "MarginPoolSummary",
       type: {
         kind: "struct",
         fields: [
           {
-            name: "kind",
-            type: {
-              defined: "AmountKind"
-            }
+            name: "borrowedTokens",
+            type: "u64"
           },
           {
-            name: "value",
+            name: "uncollectedFees",
             type: "u64"
-          }
-        ]
-      }
-    },
-    {
-      name: "PoolAction",
-      type: {
-        kind: "enum",
-        variants: [
+          },
           {
-            name: "Borrow"
+            name: "depositTokens",
+            type: "u64"
           },
           {
-            name: "Deposit"
+            name: "depositNotes",
+            type: "u64"
           },
           {
-            name: "Repay"
+            name: "loanNotes",
+            type: "u64"
           },
           {
-            name: "Withdraw"
+            name: "accruedUntil",
+            type: "i64"
           }
         ]
       }
     },
     {
-      name: "RoundingDirection",
+      name: "TokenChange",
       type: {
-        kind: "enum",
-        variants: [
+        kind: "struct",
+        fields: [
           {
-            name: "Down"
+            name: "kind",
+            type: {
+              defined: "ChangeKind"
+            }
           },
           {
-            name: "Up"
+            name: "tokens",
+            type: "u64"
           }
         ]
       }
@@ -1896,6 +2248,24 @@ export const IDL: JetMarginPool = {
           }
         ]
       }
+    },
+    {
+      name: "Amount",
+      type: {
+        kind: "struct",
+        fields: [
+          {
+            name: "kind",
+            type: {
+              defined: "AmountKind"
+            }
+          },
+          {
+            name: "value",
+            type: "u64"
+          }
+        ]
+      }
     }
   ],
   events: [
@@ -1907,6 +2277,11 @@ export const IDL: JetMarginPool = {
           type: "publicKey",
           index: false
         },
+        {
+          name: "airspace",
+          type: "publicKey",
+          index: false
+        },
         {
           name: "vault",
           type: "publicKey",
@@ -1948,6 +2323,31 @@ export const IDL: JetMarginPool = {
             defined: "MarginPoolSummary"
           },
           index: false
+        },
+        {
+          name: "version",
+          type: "u8",
+          index: false
+        }
+      ]
+    },
+    {
+      name: "PoolMigrated",
+      fields: [
+        {
+          name: "marginPool",
+          type: "publicKey",
+          index: false
+        },
+        {
+          name: "airspace",
+          type: "publicKey",
+          index: false
+        },
+        {
+          name: "version",
+          type: "u8",
+          index: false
         }
       ]
     },
@@ -1978,6 +2378,36 @@ export const IDL: JetMarginPool = {
         }
       ]
     },
+    {
+      name: "PriceBandChanged",
+      fields: [
+        {
+          name: "marginPool",
+          type: "publicKey",
+          index: false
+        },
+        {
+          name: "exceeded",
+          type: "bool",
+          index: false
+        },
+        {
+          name: "price",
+          type: "i64",
+          index: false
+        },
+        {
+          name: "emaPrice",
+          type: "i64",
+          index: false
+        },
+        {
+          name: "exponent",
+          type: "i32",
+          index: false
+        }
+      ]
+    },
     {
       name: "Deposit",
       fields: [
@@ -2264,11 +2694,11 @@ export const IDL: JetMarginPool = {
     },
     {
       code: 141105,
-      name: "InvalidPrice"
+      name: "InvalidPoolPrice"
     },
     {
       code: 141106,
-      name: "InvalidOracle"
+      name: "InvalidPoolOracle"
     },
     {
       code: 141107,
@@ -2278,6 +2708,26 @@ export const IDL: JetMarginPool = {
     {
       code: 141108,
       name: "RepaymentExceedsTotalOutstanding"
+    },
+    {
+      code: 141109,
+      name: "InvalidPoolVersion",
+      msg: "The pool account has the wrong version, it may need to be migrated"
+    },
+    {
+      code: 141110,
+      name: "PriceBandExceeded",
+      msg: "The pool is paused while the token price is outside its expected band"
+    },
+    {
+      code: 141111,
+      name: "WrongAirspace",
+      msg: "The margin account is not in the pool's airspace"
+    },
+    {
+      code: 141112,
+      name: "RateOverflow",
+      msg: "The rate is too large to be represented"
     }
   ]
 }
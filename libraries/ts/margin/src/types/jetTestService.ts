@@ -221,6 +221,14 @@ export type JetTestService = {
           name: "conf"
           type: "i64"
         },
+        {
+          name: "emaPrice"
+          type: "i64"
+        },
+        {
+          name: "emaConf"
+          type: "i64"
+        },
         {
           name: "expo"
           type: "i32"
@@ -546,6 +554,14 @@ export const IDL: JetTestService = {
           name: "conf",
           type: "i64"
         },
+        {
+          name: "emaPrice",
+          type: "i64"
+        },
+        {
+          name: "emaConf",
+          type: "i64"
+        },
         {
           name: "expo",
           type: "i32"
@@ -39,6 +39,16 @@ pub struct AirspacePermitCreated {
     pub airspace: Pubkey,
     pub issuer: Pubkey,
     pub owner: Pubkey,
+    pub expires_at: i64,
+    pub tier: u8,
+}
+
+#[event]
+pub struct AirspacePermitUpdated {
+    pub airspace: Pubkey,
+    pub owner: Pubkey,
+    pub expires_at: i64,
+    pub tier: u8,
 }
 
 #[event]
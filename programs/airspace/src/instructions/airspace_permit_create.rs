@@ -21,6 +21,7 @@ use crate::{
     events::AirspacePermitCreated,
     seeds::{AIRSPACE_PERMIT, AIRSPACE_PERMIT_ISSUER},
     state::{Airspace, AirspacePermit, AirspacePermitIssuerId},
+    AirspaceErrorCode,
 };
 
 #[derive(Accounts)]
@@ -70,6 +71,8 @@ pub struct AirspacePermitCreate<'info> {
 pub fn airspace_permit_create_handler(
     ctx: Context<AirspacePermitCreate>,
     owner: Pubkey,
+    expires_at: i64,
+    tier: u8,
 ) -> Result<()> {
     // First validate that the signer is allowed to create permits
 
@@ -86,6 +89,10 @@ pub fn airspace_permit_create_handler(
         // and the account data being valid to deserialize means the permission was granted
     }
 
+    if expires_at != 0 && expires_at <= Clock::get()?.unix_timestamp {
+        return err!(AirspaceErrorCode::InvalidPermitExpiry);
+    }
+
     let permit = &mut ctx.accounts.permit;
 
     permit.airspace = ctx.accounts.airspace.key();
@@ -95,11 +102,15 @@ pub fn airspace_permit_create_handler(
     } else {
         authority.key()
     };
+    permit.expires_at = expires_at;
+    permit.tier = tier;
 
     emit!(AirspacePermitCreated {
         airspace: airspace.key(),
         issuer: permit.issuer,
-        owner: permit.owner
+        owner: permit.owner,
+        expires_at,
+        tier,
     });
 
     Ok(())
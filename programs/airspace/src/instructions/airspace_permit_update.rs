@@ -0,0 +1,90 @@
+// SPDX-License-Identifier: AGPL-3.0-or-later
+//
+// Copyright (C) 2022 JET PROTOCOL HOLDINGS, LLC.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+use anchor_lang::prelude::*;
+
+use crate::{
+    events::AirspacePermitUpdated,
+    seeds::AIRSPACE_PERMIT_ISSUER,
+    state::{Airspace, AirspacePermit},
+    AirspaceErrorCode,
+};
+
+#[derive(Accounts)]
+pub struct AirspacePermitUpdate<'info> {
+    /// The authority allowed to update an airspace permit
+    ///
+    /// The addresses allowed to update are:
+    ///     * the airspace authority, always
+    ///     * the issuer of the permit, as long as it is still allowed to issue
+    ///       permits in the airspace
+    authority: Signer<'info>,
+
+    /// The airspace the permit belongs to
+    airspace: Account<'info, Airspace>,
+
+    /// The identity account for the issuer of the permit
+    #[account(seeds = [
+                AIRSPACE_PERMIT_ISSUER,
+                airspace.key().as_ref(),
+                permit.issuer.as_ref()
+              ],
+              bump
+    )]
+    issuer_id: AccountInfo<'info>,
+
+    /// The permit to be updated
+    #[account(mut, has_one = airspace)]
+    permit: Account<'info, AirspacePermit>,
+}
+
+pub fn airspace_permit_update_handler(
+    ctx: Context<AirspacePermitUpdate>,
+    expires_at: i64,
+    tier: u8,
+) -> Result<()> {
+    let airspace = &ctx.accounts.airspace;
+    let authority = ctx.accounts.authority.key();
+
+    if authority != airspace.authority {
+        // Other than the airspace authority, only the original issuer can change the permit,
+        // and in a restricted airspace only while its issuer license is still valid.
+        if authority != ctx.accounts.permit.issuer
+            || (airspace.is_restricted && ctx.accounts.issuer_id.data_is_empty())
+        {
+            return err!(AirspaceErrorCode::PermissionDenied);
+        }
+    }
+
+    if expires_at != 0 && expires_at <= Clock::get()?.unix_timestamp {
+        return err!(AirspaceErrorCode::InvalidPermitExpiry);
+    }
+
+    let permit = &mut ctx.accounts.permit;
+
+    permit.expires_at = expires_at;
+    permit.tier = tier;
+
+    emit!(AirspacePermitUpdated {
+        airspace: airspace.key(),
+        owner: permit.owner,
+        expires_at,
+        tier,
+    });
+
+    Ok(())
+}
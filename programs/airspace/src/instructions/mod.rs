@@ -9,6 +9,7 @@ mod airspace_permit_issuer_revoke;
 
 mod airspace_permit_create;
 mod airspace_permit_revoke;
+mod airspace_permit_update;
 
 pub use create_governor_id::*;
 pub use set_governor::*;
@@ -21,3 +22,4 @@ pub use airspace_permit_issuer_revoke::*;
 
 pub use airspace_permit_create::*;
 pub use airspace_permit_revoke::*;
+pub use airspace_permit_update::*;
@@ -46,6 +46,12 @@ pub mod seeds {
 #[constant]
 pub const GOVERNOR_DEFAULT: Pubkey = pubkey!("7R6FjP2HfXAgKQjURC4tCBrUmRQLCgEUeX2berrfU4ox");
 
+/// The address of the default airspace, created with the seed `"default"`
+///
+/// Resources that existed before airspaces were introduced belong to this airspace.
+#[constant]
+pub const DEFAULT_AIRSPACE: Pubkey = pubkey!("BwQhHumhyyyRBtCsiSrdnFCinJDCaaMBbbyRhqJ5p81d");
+
 #[program]
 pub mod jet_airspace {
     use super::*;
@@ -124,8 +130,30 @@ pub mod jet_airspace {
     ///
     /// * `owner` - The owner for the new permit, which is the address being allowed to use
     ///             the airspace.
-    pub fn airspace_permit_create(ctx: Context<AirspacePermitCreate>, owner: Pubkey) -> Result<()> {
-        instructions::airspace_permit_create_handler(ctx, owner)
+    /// * `expires_at` - The unix timestamp when the permit expires, or zero if it never expires.
+    /// * `tier` - The level of access granted by the permit.
+    pub fn airspace_permit_create(
+        ctx: Context<AirspacePermitCreate>,
+        owner: Pubkey,
+        expires_at: i64,
+        tier: u8,
+    ) -> Result<()> {
+        instructions::airspace_permit_create_handler(ctx, owner, expires_at, tier)
+    }
+
+    /// Change the expiry and tier of an existing permit, such as when renewing a permit for a
+    /// user whose verification has been refreshed.
+    ///
+    /// # Parameters
+    ///
+    /// * `expires_at` - The unix timestamp when the permit expires, or zero if it never expires.
+    /// * `tier` - The level of access granted by the permit.
+    pub fn airspace_permit_update(
+        ctx: Context<AirspacePermitUpdate>,
+        expires_at: i64,
+        tier: u8,
+    ) -> Result<()> {
+        instructions::airspace_permit_update_handler(ctx, expires_at, tier)
     }
 
     /// Revoke a previously created permit
@@ -139,4 +167,29 @@ pub enum AirspaceErrorCode {
     /// 707000 - No permissions to do an action
     #[msg("The signer does not have the required permissions to do this")]
     PermissionDenied = 701_000,
+
+    /// 701001 - The permit is past its expiry
+    #[msg("The permit has expired")]
+    PermitExpired,
+
+    /// 701002 - The permit tier is too low for the resource
+    #[msg("The permit tier is too low to use this resource")]
+    PermitTierTooLow,
+
+    /// 701003 - The expiry for a new permit is already in the past
+    #[msg("The permit expiry must be in the future")]
+    InvalidPermitExpiry,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn default_airspace_address() {
+        let (address, _) =
+            Pubkey::find_program_address(&[seeds::AIRSPACE, b"default"], &crate::ID);
+
+        assert_eq!(DEFAULT_AIRSPACE, address);
+    }
 }
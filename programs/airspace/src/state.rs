@@ -17,6 +17,8 @@
 
 use anchor_lang::prelude::*;
 
+use crate::AirspaceErrorCode;
+
 macro_rules! declare_account_size {
     ($name:ident, $size:expr) => {
         impl $name {
@@ -73,10 +75,51 @@ pub struct AirspacePermit {
 
     /// The issuer of this permit
     pub issuer: Pubkey,
+
+    /// The unix timestamp after which the permit is no longer valid, or zero if the
+    /// permit never expires
+    pub expires_at: i64,
+
+    /// The level of access granted by this permit. Resources in the airspace may require
+    /// a minimum tier, with higher tiers granting access to more resources.
+    pub tier: u8,
 }
 
 declare_account_size!(AirspacePermit, 128);
 
+impl AirspacePermit {
+    /// The tier for permits given to regular users
+    pub const TIER_RETAIL: u8 = 0;
+
+    /// The tier for permits given to users verified as institutions
+    pub const TIER_INSTITUTIONAL: u8 = 1;
+
+    /// Check if the permit has expired at the given time
+    pub fn is_expired(&self, now: i64) -> bool {
+        self.expires_at != 0 && self.expires_at <= now
+    }
+
+    /// Verify that this permit allows the owner to use a resource in the airspace that
+    /// requires the given minimum tier.
+    pub fn verify(&self, min_tier: u8) -> Result<()> {
+        if self.is_expired(Clock::get()?.unix_timestamp) {
+            msg!("permit for {} expired at {}", self.owner, self.expires_at);
+            return err!(AirspaceErrorCode::PermitExpired);
+        }
+
+        if self.tier < min_tier {
+            msg!(
+                "permit tier {} is below the required {}",
+                self.tier,
+                min_tier
+            );
+            return err!(AirspaceErrorCode::PermitTierTooLow);
+        }
+
+        Ok(())
+    }
+}
+
 /// A global account specifying the current governing address for the protocol
 #[account]
 pub struct GovernorId {
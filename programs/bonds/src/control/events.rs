@@ -1,5 +1,7 @@
 use anchor_lang::prelude::*;
 
+use super::state::CrankPolicy;
+
 #[event]
 pub struct BondManagerInitialized {
     pub version: u64,
@@ -32,3 +34,20 @@ pub struct ToggleOrderMatching {
     pub bond_manager: Pubkey,
     pub is_orderbook_paused: bool,
 }
+
+/// A configuration value of a `BondManager` that was changed, with its old and new values
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum BondManagerChange {
+    TicketsPaused { old: bool, new: bool },
+    UnderlyingOracle { old: Pubkey, new: Pubkey },
+    TicketOracle { old: Pubkey, new: Pubkey },
+    Duration { old: i64, new: i64 },
+    CrankPolicy { old: CrankPolicy, new: CrankPolicy },
+    MinPermitTier { old: u8, new: u8 },
+}
+
+#[event]
+pub struct BondManagerModified {
+    pub bond_manager: Pubkey,
+    pub change: BondManagerChange,
+}
@@ -1,17 +1,26 @@
 use anchor_lang::prelude::*;
+use jet_airspace::state::Airspace;
 
-use crate::control::state::CrankAuthorization;
+use crate::{control::state::CrankAuthorization, BondsError};
 
 #[derive(Accounts)]
 pub struct AuthorizeCrank<'info> {
     /// The crank signer pubkey
     pub crank: AccountInfo<'info>,
 
+    /// The authority that must sign to make this change
+    pub authority: Signer<'info>,
+
+    /// The airspace being modified
+    #[account(has_one = authority @ BondsError::WrongAirspaceAuthorization)]
+    pub airspace: Account<'info, Airspace>,
+
     /// The account containing the metadata for the key
     #[account(
         init,
         seeds = [
             crate::seeds::CRANK_AUTHORIZATION,
+            airspace.key().as_ref(),
             crank.key.as_ref()
         ],
         bump,
@@ -20,13 +29,6 @@ pub struct AuthorizeCrank<'info> {
     )]
     pub crank_authorization: Account<'info, CrankAuthorization>,
 
-    /// The authority that must sign to make this change
-    pub authority: Signer<'info>,
-
-    /// The airspace being modified
-    // #[account(has_one = authority @ BondsError::WrongAirspaceAuthorization)] fixme airspace
-    pub airspace: AccountInfo<'info>,
-
     /// The address paying the rent for the account
     #[account(mut)]
     pub payer: Signer<'info>,
@@ -36,5 +38,6 @@ pub struct AuthorizeCrank<'info> {
 
 pub fn handler(ctx: Context<AuthorizeCrank>) -> Result<()> {
     ctx.accounts.crank_authorization.crank = ctx.accounts.crank.key();
+    ctx.accounts.crank_authorization.airspace = ctx.accounts.airspace.key();
     Ok(())
 }
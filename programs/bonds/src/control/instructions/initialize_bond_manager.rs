@@ -2,7 +2,10 @@ use anchor_lang::prelude::*;
 use anchor_spl::token::{Mint, Token, TokenAccount};
 
 use crate::{
-    control::{events::BondManagerInitialized, state::BondManager},
+    control::{
+        events::BondManagerInitialized,
+        state::{BondManager, CrankPolicy},
+    },
     seeds,
     utils::init,
 };
@@ -147,6 +150,8 @@ pub fn handler(
             bump: [*ctx.bumps.get("bond_manager").unwrap()],
             orderbook_paused: false,
             tickets_paused: false,
+            crank_policy: CrankPolicy::Authorized as u8,
+            min_permit_tier: 0,
             duration: params.duration,
             underlying_oracle: ctx.accounts.underlying_oracle.key(),
             ticket_oracle: ctx.accounts.ticket_oracle.key(),
@@ -5,6 +5,12 @@ pub mod modify_bond_manager;
 pub mod pause_order_matching;
 pub mod resume_order_matching;
 pub mod revoke_crank;
+pub mod set_crank_policy;
+pub mod set_duration;
+pub mod set_min_permit_tier;
+pub mod set_ticket_oracle;
+pub mod set_tickets_paused;
+pub mod set_underlying_oracle;
 
 pub use authorize_crank::*;
 pub use initialize_bond_manager::*;
@@ -13,3 +19,9 @@ pub use modify_bond_manager::*;
 pub use pause_order_matching::*;
 pub use resume_order_matching::*;
 pub use revoke_crank::*;
+pub use set_crank_policy::*;
+pub use set_duration::*;
+pub use set_min_permit_tier::*;
+pub use set_ticket_oracle::*;
+pub use set_tickets_paused::*;
+pub use set_underlying_oracle::*;
@@ -1,8 +1,13 @@
-use std::io::Write;
-
 use anchor_lang::prelude::*;
+use jet_airspace::state::Airspace;
 
-use crate::{control::state::BondManager, BondsError};
+use crate::{
+    control::{
+        events::{BondManagerChange, BondManagerModified},
+        state::BondManager,
+    },
+    BondsError,
+};
 
 #[derive(Accounts)]
 pub struct ModifyBondManager<'info> {
@@ -14,17 +19,36 @@ pub struct ModifyBondManager<'info> {
     pub authority: Signer<'info>,
 
     /// The airspace being modified
-    // #[account(has_one = authority @ BondsError::WrongAirspaceAuthorization)] fixme airspace
-    pub airspace: AccountInfo<'info>,
+    #[account(has_one = authority @ BondsError::WrongAirspaceAuthorization)]
+    pub airspace: Account<'info, Airspace>,
+}
+
+impl<'info> ModifyBondManager<'info> {
+    pub(crate) fn emit_change(&self, change: BondManagerChange) {
+        emit!(BondManagerModified {
+            bond_manager: self.bond_manager.key(),
+            change,
+        });
+    }
 }
 
-pub fn handler(ctx: Context<ModifyBondManager>, data: Vec<u8>, offset: usize) -> Result<()> {
-    let info = ctx.accounts.bond_manager.to_account_info();
-    let buffer = &mut info.data.borrow_mut();
+#[derive(Accounts)]
+pub struct SetOracle<'info> {
+    pub modify: ModifyBondManager<'info>,
+
+    /// The new oracle
+    /// CHECK: must be a valid pyth price feed, verified in the handler
+    pub oracle: AccountInfo<'info>,
+}
 
-    (&mut buffer[(offset + 8)..])
-        .write_all(&data)
-        .map_err(|_| BondsError::IoError)?;
+impl<'info> SetOracle<'info> {
+    /// ensure the new oracle can be used to price positions
+    pub(crate) fn validate_oracle(&self) -> Result<()> {
+        pyth_sdk_solana::load_price_feed_from_account_info(&self.oracle).map_err(|e| {
+            msg!("oracle error: {:?}", e);
+            error!(BondsError::OracleError)
+        })?;
 
-    Ok(())
+        Ok(())
+    }
 }
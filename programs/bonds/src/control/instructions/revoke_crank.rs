@@ -1,19 +1,20 @@
 use anchor_lang::prelude::*;
+use jet_airspace::state::Airspace;
 
-use crate::control::state::CrankAuthorization;
+use crate::{control::state::CrankAuthorization, BondsError};
 
 #[derive(Accounts)]
 pub struct RevokeCrank<'info> {
     /// The account containing the metadata for the key
-    #[account(mut, close = receiver)]
+    #[account(mut, close = receiver, has_one = airspace @ BondsError::WrongAirspace)]
     pub metadata_account: Account<'info, CrankAuthorization>,
 
     /// The authority that must sign to make this change
     pub authority: Signer<'info>,
 
     /// The airspace being modified
-    // #[account(has_one = authority)] todo
-    pub airspace: AccountInfo<'info>,
+    #[account(has_one = authority @ BondsError::WrongAirspaceAuthorization)]
+    pub airspace: Account<'info, Airspace>,
 
     #[account(mut)]
     pub receiver: AccountInfo<'info>,
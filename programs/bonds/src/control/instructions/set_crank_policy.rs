@@ -0,0 +1,16 @@
+use anchor_lang::prelude::*;
+
+use crate::control::{
+    events::BondManagerChange, instructions::ModifyBondManager, state::CrankPolicy,
+};
+
+pub fn handler(ctx: Context<ModifyBondManager>, policy: CrankPolicy) -> Result<()> {
+    let mut manager = ctx.accounts.bond_manager.load_mut()?;
+    let old = manager.crank_policy();
+    manager.crank_policy = policy as u8;
+
+    ctx.accounts
+        .emit_change(BondManagerChange::CrankPolicy { old, new: policy });
+
+    Ok(())
+}
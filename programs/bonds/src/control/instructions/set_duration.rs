@@ -0,0 +1,21 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    control::{events::BondManagerChange, instructions::ModifyBondManager},
+    BondsError,
+};
+
+/// Change the tenor of the market. Only orders filled after the change are affected,
+/// existing tickets and obligations keep their maturity.
+pub fn handler(ctx: Context<ModifyBondManager>, duration: i64) -> Result<()> {
+    require!(duration > 0, BondsError::InvalidDuration);
+
+    let mut manager = ctx.accounts.bond_manager.load_mut()?;
+    let old = manager.duration;
+    manager.duration = duration;
+
+    ctx.accounts
+        .emit_change(BondManagerChange::Duration { old, new: duration });
+
+    Ok(())
+}
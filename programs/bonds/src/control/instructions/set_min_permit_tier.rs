@@ -0,0 +1,14 @@
+use anchor_lang::prelude::*;
+
+use crate::control::{events::BondManagerChange, instructions::ModifyBondManager};
+
+pub fn handler(ctx: Context<ModifyBondManager>, tier: u8) -> Result<()> {
+    let mut manager = ctx.accounts.bond_manager.load_mut()?;
+    let old = manager.min_permit_tier;
+    manager.min_permit_tier = tier;
+
+    ctx.accounts
+        .emit_change(BondManagerChange::MinPermitTier { old, new: tier });
+
+    Ok(())
+}
@@ -0,0 +1,20 @@
+use anchor_lang::prelude::*;
+
+use crate::control::{events::BondManagerChange, instructions::SetOracle};
+
+pub fn handler(ctx: Context<SetOracle>) -> Result<()> {
+    ctx.accounts.validate_oracle()?;
+
+    let mut manager = ctx.accounts.modify.bond_manager.load_mut()?;
+    let old = manager.ticket_oracle;
+    manager.ticket_oracle = ctx.accounts.oracle.key();
+
+    ctx.accounts
+        .modify
+        .emit_change(BondManagerChange::TicketOracle {
+            old,
+            new: manager.ticket_oracle,
+        });
+
+    Ok(())
+}
@@ -0,0 +1,14 @@
+use anchor_lang::prelude::*;
+
+use crate::control::{events::BondManagerChange, instructions::ModifyBondManager};
+
+pub fn handler(ctx: Context<ModifyBondManager>, paused: bool) -> Result<()> {
+    let mut manager = ctx.accounts.bond_manager.load_mut()?;
+    let old = manager.tickets_paused;
+    manager.tickets_paused = paused;
+
+    ctx.accounts
+        .emit_change(BondManagerChange::TicketsPaused { old, new: paused });
+
+    Ok(())
+}
@@ -0,0 +1,20 @@
+use anchor_lang::prelude::*;
+
+use crate::control::{events::BondManagerChange, instructions::SetOracle};
+
+pub fn handler(ctx: Context<SetOracle>) -> Result<()> {
+    ctx.accounts.validate_oracle()?;
+
+    let mut manager = ctx.accounts.modify.bond_manager.load_mut()?;
+    let old = manager.underlying_oracle;
+    manager.underlying_oracle = ctx.accounts.oracle.key();
+
+    ctx.accounts
+        .modify
+        .emit_change(BondManagerChange::UnderlyingOracle {
+            old,
+            new: manager.underlying_oracle,
+        });
+
+    Ok(())
+}
@@ -45,8 +45,12 @@ pub struct BondManager {
     pub orderbook_paused: bool,
     /// Can tickets be redeemed
     pub tickets_paused: bool,
+    /// Who may consume events from the orderbook, see [CrankPolicy]
+    pub crank_policy: u8,
+    /// The minimum `AirspacePermit` tier required for margin accounts to borrow in this market
+    pub min_permit_tier: u8,
     /// reserved for future use
-    pub(crate) _reserved: [u8; 28],
+    pub(crate) _reserved: [u8; 26],
     /// Units added to the initial stake timestamp to determine claim maturity
     pub duration: i64,
     /// Used to generate unique order tags
@@ -64,6 +68,24 @@ impl BondManager {
             &self.bump,
         ]
     }
+
+    /// The policy deciding who may consume events from the orderbook
+    pub fn crank_policy(&self) -> CrankPolicy {
+        match self.crank_policy {
+            1 => CrankPolicy::Permissionless,
+            _ => CrankPolicy::Authorized,
+        }
+    }
+}
+
+/// Decides who may run the `consume_events` instruction for a market
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+#[repr(u8)]
+pub enum CrankPolicy {
+    /// Only cranks holding a `CrankAuthorization` for the airspace
+    Authorized = 0,
+    /// Any signer
+    Permissionless = 1,
 }
 
 #[cfg(any(feature = "cli", test))]
@@ -98,6 +120,8 @@ impl Serialize for BondManager {
         s.serialize_field("seed", &Pubkey::new_from_array(self.seed).to_string())?;
         s.serialize_field("orderbookPaused", &self.orderbook_paused)?;
         s.serialize_field("ticketsPaused", &self.tickets_paused)?;
+        s.serialize_field("crankPolicy", &self.crank_policy)?;
+        s.serialize_field("minPermitTier", &self.min_permit_tier)?;
         s.serialize_field("duration", &self.duration)?;
         s.end()
     }
@@ -131,6 +155,8 @@ fn serialize_bond_manager() {
       \"seed\": \"11111111111111111111111111111111\",
       \"orderbookPaused\": false,
       \"ticketsPaused\": false,
+      \"crankPolicy\": 0,
+      \"minPermitTier\": 0,
       \"duration\": 0
     }";
     assert_eq!(
@@ -138,3 +164,32 @@ fn serialize_bond_manager() {
         itertools::Itertools::join(&mut json.split_whitespace(), " ")
     )
 }
+
+#[test]
+fn crank_policy_defaults_to_authorized() {
+    // markets created before the crank policy existed have zeroes in its place
+    let mut manager = <BondManager as bytemuck::Zeroable>::zeroed();
+    assert_eq!(manager.crank_policy(), CrankPolicy::Authorized);
+
+    manager.crank_policy = CrankPolicy::Permissionless as u8;
+    assert_eq!(manager.crank_policy(), CrankPolicy::Permissionless);
+
+    // unknown policies fall back to the restrictive one
+    manager.crank_policy = 7;
+    assert_eq!(manager.crank_policy(), CrankPolicy::Authorized);
+}
+
+#[test]
+fn crank_policy_is_carved_from_reserved_space() {
+    // the layout of existing accounts is unchanged
+    assert_eq!(
+        std::mem::size_of::<BondManager>(),
+        8 + 12 * 32 + 32 + 1 + 1 + 1 + 28 + 8 + 8
+    );
+
+    // the policy is the first byte after the bump and the pause flags
+    let mut bytes = vec![0u8; std::mem::size_of::<BondManager>()];
+    bytes[8 + 12 * 32 + 32 + 3] = CrankPolicy::Permissionless as u8;
+    let manager: &BondManager = bytemuck::from_bytes(&bytes);
+    assert_eq!(manager.crank_policy(), CrankPolicy::Permissionless);
+}
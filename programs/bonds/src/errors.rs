@@ -108,4 +108,18 @@ pub enum BondsError {
     WrongVault,
     #[msg("attempted to divide with zero")]
     ZeroDivision,
+    #[msg("immediate-or-cancel order could not be matched within its limit price")]
+    SlippageExceeded,
+    #[msg("the good-til-time of an order must be in the future")]
+    InvalidOrderExpiry,
+    #[msg("the order has not reached its good-til-time and cannot be pruned")]
+    OrderNotExpired,
+    #[msg("orders posted by a replacement must be post-only")]
+    ReplacementNotPostOnly,
+    #[msg("the duration of a bond market must be positive")]
+    InvalidDuration,
+    #[msg("the airspace permit does not belong to the owner of the margin account")]
+    WrongPermit,
+    #[msg("too many expired orders would be matched, they must be pruned first")]
+    TooManyExpiredOrders,
 }
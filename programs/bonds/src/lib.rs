@@ -31,6 +31,8 @@
 //!     post_allowed: true,
 //!     /// stake generated tickets automatically, creating `SplitTicket`s
 //!     auto_stake: true,
+//!     /// keep the order on the book until it is filled or cancelled
+//!     good_til_time: None,
 //! }
 //!```
 //!
@@ -59,6 +61,8 @@
 //!     post_allowed: true,
 //!     /// borrowers do not stake tickets
 //!     auto_stake: false,
+//!     /// allow the order to be pruned from the book after a day
+//!     good_til_time: Some(now + 86_400),
 //! }
 //! ```
 //!
@@ -113,6 +117,9 @@ pub mod orderbook;
 /// Program instructions and structs related to the redeemable bond tickets
 pub mod tickets;
 
+#[cfg(feature = "cpi")]
+pub mod views;
+
 mod errors;
 pub mod events;
 pub use errors::BondsError;
@@ -129,6 +136,7 @@ use instructions::*;
 extern crate bitflags;
 
 use anchor_lang::prelude::*;
+use control::state::CrankPolicy;
 use orderbook::state::OrderParams;
 
 declare_id!("JBond79m9K6HqYwngCjiJHb311GTXggo46kGcT2GijUc");
@@ -168,14 +176,40 @@ pub mod jet_bonds {
         jet_bonds::instructions::initialize_orderbook::handler(ctx, params)
     }
 
-    /// Modify a `BondManager` account
+    /// Pause or resume the redemption of staked tickets
     /// Authority use only
-    pub fn modify_bond_manager(
-        ctx: Context<ModifyBondManager>,
-        data: Vec<u8>,
-        offset: usize,
-    ) -> Result<()> {
-        jet_bonds::instructions::modify_bond_manager::handler(ctx, data, offset)
+    pub fn set_tickets_paused(ctx: Context<ModifyBondManager>, paused: bool) -> Result<()> {
+        jet_bonds::instructions::set_tickets_paused::handler(ctx, paused)
+    }
+
+    /// Set the oracle that values the underlying token
+    /// Authority use only
+    pub fn set_underlying_oracle(ctx: Context<SetOracle>) -> Result<()> {
+        jet_bonds::instructions::set_underlying_oracle::handler(ctx)
+    }
+
+    /// Set the oracle that values the bond tickets
+    /// Authority use only
+    pub fn set_ticket_oracle(ctx: Context<SetOracle>) -> Result<()> {
+        jet_bonds::instructions::set_ticket_oracle::handler(ctx)
+    }
+
+    /// Set the tenor applied to orders filled from now on
+    /// Authority use only
+    pub fn set_duration(ctx: Context<ModifyBondManager>, duration: i64) -> Result<()> {
+        jet_bonds::instructions::set_duration::handler(ctx, duration)
+    }
+
+    /// Set who may consume events from the orderbook
+    /// Authority use only
+    pub fn set_crank_policy(ctx: Context<ModifyBondManager>, policy: CrankPolicy) -> Result<()> {
+        jet_bonds::instructions::set_crank_policy::handler(ctx, policy)
+    }
+
+    /// Set the minimum airspace permit tier needed to create a margin user in the market
+    /// Authority use only
+    pub fn set_min_permit_tier(ctx: Context<ModifyBondManager>, tier: u8) -> Result<()> {
+        jet_bonds::instructions::set_min_permit_tier::handler(ctx, tier)
     }
 
     /// Pause matching of orders placed in the orderbook
@@ -213,6 +247,14 @@ pub mod jet_bonds {
         jet_bonds::instructions::margin_borrow_order::handler(ctx, params, seed)
     }
 
+    /// Atomically cancels a set of margin borrow orders and posts a new set of post-only borrow orders
+    pub fn margin_replace_orders(
+        ctx: Context<MarginReplaceOrders>,
+        params: MarginReplaceOrdersParams,
+    ) -> Result<()> {
+        jet_bonds::instructions::margin_replace_orders::handler(ctx, params)
+    }
+
     /// Refresh the associated margin account `claims` for a given `MarginUser` account
     pub fn refresh_position(ctx: Context<RefreshPosition>, expect_price: bool) -> Result<()> {
         jet_bonds::instructions::refresh_position::handler(ctx, expect_price)
@@ -247,11 +289,35 @@ pub mod jet_bonds {
         jet_bonds::instructions::cancel_order::handler(ctx, order_id)
     }
 
+    /// Cancels all of a user's orders on the book, returning the posted funds
+    pub fn cancel_all_orders<'a, 'b, 'info>(
+        ctx: Context<'a, 'b, 'b, 'info, CancelAllOrders<'info>>,
+    ) -> Result<()> {
+        jet_bonds::instructions::cancel_all_orders::handler(ctx)
+    }
+
+    /// Atomically cancels a set of orders and posts a new set of post-only orders
+    pub fn replace_orders(ctx: Context<ReplaceOrders>, params: ReplaceOrdersParams) -> Result<()> {
+        jet_bonds::instructions::replace_orders::handler(ctx, params)
+    }
+
     /// Place a `Lend` order to the book by depositing tokens
     pub fn lend_order(ctx: Context<LendOrder>, params: OrderParams, seed: Vec<u8>) -> Result<()> {
         jet_bonds::instructions::lend_order::handler(ctx, params, seed)
     }
 
+    /// Remove orders that are past their good-til-time from the book
+    /// Permissionless, the posted funds are returned to each order's owner
+    ///
+    /// Expired orders are also removed when an order would be matched against them, in which
+    /// case the funds are returned when the crank consumes the resulting out events.
+    pub fn prune_expired_orders<'a, 'b, 'info>(
+        ctx: Context<'a, 'b, 'b, 'info, PruneExpiredOrders<'info>>,
+        order_ids: Vec<u128>,
+    ) -> Result<()> {
+        jet_bonds::instructions::prune_expired_orders::handler(ctx, order_ids)
+    }
+
     /// Crank specific instruction, processes the event queue
     pub fn consume_events<'a, 'b, 'info>(
         ctx: Context<'a, 'b, 'b, 'info, ConsumeEvents<'info>>,
@@ -321,6 +387,26 @@ pub mod jet_bonds {
     //
     // =============================================
     //
+
+    //
+    // View Instructions
+    // =============================================
+    //
+
+    /// Get the best bid and ask on the orderbook
+    /// Does not modify any accounts, the `OrderbookQuote` is set as the return data
+    pub fn orderbook_quote(ctx: Context<OrderbookQuoteView>) -> Result<()> {
+        jet_bonds::instructions::orderbook_quote::handler(ctx)
+    }
+
+    /// Get the debt and obligations due for a margin user
+    /// Does not modify any accounts, the `MarginUserDebt` is set as the return data
+    pub fn margin_user_debt(ctx: Context<MarginUserDebtView>) -> Result<()> {
+        jet_bonds::instructions::margin_user_debt::handler(ctx)
+    }
+    //
+    // =============================================
+    //
 }
 
 pub mod seeds {
@@ -1,6 +1,7 @@
 use anchor_lang::prelude::*;
 use anchor_spl::token::{accessor::mint, Mint, Token, TokenAccount};
-use jet_margin::{AdapterResult, PositionChange};
+use jet_airspace::state::AirspacePermit;
+use jet_margin::{AdapterResult, MarginAccount, PositionChange};
 
 use crate::{
     control::state::BondManager,
@@ -80,9 +81,24 @@ pub struct InitializeMarginUser<'info> {
 
     /// Token metadata account needed by the margin program to register the claim position
     pub claims_metadata: AccountInfo<'info>,
+
+    /// The permit allowing the owner of the margin account to use the market's airspace
+    #[account(constraint = permit.airspace == bond_manager.load().unwrap().airspace @ BondsError::WrongAirspace)]
+    pub permit: Box<Account<'info, AirspacePermit>>,
 }
 
 pub fn handler(ctx: Context<InitializeMarginUser>) -> Result<()> {
+    let margin_account =
+        AccountLoader::<MarginAccount>::try_from(&ctx.accounts.margin_account.to_account_info())?;
+    require_keys_eq!(
+        ctx.accounts.permit.owner,
+        margin_account.load()?.owner,
+        BondsError::WrongPermit
+    );
+    ctx.accounts
+        .permit
+        .verify(ctx.accounts.bond_manager.load()?.min_permit_tier)?;
+
     let user = &mut ctx.accounts.borrower_account;
 
     require_eq!(
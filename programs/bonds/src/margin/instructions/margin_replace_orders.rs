@@ -0,0 +1,146 @@
+use agnostic_orderbook::state::Side;
+use anchor_lang::prelude::*;
+use anchor_spl::token::Token;
+use jet_margin::{AdapterResult, PositionChange};
+
+use crate::{
+    margin::{
+        events::MarginBorrow,
+        state::{return_to_margin, MarginUser},
+    },
+    orderbook::state::*,
+    serialization::RemainingAccounts,
+    utils::{burn, mint_to},
+    BondsError,
+};
+
+/// Parameters for an atomic replacement of a margin account's borrow orders
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, Default)]
+pub struct MarginReplaceOrdersParams {
+    /// The borrow orders to remove from the book before posting the new orders
+    pub cancel_order_ids: Vec<u128>,
+    /// New borrow orders, must be `post_only`
+    pub asks: Vec<OrderParams>,
+}
+
+#[derive(Accounts)]
+pub struct MarginReplaceOrders<'info> {
+    /// The account tracking borrower debts
+    #[account(
+        mut,
+        has_one = margin_account,
+        has_one = claims @ BondsError::WrongClaimAccount,
+    )]
+    pub borrower_account: Box<Account<'info, MarginUser>>,
+
+    /// The margin account that owns the orders
+    pub margin_account: Signer<'info>,
+
+    /// Token account used by the margin program to track the debt that must be collateralized
+    /// CHECK: borrower_account
+    #[account(mut)]
+    pub claims: UncheckedAccount<'info>,
+
+    /// Token mint used by the margin program to track the debt that must be collateralized
+    /// CHECK: address constraint
+    #[account(mut, address = orderbook_mut.bond_manager.load().unwrap().claims_mint @ BondsError::WrongClaimMint)]
+    pub claims_mint: UncheckedAccount<'info>,
+
+    pub orderbook_mut: OrderbookMut<'info>,
+
+    pub token_program: Program<'info, Token>,
+    // Optional event adapter account
+    // pub event_adapter: AccountInfo<'info>,
+}
+
+/// cancel a set of borrow orders and post a new set of borrow orders in a single step
+pub fn handler(ctx: Context<MarginReplaceOrders>, params: MarginReplaceOrdersParams) -> Result<()> {
+    let margin_account = ctx.accounts.margin_account.key();
+    let borrower_account = ctx.accounts.borrower_account.key();
+
+    let mut cancelled_quote_qty = 0u64;
+    for order_id in params.cancel_order_ids {
+        let (side, info, order_summary) = ctx
+            .accounts
+            .orderbook_mut
+            .cancel_order(order_id, margin_account)?;
+        require!(
+            matches!(side, Side::Ask)
+                && info.flags.contains(CallbackFlags::NEW_DEBT)
+                && info.out_account == borrower_account,
+            BondsError::WrongUserAccount
+        );
+
+        ctx.accounts
+            .borrower_account
+            .debt
+            .cancel_borrow_order(order_summary.total_quote_qty)?;
+        cancelled_quote_qty = cancelled_quote_qty
+            .checked_add(order_summary.total_quote_qty)
+            .ok_or(BondsError::ArithmeticOverflow)?;
+    }
+
+    let adapter = ctx
+        .remaining_accounts
+        .iter()
+        .maybe_next_adapter()?
+        .map(|a| a.key());
+
+    let mut posted_quote_qty = 0u64;
+    for order in params.asks {
+        require!(order.post_only, BondsError::ReplacementNotPostOnly);
+        let limit_price = order.limit_price;
+        let (_, order_summary) = ctx.accounts.orderbook_mut.place_order(
+            margin_account,
+            Side::Ask,
+            order,
+            borrower_account,
+            borrower_account,
+            adapter,
+            CallbackFlags::NEW_DEBT | CallbackFlags::MARGIN,
+        )?;
+
+        ctx.accounts
+            .borrower_account
+            .debt
+            .post_borrow_order(order_summary.quote_posted(limit_price))?;
+        posted_quote_qty = posted_quote_qty
+            .checked_add(order_summary.total_quote_qty)
+            .ok_or(BondsError::ArithmeticOverflow)?;
+        emit!(MarginBorrow {
+            bond_manager: ctx.accounts.orderbook_mut.bond_manager.key(),
+            margin_account,
+            borrower_account,
+            order_summary,
+        });
+    }
+
+    // only the net change in pending debt is reflected in the claims
+    if posted_quote_qty > cancelled_quote_qty {
+        mint_to!(
+            ctx,
+            claims_mint,
+            claims,
+            posted_quote_qty - cancelled_quote_qty,
+            orderbook_mut
+        )?;
+    } else if cancelled_quote_qty > posted_quote_qty {
+        burn!(
+            ctx,
+            claims_mint,
+            claims,
+            cancelled_quote_qty - posted_quote_qty,
+            orderbook_mut
+        )?;
+    }
+
+    return_to_margin(
+        &ctx.accounts.margin_account.to_account_info(),
+        &AdapterResult {
+            position_changes: vec![(
+                ctx.accounts.claims_mint.key(),
+                vec![PositionChange::Register(ctx.accounts.claims.key())],
+            )],
+        },
+    )
+}
@@ -0,0 +1,44 @@
+use anchor_lang::{prelude::*, solana_program::program::set_return_data};
+
+use crate::margin::state::{MarginUser, ObligationSequenceNumber};
+
+/// The debt owed by a margin user, as returned by the `margin_user_debt` instruction
+#[derive(AnchorSerialize, AnchorDeserialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MarginUserDebt {
+    /// Amount of underlying tokens posted in borrow orders that have not been filled
+    pub pending: u64,
+    /// Amount of underlying tokens owed for filled borrow orders, including past due debt
+    pub committed: u64,
+    /// The number of obligations that have not been fully repaid
+    pub outstanding_obligations: u64,
+    /// The sequence number of the next obligation that must be repaid, if any
+    pub next_obligation_to_repay: Option<ObligationSequenceNumber>,
+    /// The maturation timestamp of the next obligation that must be repaid
+    pub next_obligation_maturity: UnixTimestamp,
+    /// Whether the next obligation is past its maturity
+    pub is_past_due: bool,
+}
+
+#[derive(Accounts)]
+pub struct MarginUserDebtView<'info> {
+    /// The account tracking information related to this particular user
+    pub margin_user: Account<'info, MarginUser>,
+}
+
+/// returns the debt and obligations due for a margin user, without modifying any accounts
+pub fn handler(ctx: Context<MarginUserDebtView>) -> Result<()> {
+    let debt = &ctx.accounts.margin_user.debt;
+
+    let summary = MarginUserDebt {
+        pending: debt.pending(),
+        committed: debt.committed(),
+        outstanding_obligations: debt.outstanding_obligations(),
+        next_obligation_to_repay: debt.next_obligation_to_repay(),
+        next_obligation_maturity: debt.next_obligation_maturity(),
+        is_past_due: debt.is_past_due(),
+    };
+
+    set_return_data(&summary.try_to_vec()?);
+
+    Ok(())
+}
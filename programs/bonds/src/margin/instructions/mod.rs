@@ -1,11 +1,15 @@
 pub mod initialize_margin_user;
 pub mod margin_borrow_order;
+pub mod margin_replace_orders;
+pub mod margin_user_debt;
 pub mod refresh_position;
 pub mod repay;
 pub mod settle;
 
 pub use initialize_margin_user::*;
 pub use margin_borrow_order::*;
+pub use margin_replace_orders::*;
+pub use margin_user_debt::*;
 pub use refresh_position::*;
 pub use repay::*;
 pub use settle::*;
@@ -69,10 +69,25 @@ impl Debt {
         }
     }
 
-    fn outstanding_obligations(&self) -> u64 {
+    pub fn outstanding_obligations(&self) -> u64 {
         self.next_new_obligation_seqno - self.next_unpaid_obligation_seqno
     }
 
+    /// Amount posted in borrow orders that have not been filled
+    pub fn pending(&self) -> u64 {
+        self.pending
+    }
+
+    /// Amount borrowed through filled orders that has not been repaid
+    pub fn committed(&self) -> u64 {
+        self.committed
+    }
+
+    /// The maturation timestamp of the next unpaid obligation
+    pub fn next_obligation_maturity(&self) -> UnixTimestamp {
+        self.next_obligation_maturity
+    }
+
     pub fn post_borrow_order(&mut self, posted_amount: u64) -> Result<()> {
         self.pending.try_add_assign(posted_amount)
     }
@@ -93,7 +108,7 @@ impl Debt {
     }
 
     pub fn cancel_borrow_order(&mut self, amount: u64) -> Result<()> {
-        self.pending.try_add_assign(amount)
+        self.pending.try_sub_assign(amount)
     }
 
     pub fn new_obligation_from_fill(
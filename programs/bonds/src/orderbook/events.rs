@@ -8,6 +8,14 @@ pub struct OrderCancelled {
     pub order_id: u128,
 }
 
+#[event]
+pub struct OrderExpired {
+    pub bond_manager: Pubkey,
+    pub user: Pubkey,
+    pub order_id: u128,
+    pub expired_at: i64,
+}
+
 #[event]
 pub struct LendOrder {
     pub bond_market: Pubkey,
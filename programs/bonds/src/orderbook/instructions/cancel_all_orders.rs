@@ -0,0 +1,47 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{Mint, Token, TokenAccount};
+
+use crate::{
+    orderbook::{instructions::refund::refund_order, state::OrderbookMut},
+    BondsError,
+};
+
+#[derive(Accounts)]
+pub struct CancelAllOrders<'info> {
+    /// The owner of the orders. For margin orders, this is the margin account
+    pub owner: Signer<'info>,
+
+    pub orderbook_mut: OrderbookMut<'info>,
+
+    /// The market token vault
+    #[account(mut, address = orderbook_mut.bond_manager.load().unwrap().underlying_token_vault.key() @ BondsError::WrongVault)]
+    pub underlying_token_vault: Account<'info, TokenAccount>,
+
+    /// The market ticket mint
+    #[account(mut, address = orderbook_mut.bond_manager.load().unwrap().bond_ticket_mint.key() @ BondsError::WrongTicketMint)]
+    pub bond_ticket_mint: Account<'info, Mint>,
+
+    /// Token mint used by the margin program to track the debt that must be collateralized
+    /// CHECK: address constraint
+    #[account(mut, address = orderbook_mut.bond_manager.load().unwrap().claims_mint @ BondsError::WrongClaimMint)]
+    pub claims_mint: UncheckedAccount<'info>,
+
+    pub token_program: Program<'info, Token>,
+    // remaining_accounts: every distinct `out_account` of the owner's orders.
+    // token accounts for lend and sell orders, or the `MarginUser` and its claims account
+    // for margin borrow orders
+}
+
+/// remove all of the owner's orders from the book, and return the posted funds
+pub fn handler<'info>(ctx: Context<'_, '_, '_, 'info, CancelAllOrders<'info>>) -> Result<()> {
+    let owner = ctx.accounts.owner.key();
+
+    for order_id in ctx.accounts.orderbook_mut.orders_owned_by(owner)? {
+        let (side, info, order_summary) =
+            ctx.accounts.orderbook_mut.cancel_order(order_id, owner)?;
+
+        refund_order!(ctx, side, &info, &order_summary, ctx.remaining_accounts)?;
+    }
+
+    Ok(())
+}
@@ -4,7 +4,7 @@ use anchor_lang::prelude::*;
 use anchor_spl::token::Token;
 
 use crate::{
-    control::state::{BondManager, CrankAuthorization},
+    control::state::{BondManager, CrankAuthorization, CrankPolicy},
     margin::state::{MarginUser, Obligation},
     orderbook::state::EventQueue,
     serialization::{AnchorAccount, Mut},
@@ -39,11 +39,9 @@ pub struct ConsumeEvents<'info> {
     #[account(mut)]
     pub event_queue: AccountInfo<'info>,
 
-    #[account(
-        has_one = crank @ BondsError::WrongCrankAuthority,
-        constraint = crank_authorization.airspace == bond_manager.load()?.airspace @ BondsError::WrongAirspaceAuthorization
-    )]
-    pub crank_authorization: Account<'info, CrankAuthorization>,
+    /// The `CrankAuthorization` of the crank, if required by the crank policy of the market
+    /// CHECK: ConsumeEvents::verify_crank
+    pub crank_authorization: AccountInfo<'info>,
     pub crank: Signer<'info>,
 
     /// The account paying rent for PDA initialization
@@ -54,6 +52,30 @@ pub struct ConsumeEvents<'info> {
     // remaining_accounts: [EventAccounts],
 }
 
+impl<'info> ConsumeEvents<'info> {
+    /// ensure the crank is allowed to consume events under the crank policy of the market
+    pub fn verify_crank(&self) -> Result<()> {
+        let manager = self.bond_manager.load()?;
+        if manager.crank_policy() == CrankPolicy::Permissionless {
+            return Ok(());
+        }
+
+        let authorization = Account::<CrankAuthorization>::try_from(&self.crank_authorization)?;
+        require_keys_eq!(
+            authorization.crank,
+            self.crank.key(),
+            BondsError::WrongCrankAuthority
+        );
+        require_keys_eq!(
+            authorization.airspace,
+            manager.airspace,
+            BondsError::WrongAirspaceAuthorization
+        );
+
+        Ok(())
+    }
+}
+
 /// These are the additional accounts that need to be provided in the ix
 /// for every event that will be processed.
 /// For a fill, 2-6 accounts need to be appended to remaining_accounts
@@ -25,6 +25,7 @@ pub fn handler<'info>(
     num_events: u32,
     seeds: Vec<Vec<u8>>,
 ) -> Result<()> {
+    ctx.accounts.verify_crank()?;
     let duration = ctx.accounts.bond_manager.load()?.duration;
 
     let mut num_iters = 0;
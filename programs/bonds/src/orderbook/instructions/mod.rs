@@ -1,11 +1,20 @@
+pub mod cancel_all_orders;
 pub mod cancel_order;
 pub mod consume_events;
 pub mod event_adapter;
 pub mod lend_order;
+pub mod orderbook_quote;
+pub mod prune_expired_orders;
+pub mod refund;
+pub mod replace_orders;
 pub mod sell_tickets_order;
 
+pub use cancel_all_orders::*;
 pub use cancel_order::*;
 pub use consume_events::*;
 pub use event_adapter::*;
 pub use lend_order::*;
+pub use orderbook_quote::*;
+pub use prune_expired_orders::*;
+pub use replace_orders::*;
 pub use sell_tickets_order::*;
@@ -0,0 +1,69 @@
+use agnostic_orderbook::state::{critbit::Slab, AccountTag};
+use anchor_lang::{prelude::*, solana_program::program::set_return_data};
+
+use crate::{control::state::BondManager, orderbook::state::CallbackInfo, BondsError};
+
+/// The total size of all orders at a price on one side of the book
+#[derive(AnchorSerialize, AnchorDeserialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct QuoteLevel {
+    /// The limit price of the orders, as an fp32 number of underlying tokens per ticket
+    pub price: u64,
+    /// The total number of tickets in all orders at this price
+    pub quantity: u64,
+}
+
+/// The best prices currently available in a bond market, as returned by the
+/// `orderbook_quote` instruction
+#[derive(AnchorSerialize, AnchorDeserialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct OrderbookQuote {
+    /// The highest price any lender is willing to pay for tickets
+    pub best_bid: Option<QuoteLevel>,
+    /// The lowest price any borrower is willing to sell tickets for
+    pub best_ask: Option<QuoteLevel>,
+}
+
+#[derive(Accounts)]
+pub struct OrderbookQuoteView<'info> {
+    /// The `BondManager` account tracks global information related to this particular bond market
+    #[account(
+        has_one = bids @ BondsError::WrongBids,
+        has_one = asks @ BondsError::WrongAsks,
+    )]
+    pub bond_manager: AccountLoader<'info, BondManager>,
+
+    /// CHECK: has_one
+    pub bids: AccountInfo<'info>,
+
+    /// CHECK: has_one
+    pub asks: AccountInfo<'info>,
+}
+
+/// returns the best bid and ask on the book, without modifying any accounts
+pub fn handler(ctx: Context<OrderbookQuoteView>) -> Result<()> {
+    let quote = OrderbookQuote {
+        best_bid: best_level(&ctx.accounts.bids, AccountTag::Bids, false)?,
+        best_ask: best_level(&ctx.accounts.asks, AccountTag::Asks, true)?,
+    };
+
+    set_return_data(&quote.try_to_vec()?);
+
+    Ok(())
+}
+
+fn best_level(side: &AccountInfo, tag: AccountTag, ascending: bool) -> Result<Option<QuoteLevel>> {
+    // the slab can only be loaded from a mutable buffer, but nothing is written to it
+    let mut buf = side.data.borrow_mut();
+    let mut orders = Slab::<CallbackInfo>::from_buffer(&mut buf, tag)?.into_iter(ascending);
+
+    let best = match orders.next() {
+        Some(leaf) => leaf,
+        None => return Ok(None),
+    };
+    let price = best.price();
+    let quantity = std::iter::once(best)
+        .chain(orders.take_while(|leaf| leaf.price() == price))
+        .map(|leaf| leaf.base_quantity)
+        .fold(0u64, u64::saturating_add);
+
+    Ok(Some(QuoteLevel { price, quantity }))
+}
@@ -0,0 +1,48 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{Mint, Token, TokenAccount};
+
+use crate::{
+    orderbook::{instructions::refund::refund_order, state::OrderbookMut},
+    BondsError,
+};
+
+#[derive(Accounts)]
+pub struct PruneExpiredOrders<'info> {
+    pub orderbook_mut: OrderbookMut<'info>,
+
+    /// The market token vault
+    #[account(mut, address = orderbook_mut.bond_manager.load().unwrap().underlying_token_vault.key() @ BondsError::WrongVault)]
+    pub underlying_token_vault: Account<'info, TokenAccount>,
+
+    /// The market ticket mint
+    #[account(mut, address = orderbook_mut.bond_manager.load().unwrap().bond_ticket_mint.key() @ BondsError::WrongTicketMint)]
+    pub bond_ticket_mint: Account<'info, Mint>,
+
+    /// Token mint used by the margin program to track the debt that must be collateralized
+    /// CHECK: address constraint
+    #[account(mut, address = orderbook_mut.bond_manager.load().unwrap().claims_mint @ BondsError::WrongClaimMint)]
+    pub claims_mint: UncheckedAccount<'info>,
+
+    pub token_program: Program<'info, Token>,
+    // remaining_accounts: the `out_account` of each pruned order, and the claims account of
+    // each `MarginUser` with a pruned borrow order
+}
+
+/// remove orders past their good-til-time from the book, and return the posted funds to their owners
+pub fn handler<'info>(
+    ctx: Context<'_, '_, '_, 'info, PruneExpiredOrders<'info>>,
+    order_ids: Vec<u128>,
+) -> Result<()> {
+    let now = Clock::get()?.unix_timestamp;
+
+    for order_id in order_ids {
+        let (side, info, order_summary) = ctx
+            .accounts
+            .orderbook_mut
+            .prune_expired_order(order_id, now)?;
+
+        refund_order!(ctx, side, &info, &order_summary, ctx.remaining_accounts)?;
+    }
+
+    Ok(())
+}
@@ -0,0 +1,78 @@
+use anchor_lang::prelude::*;
+
+use crate::BondsError;
+
+use super::UserAccount;
+
+/// Return the funds that were posted with an order, once the order has been removed from
+/// the book: the underlying of a bid, or the tickets of an ask. Borrow orders release the
+/// pending debt of the `MarginUser` and burn the claims that were minted for the order.
+///
+/// The accounts of `$ctx` must include `orderbook_mut`, `underlying_token_vault`,
+/// `bond_ticket_mint`, `claims_mint` and `token_program`. `$accounts` must contain the
+/// `out_account` of the order, and the claims account of the `MarginUser` for borrow orders.
+macro_rules! refund_order {
+    ($ctx:ident, $side:expr, $info:expr, $order_summary:expr, $accounts:expr) => {{
+        let info: &crate::orderbook::state::CallbackInfo = $info;
+        let order_summary: &agnostic_orderbook::state::OrderSummary = $order_summary;
+        let user = crate::orderbook::instructions::refund::find_user_account(
+            $accounts,
+            &info.out_account,
+        )?;
+
+        match $side {
+            agnostic_orderbook::state::Side::Bid => crate::utils::withdraw!(
+                $ctx,
+                underlying_token_vault,
+                user.as_token_account(),
+                order_summary.total_quote_qty,
+                orderbook_mut
+            ),
+            agnostic_orderbook::state::Side::Ask
+                if info
+                    .flags
+                    .contains(crate::orderbook::state::CallbackFlags::NEW_DEBT) =>
+            {
+                let mut margin_user = user.margin_user()?;
+                margin_user
+                    .debt
+                    .cancel_borrow_order(order_summary.total_quote_qty)?;
+                let claims = crate::orderbook::instructions::refund::find_user_account(
+                    $accounts,
+                    &margin_user.claims,
+                )?;
+
+                crate::utils::burn!(
+                    $ctx,
+                    claims_mint,
+                    claims.as_token_account(),
+                    order_summary.total_quote_qty,
+                    orderbook_mut
+                )
+            }
+            agnostic_orderbook::state::Side::Ask => crate::utils::mint_to!(
+                $ctx,
+                bond_ticket_mint,
+                user.as_token_account(),
+                order_summary.total_base_qty,
+                orderbook_mut
+            ),
+        }
+    }};
+}
+pub(crate) use refund_order;
+
+/// Find the account matching an order's `out_account` among the accounts provided by the caller
+pub fn find_user_account<'info>(
+    accounts: &[AccountInfo<'info>],
+    out_account: &Pubkey,
+) -> Result<UserAccount<'info>> {
+    accounts
+        .iter()
+        .find(|account| account.key == out_account)
+        .map(|account| UserAccount::new(account.clone()))
+        .ok_or_else(|| {
+            msg!("missing out account {} for a cancelled order", out_account);
+            error!(BondsError::WrongUserAccount)
+        })
+}
@@ -0,0 +1,174 @@
+use agnostic_orderbook::state::Side;
+use anchor_lang::prelude::*;
+use anchor_spl::token::{accessor::mint, transfer, Mint, Token, TokenAccount};
+
+use crate::{
+    orderbook::state::*,
+    serialization::RemainingAccounts,
+    utils::{mint_to, transfer_context, withdraw},
+    BondsError,
+};
+
+/// Parameters for an atomic replacement of a user's resting orders
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, Default)]
+pub struct ReplaceOrdersParams {
+    /// The orders to remove from the book before posting the new orders
+    pub cancel_order_ids: Vec<u128>,
+    /// New lend orders, must be `post_only`
+    pub bids: Vec<OrderParams>,
+    /// New ticket sale orders, must be `post_only`
+    pub asks: Vec<OrderParams>,
+}
+
+#[derive(Accounts)]
+pub struct ReplaceOrders<'info> {
+    /// The owner of the orders, and signing authority over the token and ticket vaults
+    pub user: Signer<'info>,
+
+    /// Receives refunds for cancelled asks, and pays for new asks
+    #[account(mut, constraint =
+        mint(&user_ticket_vault.to_account_info()).unwrap()
+        == bond_ticket_mint.key() @ BondsError::WrongTicketMint
+    )]
+    pub user_ticket_vault: Account<'info, TokenAccount>,
+
+    /// Receives refunds for cancelled bids, and pays for new bids
+    #[account(mut, constraint =
+        mint(&user_token_vault.to_account_info()).unwrap()
+        == orderbook_mut.bond_manager.load().unwrap().underlying_token_mint.key() @ BondsError::WrongUnderlyingTokenMint
+    )]
+    pub user_token_vault: Account<'info, TokenAccount>,
+
+    pub orderbook_mut: OrderbookMut<'info>,
+
+    /// The market token vault
+    #[account(mut, address = orderbook_mut.bond_manager.load().unwrap().underlying_token_vault.key() @ BondsError::WrongVault)]
+    pub underlying_token_vault: Account<'info, TokenAccount>,
+
+    /// The market ticket mint
+    #[account(mut, address = orderbook_mut.bond_manager.load().unwrap().bond_ticket_mint.key() @ BondsError::WrongTicketMint)]
+    pub bond_ticket_mint: Account<'info, Mint>,
+
+    pub token_program: Program<'info, Token>,
+    // Optional event adapter account
+    // pub event_adapter: AccountInfo<'info>,
+}
+
+/// cancel a set of orders and post a new set of orders in a single step
+pub fn handler(ctx: Context<ReplaceOrders>, params: ReplaceOrdersParams) -> Result<()> {
+    let user = ctx.accounts.user.key();
+
+    for order_id in params.cancel_order_ids {
+        let (side, info, order_summary) =
+            ctx.accounts.orderbook_mut.cancel_order(order_id, user)?;
+
+        match side {
+            Side::Bid => {
+                require_keys_eq!(
+                    info.out_account,
+                    ctx.accounts.user_token_vault.key(),
+                    BondsError::WrongUserAccount
+                );
+                withdraw!(
+                    ctx,
+                    underlying_token_vault,
+                    user_token_vault,
+                    order_summary.total_quote_qty,
+                    orderbook_mut
+                )?;
+            }
+            Side::Ask => {
+                require_keys_eq!(
+                    info.out_account,
+                    ctx.accounts.user_ticket_vault.key(),
+                    BondsError::WrongUserAccount
+                );
+                mint_to!(
+                    ctx,
+                    bond_ticket_mint,
+                    user_ticket_vault,
+                    order_summary.total_base_qty,
+                    orderbook_mut
+                )?;
+            }
+        }
+    }
+
+    let adapter = ctx
+        .remaining_accounts
+        .iter()
+        .maybe_next_adapter()?
+        .map(|a| a.key());
+
+    let mut bid_quote_qty = 0u64;
+    for order in params.bids {
+        require!(order.post_only, BondsError::ReplacementNotPostOnly);
+        let (_, order_summary) = ctx.accounts.orderbook_mut.place_order(
+            user,
+            Side::Bid,
+            order,
+            if order.auto_stake {
+                user
+            } else {
+                ctx.accounts.user_ticket_vault.key()
+            },
+            ctx.accounts.user_token_vault.key(),
+            adapter,
+            CallbackFlags::empty(),
+        )?;
+
+        bid_quote_qty = bid_quote_qty
+            .checked_add(order_summary.total_quote_qty)
+            .ok_or(BondsError::ArithmeticOverflow)?;
+        emit!(crate::events::LendOrder {
+            bond_market: ctx.accounts.orderbook_mut.bond_manager.key(),
+            lender: user,
+            order_summary,
+        });
+    }
+
+    let mut ask_base_qty = 0u64;
+    for order in params.asks {
+        require!(order.post_only, BondsError::ReplacementNotPostOnly);
+        let (_, order_summary) = ctx.accounts.orderbook_mut.place_order(
+            user,
+            Side::Ask,
+            order,
+            ctx.accounts.user_token_vault.key(),
+            ctx.accounts.user_ticket_vault.key(),
+            adapter,
+            CallbackFlags::empty(),
+        )?;
+
+        ask_base_qty = ask_base_qty
+            .checked_add(order_summary.total_base_qty)
+            .ok_or(BondsError::ArithmeticOverflow)?;
+        emit!(crate::events::SellTicketsOrder {
+            bond_market: ctx.accounts.orderbook_mut.bond_manager.key(),
+            borrower: user,
+            order_summary,
+        });
+    }
+
+    if bid_quote_qty > 0 {
+        transfer(
+            transfer_context!(ctx, underlying_token_vault, user_token_vault, user),
+            bid_quote_qty,
+        )?;
+    }
+    if ask_base_qty > 0 {
+        anchor_spl::token::burn(
+            CpiContext::new(
+                ctx.accounts.token_program.to_account_info(),
+                anchor_spl::token::Burn {
+                    mint: ctx.accounts.bond_ticket_mint.to_account_info(),
+                    from: ctx.accounts.user_ticket_vault.to_account_info(),
+                    authority: ctx.accounts.user.to_account_info(),
+                },
+            ),
+            ask_base_qty,
+        )?;
+    }
+
+    Ok(())
+}
@@ -1,9 +1,12 @@
 use crate::{
-    control::state::BondManager, events::OrderCancelled, utils::orderbook_accounts, BondsError,
+    control::state::BondManager,
+    events::{OrderCancelled, OrderExpired},
+    utils::orderbook_accounts,
+    BondsError,
 };
 use agnostic_orderbook::{
     instruction::cancel_order,
-    state::{critbit::Slab, get_side_from_order_id, Side},
+    state::{critbit::Slab, get_side_from_order_id, AccountTag, Side},
 };
 use agnostic_orderbook::{
     instruction::new_order,
@@ -20,11 +23,14 @@ use anchor_lang::{
 use bytemuck::{CheckedBitPattern, NoUninit, Pod, Zeroable};
 use num_derive::FromPrimitive;
 use num_traits::FromPrimitive;
-use std::convert::TryInto;
+use std::convert::{TryFrom, TryInto};
 
 /// The tick_size used in fp32 operations on the orderbook
 pub const TICK_SIZE: u64 = 1;
 
+/// The most expired orders that may be removed from the book while placing a single order
+pub const MAX_EXPIRED_ORDERS_REMOVED: usize = 8;
+
 /// Find the len of the byteslab representing an orderbook side, given the maximum number of orders
 pub const fn orderbook_slab_len(capacity: usize) -> usize {
     capacity * (LeafNode::LEN + CallbackInfo::LEN + InnerNode::LEN)
@@ -80,6 +86,12 @@ impl<'info> OrderbookMut<'info> {
         adapter: Option<Pubkey>,
         extra_flags: CallbackFlags,
     ) -> Result<(CallbackInfo, OrderSummary)> {
+        let now = Clock::get()?.unix_timestamp;
+        if let Some(expiry) = params.good_til_time {
+            require!(expiry > now, BondsError::InvalidOrderExpiry);
+        }
+        self.expire_crossed_orders(side, &params, now)?;
+
         let mut manager = self.bond_manager.load_mut()?;
         let callback_info = CallbackInfo::new(
             self.bond_manager.key(),
@@ -87,7 +99,8 @@ impl<'info> OrderbookMut<'info> {
             fill,
             out,
             adapter.unwrap_or_default(),
-            Clock::get()?.unix_timestamp,
+            now,
+            params.good_til_time,
             params.callback_flags() | extra_flags,
             manager.nonce,
         );
@@ -99,6 +112,10 @@ impl<'info> OrderbookMut<'info> {
             orderbook_accounts!(self, new_order),
             order_params,
         )?;
+        require!(
+            params.post_allowed || params.post_only || order_summary.total_base_qty > 0,
+            BondsError::SlippageExceeded
+        );
         require!(
             order_summary.posted_order_id.is_some() || order_summary.total_base_qty > 0,
             BondsError::OrderRejected
@@ -113,7 +130,155 @@ impl<'info> OrderbookMut<'info> {
         &self,
         order_id: u128,
         owner: Pubkey,
-    ) -> Result<(Side, CallbackFlags, OrderSummary)> {
+    ) -> Result<(Side, CallbackInfo, OrderSummary)> {
+        let (side, info, order_summary) = self.remove_order(order_id, |info| {
+            require_eq!(info.owner, owner, BondsError::WrongUserAccount);
+            Ok(())
+        })?;
+
+        emit!(OrderCancelled {
+            bond_manager: self.bond_manager.key(),
+            user: owner,
+            order_id,
+        });
+
+        Ok((side, info, order_summary))
+    }
+
+    /// finds the ids of all orders on both sides of the book that belong to the owner
+    pub fn orders_owned_by(&self, owner: Pubkey) -> Result<Vec<u128>> {
+        let mut order_ids = vec![];
+
+        for (side, tag) in [
+            (&self.bids, agnostic_orderbook::state::AccountTag::Bids),
+            (&self.asks, agnostic_orderbook::state::AccountTag::Asks),
+        ] {
+            let mut buf = side.data.borrow_mut();
+            // iterating consumes the slab, so the callback info is read from a second view
+            let keys = Slab::<CallbackInfo>::from_buffer(&mut buf, tag)?
+                .into_iter(true)
+                .map(|leaf| leaf.key)
+                .collect::<Vec<_>>();
+            let slab = Slab::<CallbackInfo>::from_buffer(&mut buf, tag)?;
+
+            order_ids.extend(keys.into_iter().filter(|key| {
+                slab.find_by_key(*key)
+                    .map(|handle| slab.get_callback_info(handle).owner == owner)
+                    .unwrap_or(false)
+            }));
+        }
+
+        Ok(order_ids)
+    }
+
+    /// removes an order from the aaob once its good-til-time has passed
+    /// you still need to act on the callback to reconcile any balances etc.
+    pub fn prune_expired_order(
+        &self,
+        order_id: u128,
+        now: UnixTimestamp,
+    ) -> Result<(Side, CallbackInfo, OrderSummary)> {
+        let (side, info, order_summary) = self.remove_order(order_id, |info| {
+            require!(info.is_expired(now), BondsError::OrderNotExpired);
+            Ok(())
+        })?;
+
+        emit!(OrderExpired {
+            bond_manager: self.bond_manager.key(),
+            user: info.owner,
+            order_id,
+            expired_at: info.expiry_timestamp().unwrap_or_default(),
+        });
+
+        Ok((side, info, order_summary))
+    }
+
+    /// removes the expired orders that an order would otherwise be matched against, so an order
+    /// can't be filled once it has passed its good-til-time. Each removed order is queued as an
+    /// out event, which returns the posted funds to its owner when the events are consumed.
+    fn expire_crossed_orders(
+        &self,
+        side: Side,
+        params: &OrderParams,
+        now: UnixTimestamp,
+    ) -> Result<()> {
+        let maker_side = side.opposite();
+        let crosses = |leaf: &LeafNode| match side {
+            Side::Bid => leaf.price() <= params.limit_price,
+            Side::Ask => leaf.price() >= params.limit_price,
+        };
+
+        let expired = {
+            let (book_side, tag, best_first) = match maker_side {
+                Side::Bid => (&self.bids, AccountTag::Bids, false),
+                Side::Ask => (&self.asks, AccountTag::Asks, true),
+            };
+            let mut buf = book_side.data.borrow_mut();
+
+            // only the orders within the match limit can be filled, along with any expired
+            // orders in front of them
+            let scan_limit = usize::try_from(params.match_limit)
+                .unwrap_or(usize::MAX)
+                .saturating_add(MAX_EXPIRED_ORDERS_REMOVED + 1);
+
+            // iterating consumes the slab, so the callback info is read from a second view
+            let keys = Slab::<CallbackInfo>::from_buffer(&mut buf, tag)?
+                .into_iter(best_first)
+                .take_while(crosses)
+                .take(scan_limit)
+                .map(|leaf| leaf.key)
+                .collect::<Vec<_>>();
+            let slab = Slab::<CallbackInfo>::from_buffer(&mut buf, tag)?;
+
+            let mut expired = vec![];
+            let mut matchable = 0;
+            for key in keys {
+                if matchable >= params.match_limit {
+                    break;
+                }
+                match slab.find_by_key(key) {
+                    Some(handle) if slab.get_callback_info(handle).is_expired(now) => {
+                        expired.push(key)
+                    }
+                    _ => matchable += 1,
+                }
+            }
+
+            expired
+        };
+
+        if expired.is_empty() {
+            return Ok(());
+        }
+        require!(
+            expired.len() <= MAX_EXPIRED_ORDERS_REMOVED,
+            BondsError::TooManyExpiredOrders
+        );
+
+        let mut outs = Vec::with_capacity(expired.len());
+        for order_id in expired {
+            let (_, info, order_summary) = self.prune_expired_order(order_id, now)?;
+            outs.push((order_id, info, order_summary.total_base_qty));
+        }
+
+        let mut queue = EventQueue::deserialize_market(self.event_queue.to_account_info())?;
+        for (order_id, info, base_size) in outs {
+            let mut out = OutEvent::zeroed();
+            out.side = maker_side as u8;
+            out.order_id = order_id;
+            out.base_size = base_size;
+
+            queue.push_event(out, Some(&info), None)?;
+        }
+
+        Ok(())
+    }
+
+    fn remove_order(
+        &self,
+        order_id: u128,
+        validate: impl FnOnce(&CallbackInfo) -> Result<()>,
+    ) -> Result<(Side, CallbackInfo, OrderSummary)> {
         let side = get_side_from_order_id(order_id);
         let mut buf;
         let slab: Slab<CallbackInfo> = match side {
@@ -130,15 +295,12 @@ impl<'info> OrderbookMut<'info> {
             msg!("Given Order ID: [{}]", order_id);
             error!(BondsError::OrderNotFound)
         })?;
-        let info = slab.get_callback_info(handle);
-
-        let info_owner = info.owner;
-        let flags = info.flags;
+        let info = *slab.get_callback_info(handle);
 
         // drop the refs so the orderbook can borrow the slab data
         drop(buf);
 
-        require_eq!(info_owner, owner, BondsError::WrongUserAccount);
+        validate(&info)?;
         let orderbook_params = cancel_order::Params { order_id };
         let order_summary = agnostic_orderbook::instruction::cancel_order::process::<CallbackInfo>(
             &crate::id(),
@@ -146,13 +308,7 @@ impl<'info> OrderbookMut<'info> {
             orderbook_params,
         )?;
 
-        emit!(OrderCancelled {
-            bond_manager: self.bond_manager.key(),
-            user: owner,
-            order_id,
-        });
-
-        Ok((side, flags, order_summary))
+        Ok((side, info, order_summary))
     }
 }
 
@@ -209,7 +365,9 @@ pub struct CallbackInfo {
     pub order_submitted: [u8; 8],
     /// configuration used by callback execution
     pub flags: CallbackFlags,
-    _reserved: [u8; 14],
+    /// The unix timestamp after which the order may be pruned from the book, zero if it never expires
+    pub expires_at: [u8; 8],
+    _reserved: [u8; 6],
 }
 
 impl CallbackInfo {
@@ -222,6 +380,7 @@ impl CallbackInfo {
         out_account: Pubkey,
         adapter: Pubkey,
         order_submitted: UnixTimestamp,
+        expires_at: Option<UnixTimestamp>,
         flags: CallbackFlags,
         nonce: u64,
     ) -> Self {
@@ -234,7 +393,8 @@ impl CallbackInfo {
             adapter_account_key: adapter,
             order_submitted: order_submitted.to_le_bytes(),
             flags,
-            _reserved: [0u8; 14],
+            expires_at: expires_at.unwrap_or_default().to_le_bytes(),
+            _reserved: [0u8; 6],
         }
     }
 
@@ -250,6 +410,19 @@ impl CallbackInfo {
     pub fn order_submitted_timestamp(&self) -> UnixTimestamp {
         UnixTimestamp::from_le_bytes(self.order_submitted)
     }
+
+    pub fn expiry_timestamp(&self) -> Option<UnixTimestamp> {
+        match UnixTimestamp::from_le_bytes(self.expires_at) {
+            0 => None,
+            expiry => Some(expiry),
+        }
+    }
+
+    pub fn is_expired(&self, now: UnixTimestamp) -> bool {
+        self.expiry_timestamp()
+            .map(|expiry| expiry <= now)
+            .unwrap_or(false)
+    }
 }
 
 impl agnostic_orderbook::state::orderbook::CallbackInfo for CallbackInfo {
@@ -295,6 +468,10 @@ pub struct OrderParams {
     pub post_allowed: bool,
     /// Should the purchased tickets be automatically staked with the ticket program
     pub auto_stake: bool,
+    /// Good-til-time: the unix timestamp after which any posted remainder of the order is no
+    /// longer filled, and may be pruned from the book. `None` keeps the order until it is
+    /// filled or cancelled.
+    pub good_til_time: Option<UnixTimestamp>,
 }
 
 impl OrderParams {
@@ -490,6 +667,10 @@ impl<'a> EventQueue<'a> {
         maker_callback_info: Option<&CallbackInfo>,
         taker_callback_info: Option<&CallbackInfo>,
     ) -> std::result::Result<(), Error> {
+        if self.header.count as usize >= self.capacity {
+            return err!(BondsError::EventQueueFull);
+        }
+
         let mut buf = self.info.data.borrow_mut();
         let generic_event = event.to_generic();
         let event_idx = (self.header.head as usize + self.header.count as usize) % self.capacity;
@@ -55,12 +55,15 @@ pub(crate) use mint_to;
 /// burn from account owned by bond manager
 macro_rules! burn {
     ($ctx:ident, $mint:ident, $target:ident, $amount:expr $(, $bond_manager_nesting:ident)?) => {
+        crate::utils::burn!($ctx, $mint, $ctx.accounts.$target.to_account_info(), $amount $(, $bond_manager_nesting)?)
+    };
+    ($ctx:ident, $mint:ident, $target:expr, $amount:expr $(, $bond_manager_nesting:ident)?) => {
         anchor_spl::token::burn(
             anchor_lang::prelude::CpiContext::new(
                 $ctx.accounts.token_program.to_account_info(),
                 anchor_spl::token::Burn {
                     mint: $ctx.accounts.$mint.to_account_info(),
-                    from: $ctx.accounts.$target.to_account_info(),
+                    from: $target,
                     authority: $ctx.accounts.$($bond_manager_nesting.)?bond_manager.to_account_info(),
                 },
             )
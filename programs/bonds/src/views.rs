@@ -0,0 +1,24 @@
+//! Helpers for other programs to get bond market information through CPI
+
+use anchor_lang::prelude::*;
+
+use jet_margin::views::read_return_data;
+
+use crate::cpi::accounts::{MarginUserDebtView, OrderbookQuoteView};
+use crate::{margin::instructions::MarginUserDebt, orderbook::instructions::OrderbookQuote};
+
+/// Get the best bid and ask in a bond market
+pub fn orderbook_quote<'info>(
+    ctx: CpiContext<'_, '_, '_, 'info, OrderbookQuoteView<'info>>,
+) -> Result<OrderbookQuote> {
+    crate::cpi::orderbook_quote(ctx)?;
+    read_return_data(&crate::ID)
+}
+
+/// Get the debt and obligations due for a margin user
+pub fn margin_user_debt<'info>(
+    ctx: CpiContext<'_, '_, '_, 'info, MarginUserDebtView<'info>>,
+) -> Result<MarginUserDebt> {
+    crate::cpi::margin_user_debt(ctx)?;
+    read_return_data(&crate::ID)
+}
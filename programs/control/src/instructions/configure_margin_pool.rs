@@ -17,6 +17,7 @@
 
 use anchor_lang::prelude::*;
 
+use jet_airspace::{state::Airspace, DEFAULT_AIRSPACE};
 use jet_margin_pool::program::JetMarginPool;
 use jet_margin_pool::MarginPoolConfig;
 use jet_margin_pool::{cpi::accounts::Configure, MarginPool};
@@ -42,24 +43,34 @@ pub struct TokenMetadataParams {
 
 #[derive(Accounts)]
 pub struct ConfigureMarginPool<'info> {
-    #[cfg_attr(not(feature = "testing"), account(address = crate::ROOT_AUTHORITY))]
+    /// The authority of the airspace the pool belongs to
     pub requester: Signer<'info>,
     pub authority: Box<Account<'info, Authority>>,
 
+    /// The airspace the pool belongs to
+    #[account(constraint = airspace.authority == requester.key())]
+    pub airspace: Box<Account<'info, Airspace>>,
+
     /// CHECK:
     pub token_mint: UncheckedAccount<'info>,
 
-    #[account(mut, has_one = token_mint)]
+    #[account(mut, has_one = token_mint, has_one = airspace)]
     pub margin_pool: Box<Account<'info, MarginPool>>,
 
-    #[account(mut, has_one = token_mint)]
-    pub token_metadata: Box<Account<'info, TokenMetadata>>,
+    /// The metadata for the token, which is shared by the pools in every airspace and
+    /// is only written for pools in the default airspace.
+    ///
+    /// CHECK: loaded in the handler for pools in the default airspace
+    #[account(mut)]
+    pub token_metadata: UncheckedAccount<'info>,
 
-    #[account(mut, constraint = deposit_metadata.underlying_token_mint == token_mint.key())]
-    pub deposit_metadata: Box<Account<'info, PositionTokenMetadata>>,
+    /// CHECK: loaded in the handler for pools in the default airspace
+    #[account(mut)]
+    pub deposit_metadata: UncheckedAccount<'info>,
 
-    #[account(mut, constraint = loan_metadata.underlying_token_mint == token_mint.key())]
-    pub loan_metadata: Box<Account<'info, PositionTokenMetadata>>,
+    /// CHECK: loaded in the handler for pools in the default airspace
+    #[account(mut)]
+    pub loan_metadata: UncheckedAccount<'info>,
 
     /// CHECK:
     pub pyth_product: UncheckedAccount<'info>,
@@ -77,7 +88,8 @@ impl<'info> ConfigureMarginPool<'info> {
             self.margin_pool_program.to_account_info(),
             Configure {
                 margin_pool: self.margin_pool.to_account_info(),
-                authority: self.authority.to_account_info(),
+                airspace: self.airspace.to_account_info(),
+                authority: self.requester.to_account_info(),
                 pyth_product: self.pyth_product.to_account_info(),
                 pyth_price: self.pyth_price.to_account_info(),
             },
@@ -124,18 +136,25 @@ pub fn configure_margin_pool_handler(
     let authority = [&ctx.accounts.authority.seed[..]];
 
     if *ctx.accounts.pyth_price.key != Pubkey::default() || pool_config.is_some() {
-        jet_margin_pool::cpi::configure(
-            ctx.accounts
-                .configure_pool_context()
-                .with_signer(&[&authority]),
-            pool_config,
-        )?;
+        jet_margin_pool::cpi::configure(ctx.accounts.configure_pool_context(), pool_config)?;
+    }
+
+    // Pools in other airspaces are only configured through their token configs in the
+    // margin program, and can't change the metadata used by the default airspace.
+    if ctx.accounts.airspace.key() != DEFAULT_AIRSPACE {
+        return Ok(());
     }
 
     if *ctx.accounts.pyth_price.key != Pubkey::default() {
-        let mut metadata = ctx.accounts.token_metadata.clone();
+        let mut metadata = Account::<TokenMetadata>::try_from(&ctx.accounts.token_metadata)?;
         let mut data = vec![];
 
+        require_keys_eq!(
+            metadata.token_mint,
+            ctx.accounts.token_mint.key(),
+            anchor_lang::error::ErrorCode::ConstraintHasOne
+        );
+
         metadata.pyth_product = ctx.accounts.pyth_product.key();
         metadata.pyth_price = ctx.accounts.pyth_price.key();
 
@@ -158,9 +177,16 @@ pub fn configure_margin_pool_handler(
     }
 
     if let Some(params) = metadata {
-        let mut metadata = ctx.accounts.deposit_metadata.clone();
+        let mut metadata =
+            Account::<PositionTokenMetadata>::try_from(&ctx.accounts.deposit_metadata)?;
         let mut data = vec![];
 
+        require_keys_eq!(
+            metadata.position_token_mint,
+            ctx.accounts.margin_pool.deposit_note_mint,
+            anchor_lang::error::ErrorCode::ConstraintRaw
+        );
+
         metadata.token_kind = params.token_kind;
         metadata.value_modifier = params.collateral_weight;
         metadata.max_staleness = 0;
@@ -182,9 +208,15 @@ pub fn configure_margin_pool_handler(
             metadata: metadata.into_inner(),
         });
 
-        metadata = ctx.accounts.loan_metadata.clone();
+        metadata = Account::<PositionTokenMetadata>::try_from(&ctx.accounts.loan_metadata)?;
         let mut data = vec![];
 
+        require_keys_eq!(
+            metadata.position_token_mint,
+            ctx.accounts.margin_pool.loan_note_mint,
+            anchor_lang::error::ErrorCode::ConstraintRaw
+        );
+
         metadata.token_kind = TokenKind::Claim;
         metadata.value_modifier = params.max_leverage;
         metadata.max_staleness = 0;
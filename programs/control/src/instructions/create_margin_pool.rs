@@ -19,6 +19,7 @@ use anchor_lang::prelude::*;
 use anchor_spl::token::{InitializeAccount, Token, TokenAccount};
 use std::convert::TryInto;
 
+use jet_airspace::{state::Airspace, DEFAULT_AIRSPACE};
 use jet_margin_pool::cpi::accounts::CreatePool;
 use jet_margin_pool::program::JetMarginPool;
 use jet_metadata::cpi::accounts::{CreateEntry, SetEntry};
@@ -31,11 +32,15 @@ use super::Authority;
 
 #[derive(Accounts)]
 pub struct CreateMarginPool<'info> {
-    #[cfg_attr(not(feature = "testing"), account(address = crate::ROOT_AUTHORITY))]
+    /// The authority of the airspace the pool is created within
     #[account(mut)]
     requester: Signer<'info>,
     authority: Account<'info, Authority>,
 
+    /// The airspace the pool is created within
+    #[account(constraint = airspace.authority == requester.key())]
+    airspace: Account<'info, Airspace>,
+
     /// CHECK:
     #[account(mut)]
     margin_pool: UncheckedAccount<'info>,
@@ -97,7 +102,8 @@ impl<'info> CreateMarginPool<'info> {
                 deposit_note_mint: self.deposit_note_mint.to_account_info(),
                 loan_note_mint: self.loan_note_mint.to_account_info(),
                 token_mint: self.token_mint.to_account_info(),
-                authority: self.authority.to_account_info(),
+                airspace: self.airspace.to_account_info(),
+                authority: self.requester.to_account_info(),
                 payer: self.requester.to_account_info(),
                 token_program: self.token_program.to_account_info(),
                 system_program: self.system_program.to_account_info(),
@@ -194,17 +200,21 @@ impl<'info> CreateMarginPool<'info> {
 pub fn create_margin_pool_handler(ctx: Context<CreateMarginPool>) -> Result<()> {
     let authority = [&ctx.accounts.authority.seed[..]];
 
-    // create the pool
+    // create the pool, which is authorized by the airspace authority signing as the requester
     jet_margin_pool::cpi::create_pool(
-        ctx.accounts
-            .create_pool_context()
-            .with_signer(&[&authority]),
+        ctx.accounts.create_pool_context(),
         ctx.accounts.fee_destination.key(),
     )?;
 
     // create fee collection account
     anchor_spl::token::initialize_account(ctx.accounts.create_fee_destination_context())?;
 
+    // The metadata is only used by margin accounts in the default airspace, pools in other
+    // airspaces are configured through token configs in the margin program.
+    if ctx.accounts.airspace.key() != DEFAULT_AIRSPACE {
+        return Ok(());
+    }
+
     // set metadata for the deposit/loan tokens to be used as positions
     let deposit_note_metadata = PositionTokenMetadata {
         underlying_token_mint: ctx.accounts.token_mint.key(),
@@ -224,18 +234,11 @@ pub fn create_margin_pool_handler(ctx: Context<CreateMarginPool>) -> Result<()>
         max_staleness: 0,
     };
 
-    let token_metadata = TokenMetadata {
-        token_mint: ctx.accounts.token_mint.key(),
-        ..Default::default()
-    };
-
-    let mut token_md_data = vec![];
     let mut deposit_md_data = vec![];
     let mut loan_md_data = vec![];
 
     deposit_note_metadata.try_serialize(&mut deposit_md_data)?;
     loan_note_metadata.try_serialize(&mut loan_md_data)?;
-    token_metadata.try_serialize(&mut token_md_data)?;
 
     jet_metadata::cpi::create_entry(
         ctx.accounts
@@ -283,28 +286,39 @@ pub fn create_margin_pool_handler(ctx: Context<CreateMarginPool>) -> Result<()>
         metadata: loan_note_metadata,
     });
 
-    jet_metadata::cpi::create_entry(
-        ctx.accounts
-            .create_token_metadata_context()
-            .with_signer(&[&authority]),
-        String::new(),
-        token_md_data.len().try_into().unwrap(),
-    )?;
-
-    jet_metadata::cpi::set_entry(
-        ctx.accounts
-            .set_token_metadata_context()
-            .with_signer(&[&authority]),
-        0,
-        token_md_data,
-    )?;
-
-    emit!(events::TokenMetadataConfigured {
-        requester: ctx.accounts.requester.key(),
-        authority: ctx.accounts.authority.key(),
-        metadata_account: ctx.accounts.token_metadata.key(),
-        metadata: token_metadata,
-    });
+    // the token metadata is keyed by the token mint alone, so it may already exist
+    if ctx.accounts.token_metadata.data_is_empty() {
+        let token_metadata = TokenMetadata {
+            token_mint: ctx.accounts.token_mint.key(),
+            ..Default::default()
+        };
+
+        let mut token_md_data = vec![];
+        token_metadata.try_serialize(&mut token_md_data)?;
+
+        jet_metadata::cpi::create_entry(
+            ctx.accounts
+                .create_token_metadata_context()
+                .with_signer(&[&authority]),
+            String::new(),
+            token_md_data.len().try_into().unwrap(),
+        )?;
+
+        jet_metadata::cpi::set_entry(
+            ctx.accounts
+                .set_token_metadata_context()
+                .with_signer(&[&authority]),
+            0,
+            token_md_data,
+        )?;
+
+        emit!(events::TokenMetadataConfigured {
+            requester: ctx.accounts.requester.key(),
+            authority: ctx.accounts.authority.key(),
+            metadata_account: ctx.accounts.token_metadata.key(),
+            metadata: token_metadata,
+        });
+    }
 
     Ok(())
 }
@@ -52,7 +52,7 @@ mod jet_control {
     /// Register an SPL token for use with the protocol, by creating
     /// a margin pool which can accept deposits for the token.
     ///
-    /// Does not require special permission
+    /// Requires the airspace authority to sign as the requester
     pub fn create_margin_pool(ctx: Context<CreateMarginPool>) -> Result<()> {
         instructions::create_margin_pool_handler(ctx)
     }
@@ -64,6 +64,8 @@ mod jet_control {
     }
 
     /// Configure details about a margin pool
+    ///
+    /// Requires the airspace authority to sign as the requester
     pub fn configure_margin_pool(
         ctx: Context<ConfigureMarginPool>,
         metadata: Option<TokenMetadataParams>,
@@ -4,6 +4,7 @@ use anchor_lang::prelude::*;
 #[event]
 pub struct PoolCreated {
     pub margin_pool: Pubkey,
+    pub airspace: Pubkey,
     pub vault: Pubkey,
     pub fee_destination: Pubkey,
     pub deposit_note_mint: Pubkey,
@@ -15,6 +16,13 @@ pub struct PoolCreated {
     pub version: u8,
 }
 
+#[event]
+pub struct PoolMigrated {
+    pub margin_pool: Pubkey,
+    pub airspace: Pubkey,
+    pub version: u8,
+}
+
 #[event]
 pub struct PoolConfigured {
     pub margin_pool: Pubkey,
@@ -23,6 +31,15 @@ pub struct PoolConfigured {
     pub config: MarginPoolConfig,
 }
 
+#[event]
+pub struct PriceBandChanged {
+    pub margin_pool: Pubkey,
+    pub exceeded: bool,
+    pub price: i64,
+    pub ema_price: i64,
+    pub exponent: i32,
+}
+
 #[event]
 pub struct Deposit {
     pub margin_pool: Pubkey,
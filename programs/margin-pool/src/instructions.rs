@@ -18,11 +18,15 @@
 mod close_loan;
 mod collect;
 mod configure;
+mod create_deposit_note_metadata;
 mod create_pool;
 mod deposit;
+mod deposit_note_exchange_rate;
 mod margin_borrow;
 mod margin_refresh_position;
 mod margin_repay;
+mod migrate_pool;
+mod pool_rates;
 mod register_loan;
 mod repay;
 mod withdraw;
@@ -30,11 +34,15 @@ mod withdraw;
 pub use close_loan::*;
 pub use collect::*;
 pub use configure::*;
+pub use create_deposit_note_metadata::*;
 pub use create_pool::*;
 pub use deposit::*;
+pub use deposit_note_exchange_rate::*;
 pub use margin_borrow::*;
 pub use margin_refresh_position::*;
 pub use margin_repay::*;
+pub use migrate_pool::*;
+pub use pool_rates::*;
 pub use register_loan::*;
 pub use repay::*;
 pub use withdraw::*;
@@ -21,6 +21,7 @@ use anchor_spl::token::{self, CloseAccount, Token, TokenAccount};
 use jet_margin::{AdapterResult, MarginAccount, PositionChange};
 
 use crate::state::*;
+use crate::ErrorCode;
 
 #[derive(Accounts)]
 pub struct CloseLoan<'info> {
@@ -40,7 +41,10 @@ pub struct CloseLoan<'info> {
     /// CHECK:
     pub loan_note_mint: AccountInfo<'info>,
 
-    #[account(has_one = loan_note_mint)]
+    #[account(
+        has_one = loan_note_mint,
+        constraint = margin_pool.version == MARGIN_POOL_VERSION @ ErrorCode::InvalidPoolVersion
+    )]
     pub margin_pool: Account<'info, MarginPool>,
 
     #[account(mut)]
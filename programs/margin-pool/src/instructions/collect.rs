@@ -20,7 +20,7 @@ use std::ops::Deref;
 use anchor_lang::prelude::*;
 use anchor_spl::token::{self, MintTo, Token, TokenAccount};
 
-use crate::{events, state::*, Amount};
+use crate::{events, state::*, Amount, ErrorCode};
 
 #[derive(Accounts)]
 pub struct Collect<'info> {
@@ -28,7 +28,8 @@ pub struct Collect<'info> {
     #[account(mut,
               has_one = vault,
               has_one = deposit_note_mint,
-              has_one = fee_destination)]
+              has_one = fee_destination,
+              constraint = margin_pool.version == MARGIN_POOL_VERSION @ ErrorCode::InvalidPoolVersion)]
     pub margin_pool: Account<'info, MarginPool>,
 
     /// The vault for the pool, where tokens are held
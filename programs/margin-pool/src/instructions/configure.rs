@@ -17,7 +17,7 @@
 
 use anchor_lang::prelude::*;
 
-use jet_metadata::ControlAuthority;
+use jet_airspace::state::Airspace;
 
 use crate::ErrorCode;
 use crate::{events, state::*};
@@ -25,12 +25,19 @@ use crate::{events, state::*};
 #[derive(Accounts)]
 pub struct Configure<'info> {
     /// The pool to be configured
-    #[account(mut)]
+    #[account(
+        mut,
+        has_one = airspace,
+        constraint = margin_pool.version == MARGIN_POOL_VERSION @ ErrorCode::InvalidPoolVersion
+    )]
     pub margin_pool: Account<'info, MarginPool>,
 
-    /// The authority allowed to modify the pool, which must sign
-    #[cfg_attr(not(feature = "testing"), account(signer))]
-    pub authority: Account<'info, ControlAuthority>,
+    /// The airspace the pool belongs to
+    #[account(has_one = authority)]
+    pub airspace: Account<'info, Airspace>,
+
+    /// The airspace authority, which must sign to modify the pool
+    pub authority: Signer<'info>,
 
     /// CHECK:
     pub pyth_product: AccountInfo<'info>,
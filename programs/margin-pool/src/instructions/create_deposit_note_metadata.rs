@@ -0,0 +1,105 @@
+// SPDX-License-Identifier: AGPL-3.0-or-later
+//
+// Copyright (C) 2022 JET PROTOCOL HOLDINGS, LLC.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::program::invoke_signed;
+use anchor_spl::token::Mint;
+
+use jet_airspace::state::Airspace;
+
+use crate::state::*;
+use crate::ErrorCode;
+
+#[derive(Accounts)]
+pub struct CreateDepositNoteMetadata<'info> {
+    /// The pool whose deposit notes are being described
+    #[account(
+        has_one = airspace,
+        has_one = deposit_note_mint,
+        constraint = margin_pool.version == MARGIN_POOL_VERSION @ ErrorCode::InvalidPoolVersion
+    )]
+    pub margin_pool: Account<'info, MarginPool>,
+
+    /// The mint for the pool's deposit notes
+    pub deposit_note_mint: Account<'info, Mint>,
+
+    /// The metadata account for the deposit notes, to be created by the metadata program
+    /// CHECK: the address is verified by the metadata program
+    #[account(mut)]
+    pub deposit_note_metadata: AccountInfo<'info>,
+
+    /// The airspace the pool belongs to
+    #[account(has_one = authority)]
+    pub airspace: Account<'info, Airspace>,
+
+    /// The airspace authority, which becomes the update authority for the metadata
+    pub authority: Signer<'info>,
+
+    /// The payer of rent for the metadata account
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    /// CHECK:
+    #[account(address = mpl_token_metadata::ID)]
+    pub token_metadata_program: AccountInfo<'info>,
+
+    pub system_program: Program<'info, System>,
+    pub rent: Sysvar<'info, Rent>,
+}
+
+pub fn create_deposit_note_metadata_handler(
+    ctx: Context<CreateDepositNoteMetadata>,
+    name: String,
+    symbol: String,
+    uri: String,
+) -> Result<()> {
+    let pool = &ctx.accounts.margin_pool;
+
+    let ix = mpl_token_metadata::instruction::create_metadata_accounts_v2(
+        mpl_token_metadata::ID,
+        ctx.accounts.deposit_note_metadata.key(),
+        pool.deposit_note_mint,
+        pool.key(),
+        ctx.accounts.payer.key(),
+        ctx.accounts.authority.key(),
+        name,
+        symbol,
+        uri,
+        None,
+        0,
+        true,
+        true,
+        None,
+        None,
+    );
+
+    invoke_signed(
+        &ix,
+        &[
+            ctx.accounts.deposit_note_metadata.to_account_info(),
+            ctx.accounts.deposit_note_mint.to_account_info(),
+            pool.to_account_info(),
+            ctx.accounts.payer.to_account_info(),
+            ctx.accounts.authority.to_account_info(),
+            ctx.accounts.system_program.to_account_info(),
+            ctx.accounts.rent.to_account_info(),
+        ],
+        &[&pool.authority_seeds()],
+    )?;
+
+    Ok(())
+}
@@ -20,7 +20,7 @@ use std::ops::Deref;
 use anchor_lang::prelude::*;
 use anchor_spl::token::{Mint, Token, TokenAccount};
 
-use jet_metadata::ControlAuthority;
+use jet_airspace::state::Airspace;
 
 use crate::{events, state::*};
 
@@ -29,7 +29,10 @@ pub struct CreatePool<'info> {
     /// The pool to be created
     #[account(
         init,
-        seeds = [token_mint.key().as_ref()],
+        seeds = [
+            pool_airspace_seed(&airspace.key()),
+            token_mint.key().as_ref()
+        ],
         bump,
         payer = payer,
         space = 8 + std::mem::size_of::<MarginPool>(),
@@ -75,9 +78,12 @@ pub struct CreatePool<'info> {
     /// The mint for the token being custodied by the pool
     pub token_mint: Box<Account<'info, Mint>>,
 
-    /// The authority to create pools, which must sign
-    #[account(signer)]
-    pub authority: Box<Account<'info, ControlAuthority>>,
+    /// The airspace the pool is created within
+    #[account(has_one = authority)]
+    pub airspace: Box<Account<'info, Airspace>>,
+
+    /// The airspace authority, which must sign to create pools
+    pub authority: Signer<'info>,
 
     /// The payer of rent for new accounts
     #[account(mut)]
@@ -91,7 +97,9 @@ pub struct CreatePool<'info> {
 pub fn create_pool_handler(ctx: Context<CreatePool>, fee_destination: Pubkey) -> Result<()> {
     let pool = &mut ctx.accounts.margin_pool;
 
+    pool.version = MARGIN_POOL_VERSION;
     pool.address = pool.key();
+    pool.airspace = ctx.accounts.airspace.key();
     pool.pool_bump[0] = *ctx.bumps.get("margin_pool").unwrap();
     pool.token_mint = ctx.accounts.token_mint.key();
     pool.vault = ctx.accounts.vault.key();
@@ -107,6 +115,7 @@ pub fn create_pool_handler(ctx: Context<CreatePool>, fee_destination: Pubkey) ->
     emit!(events::PoolCreated {
         fee_destination,
         margin_pool: ctx.accounts.margin_pool.key(),
+        airspace: ctx.accounts.airspace.key(),
         vault: ctx.accounts.vault.key(),
         deposit_note_mint: ctx.accounts.deposit_note_mint.key(),
         loan_note_mint: ctx.accounts.loan_note_mint.key(),
@@ -28,7 +28,9 @@ pub struct Deposit<'info> {
     /// The pool to deposit into
     #[account(mut,
               has_one = vault,
-              has_one = deposit_note_mint)]
+              has_one = deposit_note_mint,
+              constraint = margin_pool.version == MARGIN_POOL_VERSION @ ErrorCode::InvalidPoolVersion,
+              constraint = margin_pool.accepts_signer(&depositor)? @ ErrorCode::WrongAirspace)]
     pub margin_pool: Account<'info, MarginPool>,
 
     /// The vault for the pool, where tokens are held
@@ -0,0 +1,95 @@
+// SPDX-License-Identifier: AGPL-3.0-or-later
+//
+// Copyright (C) 2022 JET PROTOCOL HOLDINGS, LLC.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::program::set_return_data;
+
+use jet_proto_math::Number;
+
+use crate::state::*;
+use crate::ErrorCode;
+
+/// The exponent for the fixed point value of an [ExchangeRate]
+pub const EXCHANGE_RATE_EXPONENT: i32 = -15;
+
+/// Convert a rate to a fixed point number with an exponent of [EXCHANGE_RATE_EXPONENT],
+/// failing if it is too large to be represented.
+pub fn fixed_point_rate(rate: Number) -> Result<u64> {
+    if rate > Number::from_decimal(u64::MAX, EXCHANGE_RATE_EXPONENT) {
+        msg!("the rate is too large to be represented");
+        return err!(ErrorCode::RateOverflow);
+    }
+
+    Ok(rate.as_u64(EXCHANGE_RATE_EXPONENT))
+}
+
+/// The value of a pool's deposit notes, in the underlying token
+#[derive(AnchorSerialize, AnchorDeserialize, Debug, Clone, Copy, Eq, PartialEq)]
+pub struct ExchangeRate {
+    /// The number of tokens each note can be exchanged for, as a fixed point
+    /// number with an exponent of [EXCHANGE_RATE_EXPONENT]
+    pub tokens_per_note: u64,
+
+    /// The time the rate was calculated at, including all interest up to this time
+    pub timestamp: i64,
+}
+
+#[derive(Accounts)]
+pub struct DepositNoteExchangeRate<'info> {
+    /// The pool to get the exchange rate for
+    #[account(constraint = margin_pool.version == MARGIN_POOL_VERSION @ ErrorCode::InvalidPoolVersion)]
+    pub margin_pool: Account<'info, MarginPool>,
+}
+
+pub fn deposit_note_exchange_rate_handler(ctx: Context<DepositNoteExchangeRate>) -> Result<()> {
+    let clock = Clock::get()?;
+
+    // accrue interest on a copy, since the pool is not modified
+    let mut pool = MarginPool::clone(&ctx.accounts.margin_pool);
+    pool.accrue_interest(clock.unix_timestamp);
+
+    let rate = ExchangeRate {
+        tokens_per_note: fixed_point_rate(pool.deposit_note_exchange_rate())?,
+        timestamp: pool.accrued_until,
+    };
+
+    set_return_data(&rate.try_to_vec()?);
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fixed_point_rate_is_checked() {
+        assert_eq!(
+            1_000_000_000_000_000,
+            fixed_point_rate(Number::ONE).unwrap()
+        );
+
+        let max = Number::from_decimal(u64::MAX, EXCHANGE_RATE_EXPONENT);
+        assert_eq!(u64::MAX, fixed_point_rate(max).unwrap());
+
+        // rates above about 18,446 don't fit
+        assert!(fixed_point_rate(Number::from(20_000u64)).is_err());
+        assert!(
+            fixed_point_rate(max + Number::from_decimal(1u64, EXCHANGE_RATE_EXPONENT)).is_err()
+        );
+    }
+}
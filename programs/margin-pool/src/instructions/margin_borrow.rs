@@ -34,7 +34,9 @@ pub struct MarginBorrow<'info> {
     /// The pool to borrow from
     #[account(mut,
               has_one = loan_note_mint,
-              has_one = deposit_note_mint)]
+              has_one = deposit_note_mint,
+              constraint = margin_pool.version == MARGIN_POOL_VERSION @ ErrorCode::InvalidPoolVersion,
+              constraint = margin_pool.airspace == margin_account.load()?.airspace @ ErrorCode::WrongAirspace)]
     pub margin_pool: Account<'info, MarginPool>,
 
     /// The mint for the notes representing loans from the pool
@@ -19,8 +19,10 @@ use anchor_lang::prelude::*;
 
 use jet_margin::{AdapterResult, MarginAccount, PositionChange, PriceChangeInfo};
 
+use jet_proto_math::Number;
+
 use crate::state::*;
-use crate::ErrorCode;
+use crate::{events, ErrorCode};
 
 #[derive(Accounts)]
 pub struct MarginRefreshPosition<'info> {
@@ -28,7 +30,11 @@ pub struct MarginRefreshPosition<'info> {
     pub margin_account: AccountLoader<'info, MarginAccount>,
 
     /// The pool to be refreshed
-    #[account(has_one = token_price_oracle)]
+    #[account(
+        mut,
+        has_one = token_price_oracle,
+        constraint = margin_pool.version == MARGIN_POOL_VERSION @ ErrorCode::InvalidPoolVersion
+    )]
     pub margin_pool: Account<'info, MarginPool>,
 
     /// The pyth price account for the pool's token
@@ -37,7 +43,7 @@ pub struct MarginRefreshPosition<'info> {
 }
 
 pub fn margin_refresh_position_handler(ctx: Context<MarginRefreshPosition>) -> Result<()> {
-    let pool = &ctx.accounts.margin_pool;
+    let pool = &mut ctx.accounts.margin_pool;
 
     // read from the pyth oracle
     let token_oracle = match pyth_sdk_solana::load_price_feed_from_account_info(
@@ -52,6 +58,27 @@ pub fn margin_refresh_position_handler(ctx: Context<MarginRefreshPosition>) -> R
 
     let prices = pool.calculate_prices(&token_oracle)?;
 
+    // Pause borrows and withdrawals while the price is far from its moving average
+    let price = token_oracle
+        .get_current_price()
+        .ok_or(ErrorCode::InvalidPoolPrice)?;
+    let ema_price = token_oracle
+        .get_ema_price()
+        .ok_or(ErrorCode::InvalidPoolPrice)?;
+
+    if pool.update_price_band(
+        Number::from_decimal(price.price, price.expo),
+        Number::from_decimal(ema_price.price, ema_price.expo),
+    ) {
+        emit!(events::PriceBandChanged {
+            margin_pool: pool.key(),
+            exceeded: pool.price_band_exceeded,
+            price: price.price,
+            ema_price: ema_price.price,
+            exponent: price.expo,
+        });
+    }
+
     // Tell the margin program what the current prices are
     jet_margin::write_adapter_result(
         &*ctx.accounts.margin_account.load()?,
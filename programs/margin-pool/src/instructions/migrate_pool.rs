@@ -0,0 +1,101 @@
+// SPDX-License-Identifier: AGPL-3.0-or-later
+//
+// Copyright (C) 2022 JET PROTOCOL HOLDINGS, LLC.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+use anchor_lang::{prelude::*, system_program};
+
+use crate::{events, state::*, ErrorCode};
+
+#[derive(Accounts)]
+pub struct MigratePool<'info> {
+    /// The pool to be migrated, which is loaded manually since its layout is outdated
+    /// CHECK: the owner, discriminator and version are verified by the handler
+    #[account(mut, owner = crate::ID)]
+    pub margin_pool: AccountInfo<'info>,
+
+    /// The payer for the rent of the additional space
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn migrate_pool_handler(ctx: Context<MigratePool>) -> Result<()> {
+    let pool_info = &ctx.accounts.margin_pool;
+    let new_size = 8 + std::mem::size_of::<MarginPool>();
+
+    {
+        let data = pool_info.try_borrow_data()?;
+
+        if data.len() < 9 || data[..8] != MarginPool::discriminator() {
+            return err!(ErrorCode::InvalidPoolVersion);
+        }
+
+        // version 0 pools existed before airspaces
+        if data[8] != 0 {
+            msg!("the pool has already been migrated");
+            return err!(ErrorCode::InvalidPoolVersion);
+        }
+    }
+
+    let required_rent = Rent::get()?
+        .minimum_balance(new_size)
+        .saturating_sub(pool_info.lamports());
+
+    if required_rent > 0 {
+        system_program::transfer(
+            CpiContext::new(
+                ctx.accounts.system_program.to_account_info(),
+                system_program::Transfer {
+                    from: ctx.accounts.payer.to_account_info(),
+                    to: pool_info.clone(),
+                },
+            ),
+            required_rent,
+        )?;
+    }
+
+    if pool_info.data_len() < new_size {
+        pool_info.realloc(new_size, true)?;
+    }
+
+    let mut pool = MarginPool::try_deserialize(&mut &pool_info.try_borrow_data()?[..])?;
+
+    // only pools at their legacy address, derived from just the token mint, existed
+    // before airspaces, and they all belong to the default airspace
+    let legacy_address = Pubkey::create_program_address(
+        &[pool.token_mint.as_ref(), pool.pool_bump.as_ref()],
+        &crate::ID,
+    )
+    .map_err(|_| error!(ErrorCode::InvalidPoolVersion))?;
+
+    if legacy_address != pool_info.key() {
+        return err!(ErrorCode::InvalidPoolVersion);
+    }
+
+    pool.version = MARGIN_POOL_VERSION;
+    pool.airspace = jet_airspace::DEFAULT_AIRSPACE;
+
+    pool.try_serialize(&mut &mut pool_info.try_borrow_mut_data()?[..])?;
+
+    emit!(events::PoolMigrated {
+        margin_pool: pool_info.key(),
+        airspace: pool.airspace,
+        version: pool.version,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,80 @@
+// SPDX-License-Identifier: AGPL-3.0-or-later
+//
+// Copyright (C) 2022 JET PROTOCOL HOLDINGS, LLC.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::program::set_return_data;
+
+use jet_proto_math::Number;
+
+use crate::state::*;
+use crate::{fixed_point_rate, ErrorCode};
+
+/// The current rates for a pool, as fixed point numbers with an exponent of
+/// [EXCHANGE_RATE_EXPONENT](crate::EXCHANGE_RATE_EXPONENT)
+#[derive(AnchorSerialize, AnchorDeserialize, Debug, Clone, Copy, Eq, PartialEq)]
+pub struct PoolRates {
+    /// The number of tokens each deposit note can be exchanged for
+    pub deposit_note_exchange_rate: u64,
+
+    /// The number of tokens owed for each loan note
+    pub loan_note_exchange_rate: u64,
+
+    /// The annual interest rate charged on borrows
+    pub borrow_rate: u64,
+
+    /// The annual interest rate earned by depositors, after fees
+    pub deposit_rate: u64,
+
+    /// The fraction of deposited tokens that are currently borrowed
+    pub utilization_rate: u64,
+
+    /// The time the rates were calculated at, including all interest up to this time
+    pub timestamp: i64,
+}
+
+#[derive(Accounts)]
+pub struct PoolRatesView<'info> {
+    /// The pool to get the rates for
+    #[account(constraint = margin_pool.version == MARGIN_POOL_VERSION @ ErrorCode::InvalidPoolVersion)]
+    pub margin_pool: Account<'info, MarginPool>,
+}
+
+pub fn pool_rates_handler(ctx: Context<PoolRatesView>) -> Result<()> {
+    let clock = Clock::get()?;
+
+    // accrue interest on a copy, since the pool is not modified
+    let mut pool = MarginPool::clone(&ctx.accounts.margin_pool);
+    pool.accrue_interest(clock.unix_timestamp);
+
+    let borrow_rate = pool.interest_rate();
+    let utilization_rate = pool.utilization_rate();
+    let fee_rate = Number::from_bps(pool.config.management_fee_rate);
+    let deposit_rate = borrow_rate * utilization_rate * (Number::ONE - fee_rate);
+
+    let rates = PoolRates {
+        deposit_note_exchange_rate: fixed_point_rate(pool.deposit_note_exchange_rate())?,
+        loan_note_exchange_rate: fixed_point_rate(pool.loan_note_exchange_rate())?,
+        borrow_rate: fixed_point_rate(borrow_rate)?,
+        deposit_rate: fixed_point_rate(deposit_rate)?,
+        utilization_rate: fixed_point_rate(utilization_rate)?,
+        timestamp: pool.accrued_until,
+    };
+
+    set_return_data(&rates.try_to_vec()?);
+
+    Ok(())
+}
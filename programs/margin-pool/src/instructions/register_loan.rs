@@ -21,6 +21,7 @@ use anchor_spl::token::{Token, TokenAccount};
 use jet_margin::{AdapterResult, MarginAccount, PositionChange};
 
 use crate::state::*;
+use crate::ErrorCode;
 
 #[derive(Accounts)]
 pub struct RegisterLoan<'info> {
@@ -50,7 +51,10 @@ pub struct RegisterLoan<'info> {
     /// CHECK:
     pub loan_note_mint: AccountInfo<'info>,
 
-    #[account(has_one = loan_note_mint)]
+    #[account(
+        has_one = loan_note_mint,
+        constraint = margin_pool.version == MARGIN_POOL_VERSION @ ErrorCode::InvalidPoolVersion
+    )]
     pub margin_pool: Account<'info, MarginPool>,
 
     #[account(mut)]
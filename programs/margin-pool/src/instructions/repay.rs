@@ -18,7 +18,8 @@
 use anchor_lang::prelude::*;
 use anchor_spl::token::{self, Burn, Token, TokenAccount, Transfer};
 
-use crate::{events, state::PoolAction, ChangeKind, ErrorCode, MarginPool, TokenChange};
+use crate::state::{PoolAction, MARGIN_POOL_VERSION};
+use crate::{events, ChangeKind, ErrorCode, MarginPool, TokenChange};
 
 #[derive(Accounts)]
 pub struct Repay<'info> {
@@ -26,7 +27,9 @@ pub struct Repay<'info> {
     #[account(
         mut,
         has_one = loan_note_mint,
-        has_one = vault
+        has_one = vault,
+        constraint = margin_pool.version == MARGIN_POOL_VERSION @ ErrorCode::InvalidPoolVersion,
+        constraint = margin_pool.accepts_signer(&repayment_account_authority)? @ ErrorCode::WrongAirspace
     )]
     pub margin_pool: Box<Account<'info, MarginPool>>,
 
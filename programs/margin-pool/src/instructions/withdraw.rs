@@ -31,7 +31,9 @@ pub struct Withdraw<'info> {
     /// The pool to withdraw from
     #[account(mut,
               has_one = vault,
-              has_one = deposit_note_mint)]
+              has_one = deposit_note_mint,
+              constraint = margin_pool.version == MARGIN_POOL_VERSION @ ErrorCode::InvalidPoolVersion,
+              constraint = margin_pool.accepts_signer(&depositor)? @ ErrorCode::WrongAirspace)]
     pub margin_pool: Account<'info, MarginPool>,
 
     /// The vault for the pool, where tokens are held
@@ -99,6 +101,10 @@ pub fn withdraw_handler(
         return Err(ErrorCode::InterestAccrualBehind.into());
     }
 
+    // Checked here rather than in `MarginPool::withdraw`, so repayments using
+    // deposits are still allowed
+    pool.verify_price_band()?;
+
     let withdraw_amount = pool.calculate_full_amount(
         token::accessor::amount(&ctx.accounts.source.to_account_info())?,
         change,
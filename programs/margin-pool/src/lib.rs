@@ -22,9 +22,15 @@ mod state;
 mod util;
 use instructions::*;
 
-pub use state::{MarginPool, MarginPoolConfig, PoolFlags};
+pub use instructions::{ExchangeRate, PoolRates, EXCHANGE_RATE_EXPONENT};
+pub use state::{
+    pool_airspace_seed, MarginPool, MarginPoolConfig, PoolAction, PoolFlags, MARGIN_POOL_VERSION,
+};
 pub mod events;
 
+#[cfg(feature = "cpi")]
+pub mod views;
+
 declare_id!("JPPooLEqRo3NCSx82EdE2VZY5vUaSsgskpZPBHNGVLZ");
 
 pub mod authority {
@@ -42,6 +48,41 @@ mod jet_margin_pool {
         instructions::create_pool_handler(ctx, fee_destination)
     }
 
+    /// Create token metadata for the pool's deposit notes, so they can be identified
+    /// by wallets. The airspace authority becomes the update authority.
+    ///
+    /// This doesn't require the pool to be enabled, so it can be included in the same
+    /// transaction that creates the pool.
+    pub fn create_deposit_note_metadata(
+        ctx: Context<CreateDepositNoteMetadata>,
+        name: String,
+        symbol: String,
+        uri: String,
+    ) -> Result<()> {
+        instructions::create_deposit_note_metadata_handler(ctx, name, symbol, uri)
+    }
+
+    /// Get the current exchange rate for the pool's deposit notes, including any interest
+    /// not yet accrued, as an [ExchangeRate] in the return data.
+    ///
+    /// This does not modify the pool, so it can be used by other programs to value
+    /// deposit notes through CPI.
+    pub fn deposit_note_exchange_rate(ctx: Context<DepositNoteExchangeRate>) -> Result<()> {
+        instructions::deposit_note_exchange_rate_handler(ctx)
+    }
+
+    /// Get the current exchange rates, interest rates and utilization of the pool,
+    /// as [PoolRates] in the return data. This does not modify the pool.
+    pub fn pool_rates(ctx: Context<PoolRatesView>) -> Result<()> {
+        instructions::pool_rates_handler(ctx)
+    }
+
+    /// Migrate a pool created before airspaces into the default airspace, which
+    /// extends the pool account for its new fields.
+    pub fn migrate_pool(ctx: Context<MigratePool>) -> Result<()> {
+        instructions::migrate_pool_handler(ctx)
+    }
+
     /// Configure an existing pool
     pub fn configure(ctx: Context<Configure>, config: Option<MarginPoolConfig>) -> Result<()> {
         instructions::configure_handler(ctx, config)
@@ -88,7 +129,8 @@ mod jet_margin_pool {
         instructions::repay_handler(ctx, change_kind, amount)
     }
 
-    /// Update the pool position on a margin account
+    /// Update the pool position on a margin account, and pause borrows and
+    /// withdrawals if the token price is outside its band around the EMA
     pub fn margin_refresh_position(ctx: Context<MarginRefreshPosition>) -> Result<()> {
         instructions::margin_refresh_position_handler(ctx)
     }
@@ -228,4 +270,20 @@ pub enum ErrorCode {
 
     /// 141108 - Attempt repayment of more tokens than total outstanding
     RepaymentExceedsTotalOutstanding,
+
+    /// 141109 - The pool does not have the layout version required by the instruction
+    #[msg("The pool account has the wrong version, it may need to be migrated")]
+    InvalidPoolVersion,
+
+    /// 141110 - The oracle price is too far from its moving average
+    #[msg("The pool is paused while the token price is outside its expected band")]
+    PriceBandExceeded,
+
+    /// 141111 - The margin account is not in the airspace of the pool
+    #[msg("The margin account is not in the pool's airspace")]
+    WrongAirspace,
+
+    /// 141112 - A rate is too large to be returned as a fixed point number
+    #[msg("The rate is too large to be represented")]
+    RateOverflow,
 }
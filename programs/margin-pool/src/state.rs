@@ -29,7 +29,7 @@ use crate::{util, Amount, AmountKind, ChangeKind, ErrorCode, TokenChange};
 /// services lending/borrowing operations.
 #[account]
 #[repr(C, align(8))]
-#[derive(Debug, Default)]
+#[derive(Debug, Default, Clone)]
 pub struct MarginPool {
     pub version: u8,
 
@@ -81,6 +81,34 @@ pub struct MarginPool {
 
     /// The time the interest was last accrued up to
     pub accrued_until: i64,
+
+    /// The airspace the pool belongs to, whose authority is allowed to configure it
+    ///
+    /// Only set for pools with a version of at least 1, see [MARGIN_POOL_VERSION].
+    pub airspace: Pubkey,
+
+    /// Set while the oracle price is outside the configured band around its EMA,
+    /// which pauses new borrows and withdrawals from the pool
+    pub price_band_exceeded: bool,
+}
+
+/// The current version of the [MarginPool] layout
+///
+/// Pools created before airspaces existed have version 0, and must be migrated
+/// into the default airspace before they can be used.
+pub const MARGIN_POOL_VERSION: u8 = 1;
+
+/// The seed identifying the airspace in the address of a pool
+///
+/// Pools in the default airspace keep the address they had before airspaces were
+/// introduced, which was derived from only the token mint. An empty seed does not
+/// change the derived address.
+pub fn pool_airspace_seed(airspace: &Pubkey) -> &[u8] {
+    if *airspace == jet_airspace::DEFAULT_AIRSPACE {
+        &[]
+    } else {
+        airspace.as_ref()
+    }
 }
 
 #[cfg(any(test, feature = "cli"))]
@@ -89,8 +117,9 @@ impl Serialize for MarginPool {
     where
         S: Serializer,
     {
-        let mut s = serializer.serialize_struct("MarginPool", 13)?;
+        let mut s = serializer.serialize_struct("MarginPool", 15)?;
         s.serialize_field("version", &self.version)?;
+        s.serialize_field("airspace", &self.airspace.to_string())?;
         s.serialize_field("vault", &self.vault.to_string())?;
         s.serialize_field("feeDestination", &self.fee_destination.to_string())?;
         s.serialize_field("depositNoteMint", &self.deposit_note_mint.to_string())?;
@@ -106,19 +135,45 @@ impl Serialize for MarginPool {
         s.serialize_field("depositNotes", &self.deposit_notes)?;
         s.serialize_field("loanNotes", &self.loan_notes)?;
         s.serialize_field("accruedUntil", &self.accrued_until)?;
+        s.serialize_field("priceBandExceeded", &self.price_band_exceeded)?;
         s.end()
     }
 }
 
 impl MarginPool {
     /// Get the seeds needed to sign for the vault
-    pub fn signer_seeds(&self) -> Result<[&[u8]; 2]> {
+    pub fn signer_seeds(&self) -> Result<[&[u8]; 3]> {
         if self.flags().contains(PoolFlags::DISABLED) {
             msg!("the pool is currently disabled");
             return err!(ErrorCode::Disabled);
         }
 
-        Ok([self.token_mint.as_ref(), self.pool_bump.as_ref()])
+        Ok(self.authority_seeds())
+    }
+
+    /// Get the seeds needed to sign as the pool, even while it is disabled. Only for
+    /// administrative actions that don't move any tokens.
+    pub fn authority_seeds(&self) -> [&[u8]; 3] {
+        [
+            pool_airspace_seed(&self.airspace),
+            self.token_mint.as_ref(),
+            self.pool_bump.as_ref(),
+        ]
+    }
+
+    /// Check whether a signer may use the pool
+    ///
+    /// Margin accounts can only use pools in their own airspace, other signers
+    /// are not restricted.
+    pub fn accepts_signer(&self, signer: &AccountInfo) -> Result<bool> {
+        if *signer.owner != jet_margin::ID {
+            return Ok(true);
+        }
+
+        let margin_account = AccountLoader::<jet_margin::MarginAccount>::try_from(signer)?;
+        let airspace = margin_account.load()?.airspace;
+
+        Ok(airspace == self.airspace)
     }
 
     /// Record a deposit into the pool
@@ -148,6 +203,8 @@ impl MarginPool {
             return err!(ErrorCode::DepositsOnly);
         }
 
+        self.verify_price_band()?;
+
         self.deposit_tokens = self
             .deposit_tokens
             .checked_sub(amount.tokens)
@@ -280,6 +337,39 @@ impl MarginPool {
         fee_notes
     }
 
+    /// Check that the oracle price was within the configured band at the last refresh
+    pub fn verify_price_band(&self) -> Result<()> {
+        if self.price_band_exceeded {
+            msg!("the token price has moved too far from its moving average");
+            return err!(ErrorCode::PriceBandExceeded);
+        }
+
+        Ok(())
+    }
+
+    /// Update the circuit breaker with the latest oracle price and its EMA.
+    ///
+    /// Returns true if the state of the breaker changed.
+    pub fn update_price_band(&mut self, price: Number, ema_price: Number) -> bool {
+        let exceeded = match self.config.max_price_deviation {
+            0 => false,
+            _ if ema_price == Number::ZERO => true,
+            max_deviation => {
+                let deviation = match price > ema_price {
+                    true => price - ema_price,
+                    false => ema_price - price,
+                };
+
+                deviation / ema_price > Number::from_bps(max_deviation)
+            }
+        };
+
+        let changed = exceeded != self.price_band_exceeded;
+        self.price_band_exceeded = exceeded;
+
+        changed
+    }
+
     /// Calculate the prices for the deposit and loan notes, based on
     /// the price of the underlying token.
     pub fn calculate_prices(&self, pyth_price: &PriceFeed) -> Result<PriceResult> {
@@ -434,14 +524,14 @@ impl MarginPool {
     }
 
     /// Get the exchange rate for deposit note -> token
-    fn deposit_note_exchange_rate(&self) -> Number {
+    pub fn deposit_note_exchange_rate(&self) -> Number {
         let deposit_notes = std::cmp::max(1, self.deposit_notes);
         let total_value = std::cmp::max(Number::ONE, self.total_value());
         (total_value - *self.total_uncollected_fees()) / Number::from(deposit_notes)
     }
 
     /// Get the exchange rate for loan note -> token
-    fn loan_note_exchange_rate(&self) -> Number {
+    pub fn loan_note_exchange_rate(&self) -> Number {
         let loan_notes = std::cmp::max(1, self.loan_notes);
         let total_borrowed = std::cmp::max(Number::ONE, *self.total_borrowed());
         total_borrowed / Number::from(loan_notes)
@@ -575,8 +665,12 @@ pub struct MarginPoolConfig {
     /// The fee rate applied to interest payments collected
     pub management_fee_rate: u16,
 
+    /// The maximum deviation of the oracle price from its EMA, in basis points,
+    /// before new borrows and withdrawals are paused. Zero disables the check.
+    pub max_price_deviation: u16,
+
     /// Unused
-    pub reserved: u64,
+    pub reserved: [u8; 6],
 }
 
 bitflags::bitflags! {
@@ -850,6 +944,69 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn default_airspace_pools_keep_legacy_address() {
+        let mint = Pubkey::new_unique();
+        let legacy = Pubkey::find_program_address(&[mint.as_ref()], &crate::ID);
+
+        let default_seeds = [
+            pool_airspace_seed(&jet_airspace::DEFAULT_AIRSPACE),
+            mint.as_ref(),
+        ];
+        assert_eq!(
+            legacy,
+            Pubkey::find_program_address(&default_seeds, &crate::ID)
+        );
+
+        let other_airspace = Pubkey::new_unique();
+        let other_seeds = [pool_airspace_seed(&other_airspace), mint.as_ref()];
+        assert_ne!(
+            legacy,
+            Pubkey::find_program_address(&other_seeds, &crate::ID)
+        );
+    }
+
+    #[test]
+    fn disabled_pool_keeps_authority_seeds() {
+        let enabled = MarginPool::default();
+        let mut disabled = enabled.clone();
+        disabled.config.flags = PoolFlags::DISABLED.bits();
+
+        assert!(disabled.signer_seeds().is_err());
+        assert_eq!(enabled.signer_seeds().unwrap(), disabled.authority_seeds());
+    }
+
+    #[test]
+    fn price_band_trips_and_clears() {
+        let mut margin_pool = MarginPool::default();
+        let ema = Number::from(100u64);
+
+        // disabled by default
+        assert!(!margin_pool.update_price_band(Number::from(200u64), ema));
+        margin_pool.verify_price_band().unwrap();
+
+        margin_pool.config.max_price_deviation = 1_000;
+
+        assert!(!margin_pool.update_price_band(Number::from(109u64), ema));
+        assert!(margin_pool.update_price_band(Number::from(111u64), ema));
+        assert!(margin_pool.verify_price_band().is_err());
+
+        // borrows are paused, but repayments are not
+        let amount = FullAmount {
+            tokens: 1,
+            notes: 1,
+        };
+        margin_pool.config.flags = PoolFlags::ALLOW_LENDING.bits();
+        margin_pool.deposit(&amount);
+        assert!(margin_pool.borrow(&amount).is_err());
+
+        assert!(!margin_pool.update_price_band(Number::from(89u64), ema));
+        assert!(margin_pool.update_price_band(Number::from(91u64), ema));
+        margin_pool.verify_price_band().unwrap();
+        margin_pool.borrow(&amount).unwrap();
+        margin_pool.repay(&amount).unwrap();
+    }
+
     #[test]
     fn margin_pool_serialization() {
         let pool = MarginPool::default();
@@ -858,10 +1015,12 @@ mod tests {
             &[
                 Token::Struct {
                     name: "MarginPool",
-                    len: 13,
+                    len: 15,
                 },
                 Token::Str("version"),
                 Token::U8(0),
+                Token::Str("airspace"),
+                Token::Str("11111111111111111111111111111111"),
                 Token::Str("vault"),
                 Token::Str("11111111111111111111111111111111"),
                 Token::Str("feeDestination"),
@@ -886,6 +1045,8 @@ mod tests {
                 Token::U64(0),
                 Token::Str("accruedUntil"),
                 Token::I64(0),
+                Token::Str("priceBandExceeded"),
+                Token::Bool(false),
                 Token::StructEnd,
             ],
         );
@@ -0,0 +1,41 @@
+// SPDX-License-Identifier: AGPL-3.0-or-later
+//
+// Copyright (C) 2022 JET PROTOCOL HOLDINGS, LLC.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+//! Helpers for other programs to get pool information through CPI
+
+use anchor_lang::prelude::*;
+
+use jet_margin::views::read_return_data;
+
+use crate::cpi::accounts::{DepositNoteExchangeRate, PoolRatesView};
+use crate::{ExchangeRate, PoolRates};
+
+/// Get the current exchange rate for a pool's deposit notes
+pub fn deposit_note_exchange_rate<'info>(
+    ctx: CpiContext<'_, '_, '_, 'info, DepositNoteExchangeRate<'info>>,
+) -> Result<ExchangeRate> {
+    crate::cpi::deposit_note_exchange_rate(ctx)?;
+    read_return_data(&crate::ID)
+}
+
+/// Get the current exchange and interest rates for a pool
+pub fn pool_rates<'info>(
+    ctx: CpiContext<'_, '_, '_, 'info, PoolRatesView<'info>>,
+) -> Result<PoolRates> {
+    crate::cpi::pool_rates(ctx)?;
+    read_return_data(&crate::ID)
+}
@@ -0,0 +1,32 @@
+// SPDX-License-Identifier: AGPL-3.0-or-later
+//
+// Copyright (C) 2022 JET PROTOCOL HOLDINGS, LLC.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+mod cancel_order;
+mod close_open_orders;
+mod init_open_orders;
+mod new_order;
+mod refresh_open_orders;
+mod register_market;
+mod settle_funds;
+
+pub use cancel_order::*;
+pub use close_open_orders::*;
+pub use init_open_orders::*;
+pub use new_order::*;
+pub use refresh_open_orders::*;
+pub use register_market::*;
+pub use settle_funds::*;
@@ -0,0 +1,135 @@
+// SPDX-License-Identifier: AGPL-3.0-or-later
+//
+// Copyright (C) 2022 JET PROTOCOL HOLDINGS, LLC.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+use std::convert::TryFrom;
+
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::program::invoke;
+use anchor_spl::dex::{
+    self,
+    serum_dex::{self, matching::Side},
+    Dex,
+};
+
+use jet_margin::MarginAccount;
+
+use crate::{positions::OpenOrdersPositions, ErrorCode};
+
+#[derive(Accounts)]
+pub struct CancelOrderV2<'info> {
+    /// The margin account that placed the order
+    #[account(signer)]
+    pub margin_account: AccountLoader<'info, MarginAccount>,
+
+    /// The margin account's positions for the tokens held in open orders
+    #[account(constraint = positions.market_info.market == market.key() @ ErrorCode::InvalidMarket)]
+    pub positions: OpenOrdersPositions<'info>,
+
+    /// CHECK: constraint on positions
+    #[account(mut)]
+    pub market: AccountInfo<'info>,
+
+    /// CHECK: checked by positions
+    #[account(mut)]
+    pub open_orders: AccountInfo<'info>,
+
+    /// CHECK: validated by the dex
+    #[account(mut)]
+    pub bids: AccountInfo<'info>,
+
+    /// CHECK: validated by the dex
+    #[account(mut)]
+    pub asks: AccountInfo<'info>,
+
+    /// CHECK: validated by the dex
+    #[account(mut)]
+    pub event_queue: AccountInfo<'info>,
+
+    pub dex_program: Program<'info, Dex>,
+}
+
+impl<'info> CancelOrderV2<'info> {
+    fn cancel_order(&self, side: Side, order_id: u128) -> Result<()> {
+        dex::cancel_order_v2(
+            CpiContext::new(
+                self.dex_program.to_account_info(),
+                dex::CancelOrderV2 {
+                    market: self.market.to_account_info(),
+                    market_bids: self.bids.to_account_info(),
+                    market_asks: self.asks.to_account_info(),
+                    open_orders: self.open_orders.to_account_info(),
+                    open_orders_authority: self.margin_account.to_account_info(),
+                    event_queue: self.event_queue.to_account_info(),
+                },
+            ),
+            side,
+            order_id,
+        )
+    }
+
+    fn cancel_order_by_client_id(&self, client_order_id: u64) -> Result<()> {
+        let ix = serum_dex::instruction::cancel_order_by_client_order_id(
+            self.dex_program.key,
+            self.market.key,
+            self.bids.key,
+            self.asks.key,
+            self.open_orders.key,
+            &self.margin_account.key(),
+            self.event_queue.key,
+            client_order_id,
+        )
+        .map_err(ProgramError::from)?;
+
+        invoke(
+            &ix,
+            &[
+                self.market.to_account_info(),
+                self.bids.to_account_info(),
+                self.asks.to_account_info(),
+                self.open_orders.to_account_info(),
+                self.margin_account.to_account_info(),
+                self.event_queue.to_account_info(),
+            ],
+        )?;
+
+        Ok(())
+    }
+}
+
+pub fn cancel_order_v2_handler(
+    ctx: Context<CancelOrderV2>,
+    side: u8,
+    order_id: u128,
+) -> Result<()> {
+    let side = Side::try_from(side).map_err(|_| error!(ErrorCode::InvalidSide))?;
+    ctx.accounts.cancel_order(side, order_id)?;
+
+    ctx.accounts
+        .positions
+        .sync(&ctx.accounts.margin_account, &ctx.accounts.open_orders)
+}
+
+pub fn cancel_order_by_client_id_v2_handler(
+    ctx: Context<CancelOrderV2>,
+    client_order_id: u64,
+) -> Result<()> {
+    ctx.accounts.cancel_order_by_client_id(client_order_id)?;
+
+    ctx.accounts
+        .positions
+        .sync(&ctx.accounts.margin_account, &ctx.accounts.open_orders)
+}
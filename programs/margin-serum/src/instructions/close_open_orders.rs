@@ -0,0 +1,137 @@
+// SPDX-License-Identifier: AGPL-3.0-or-later
+//
+// Copyright (C) 2022 JET PROTOCOL HOLDINGS, LLC.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+use anchor_lang::prelude::*;
+use anchor_spl::dex::{self, Dex};
+use anchor_spl::token::{self, CloseAccount, Token, TokenAccount};
+
+use jet_margin::{AdapterResult, MarginAccount, PositionChange};
+
+use crate::{state::MarketInfo, ErrorCode};
+
+#[derive(Accounts)]
+pub struct CloseOpenOrders<'info> {
+    /// The margin account that owns the open orders
+    #[account(signer)]
+    pub margin_account: AccountLoader<'info, MarginAccount>,
+
+    /// The registration of the market
+    #[account(
+        has_one = market,
+        has_one = base_note_mint @ ErrorCode::WrongNoteAccount,
+        has_one = quote_note_mint @ ErrorCode::WrongNoteAccount,
+    )]
+    pub market_info: Box<Account<'info, MarketInfo>>,
+
+    /// CHECK: has_one on market info
+    pub market: AccountInfo<'info>,
+
+    /// The open orders account to be closed, which must not hold any tokens
+    /// CHECK: seeds
+    #[account(mut,
+              seeds = [
+                margin_account.key().as_ref(),
+                market.key().as_ref(),
+                b"open-orders".as_ref()
+              ],
+              bump)]
+    pub open_orders: AccountInfo<'info>,
+
+    /// The account to receive the rent of the closed accounts
+    /// CHECK: any account may receive the rent
+    #[account(mut)]
+    pub destination: AccountInfo<'info>,
+
+    /// CHECK: has_one on market info
+    pub base_note_mint: AccountInfo<'info>,
+
+    /// CHECK: has_one on market info
+    pub quote_note_mint: AccountInfo<'info>,
+
+    /// The margin account's position for base tokens held by the open orders account
+    #[account(mut,
+              seeds = [
+                margin_account.key().as_ref(),
+                base_note_mint.key().as_ref()
+              ],
+              bump)]
+    pub base_notes: Box<Account<'info, TokenAccount>>,
+
+    /// The margin account's position for quote tokens held by the open orders account
+    #[account(mut,
+              seeds = [
+                margin_account.key().as_ref(),
+                quote_note_mint.key().as_ref()
+              ],
+              bump)]
+    pub quote_notes: Box<Account<'info, TokenAccount>>,
+
+    pub dex_program: Program<'info, Dex>,
+    pub token_program: Program<'info, Token>,
+}
+
+impl<'info> CloseOpenOrders<'info> {
+    fn close_open_orders(&self) -> Result<()> {
+        dex::close_open_orders(CpiContext::new(
+            self.dex_program.to_account_info(),
+            dex::CloseOpenOrders {
+                open_orders: self.open_orders.to_account_info(),
+                authority: self.margin_account.to_account_info(),
+                destination: self.destination.to_account_info(),
+                market: self.market.to_account_info(),
+            },
+        ))
+    }
+
+    fn close_notes(&self, notes: AccountInfo<'info>) -> Result<()> {
+        token::close_account(CpiContext::new_with_signer(
+            self.token_program.to_account_info(),
+            CloseAccount {
+                account: notes,
+                destination: self.destination.to_account_info(),
+                authority: self.market_info.to_account_info(),
+            },
+            &[&self.market_info.signer_seeds()],
+        ))
+    }
+}
+
+pub fn close_open_orders_handler(ctx: Context<CloseOpenOrders>) -> Result<()> {
+    // the dex refuses to close an account that still holds tokens, so the notes
+    // are empty as long as the positions were synced
+    ctx.accounts.close_open_orders()?;
+    ctx.accounts
+        .close_notes(ctx.accounts.base_notes.to_account_info())?;
+    ctx.accounts
+        .close_notes(ctx.accounts.quote_notes.to_account_info())?;
+
+    jet_margin::write_adapter_result(
+        &*ctx.accounts.margin_account.load()?,
+        &AdapterResult {
+            position_changes: vec![
+                (
+                    ctx.accounts.base_note_mint.key(),
+                    vec![PositionChange::Close(ctx.accounts.base_notes.key())],
+                ),
+                (
+                    ctx.accounts.quote_note_mint.key(),
+                    vec![PositionChange::Close(ctx.accounts.quote_notes.key())],
+                ),
+            ],
+        },
+    )
+}
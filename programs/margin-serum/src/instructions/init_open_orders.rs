@@ -0,0 +1,155 @@
+// SPDX-License-Identifier: AGPL-3.0-or-later
+//
+// Copyright (C) 2022 JET PROTOCOL HOLDINGS, LLC.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+use anchor_lang::prelude::*;
+use anchor_lang::system_program::{self, CreateAccount};
+use anchor_spl::dex::{self, Dex};
+use anchor_spl::token::{Mint, Token, TokenAccount};
+
+use jet_margin::{AdapterResult, MarginAccount, PositionChange};
+
+use crate::state::{MarketInfo, OpenOrdersBalances};
+
+#[derive(Accounts)]
+pub struct InitOpenOrders<'info> {
+    /// The margin account that will own the open orders
+    #[account(signer)]
+    pub margin_account: AccountLoader<'info, MarginAccount>,
+
+    /// The payer of rent for new accounts
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    /// The registration of the market to be traded
+    #[account(has_one = market, has_one = base_note_mint, has_one = quote_note_mint)]
+    pub market_info: Box<Account<'info, MarketInfo>>,
+
+    /// The serum market
+    /// CHECK: has_one on market info
+    pub market: AccountInfo<'info>,
+
+    /// The open orders account to be created for the margin account
+    /// CHECK: created and initialized by this instruction
+    #[account(mut,
+              seeds = [
+                margin_account.key().as_ref(),
+                market.key().as_ref(),
+                b"open-orders".as_ref()
+              ],
+              bump)]
+    pub open_orders: AccountInfo<'info>,
+
+    /// The mint for notes representing the base tokens held by the open orders account
+    pub base_note_mint: Box<Account<'info, Mint>>,
+
+    /// The mint for notes representing the quote tokens held by the open orders account
+    pub quote_note_mint: Box<Account<'info, Mint>>,
+
+    /// The margin account's position for base tokens held by the open orders account
+    #[account(init,
+              seeds = [
+                margin_account.key().as_ref(),
+                base_note_mint.key().as_ref()
+              ],
+              bump,
+              token::mint = base_note_mint,
+              token::authority = market_info,
+              payer = payer)]
+    pub base_notes: Box<Account<'info, TokenAccount>>,
+
+    /// The margin account's position for quote tokens held by the open orders account
+    #[account(init,
+              seeds = [
+                margin_account.key().as_ref(),
+                quote_note_mint.key().as_ref()
+              ],
+              bump,
+              token::mint = quote_note_mint,
+              token::authority = market_info,
+              payer = payer)]
+    pub quote_notes: Box<Account<'info, TokenAccount>>,
+
+    pub dex_program: Program<'info, Dex>,
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+    pub rent: Sysvar<'info, Rent>,
+    // remaining_accounts: the margin `TokenConfig` for each note mint, so that
+    // the notes can be registered as positions
+}
+
+impl<'info> InitOpenOrders<'info> {
+    fn create_open_orders_account(&self, bump: u8) -> Result<()> {
+        let margin_account = self.margin_account.key();
+        let market = self.market.key();
+        let seeds = [
+            margin_account.as_ref(),
+            market.as_ref(),
+            b"open-orders".as_ref(),
+            &[bump],
+        ];
+        let space = OpenOrdersBalances::ACCOUNT_SIZE;
+
+        system_program::create_account(
+            CpiContext::new_with_signer(
+                self.system_program.to_account_info(),
+                CreateAccount {
+                    from: self.payer.to_account_info(),
+                    to: self.open_orders.to_account_info(),
+                },
+                &[&seeds],
+            ),
+            self.rent.minimum_balance(space),
+            space as u64,
+            self.dex_program.key,
+        )
+    }
+
+    fn init_open_orders(&self) -> Result<()> {
+        dex::init_open_orders(CpiContext::new(
+            self.dex_program.to_account_info(),
+            dex::InitOpenOrders {
+                open_orders: self.open_orders.to_account_info(),
+                authority: self.margin_account.to_account_info(),
+                market: self.market.to_account_info(),
+                rent: self.rent.to_account_info(),
+            },
+        ))
+    }
+}
+
+pub fn init_open_orders_handler(ctx: Context<InitOpenOrders>) -> Result<()> {
+    ctx.accounts
+        .create_open_orders_account(*ctx.bumps.get("open_orders").unwrap())?;
+    ctx.accounts.init_open_orders()?;
+
+    // The notes start out empty, so they only need to be registered with the margin account
+    jet_margin::write_adapter_result(
+        &*ctx.accounts.margin_account.load()?,
+        &AdapterResult {
+            position_changes: vec![
+                (
+                    ctx.accounts.base_note_mint.key(),
+                    vec![PositionChange::Register(ctx.accounts.base_notes.key())],
+                ),
+                (
+                    ctx.accounts.quote_note_mint.key(),
+                    vec![PositionChange::Register(ctx.accounts.quote_notes.key())],
+                ),
+            ],
+        },
+    )
+}
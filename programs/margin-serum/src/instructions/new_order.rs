@@ -0,0 +1,166 @@
+// SPDX-License-Identifier: AGPL-3.0-or-later
+//
+// Copyright (C) 2022 JET PROTOCOL HOLDINGS, LLC.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+use std::convert::TryFrom;
+use std::num::NonZeroU64;
+
+use anchor_lang::prelude::*;
+use anchor_spl::dex::{
+    self,
+    serum_dex::{
+        instruction::SelfTradeBehavior,
+        matching::{OrderType, Side},
+    },
+    Dex,
+};
+
+use jet_margin::MarginAccount;
+
+use crate::{positions::OpenOrdersPositions, ErrorCode};
+
+#[derive(Accounts)]
+pub struct NewOrderV3<'info> {
+    /// The margin account placing the order
+    #[account(signer)]
+    pub margin_account: AccountLoader<'info, MarginAccount>,
+
+    /// The margin account's positions for the tokens held in open orders
+    #[account(constraint = positions.market_info.market == market.key() @ ErrorCode::InvalidMarket)]
+    pub positions: OpenOrdersPositions<'info>,
+
+    /// CHECK: constraint on positions
+    #[account(mut)]
+    pub market: AccountInfo<'info>,
+
+    /// CHECK: checked by positions
+    #[account(mut)]
+    pub open_orders: AccountInfo<'info>,
+
+    /// CHECK: validated by the dex
+    #[account(mut)]
+    pub request_queue: AccountInfo<'info>,
+
+    /// CHECK: validated by the dex
+    #[account(mut)]
+    pub event_queue: AccountInfo<'info>,
+
+    /// CHECK: validated by the dex
+    #[account(mut)]
+    pub bids: AccountInfo<'info>,
+
+    /// CHECK: validated by the dex
+    #[account(mut)]
+    pub asks: AccountInfo<'info>,
+
+    /// The margin account's token account paying for the order, holding
+    /// base tokens for asks and quote tokens for bids
+    /// CHECK: validated by the dex
+    #[account(mut)]
+    pub payer: AccountInfo<'info>,
+
+    /// CHECK: validated by the dex
+    #[account(mut)]
+    pub base_vault: AccountInfo<'info>,
+
+    /// CHECK: validated by the dex
+    #[account(mut)]
+    pub quote_vault: AccountInfo<'info>,
+
+    pub dex_program: Program<'info, Dex>,
+    pub rent: Sysvar<'info, Rent>,
+}
+
+impl<'info> NewOrderV3<'info> {
+    #[allow(clippy::too_many_arguments)]
+    fn new_order(
+        &self,
+        side: Side,
+        limit_price: NonZeroU64,
+        max_coin_qty: NonZeroU64,
+        max_native_pc_qty_including_fees: NonZeroU64,
+        self_trade_behavior: SelfTradeBehavior,
+        order_type: OrderType,
+        client_order_id: u64,
+        limit: u16,
+    ) -> Result<()> {
+        dex::new_order_v3(
+            CpiContext::new(
+                self.dex_program.to_account_info(),
+                dex::NewOrderV3 {
+                    market: self.market.to_account_info(),
+                    open_orders: self.open_orders.to_account_info(),
+                    request_queue: self.request_queue.to_account_info(),
+                    event_queue: self.event_queue.to_account_info(),
+                    market_bids: self.bids.to_account_info(),
+                    market_asks: self.asks.to_account_info(),
+                    order_payer_token_account: self.payer.to_account_info(),
+                    open_orders_authority: self.margin_account.to_account_info(),
+                    coin_vault: self.base_vault.to_account_info(),
+                    pc_vault: self.quote_vault.to_account_info(),
+                    token_program: self.positions.token_program.to_account_info(),
+                    rent: self.rent.to_account_info(),
+                },
+            ),
+            side,
+            limit_price,
+            max_coin_qty,
+            max_native_pc_qty_including_fees,
+            self_trade_behavior,
+            order_type,
+            client_order_id,
+            limit,
+        )
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn new_order_v3_handler(
+    ctx: Context<NewOrderV3>,
+    side: u8,
+    limit_price: u64,
+    max_coin_qty: u64,
+    max_native_pc_qty_including_fees: u64,
+    self_trade_behavior: u8,
+    order_type: u8,
+    client_order_id: u64,
+    limit: u16,
+) -> Result<()> {
+    let side = Side::try_from(side).map_err(|_| error!(ErrorCode::InvalidSide))?;
+    let self_trade_behavior = SelfTradeBehavior::try_from(self_trade_behavior)
+        .map_err(|_| error!(ErrorCode::InvalidSelfTradeBehavior))?;
+    let order_type =
+        OrderType::try_from(order_type).map_err(|_| error!(ErrorCode::InvalidOrderType))?;
+
+    ctx.accounts.new_order(
+        side,
+        non_zero(limit_price)?,
+        non_zero(max_coin_qty)?,
+        non_zero(max_native_pc_qty_including_fees)?,
+        self_trade_behavior,
+        order_type,
+        client_order_id,
+        limit,
+    )?;
+
+    ctx.accounts
+        .positions
+        .sync(&ctx.accounts.margin_account, &ctx.accounts.open_orders)
+}
+
+fn non_zero(value: u64) -> Result<NonZeroU64> {
+    NonZeroU64::new(value).ok_or_else(|| error!(ErrorCode::InvalidOrderQuantity))
+}
@@ -0,0 +1,43 @@
+// SPDX-License-Identifier: AGPL-3.0-or-later
+//
+// Copyright (C) 2022 JET PROTOCOL HOLDINGS, LLC.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+use anchor_lang::prelude::*;
+
+use jet_margin::MarginAccount;
+
+use crate::positions::OpenOrdersPositions;
+
+#[derive(Accounts)]
+pub struct RefreshOpenOrders<'info> {
+    /// The margin account that owns the open orders
+    pub margin_account: AccountLoader<'info, MarginAccount>,
+
+    /// The margin account's positions for the tokens held in open orders
+    pub positions: OpenOrdersPositions<'info>,
+
+    /// CHECK: checked by positions
+    pub open_orders: AccountInfo<'info>,
+}
+
+/// Orders are filled when the market's event queue is consumed, without any
+/// involvement of the margin account. The positions are refreshed to reflect
+/// the tokens received by the open orders account.
+pub fn refresh_open_orders_handler(ctx: Context<RefreshOpenOrders>) -> Result<()> {
+    ctx.accounts
+        .positions
+        .sync(&ctx.accounts.margin_account, &ctx.accounts.open_orders)
+}
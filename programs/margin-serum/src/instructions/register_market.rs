@@ -0,0 +1,148 @@
+// SPDX-License-Identifier: AGPL-3.0-or-later
+//
+// Copyright (C) 2022 JET PROTOCOL HOLDINGS, LLC.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+use anchor_lang::prelude::*;
+use anchor_spl::dex::{serum_dex::state::Market, Dex};
+use anchor_spl::token::{Mint, Token};
+
+use jet_airspace::state::Airspace;
+
+use crate::{
+    state::{read_address, MarketInfo},
+    ErrorCode,
+};
+
+#[derive(Accounts)]
+pub struct RegisterMarket<'info> {
+    /// The authority over the airspace, which must sign
+    pub authority: Signer<'info>,
+
+    /// The airspace the market is being registered in
+    #[account(has_one = authority @ ErrorCode::WrongAirspaceAuthority)]
+    pub airspace: Account<'info, Airspace>,
+
+    /// The payer of rent for new accounts
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    /// The registration of the market to be created
+    #[account(
+        init,
+        seeds = [
+            airspace.key().as_ref(),
+            market.key().as_ref()
+        ],
+        bump,
+        payer = payer,
+        space = 8 + std::mem::size_of::<MarketInfo>(),
+    )]
+    pub market_info: Box<Account<'info, MarketInfo>>,
+
+    /// The serum market
+    /// CHECK: loaded by the dex state parser
+    pub market: AccountInfo<'info>,
+
+    /// The token bought and sold on the market
+    pub base_mint: Box<Account<'info, Mint>>,
+
+    /// The token used to price the base token
+    pub quote_mint: Box<Account<'info, Mint>>,
+
+    /// The mint for notes representing the base tokens held by open orders accounts
+    #[account(init,
+              seeds = [
+                market_info.key().as_ref(),
+                b"base-notes".as_ref()
+              ],
+              bump,
+              mint::decimals = base_mint.decimals,
+              mint::authority = market_info,
+              payer = payer)]
+    pub base_note_mint: Box<Account<'info, Mint>>,
+
+    /// The mint for notes representing the quote tokens held by open orders accounts
+    #[account(init,
+              seeds = [
+                market_info.key().as_ref(),
+                b"quote-notes".as_ref()
+              ],
+              bump,
+              mint::decimals = quote_mint.decimals,
+              mint::authority = market_info,
+              payer = payer)]
+    pub quote_note_mint: Box<Account<'info, Mint>>,
+
+    /// The pyth price account for the base token
+    /// CHECK: loaded as a pyth price feed
+    pub base_oracle: AccountInfo<'info>,
+
+    /// The pyth price account for the quote token
+    /// CHECK: loaded as a pyth price feed
+    pub quote_oracle: AccountInfo<'info>,
+
+    pub dex_program: Program<'info, Dex>,
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+    pub rent: Sysvar<'info, Rent>,
+}
+
+impl<'info> RegisterMarket<'info> {
+    fn verify_market(&self) -> Result<()> {
+        let market = Market::load(&self.market, self.dex_program.key, false).map_err(|e| {
+            msg!("the market account is not valid: {:?}", e);
+            error!(ErrorCode::InvalidMarket)
+        })?;
+
+        if read_address(market.coin_mint) != self.base_mint.key()
+            || read_address(market.pc_mint) != self.quote_mint.key()
+        {
+            return err!(ErrorCode::WrongMarketMints);
+        }
+
+        Ok(())
+    }
+
+    fn verify_oracle(oracle: &AccountInfo) -> Result<()> {
+        match pyth_sdk_solana::load_price_feed_from_account_info(oracle) {
+            Ok(_) => Ok(()),
+            Err(e) => {
+                msg!("the oracle account is not valid: {:?}", e);
+                err!(ErrorCode::InvalidOracle)
+            }
+        }
+    }
+}
+
+pub fn register_market_handler(ctx: Context<RegisterMarket>) -> Result<()> {
+    ctx.accounts.verify_market()?;
+    RegisterMarket::verify_oracle(&ctx.accounts.base_oracle)?;
+    RegisterMarket::verify_oracle(&ctx.accounts.quote_oracle)?;
+
+    let info = &mut ctx.accounts.market_info;
+
+    info.airspace = ctx.accounts.airspace.key();
+    info.bump[0] = *ctx.bumps.get("market_info").unwrap();
+    info.market = ctx.accounts.market.key();
+    info.base_mint = ctx.accounts.base_mint.key();
+    info.quote_mint = ctx.accounts.quote_mint.key();
+    info.base_note_mint = ctx.accounts.base_note_mint.key();
+    info.quote_note_mint = ctx.accounts.quote_note_mint.key();
+    info.base_oracle = ctx.accounts.base_oracle.key();
+    info.quote_oracle = ctx.accounts.quote_oracle.key();
+
+    Ok(())
+}
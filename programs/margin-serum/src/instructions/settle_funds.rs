@@ -0,0 +1,92 @@
+// SPDX-License-Identifier: AGPL-3.0-or-later
+//
+// Copyright (C) 2022 JET PROTOCOL HOLDINGS, LLC.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+use anchor_lang::prelude::*;
+use anchor_spl::dex::{self, Dex};
+
+use jet_margin::MarginAccount;
+
+use crate::{positions::OpenOrdersPositions, ErrorCode};
+
+#[derive(Accounts)]
+pub struct SettleFunds<'info> {
+    /// The margin account that owns the open orders
+    #[account(signer)]
+    pub margin_account: AccountLoader<'info, MarginAccount>,
+
+    /// The margin account's positions for the tokens held in open orders
+    #[account(constraint = positions.market_info.market == market.key() @ ErrorCode::InvalidMarket)]
+    pub positions: OpenOrdersPositions<'info>,
+
+    /// CHECK: constraint on positions
+    #[account(mut)]
+    pub market: AccountInfo<'info>,
+
+    /// CHECK: checked by positions
+    #[account(mut)]
+    pub open_orders: AccountInfo<'info>,
+
+    /// CHECK: validated by the dex
+    #[account(mut)]
+    pub base_vault: AccountInfo<'info>,
+
+    /// CHECK: validated by the dex
+    #[account(mut)]
+    pub quote_vault: AccountInfo<'info>,
+
+    /// The margin account's token account to receive the free base tokens
+    /// CHECK: validated by the dex
+    #[account(mut)]
+    pub base_wallet: AccountInfo<'info>,
+
+    /// The margin account's token account to receive the free quote tokens
+    /// CHECK: validated by the dex
+    #[account(mut)]
+    pub quote_wallet: AccountInfo<'info>,
+
+    /// CHECK: validated by the dex
+    pub vault_signer: AccountInfo<'info>,
+
+    pub dex_program: Program<'info, Dex>,
+}
+
+impl<'info> SettleFunds<'info> {
+    fn settle_funds(&self) -> Result<()> {
+        dex::settle_funds(CpiContext::new(
+            self.dex_program.to_account_info(),
+            dex::SettleFunds {
+                market: self.market.to_account_info(),
+                open_orders: self.open_orders.to_account_info(),
+                open_orders_authority: self.margin_account.to_account_info(),
+                coin_vault: self.base_vault.to_account_info(),
+                pc_vault: self.quote_vault.to_account_info(),
+                coin_wallet: self.base_wallet.to_account_info(),
+                pc_wallet: self.quote_wallet.to_account_info(),
+                vault_signer: self.vault_signer.to_account_info(),
+                token_program: self.positions.token_program.to_account_info(),
+            },
+        ))
+    }
+}
+
+pub fn settle_funds_handler(ctx: Context<SettleFunds>) -> Result<()> {
+    ctx.accounts.settle_funds()?;
+
+    ctx.accounts
+        .positions
+        .sync(&ctx.accounts.margin_account, &ctx.accounts.open_orders)
+}
@@ -0,0 +1,134 @@
+// SPDX-License-Identifier: AGPL-3.0-or-later
+//
+// Copyright (C) 2022 JET PROTOCOL HOLDINGS, LLC.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+use anchor_lang::prelude::*;
+
+declare_id!("JPSRM5EALmzGY2o37MZNv4amyXQf1bqs9a1YorvDvKD");
+
+mod instructions;
+mod positions;
+mod state;
+use instructions::*;
+use positions::*;
+
+pub use positions::OpenOrdersPositions;
+pub use state::MarketInfo;
+
+#[program]
+mod jet_margin_serum {
+    use super::*;
+
+    /// Register a serum market for trading by margin accounts within an airspace
+    pub fn register_market(ctx: Context<RegisterMarket>) -> Result<()> {
+        register_market_handler(ctx)
+    }
+
+    /// Create the open orders account used by a margin account to trade on a market
+    pub fn init_open_orders(ctx: Context<InitOpenOrders>) -> Result<()> {
+        init_open_orders_handler(ctx)
+    }
+
+    /// Place a limit order on the market
+    #[allow(clippy::too_many_arguments)]
+    pub fn new_order_v3(
+        ctx: Context<NewOrderV3>,
+        side: u8,
+        limit_price: u64,
+        max_coin_qty: u64,
+        max_native_pc_qty_including_fees: u64,
+        self_trade_behavior: u8,
+        order_type: u8,
+        client_order_id: u64,
+        limit: u16,
+    ) -> Result<()> {
+        new_order_v3_handler(
+            ctx,
+            side,
+            limit_price,
+            max_coin_qty,
+            max_native_pc_qty_including_fees,
+            self_trade_behavior,
+            order_type,
+            client_order_id,
+            limit,
+        )
+    }
+
+    /// Cancel an order by its order id
+    pub fn cancel_order_v2(ctx: Context<CancelOrderV2>, side: u8, order_id: u128) -> Result<()> {
+        cancel_order_v2_handler(ctx, side, order_id)
+    }
+
+    /// Cancel an order by the id assigned by the client when it was placed
+    pub fn cancel_order_by_client_id_v2(
+        ctx: Context<CancelOrderV2>,
+        client_order_id: u64,
+    ) -> Result<()> {
+        cancel_order_by_client_id_v2_handler(ctx, client_order_id)
+    }
+
+    /// Move the free balances of the open orders account into the margin account
+    pub fn settle_funds(ctx: Context<SettleFunds>) -> Result<()> {
+        settle_funds_handler(ctx)
+    }
+
+    /// Close an empty open orders account, and its positions in the margin account
+    pub fn close_open_orders(ctx: Context<CloseOpenOrders>) -> Result<()> {
+        close_open_orders_handler(ctx)
+    }
+
+    /// Update the value of the open orders positions, after orders were filled
+    pub fn refresh_open_orders(ctx: Context<RefreshOpenOrders>) -> Result<()> {
+        refresh_open_orders_handler(ctx)
+    }
+}
+
+#[error_code]
+pub enum ErrorCode {
+    #[msg("The order type is not valid")]
+    InvalidOrderType,
+
+    #[msg("The self trade behavior is not valid")]
+    InvalidSelfTradeBehavior,
+
+    #[msg("The order side is not valid")]
+    InvalidSide,
+
+    #[msg("Order prices and quantities must be greater than zero")]
+    InvalidOrderQuantity,
+
+    #[msg("The account is not a valid serum market")]
+    InvalidMarket,
+
+    #[msg("The market does not trade the provided token mints")]
+    WrongMarketMints,
+
+    #[msg("The oracle account is not valid")]
+    InvalidOracle,
+
+    #[msg("The oracle does not have a current price")]
+    PriceMissing,
+
+    #[msg("The airspace is not managed by this authority")]
+    WrongAirspaceAuthority,
+
+    #[msg("The open orders account does not belong to this margin account and market")]
+    WrongOpenOrders,
+
+    #[msg("The note account does not belong to this margin account and market")]
+    WrongNoteAccount,
+}
@@ -0,0 +1,186 @@
+// SPDX-License-Identifier: AGPL-3.0-or-later
+//
+// Copyright (C) 2022 JET PROTOCOL HOLDINGS, LLC.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+use std::cmp::Ordering;
+
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Burn, MintTo, Token, TokenAccount};
+
+use jet_margin::{AdapterResult, MarginAccount, PositionChange, PriceChangeInfo};
+
+use crate::{
+    state::{MarketInfo, OpenOrdersBalances},
+    ErrorCode,
+};
+
+/// The accounts used to track the tokens held by a margin account's open orders
+/// account as `AdapterCollateral` positions.
+///
+/// The margin account holds one note for each token held by the open orders
+/// account, whether locked in an order or waiting to be settled.
+#[derive(Accounts)]
+pub struct OpenOrdersPositions<'info> {
+    /// The registration of the market being traded
+    #[account(
+        has_one = base_note_mint @ ErrorCode::WrongNoteAccount,
+        has_one = quote_note_mint @ ErrorCode::WrongNoteAccount,
+        has_one = base_oracle @ ErrorCode::InvalidOracle,
+        has_one = quote_oracle @ ErrorCode::InvalidOracle,
+    )]
+    pub market_info: Box<Account<'info, MarketInfo>>,
+
+    /// CHECK: has_one on market info
+    #[account(mut)]
+    pub base_note_mint: AccountInfo<'info>,
+
+    /// CHECK: has_one on market info
+    #[account(mut)]
+    pub quote_note_mint: AccountInfo<'info>,
+
+    /// The margin account's position for base tokens held by the open orders account
+    #[account(mut)]
+    pub base_notes: Box<Account<'info, TokenAccount>>,
+
+    /// The margin account's position for quote tokens held by the open orders account
+    #[account(mut)]
+    pub quote_notes: Box<Account<'info, TokenAccount>>,
+
+    /// The pyth price account for the base token
+    /// CHECK: has_one on market info
+    pub base_oracle: AccountInfo<'info>,
+
+    /// The pyth price account for the quote token
+    /// CHECK: has_one on market info
+    pub quote_oracle: AccountInfo<'info>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+impl<'info> OpenOrdersPositions<'info> {
+    /// Match the notes held by the margin account to the current balances of
+    /// its open orders account, and tell the margin program the current prices.
+    pub fn sync(
+        &self,
+        margin_account: &AccountLoader<'info, MarginAccount>,
+        open_orders: &AccountInfo<'info>,
+    ) -> Result<()> {
+        let margin_key = margin_account.key();
+        self.verify_note_accounts(&margin_key)?;
+
+        let balances = OpenOrdersBalances::load(open_orders, &self.market_info, &margin_key)?;
+
+        self.set_note_balance(
+            self.base_note_mint.clone(),
+            self.base_notes.to_account_info(),
+            self.base_notes.amount,
+            balances.base,
+        )?;
+        self.set_note_balance(
+            self.quote_note_mint.clone(),
+            self.quote_notes.to_account_info(),
+            self.quote_notes.amount,
+            balances.quote,
+        )?;
+
+        jet_margin::write_adapter_result(
+            &*margin_account.load()?,
+            &AdapterResult {
+                position_changes: vec![
+                    (
+                        self.base_note_mint.key(),
+                        vec![load_price(&self.base_oracle)?],
+                    ),
+                    (
+                        self.quote_note_mint.key(),
+                        vec![load_price(&self.quote_oracle)?],
+                    ),
+                ],
+            },
+        )
+    }
+
+    /// The note accounts must be the ones created for the margin account, so that
+    /// the notes are always held by the registered positions.
+    pub fn verify_note_accounts(&self, margin_account: &Pubkey) -> Result<()> {
+        if self.base_notes.key()
+            != MarketInfo::note_account_address(margin_account, &self.base_note_mint.key())
+            || self.quote_notes.key()
+                != MarketInfo::note_account_address(margin_account, &self.quote_note_mint.key())
+        {
+            return err!(ErrorCode::WrongNoteAccount);
+        }
+
+        Ok(())
+    }
+
+    fn set_note_balance(
+        &self,
+        mint: AccountInfo<'info>,
+        notes: AccountInfo<'info>,
+        current: u64,
+        target: u64,
+    ) -> Result<()> {
+        let seeds = self.market_info.signer_seeds();
+        let authority = self.market_info.to_account_info();
+        let token_program = self.token_program.to_account_info();
+
+        match target.cmp(&current) {
+            Ordering::Equal => Ok(()),
+            Ordering::Greater => token::mint_to(
+                CpiContext::new_with_signer(
+                    token_program,
+                    MintTo {
+                        mint,
+                        to: notes,
+                        authority,
+                    },
+                    &[&seeds],
+                ),
+                target - current,
+            ),
+            Ordering::Less => token::burn(
+                CpiContext::new_with_signer(
+                    token_program,
+                    Burn {
+                        mint,
+                        from: notes,
+                        authority,
+                    },
+                    &[&seeds],
+                ),
+                current - target,
+            ),
+        }
+    }
+}
+
+fn load_price(oracle_info: &AccountInfo) -> Result<PositionChange> {
+    let oracle = pyth_sdk_solana::load_price_feed_from_account_info(oracle_info).map_err(|e| {
+        msg!("the oracle account is not valid: {:?}", e);
+        error!(ErrorCode::InvalidOracle)
+    })?;
+    let price = oracle.get_current_price().ok_or(ErrorCode::PriceMissing)?;
+    let ema_price = oracle.get_ema_price().ok_or(ErrorCode::PriceMissing)?;
+
+    Ok(PositionChange::Price(PriceChangeInfo {
+        publish_time: oracle.publish_time,
+        exponent: oracle.expo,
+        value: price.price,
+        confidence: price.conf,
+        twap: ema_price.price,
+    }))
+}
@@ -0,0 +1,141 @@
+// SPDX-License-Identifier: AGPL-3.0-or-later
+//
+// Copyright (C) 2022 JET PROTOCOL HOLDINGS, LLC.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+use anchor_lang::prelude::*;
+use anchor_spl::dex::serum_dex::state::{AccountFlag, OpenOrders};
+
+use crate::ErrorCode;
+
+/// Account containing information about a serum market that margin accounts
+/// are allowed to trade on
+#[account]
+#[derive(Debug, Default)]
+pub struct MarketInfo {
+    /// The airspace the market was registered in
+    pub airspace: Pubkey,
+
+    /// The bump seed used to create the market info address
+    pub bump: [u8; 1],
+
+    /// The address of the serum market
+    pub market: Pubkey,
+
+    /// The token bought and sold on the market
+    pub base_mint: Pubkey,
+
+    /// The token used to price the base token
+    pub quote_mint: Pubkey,
+
+    /// The mint for notes representing the base tokens held by open orders accounts
+    pub base_note_mint: Pubkey,
+
+    /// The mint for notes representing the quote tokens held by open orders accounts
+    pub quote_note_mint: Pubkey,
+
+    /// The address of the pyth oracle with price information for the base token
+    pub base_oracle: Pubkey,
+
+    /// The address of the pyth oracle with price information for the quote token
+    pub quote_oracle: Pubkey,
+}
+
+impl MarketInfo {
+    pub fn signer_seeds(&self) -> [&[u8]; 3] {
+        [
+            self.airspace.as_ref(),
+            self.market.as_ref(),
+            self.bump.as_ref(),
+        ]
+    }
+
+    /// The address of the open orders account used by a margin account on this market
+    pub fn open_orders_address(&self, margin_account: &Pubkey) -> Pubkey {
+        Pubkey::find_program_address(
+            &[
+                margin_account.as_ref(),
+                self.market.as_ref(),
+                b"open-orders".as_ref(),
+            ],
+            &crate::ID,
+        )
+        .0
+    }
+
+    /// The address of the token account holding a margin account's notes
+    pub fn note_account_address(margin_account: &Pubkey, note_mint: &Pubkey) -> Pubkey {
+        Pubkey::find_program_address(&[margin_account.as_ref(), note_mint.as_ref()], &crate::ID).0
+    }
+}
+
+/// The tokens held by an open orders account, either locked in orders or free
+/// to be settled
+#[derive(Debug, Clone, Copy)]
+pub struct OpenOrdersBalances {
+    pub base: u64,
+    pub quote: u64,
+}
+
+impl OpenOrdersBalances {
+    /// serum accounts are wrapped in a 5 byte header and a 7 byte footer
+    const HEAD_PADDING: usize = 5;
+    const TAIL_PADDING: usize = 7;
+
+    /// The size of an open orders account, including padding
+    pub const ACCOUNT_SIZE: usize =
+        Self::HEAD_PADDING + std::mem::size_of::<OpenOrders>() + Self::TAIL_PADDING;
+
+    /// Read the balances of an open orders account owned by a margin account
+    pub fn load(
+        info: &AccountInfo,
+        market_info: &MarketInfo,
+        margin_account: &Pubkey,
+    ) -> Result<Self> {
+        if info.key() != market_info.open_orders_address(margin_account) {
+            return err!(ErrorCode::WrongOpenOrders);
+        }
+
+        let data = info.try_borrow_data()?;
+        if data.len() != Self::ACCOUNT_SIZE {
+            return err!(ErrorCode::WrongOpenOrders);
+        }
+
+        let open_orders: OpenOrders = bytemuck::pod_read_unaligned(
+            &data[Self::HEAD_PADDING..data.len() - Self::TAIL_PADDING],
+        );
+        let flags = { open_orders.account_flags };
+        let expected_flags = (AccountFlag::Initialized | AccountFlag::OpenOrders).bits();
+        let market = { open_orders.market };
+        let owner = { open_orders.owner };
+
+        if flags & expected_flags != expected_flags
+            || read_address(market) != market_info.market
+            || read_address(owner) != *margin_account
+        {
+            msg!("open orders account {} is not valid", info.key());
+            return err!(ErrorCode::WrongOpenOrders);
+        }
+
+        Ok(Self {
+            base: open_orders.native_coin_total,
+            quote: open_orders.native_pc_total,
+        })
+    }
+}
+
+pub(crate) fn read_address(bytes: [u64; 4]) -> Pubkey {
+    Pubkey::new(bytemuck::bytes_of(&bytes))
+}
@@ -15,6 +15,12 @@
 // You should have received a copy of the GNU Affero General Public License
 // along with this program.  If not, see <https://www.gnu.org/licenses/>.
 
+mod leveraged_swap;
 mod margin_spl_swap;
+mod route_swap;
+mod swap_positions;
 
+pub use leveraged_swap::*;
 pub use margin_spl_swap::*;
+pub use route_swap::*;
+pub use swap_positions::*;
@@ -0,0 +1,188 @@
+// SPDX-License-Identifier: AGPL-3.0-or-later
+//
+// Copyright (C) 2022 JET PROTOCOL HOLDINGS, LLC.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+use anchor_lang::AccountDeserialize;
+use anchor_spl::token;
+use jet_margin_pool::{
+    cpi::accounts::{MarginBorrow, MarginRepay},
+    Amount, ChangeKind, MarginPool, PoolAction,
+};
+
+use crate::*;
+
+#[derive(Accounts)]
+pub struct MarginLeveragedSwap<'info> {
+    /// The accounts for the swap between the margin pools
+    pub swap: MarginSplSwap<'info>,
+
+    /// The mint for the loan notes of the pool that is borrowed from, or repaid.
+    /// This is the source pool when opening a position, and the destination pool
+    /// when closing it.
+    /// CHECK:
+    #[account(mut)]
+    pub loan_note_mint: AccountInfo<'info>,
+
+    /// The margin account's loan position in the pool that is borrowed from, or repaid
+    /// CHECK:
+    #[account(mut)]
+    pub loan_account: AccountInfo<'info>,
+}
+
+impl<'info> MarginLeveragedSwap<'info> {
+    #[inline(never)]
+    fn borrow(
+        &self,
+        pool: &MarginPoolInfo<'info>,
+        deposit: &AccountInfo<'info>,
+        amount: u64,
+    ) -> Result<()> {
+        jet_margin_pool::cpi::margin_borrow(
+            CpiContext::new(
+                self.swap.margin_pool_program.to_account_info(),
+                MarginBorrow {
+                    margin_account: self.swap.margin_account.to_account_info(),
+                    margin_pool: pool.margin_pool.to_account_info(),
+                    loan_note_mint: self.loan_note_mint.to_account_info(),
+                    deposit_note_mint: pool.deposit_note_mint.to_account_info(),
+                    loan_account: self.loan_account.to_account_info(),
+                    deposit_account: deposit.clone(),
+                    token_program: self.swap.token_program.to_account_info(),
+                },
+            ),
+            ChangeKind::ShiftBy,
+            amount,
+        )
+    }
+
+    #[inline(never)]
+    fn repay(
+        &self,
+        pool: &MarginPoolInfo<'info>,
+        deposit: &AccountInfo<'info>,
+        change_kind: ChangeKind,
+        amount: u64,
+    ) -> Result<()> {
+        jet_margin_pool::cpi::margin_repay(
+            CpiContext::new(
+                self.swap.margin_pool_program.to_account_info(),
+                MarginRepay {
+                    margin_account: self.swap.margin_account.to_account_info(),
+                    margin_pool: pool.margin_pool.to_account_info(),
+                    loan_note_mint: self.loan_note_mint.to_account_info(),
+                    deposit_note_mint: pool.deposit_note_mint.to_account_info(),
+                    loan_account: self.loan_account.to_account_info(),
+                    deposit_account: deposit.clone(),
+                    token_program: self.swap.token_program.to_account_info(),
+                },
+            ),
+            change_kind,
+            amount,
+        )
+    }
+}
+
+/// Borrow tokens from the source pool, swap them, and deposit the output into the
+/// destination pool.
+///
+/// Only the deposit notes minted by the borrow are swapped, so any existing deposit
+/// in the source pool is left untouched. The margin account's health is checked once
+/// the whole instruction completes.
+pub fn open_leveraged_position_handler<'info>(
+    ctx: Context<'_, '_, '_, 'info, MarginLeveragedSwap<'info>>,
+    borrow_amount: u64,
+    minimum_amount_out: u64,
+) -> Result<()> {
+    let accounts = &ctx.accounts;
+    let source = &accounts.swap.source_account;
+
+    let notes_before = token::accessor::amount(source)?;
+    accounts.borrow(&accounts.swap.source_margin_pool, source, borrow_amount)?;
+    let notes_borrowed = token::accessor::amount(source)?
+        .checked_sub(notes_before)
+        .unwrap();
+
+    // The notes minted for the borrow may be worth slightly less than the tokens
+    // borrowed, so only withdraw what those notes can cover.
+    let withdrawal_amount = withdrawable_tokens(&accounts.swap.source_margin_pool, notes_borrowed)?;
+
+    let hops = route_hops(&accounts.swap, ctx.remaining_accounts)?;
+    swap_along_route(
+        &accounts.swap,
+        hops,
+        SwapEndpoint::MarginPool,
+        SwapEndpoint::MarginPool,
+        ChangeKind::ShiftBy,
+        withdrawal_amount,
+        minimum_amount_out,
+    )
+}
+
+/// Withdraw tokens from the source pool, swap them, and use the output to repay the
+/// loan in the destination pool.
+///
+/// If the output is worth more than the loan, the loan is repaid in full and the
+/// excess remains deposited in the destination pool.
+pub fn close_leveraged_position_handler<'info>(
+    ctx: Context<'_, '_, '_, 'info, MarginLeveragedSwap<'info>>,
+    withdrawal_change_kind: ChangeKind,
+    withdrawal_amount: u64,
+    minimum_amount_out: u64,
+) -> Result<()> {
+    let accounts = &ctx.accounts;
+    let destination = &accounts.swap.destination_account;
+
+    let notes_before = token::accessor::amount(destination)?;
+    let hops = route_hops(&accounts.swap, ctx.remaining_accounts)?;
+    swap_along_route(
+        &accounts.swap,
+        hops,
+        SwapEndpoint::MarginPool,
+        SwapEndpoint::MarginPool,
+        withdrawal_change_kind,
+        withdrawal_amount,
+        minimum_amount_out,
+    )?;
+    let notes_received = token::accessor::amount(destination)?
+        .checked_sub(notes_before)
+        .unwrap();
+
+    let pool = &accounts.swap.destination_margin_pool;
+    let repay_amount = withdrawable_tokens(pool, notes_received)?;
+    let loan_amount = loan_tokens(pool, token::accessor::amount(&accounts.loan_account)?)?;
+
+    if repay_amount >= loan_amount {
+        accounts.repay(pool, destination, ChangeKind::SetTo, 0)
+    } else {
+        accounts.repay(pool, destination, ChangeKind::ShiftBy, repay_amount)
+    }
+}
+
+/// The tokens that can be withdrawn by exchanging no more than the given deposit notes
+fn withdrawable_tokens(pool: &MarginPoolInfo, notes: u64) -> Result<u64> {
+    let pool = MarginPool::try_deserialize(&mut &pool.margin_pool.try_borrow_data()?[..])?;
+    Ok(pool
+        .convert_amount(Amount::notes(notes), PoolAction::Withdraw)?
+        .tokens)
+}
+
+/// The tokens needed to repay the given loan notes in full
+fn loan_tokens(pool: &MarginPoolInfo, notes: u64) -> Result<u64> {
+    let pool = MarginPool::try_deserialize(&mut &pool.margin_pool.try_borrow_data()?[..])?;
+    Ok(pool
+        .convert_amount(Amount::notes(notes), PoolAction::Repay)?
+        .tokens)
+}
@@ -74,7 +74,7 @@ pub struct MarginSplSwap<'info> {
 
 impl<'info> MarginSplSwap<'info> {
     #[inline(never)]
-    fn withdraw(&self, change_kind: ChangeKind, amount_in: u64) -> Result<()> {
+    pub(crate) fn withdraw(&self, change_kind: ChangeKind, amount_in: u64) -> Result<()> {
         jet_margin_pool::cpi::withdraw(
             CpiContext::new(
                 self.margin_pool_program.to_account_info(),
@@ -96,7 +96,7 @@ impl<'info> MarginSplSwap<'info> {
     }
 
     #[inline(never)]
-    fn deposit_destination(&self, amount: u64) -> Result<()> {
+    pub(crate) fn deposit_destination(&self, amount: u64) -> Result<()> {
         jet_margin_pool::cpi::deposit(
             CpiContext::new(
                 self.margin_pool_program.to_account_info(),
@@ -121,7 +121,7 @@ impl<'info> MarginSplSwap<'info> {
     }
 
     #[inline(never)]
-    fn deposit_source(&self, amount: u64) -> Result<()> {
+    pub(crate) fn deposit_source(&self, amount: u64) -> Result<()> {
         jet_margin_pool::cpi::deposit(
             CpiContext::new(
                 self.margin_pool_program.to_account_info(),
@@ -141,48 +141,6 @@ impl<'info> MarginSplSwap<'info> {
 
         Ok(())
     }
-
-    #[inline(never)]
-    fn swap(&self, amount_in: u64, minimum_amount_out: u64) -> Result<()> {
-        let swap_ix = use_client!(self.swap_info.swap_program.key(), {
-            client::instruction::swap(
-                self.swap_info.swap_program.key,
-                self.token_program.key,
-                self.swap_info.swap_pool.key,
-                self.swap_info.authority.key,
-                &self.margin_account.key(),
-                self.transit_source_account.key,
-                self.swap_info.vault_into.key,
-                self.swap_info.vault_from.key,
-                self.transit_destination_account.key,
-                self.swap_info.token_mint.key,
-                self.swap_info.fee_account.key,
-                None,
-                client::instruction::Swap {
-                    amount_in,
-                    minimum_amount_out,
-                },
-            )?
-        })?;
-
-        invoke(
-            &swap_ix,
-            &[
-                self.swap_info.swap_pool.to_account_info(),
-                self.margin_account.to_account_info(),
-                self.swap_info.authority.to_account_info(),
-                self.transit_source_account.to_account_info(),
-                self.swap_info.vault_into.to_account_info(),
-                self.swap_info.vault_from.to_account_info(),
-                self.transit_destination_account.to_account_info(),
-                self.swap_info.token_mint.to_account_info(),
-                self.swap_info.fee_account.to_account_info(),
-                self.token_program.to_account_info(),
-            ],
-        )?;
-
-        Ok(())
-    }
 }
 
 #[derive(Accounts)]
@@ -214,6 +172,84 @@ pub struct SwapInfo<'info> {
     pub swap_program: UncheckedAccount<'info>,
 }
 
+impl<'info> SwapInfo<'info> {
+    pub(crate) fn pool(&self) -> SwapPool<'info> {
+        SwapPool {
+            swap_pool: self.swap_pool.to_account_info(),
+            authority: self.authority.to_account_info(),
+            vault_into: self.vault_into.to_account_info(),
+            vault_from: self.vault_from.to_account_info(),
+            token_mint: self.token_mint.to_account_info(),
+            fee_account: self.fee_account.to_account_info(),
+            swap_program: self.swap_program.to_account_info(),
+        }
+    }
+}
+
+/// The accounts of a swap pool that tokens are exchanged through
+pub(crate) struct SwapPool<'info> {
+    pub swap_pool: AccountInfo<'info>,
+    pub authority: AccountInfo<'info>,
+    pub vault_into: AccountInfo<'info>,
+    pub vault_from: AccountInfo<'info>,
+    pub token_mint: AccountInfo<'info>,
+    pub fee_account: AccountInfo<'info>,
+    pub swap_program: AccountInfo<'info>,
+}
+
+impl<'info> SwapPool<'info> {
+    /// Swap tokens from the source account into the destination account, both
+    /// of which are owned by the margin account
+    #[inline(never)]
+    pub(crate) fn swap(
+        &self,
+        accounts: &MarginSplSwap<'info>,
+        source: &AccountInfo<'info>,
+        destination: &AccountInfo<'info>,
+        amount_in: u64,
+        minimum_amount_out: u64,
+    ) -> Result<()> {
+        let swap_ix = use_client!(self.swap_program.key(), {
+            client::instruction::swap(
+                self.swap_program.key,
+                accounts.token_program.key,
+                self.swap_pool.key,
+                self.authority.key,
+                &accounts.margin_account.key(),
+                source.key,
+                self.vault_into.key,
+                self.vault_from.key,
+                destination.key,
+                self.token_mint.key,
+                self.fee_account.key,
+                None,
+                client::instruction::Swap {
+                    amount_in,
+                    minimum_amount_out,
+                },
+            )?
+        })?;
+
+        invoke(
+            &swap_ix,
+            &[
+                self.swap_pool.clone(),
+                accounts.margin_account.to_account_info(),
+                self.authority.clone(),
+                source.clone(),
+                self.vault_into.clone(),
+                self.vault_from.clone(),
+                destination.clone(),
+                self.token_mint.clone(),
+                self.fee_account.clone(),
+                accounts.token_program.to_account_info(),
+            ],
+        )?;
+
+        Ok(())
+    }
+}
+
 /// Execute a swap by withdrawing tokens from a deposit pool, swapping them for
 /// other tokens, then depositing those other tokens to another deposit pool.
 ///
@@ -229,52 +265,18 @@ pub fn margin_spl_swap_handler(
     withdrawal_amount: u64,
     minimum_amount_out: u64,
 ) -> Result<()> {
-    // Get the balance before the withdrawal. The balance should almost always
-    // be zero, however it could already have a value.
-    let source_opening_balance =
-        token::accessor::amount(&ctx.accounts.transit_source_account.to_account_info())?;
-    ctx.accounts
-        .withdraw(withdrawal_change_kind, withdrawal_amount)?;
-    let source_closing_balance =
-        token::accessor::amount(&ctx.accounts.transit_source_account.to_account_info())?;
-
-    // The closing balance should be > opening balance after the withdrawal
-    let swap_amount_in = source_closing_balance
-        .checked_sub(source_opening_balance)
-        .unwrap();
-    if swap_amount_in == 0 {
-        return err!(crate::ErrorCode::NoSwapTokensWithdrawn);
-    }
-
-    let destination_opening_balance =
-        token::accessor::amount(&ctx.accounts.transit_destination_account.to_account_info())?;
-    ctx.accounts.swap(swap_amount_in, minimum_amount_out)?;
-    let destination_closing_balance =
-        token::accessor::amount(&ctx.accounts.transit_destination_account.to_account_info())?;
-
-    // If the swap would have resulted in 0 tokens, the swap program would error out,
-    // thus balance below will be positive.
-    let swap_amount_out = destination_closing_balance
-        .checked_sub(destination_opening_balance)
-        .unwrap();
-    ctx.accounts.deposit_destination(swap_amount_out)?;
-
-    // check if there was less required for the min amount out then expected
-    // aka if there was unexpected leftover balance in the source account after
-    // the swap occurred.
-
-    let source_amount_after_swap =
-        token::accessor::amount(&ctx.accounts.transit_source_account.to_account_info())?;
-
-    let leftover_balance_from_source_account = source_amount_after_swap
-        .checked_sub(source_opening_balance)
-        .unwrap();
-
-    // if there was leftover balance in the source transit account, deposit into the pool
-    if leftover_balance_from_source_account > 0 {
-        ctx.accounts
-            .deposit_source(leftover_balance_from_source_account)?;
-    }
-
-    Ok(())
+    let hops = vec![RouteHop {
+        pool: ctx.accounts.swap_info.pool(),
+        destination: ctx.accounts.transit_destination_account.to_account_info(),
+    }];
+
+    swap_along_route(
+        &ctx.accounts,
+        hops,
+        SwapEndpoint::MarginPool,
+        SwapEndpoint::MarginPool,
+        withdrawal_change_kind,
+        withdrawal_amount,
+        minimum_amount_out,
+    )
 }
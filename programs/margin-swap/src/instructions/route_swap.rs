@@ -0,0 +1,230 @@
+// SPDX-License-Identifier: AGPL-3.0-or-later
+//
+// Copyright (C) 2022 JET PROTOCOL HOLDINGS, LLC.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+use anchor_spl::token;
+use jet_margin_pool::ChangeKind;
+
+use crate::*;
+
+/// The maximum number of swap pools that a route can pass through
+pub const MAX_ROUTE_HOPS: usize = 3;
+
+/// The number of accounts that describe each additional hop of a route
+const ACCOUNTS_PER_HOP: usize = 8;
+
+/// A single exchange along a swap route
+pub(crate) struct RouteHop<'info> {
+    /// The pool that the tokens are swapped through
+    pub pool: SwapPool<'info>,
+
+    /// The transit account receiving the output of the swap
+    pub destination: AccountInfo<'info>,
+}
+
+/// Execute a swap that passes through multiple swap pools, e.g. BTC -> USDC -> SOL.
+///
+/// The first hop uses the `swap_info` accounts, and each additional hop is described
+/// by the remaining accounts, in groups of:
+///
+/// 0. `[writable]` The transit account receiving the output of the previous hop,
+///    which is then swapped through this hop's pool
+/// 1. `[]` The swap pool
+/// 2. `[]` The swap pool authority
+/// 3. `[writable]` The pool vault receiving tokens
+/// 4. `[writable]` The pool vault sending tokens
+/// 5. `[writable]` The pool token mint
+/// 6. `[writable]` The pool fee account
+/// 7. `[]` The swap program
+///
+/// The output of the final hop is received by `transit_destination_account`. Only
+/// the final output is deposited, and it must be at least `minimum_amount_out`.
+pub fn route_swap_handler<'info>(
+    ctx: Context<'_, '_, '_, 'info, MarginSplSwap<'info>>,
+    withdrawal_change_kind: ChangeKind,
+    withdrawal_amount: u64,
+    minimum_amount_out: u64,
+) -> Result<()> {
+    let hops = route_hops(&ctx.accounts, ctx.remaining_accounts)?;
+
+    swap_along_route(
+        &ctx.accounts,
+        hops,
+        SwapEndpoint::MarginPool,
+        SwapEndpoint::MarginPool,
+        withdrawal_change_kind,
+        withdrawal_amount,
+        minimum_amount_out,
+    )
+}
+
+/// Read the hops of a route from the `swap_info` accounts followed by the
+/// remaining accounts
+pub(crate) fn route_hops<'info>(
+    accounts: &MarginSplSwap<'info>,
+    remaining: &[AccountInfo<'info>],
+) -> Result<Vec<RouteHop<'info>>> {
+    if remaining.len() % ACCOUNTS_PER_HOP != 0
+        || remaining.len() / ACCOUNTS_PER_HOP + 1 > MAX_ROUTE_HOPS
+    {
+        msg!(
+            "a route must have at most {} hops, described by {} accounts each",
+            MAX_ROUTE_HOPS,
+            ACCOUNTS_PER_HOP
+        );
+        return err!(crate::ErrorCode::InvalidSwapRoute);
+    }
+
+    let mut pools = vec![accounts.swap_info.pool()];
+    let mut destinations = vec![];
+
+    for hop in remaining.chunks(ACCOUNTS_PER_HOP) {
+        destinations.push(hop[0].clone());
+        pools.push(SwapPool {
+            swap_pool: hop[1].clone(),
+            authority: hop[2].clone(),
+            vault_into: hop[3].clone(),
+            vault_from: hop[4].clone(),
+            token_mint: hop[5].clone(),
+            fee_account: hop[6].clone(),
+            swap_program: hop[7].clone(),
+        });
+    }
+    destinations.push(accounts.transit_destination_account.to_account_info());
+
+    Ok(pools
+        .into_iter()
+        .zip(destinations)
+        .map(|(pool, destination)| RouteHop { pool, destination })
+        .collect())
+}
+
+/// Withdraw from the source, swap through each hop in turn, and deposit the
+/// output of the final hop.
+///
+/// The amount swapped by each hop is the change in the balance of its input transit
+/// account, so any tokens already held by a transit account are left untouched.
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn swap_along_route<'info>(
+    accounts: &MarginSplSwap<'info>,
+    hops: Vec<RouteHop<'info>>,
+    source: SwapEndpoint,
+    destination: SwapEndpoint,
+    withdrawal_change_kind: ChangeKind,
+    withdrawal_amount: u64,
+    minimum_amount_out: u64,
+) -> Result<()> {
+    source.verify(
+        &accounts.margin_account.key(),
+        &accounts.source_account,
+        &accounts.transit_source_account,
+    )?;
+    destination.verify(
+        &accounts.margin_account.key(),
+        &accounts.destination_account,
+        &accounts.transit_destination_account,
+    )?;
+
+    // Get the balance before the withdrawal. The balance should almost always
+    // be zero, however it could already have a value.
+    let source_opening_balance =
+        token::accessor::amount(&accounts.transit_source_account.to_account_info())?;
+
+    let swap_amount_in = match source {
+        SwapEndpoint::MarginPool => {
+            accounts.withdraw(withdrawal_change_kind, withdrawal_amount)?;
+            let source_closing_balance =
+                token::accessor::amount(&accounts.transit_source_account.to_account_info())?;
+
+            // The closing balance should be > opening balance after the withdrawal
+            source_closing_balance
+                .checked_sub(source_opening_balance)
+                .unwrap()
+        }
+        SwapEndpoint::Deposit => deposit_swap_amount(
+            source_opening_balance,
+            withdrawal_change_kind,
+            withdrawal_amount,
+        )?,
+    };
+    if swap_amount_in == 0 {
+        return err!(crate::ErrorCode::NoSwapTokensWithdrawn);
+    }
+
+    let last_hop = hops.len() - 1;
+    let mut hop_source = accounts.transit_source_account.to_account_info();
+    let mut hop_amount_in = swap_amount_in;
+
+    for (index, hop) in hops.into_iter().enumerate() {
+        // intermediate hops accept any output, the route as a whole is protected by
+        // the minimum on the final hop
+        let hop_minimum_out = if index == last_hop {
+            minimum_amount_out
+        } else {
+            0
+        };
+
+        let destination_opening_balance = token::accessor::amount(&hop.destination)?;
+        hop.pool.swap(
+            accounts,
+            &hop_source,
+            &hop.destination,
+            hop_amount_in,
+            hop_minimum_out,
+        )?;
+        let destination_closing_balance = token::accessor::amount(&hop.destination)?;
+
+        // If the swap would have resulted in 0 tokens, the swap program would error out,
+        // thus balance below will be positive.
+        hop_amount_in = destination_closing_balance
+            .checked_sub(destination_opening_balance)
+            .unwrap();
+        hop_source = hop.destination;
+    }
+
+    let swap_amount_out = hop_amount_in;
+    if swap_amount_out < minimum_amount_out {
+        return err!(crate::ErrorCode::SwapOutputBelowMinimum);
+    }
+
+    // a deposit destination already holds the output, as its own transit account
+    if destination == SwapEndpoint::MarginPool {
+        accounts.deposit_destination(swap_amount_out)?;
+    }
+
+    // a deposit source keeps whatever was not swapped
+    if source == SwapEndpoint::Deposit {
+        return Ok(());
+    }
+
+    // check if there was less required for the min amount out then expected
+    // aka if there was unexpected leftover balance in the source account after
+    // the swap occurred.
+
+    let source_amount_after_swap =
+        token::accessor::amount(&accounts.transit_source_account.to_account_info())?;
+
+    let leftover_balance_from_source_account = source_amount_after_swap
+        .checked_sub(source_opening_balance)
+        .unwrap();
+
+    // if there was leftover balance in the source transit account, deposit into the pool
+    if leftover_balance_from_source_account > 0 {
+        accounts.deposit_source(leftover_balance_from_source_account)?;
+    }
+
+    Ok(())
+}
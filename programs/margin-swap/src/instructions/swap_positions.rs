@@ -0,0 +1,103 @@
+// SPDX-License-Identifier: AGPL-3.0-or-later
+//
+// Copyright (C) 2022 JET PROTOCOL HOLDINGS, LLC.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+use anchor_spl::token;
+use jet_margin_pool::ChangeKind;
+
+use crate::*;
+
+/// Where the tokens of a swap are taken from, or where they are sent to
+#[derive(AnchorSerialize, AnchorDeserialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SwapEndpoint {
+    /// Deposit notes of a margin pool, which are exchanged for tokens through
+    /// the transit account
+    MarginPool,
+
+    /// Tokens held directly by the margin account in a deposit position. The
+    /// deposit account is also its own transit account, and the margin pool
+    /// accounts on this side of the swap are not used.
+    Deposit,
+}
+
+impl SwapEndpoint {
+    pub(crate) fn verify(
+        &self,
+        margin_account: &Pubkey,
+        account: &AccountInfo,
+        transit_account: &AccountInfo,
+    ) -> Result<()> {
+        if *self == SwapEndpoint::MarginPool {
+            return Ok(());
+        }
+
+        if account.key != transit_account.key
+            || token::accessor::authority(account)? != *margin_account
+        {
+            msg!(
+                "deposit {} must be owned by the margin account, and be its own transit account",
+                account.key
+            );
+            return err!(crate::ErrorCode::InvalidDepositAccount);
+        }
+
+        Ok(())
+    }
+}
+
+/// The number of tokens to swap out of a deposit account with the given balance
+pub(crate) fn deposit_swap_amount(
+    balance: u64,
+    change_kind: ChangeKind,
+    amount: u64,
+) -> Result<u64> {
+    match change_kind {
+        ChangeKind::ShiftBy => Ok(amount),
+        ChangeKind::SetTo => balance
+            .checked_sub(amount)
+            .ok_or_else(|| error!(crate::ErrorCode::InvalidSetTo)),
+    }
+}
+
+/// Execute a swap where either side can be a margin pool or a deposit position.
+///
+/// With a [SwapEndpoint::Deposit] source, the change is applied to the balance
+/// of the deposit account rather than to the pool deposit. With a
+/// [SwapEndpoint::Deposit] destination, the output is received directly by the
+/// deposit account. The margin program updates the balances of any deposit
+/// positions passed to the instruction once the swap completes.
+///
+/// Additional hops can be provided in the remaining accounts, as with `route_swap`.
+pub fn margin_swap_positions_handler<'info>(
+    ctx: Context<'_, '_, '_, 'info, MarginSplSwap<'info>>,
+    source: SwapEndpoint,
+    destination: SwapEndpoint,
+    change_kind: ChangeKind,
+    amount: u64,
+    minimum_amount_out: u64,
+) -> Result<()> {
+    let hops = route_hops(&ctx.accounts, ctx.remaining_accounts)?;
+
+    swap_along_route(
+        &ctx.accounts,
+        hops,
+        source,
+        destination,
+        change_kind,
+        amount,
+        minimum_amount_out,
+    )
+}
@@ -30,6 +30,7 @@ declare_id!("JPMAa5dnWLFRvUsumawFcGhnwikqZziLLfqn9SLNXPN");
 
 mod instructions;
 use instructions::*;
+pub use instructions::{SwapEndpoint, MAX_ROUTE_HOPS};
 
 #[program]
 mod jet_margin_swap {
@@ -48,6 +49,66 @@ mod jet_margin_swap {
             minimum_amount_out,
         )
     }
+
+    /// Swap between margin pool deposits and deposit positions, in any combination
+    pub fn margin_swap_positions<'info>(
+        ctx: Context<'_, '_, '_, 'info, MarginSplSwap<'info>>,
+        source: SwapEndpoint,
+        destination: SwapEndpoint,
+        change_kind: ChangeKind,
+        amount: u64,
+        minimum_amount_out: u64,
+    ) -> Result<()> {
+        margin_swap_positions_handler(
+            ctx,
+            source,
+            destination,
+            change_kind,
+            amount,
+            minimum_amount_out,
+        )
+    }
+
+    /// Borrow from the source pool and swap the borrowed tokens into the
+    /// destination pool, in a single step
+    pub fn open_leveraged_position<'info>(
+        ctx: Context<'_, '_, '_, 'info, MarginLeveragedSwap<'info>>,
+        borrow_amount: u64,
+        minimum_amount_out: u64,
+    ) -> Result<()> {
+        open_leveraged_position_handler(ctx, borrow_amount, minimum_amount_out)
+    }
+
+    /// Withdraw from the source pool and swap into the destination pool, using
+    /// the swapped tokens to repay the loan in the destination pool
+    pub fn close_leveraged_position<'info>(
+        ctx: Context<'_, '_, '_, 'info, MarginLeveragedSwap<'info>>,
+        withdrawal_change_kind: ChangeKind,
+        withdrawal_amount: u64,
+        minimum_amount_out: u64,
+    ) -> Result<()> {
+        close_leveraged_position_handler(
+            ctx,
+            withdrawal_change_kind,
+            withdrawal_amount,
+            minimum_amount_out,
+        )
+    }
+
+    /// Swap through up to [MAX_ROUTE_HOPS] pools, depositing only the final output
+    pub fn route_swap<'info>(
+        ctx: Context<'_, '_, '_, 'info, MarginSplSwap<'info>>,
+        withdrawal_change_kind: ChangeKind,
+        withdrawal_amount: u64,
+        minimum_amount_out: u64,
+    ) -> Result<()> {
+        route_swap_handler(
+            ctx,
+            withdrawal_change_kind,
+            withdrawal_amount,
+            minimum_amount_out,
+        )
+    }
 }
 
 #[derive(Accounts)]
@@ -69,4 +130,16 @@ pub struct MarginPoolInfo<'info> {
 pub enum ErrorCode {
     #[msg("Zero tokens have been withdrawn from a pool for the swap")]
     NoSwapTokensWithdrawn,
+
+    #[msg("The swap route has too many hops, or its accounts are incomplete")]
+    InvalidSwapRoute,
+
+    #[msg("The swap route produced less than the minimum amount out")]
+    SwapOutputBelowMinimum,
+
+    #[msg("A deposit must be owned by the margin account, and be its own transit account")]
+    InvalidDepositAccount,
+
+    #[msg("The deposit balance is lower than the balance it should be set to")]
+    InvalidSetTo,
 }
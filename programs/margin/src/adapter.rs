@@ -20,14 +20,15 @@ use std::{collections::BTreeMap, convert::TryInto};
 use crate::{
     events::{PositionClosed, PositionEvent, PositionRegistered, PositionTouched},
     util::{log_on_error, Require},
-    AccountPositionKey, AdapterPositionFlags, Approver, ErrorCode, MarginAccount, SignerSeeds,
-    TokenConfig,
+    AccountPositionKey, AdapterPositionFlags, Approver, ErrorCode, MarginAccount, PositionParams,
+    SignerSeeds, TokenConfig,
 };
 use anchor_lang::{
     prelude::*,
     solana_program::{instruction::Instruction, program},
 };
 use anchor_spl::token::{Mint, TokenAccount};
+#[cfg(feature = "metadata-compat")]
 use jet_metadata::PositionTokenMetadata;
 
 pub struct InvokeAdapter<'a, 'info> {
@@ -206,7 +207,7 @@ fn apply_changes(
         match change {
             PositionChange::Price(px) => {
                 if let Some(pos) = position {
-                    pos.set_price(&px.try_into()?)?;
+                    pos.set_price_and_twap(&px.try_into()?, px.twap)?;
                 }
             }
             PositionChange::Flags(flags, true) => position.require_mut()?.flags |= flags,
@@ -278,27 +279,29 @@ fn register_position(
     mint_address: Pubkey,
     token_account_address: Pubkey,
 ) -> Result<AccountPositionKey> {
-    let mut token_config: Option<Account<TokenConfig>> = None;
-    let mut metadata: Result<Account<PositionTokenMetadata>> =
-        err!(ErrorCode::PositionNotRegisterable);
+    let mut token_config: Option<PositionParams> = None;
+    #[cfg(feature = "metadata-compat")]
+    let mut metadata: Option<PositionParams> = None;
     let mut token_account: Result<Account<TokenAccount>> = err!(ErrorCode::PositionNotRegisterable);
     let mut mint: Result<Account<Mint>> = err!(ErrorCode::PositionNotRegisterable);
     for info in remaining_accounts {
-        if info.key == &token_account_address {
-            token_account = Ok(Account::<TokenAccount>::try_from(info)?);
-        } else if info.key == &mint_address {
-            mint = Ok(Account::<Mint>::try_from(info)?);
-        } else if info.owner == &PositionTokenMetadata::owner() {
-            // TODO: remove backwards compat
+        #[cfg(feature = "metadata-compat")]
+        if info.owner == &PositionTokenMetadata::owner() {
             if let Ok(ptm) = Account::<PositionTokenMetadata>::try_from(info) {
                 if ptm.position_token_mint == mint_address {
-                    metadata = Ok(ptm);
+                    metadata = Some(PositionParams::from(&*ptm));
                 }
             }
+        }
+
+        if info.key == &token_account_address {
+            token_account = Ok(Account::<TokenAccount>::try_from(info)?);
+        } else if info.key == &mint_address {
+            mint = Ok(Account::<Mint>::try_from(info)?);
         } else if info.owner == &TokenConfig::owner() {
             if let Ok(config) = Account::<TokenConfig>::try_from(info) {
                 if config.mint == mint_address {
-                    token_config = Some(config);
+                    token_config = Some(PositionParams::from(&*config));
                 }
             }
         }
@@ -316,37 +319,35 @@ fn register_position(
         return err!(ErrorCode::PositionNotRegisterable);
     }
 
-    let key = match token_config {
-        Some(config) => margin_account.register_position(
-            mint.key(),
-            mint.decimals,
-            token_account.key(),
-            config.adapter_program().unwrap_or_default(),
-            config.token_kind,
-            config.value_modifier,
-            config.max_staleness,
-            approvals,
-        )?,
-        // TODO: remove backwards compat
+    #[cfg(feature = "metadata-compat")]
+    let token_config = token_config.or(metadata);
+
+    let mut params = match token_config {
+        Some(params) => params,
         None => {
-            let metadata = log_on_error!(
-                metadata,
-                "position token metadata not found for mint {:?}",
-                mint_address
-            )?;
-            margin_account.register_position(
-                mint.key(),
-                mint.decimals,
-                token_account.key(),
-                metadata.adapter_program,
-                metadata.token_kind.into(),
-                metadata.value_modifier,
-                metadata.max_staleness,
-                approvals,
-            )?
+            msg!("token config not found for mint {:?}", mint_address);
+            return err!(ErrorCode::PositionNotRegisterable);
         }
     };
 
+    params.load_category(remaining_accounts)?;
+    params.verify_airspace(margin_account, remaining_accounts)?;
+    params.verify_permit_tier(margin_account, remaining_accounts)?;
+
+    let key = margin_account.register_position(
+        mint.key(),
+        mint.decimals,
+        token_account.key(),
+        params.adapter_program,
+        params.token_kind,
+        params.value_modifier,
+        params.max_staleness,
+        params.conservative_valuation,
+        params.category,
+        params.category_value_modifier,
+        approvals,
+    )?;
+
     margin_account.set_position_balance(
         &mint_address,
         &token_account_address,
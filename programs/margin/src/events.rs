@@ -15,9 +15,17 @@ event_groups! {
 pub struct AccountCreated {
     pub margin_account: Pubkey,
     pub owner: Pubkey,
+    pub airspace: Pubkey,
     pub seed: u16,
 }
 
+#[event]
+pub struct AccountMigrated {
+    pub margin_account: Pubkey,
+    pub airspace: Pubkey,
+    pub version: u8,
+}
+
 #[event]
 pub struct AccountClosed {
     pub margin_account: Pubkey,
@@ -28,6 +36,14 @@ pub struct VerifiedHealthy {
     pub margin_account: Pubkey,
 }
 
+#[event]
+pub struct HealthSnapshot {
+    pub margin_account: Pubkey,
+    pub timestamp: u64,
+    pub health_ratio_bps: u64,
+    pub valuation_summary: ValuationSummary,
+}
+
 #[event]
 pub struct PositionRegistered {
     pub margin_account: Pubkey,
@@ -98,6 +114,16 @@ pub struct LiquidatorInvokeEnd {
     pub valuation_summary: ValuationSummary,
 }
 
+#[event]
+pub struct LiquidatorDepositTransferred {
+    pub margin_account: Pubkey,
+    pub liquidator: Pubkey,
+    pub destination: Pubkey,
+    pub amount: u64,
+    pub liquidation_data: Liquidation,
+    pub valuation_summary: ValuationSummary,
+}
+
 #[event]
 pub struct LiquidationEnded {
     pub margin_account: Pubkey,
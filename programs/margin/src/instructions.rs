@@ -15,14 +15,18 @@
 // You should have received a copy of the GNU Affero General Public License
 // along with this program.  If not, see <https://www.gnu.org/licenses/>.
 
+mod account_health;
 mod accounting_invoke;
 mod adapter_invoke;
+mod adapter_invoke_batch;
 mod close_account;
 mod close_position;
 mod create_account;
 mod liquidate_begin;
 mod liquidate_end;
 mod liquidator_invoke;
+mod migrate_account;
+mod record_health;
 mod refresh_position_metadata;
 mod register_position;
 mod update_position_balance;
@@ -31,14 +35,18 @@ mod verify_healthy;
 mod configure;
 mod positions;
 
+pub use account_health::*;
 pub use accounting_invoke::*;
 pub use adapter_invoke::*;
+pub use adapter_invoke_batch::*;
 pub use close_account::*;
 pub use close_position::*;
 pub use create_account::*;
 pub use liquidate_begin::*;
 pub use liquidate_end::*;
 pub use liquidator_invoke::*;
+pub use migrate_account::*;
+pub use record_health::*;
 pub use refresh_position_metadata::*;
 pub use register_position::*;
 pub use update_position_balance::*;
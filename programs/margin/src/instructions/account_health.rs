@@ -0,0 +1,58 @@
+// SPDX-License-Identifier: AGPL-3.0-or-later
+//
+// Copyright (C) 2022 JET PROTOCOL HOLDINGS, LLC.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::program::set_return_data;
+
+use crate::{events::ValuationSummary, util::get_timestamp, ErrorCode, MarginAccount};
+
+/// The health of a margin account, as returned by the `account_health` instruction
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct AccountHealth {
+    /// The time the valuation was calculated at
+    pub timestamp: u64,
+
+    /// The ratio of effective collateral to required collateral, in basis points
+    pub health_ratio_bps: u64,
+
+    /// The valuation of the account
+    pub valuation_summary: ValuationSummary,
+}
+
+#[derive(Accounts)]
+pub struct AccountHealthView<'info> {
+    /// The account to get the health of
+    #[account(
+        constraint = MarginAccount::has_current_version(&margin_account.to_account_info()) @ ErrorCode::WrongAccountVersion
+    )]
+    pub margin_account: AccountLoader<'info, MarginAccount>,
+}
+
+pub fn account_health_handler(ctx: Context<AccountHealthView>) -> Result<()> {
+    let account = ctx.accounts.margin_account.load()?;
+    let valuation = account.valuation()?;
+
+    let health = AccountHealth {
+        timestamp: get_timestamp(),
+        health_ratio_bps: valuation.health_ratio_bps(),
+        valuation_summary: valuation.into(),
+    };
+
+    set_return_data(&health.try_to_vec()?);
+
+    Ok(())
+}
@@ -17,30 +17,39 @@
 
 use anchor_lang::prelude::*;
 
-use jet_metadata::MarginAdapterMetadata;
-
 use crate::adapter::{self, InvokeAdapter};
-use crate::{events, MarginAccount};
+use crate::{events, AdapterConfig, ErrorCode, MarginAccount};
 
 #[derive(Accounts)]
 pub struct AccountingInvoke<'info> {
     /// The margin account to proxy an action for
-    #[account(mut)]
+    #[account(
+        mut,
+        constraint = MarginAccount::has_current_version(&margin_account.to_account_info()) @ ErrorCode::WrongAccountVersion
+    )]
     pub margin_account: AccountLoader<'info, MarginAccount>,
 
     /// The program to be invoked
     /// CHECK:
     pub adapter_program: AccountInfo<'info>,
 
-    /// The metadata about the proxy program
-    #[account(has_one = adapter_program)]
-    pub adapter_metadata: Account<'info, MarginAdapterMetadata>,
+    /// The config for the proxy program in the margin account's airspace, or the legacy
+    /// metadata while compatibility with metadata is enabled.
+    ///
+    /// CHECK: validated in the handler
+    pub adapter_metadata: AccountInfo<'info>,
 }
 
 pub fn accounting_invoke_handler<'info>(
     ctx: Context<'_, '_, '_, 'info, AccountingInvoke<'info>>,
     data: Vec<u8>,
 ) -> Result<()> {
+    AdapterConfig::verify(
+        &ctx.accounts.adapter_metadata,
+        ctx.accounts.adapter_program.key,
+        &*ctx.accounts.margin_account.load()?,
+    )?;
+
     emit!(events::AccountingInvokeBegin {
         margin_account: ctx.accounts.margin_account.key(),
         adapter_program: ctx.accounts.adapter_program.key(),
@@ -17,10 +17,8 @@
 
 use anchor_lang::prelude::*;
 
-use jet_metadata::MarginAdapterMetadata;
-
 use crate::adapter::{self, InvokeAdapter};
-use crate::{events, ErrorCode, MarginAccount};
+use crate::{events, AdapterConfig, ErrorCode, MarginAccount};
 
 #[derive(Accounts)]
 pub struct AdapterInvoke<'info> {
@@ -28,25 +26,41 @@ pub struct AdapterInvoke<'info> {
     pub owner: Signer<'info>,
 
     /// The margin account to proxy an action for
-    #[account(mut, has_one = owner)]
+    #[account(
+        mut,
+        constraint = MarginAccount::has_current_version(&margin_account.to_account_info()) @ ErrorCode::WrongAccountVersion,
+        constraint = margin_account.load()?.owner == owner.key() @ anchor_lang::error::ErrorCode::ConstraintHasOne
+    )]
     pub margin_account: AccountLoader<'info, MarginAccount>,
 
     /// The program to be invoked
     /// CHECK:
     pub adapter_program: AccountInfo<'info>,
 
-    /// The metadata about the proxy program
-    #[account(has_one = adapter_program)]
-    pub adapter_metadata: Account<'info, MarginAdapterMetadata>,
+    /// The config for the proxy program in the margin account's airspace, or the legacy
+    /// metadata while compatibility with metadata is enabled.
+    ///
+    /// CHECK: validated in the handler
+    pub adapter_metadata: AccountInfo<'info>,
 }
 
 pub fn adapter_invoke_handler<'info>(
     ctx: Context<'_, '_, '_, 'info, AdapterInvoke<'info>>,
     data: Vec<u8>,
 ) -> Result<()> {
-    if ctx.accounts.margin_account.load()?.liquidation != Pubkey::default() {
-        msg!("account is being liquidated");
-        return Err(ErrorCode::Liquidating.into());
+    {
+        let margin_account = ctx.accounts.margin_account.load()?;
+
+        if margin_account.liquidation != Pubkey::default() {
+            msg!("account is being liquidated");
+            return Err(ErrorCode::Liquidating.into());
+        }
+
+        AdapterConfig::verify(
+            &ctx.accounts.adapter_metadata,
+            ctx.accounts.adapter_program.key,
+            &margin_account,
+        )?;
     }
 
     emit!(events::AdapterInvokeBegin {
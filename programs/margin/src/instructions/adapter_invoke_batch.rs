@@ -0,0 +1,105 @@
+// SPDX-License-Identifier: AGPL-3.0-or-later
+//
+// Copyright (C) 2022 JET PROTOCOL HOLDINGS, LLC.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+use anchor_lang::prelude::*;
+
+use crate::adapter::{self, InvokeAdapter};
+use crate::{events, AdapterConfig, ErrorCode, MarginAccount};
+
+/// An instruction for an adapter, to be invoked as part of a batch
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct AdapterInstruction {
+    /// The number of accounts to pass through to the adapter, which follow the
+    /// adapter program and its config in the remaining accounts
+    pub num_accounts: u8,
+
+    /// The instruction data to pass to the adapter program
+    pub data: Vec<u8>,
+}
+
+#[derive(Accounts)]
+pub struct AdapterInvokeBatch<'info> {
+    /// The authority that owns the margin account
+    pub owner: Signer<'info>,
+
+    /// The margin account to proxy actions for
+    #[account(
+        mut,
+        constraint = MarginAccount::has_current_version(&margin_account.to_account_info()) @ ErrorCode::WrongAccountVersion,
+        constraint = margin_account.load()?.owner == owner.key() @ anchor_lang::error::ErrorCode::ConstraintHasOne
+    )]
+    pub margin_account: AccountLoader<'info, MarginAccount>,
+}
+
+pub fn adapter_invoke_batch_handler<'info>(
+    ctx: Context<'_, '_, '_, 'info, AdapterInvokeBatch<'info>>,
+    instructions: Vec<AdapterInstruction>,
+) -> Result<()> {
+    ctx.accounts
+        .margin_account
+        .load()?
+        .verify_not_liquidating()?;
+
+    let mut remaining = ctx.remaining_accounts;
+
+    for instruction in instructions {
+        let num_accounts = instruction.num_accounts as usize;
+
+        if remaining.len() < num_accounts + 2 {
+            msg!("not enough accounts for the adapter instruction");
+            return Err(anchor_lang::error::ErrorCode::AccountNotEnoughKeys.into());
+        }
+
+        let (adapter_program, adapter_config) = (&remaining[0], &remaining[1]);
+        let accounts = &remaining[2..num_accounts + 2];
+        remaining = &remaining[num_accounts + 2..];
+
+        AdapterConfig::verify(
+            adapter_config,
+            adapter_program.key,
+            &*ctx.accounts.margin_account.load()?,
+        )?;
+
+        emit!(events::AdapterInvokeBegin {
+            margin_account: ctx.accounts.margin_account.key(),
+            adapter_program: adapter_program.key(),
+        });
+
+        let events = adapter::invoke(
+            &InvokeAdapter {
+                margin_account: &ctx.accounts.margin_account,
+                adapter_program,
+                accounts,
+                signed: true,
+            },
+            instruction.data,
+        )?;
+
+        for event in events {
+            event.emit();
+        }
+
+        emit!(events::AdapterInvokeEnd {});
+    }
+
+    ctx.accounts
+        .margin_account
+        .load()?
+        .verify_healthy_positions()?;
+
+    Ok(())
+}
@@ -30,9 +30,12 @@ pub struct CloseAccount<'info> {
     pub receiver: AccountInfo<'info>,
 
     /// The account being closed
-    #[account(mut,
-              close = receiver,
-              has_one = owner)]
+    #[account(
+        mut,
+        close = receiver,
+        constraint = MarginAccount::has_current_version(&margin_account.to_account_info()) @ ErrorCode::WrongAccountVersion,
+        constraint = margin_account.load()?.owner == owner.key() @ anchor_lang::error::ErrorCode::ConstraintHasOne
+    )]
     pub margin_account: AccountLoader<'info, MarginAccount>,
 }
 
@@ -18,7 +18,7 @@
 use anchor_lang::prelude::*;
 use anchor_spl::token::{self, CloseAccount, Mint, Token, TokenAccount};
 
-use crate::{events, Approver, MarginAccount, SignerSeeds};
+use crate::{events, Approver, ErrorCode, MarginAccount, SignerSeeds};
 
 #[derive(Accounts)]
 pub struct ClosePosition<'info> {
@@ -31,7 +31,10 @@ pub struct ClosePosition<'info> {
     pub receiver: AccountInfo<'info>,
 
     /// The margin account with the position to close
-    #[account(mut)]
+    #[account(
+        mut,
+        constraint = MarginAccount::has_current_version(&margin_account.to_account_info()) @ ErrorCode::WrongAccountVersion
+    )]
     pub margin_account: AccountLoader<'info, MarginAccount>,
 
     /// The mint for the position token being deregistered
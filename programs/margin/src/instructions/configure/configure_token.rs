@@ -38,6 +38,15 @@ pub struct TokenConfigUpdate {
 
     /// The maximum staleness (seconds) that's acceptable for balances of this token
     pub max_staleness: u64,
+
+    /// Value positions using the less favorable of the spot and average prices
+    pub conservative_valuation: bool,
+
+    /// The category this token belongs to, or zero if it has none
+    pub category: u16,
+
+    /// The minimum permit tier needed to register a position for this token
+    pub min_permit_tier: u8,
 }
 
 #[derive(Accounts)]
@@ -97,6 +106,9 @@ pub fn configure_token_handler(
     config.token_kind = updated_config.token_kind;
     config.value_modifier = updated_config.value_modifier;
     config.max_staleness = updated_config.max_staleness;
+    config.conservative_valuation = updated_config.conservative_valuation;
+    config.category = updated_config.category;
+    config.min_permit_tier = updated_config.min_permit_tier;
 
     config.validate()?;
 
@@ -0,0 +1,85 @@
+// SPDX-License-Identifier: AGPL-3.0-or-later
+//
+// Copyright (C) 2022 JET PROTOCOL HOLDINGS, LLC.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+use anchor_lang::{prelude::*, AccountsClose};
+
+use jet_airspace::state::Airspace;
+
+use crate::{seeds::TOKEN_CATEGORY_SEED, ErrorCode, TokenCategory};
+
+#[derive(AnchorDeserialize, AnchorSerialize, Debug, Eq, PartialEq, Clone)]
+pub struct TokenCategoryUpdate {
+    /// The collateral weight for collateral tokens in the category
+    pub collateral_weight: u16,
+
+    /// The maximum leverage for claims in the category
+    pub max_leverage: u16,
+}
+
+#[derive(Accounts)]
+#[instruction(category: u16)]
+pub struct ConfigureTokenCategory<'info> {
+    /// The authority allowed to make changes to configuration
+    pub authority: Signer<'info>,
+
+    /// The airspace being modified
+    #[account(has_one = authority)]
+    pub airspace: Account<'info, Airspace>,
+
+    /// The payer for any rent costs, if required
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    /// The category account to be modified
+    #[account(init_if_needed,
+              seeds = [
+                TOKEN_CATEGORY_SEED,
+                airspace.key().as_ref(),
+                category.to_le_bytes().as_ref()
+              ],
+              bump,
+              payer = payer,
+              space = TokenCategory::SPACE,
+    )]
+    pub token_category: Account<'info, TokenCategory>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn configure_token_category_handler(
+    ctx: Context<ConfigureTokenCategory>,
+    category: u16,
+    update: Option<TokenCategoryUpdate>,
+) -> Result<()> {
+    let config = &mut ctx.accounts.token_category;
+
+    let update = match update {
+        Some(update) => update,
+        None => return config.close(ctx.accounts.payer.to_account_info()),
+    };
+
+    if category == 0 {
+        msg!("category zero is reserved for tokens without a category");
+        return err!(ErrorCode::InvalidConfig);
+    }
+
+    config.airspace = ctx.accounts.airspace.key();
+    config.category = category;
+    config.collateral_weight = update.collateral_weight;
+    config.max_leverage = update.max_leverage;
+
+    Ok(())
+}
@@ -1,7 +1,9 @@
 mod configure_adapter;
 mod configure_liquidator;
 mod configure_token;
+mod configure_token_category;
 
 pub use configure_adapter::*;
 pub use configure_liquidator::*;
 pub use configure_token::*;
+pub use configure_token_category::*;
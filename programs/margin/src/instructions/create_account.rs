@@ -17,7 +17,9 @@
 
 use anchor_lang::prelude::*;
 
-use crate::{events, MarginAccount};
+use jet_airspace::state::{Airspace, AirspacePermit};
+
+use crate::{events, ErrorCode, MarginAccount};
 
 #[derive(Accounts)]
 #[instruction(seed: u16)]
@@ -37,13 +39,29 @@ pub struct CreateAccount<'info> {
     )]
     pub margin_account: AccountLoader<'info, MarginAccount>,
 
+    /// The airspace the new account will belong to
+    pub airspace: Account<'info, Airspace>,
+
+    /// The permit allowing the owner to use the airspace, which is only
+    /// required when the airspace is restricted.
+    ///
+    /// CHECK: validated in the handler
+    pub permit: AccountInfo<'info>,
+
     pub system_program: Program<'info, System>,
 }
 
 pub fn create_account_handler(ctx: Context<CreateAccount>, seed: u16) -> Result<()> {
+    verify_permit(
+        &ctx.accounts.airspace,
+        &ctx.accounts.permit,
+        ctx.accounts.owner.key,
+    )?;
+
     let mut account = ctx.accounts.margin_account.load_init()?;
 
     account.initialize(
+        ctx.accounts.airspace.key(),
         *ctx.accounts.owner.key,
         seed,
         *ctx.bumps.get("margin_account").unwrap(),
@@ -52,8 +70,32 @@ pub fn create_account_handler(ctx: Context<CreateAccount>, seed: u16) -> Result<
     emit!(events::AccountCreated {
         margin_account: ctx.accounts.margin_account.key(),
         owner: ctx.accounts.owner.key(),
+        airspace: ctx.accounts.airspace.key(),
         seed,
     });
 
     Ok(())
 }
+
+/// Verify that the owner is allowed to have a margin account within the airspace
+fn verify_permit(airspace: &Account<Airspace>, permit: &AccountInfo, owner: &Pubkey) -> Result<()> {
+    if !airspace.is_restricted {
+        return Ok(());
+    }
+
+    let permit = Account::<AirspacePermit>::try_from(permit)?;
+
+    if permit.airspace != airspace.key() {
+        msg!("the permit is for airspace {}", permit.airspace);
+        return err!(ErrorCode::WrongAirspace);
+    }
+
+    if permit.owner != *owner {
+        msg!("the permit is for owner {}", permit.owner);
+        return err!(ErrorCode::WrongPermit);
+    }
+
+    // a permit of any tier allows an account, tokens that require a higher tier check
+    // the permit again when positions are registered for them
+    permit.verify(AirspacePermit::TIER_RETAIL)
+}
@@ -20,15 +20,17 @@ use anchor_lang::prelude::*;
 use jet_proto_math::Number128;
 
 use crate::{
-    events, ErrorCode, Liquidation, LiquidationState, MarginAccount,
+    events, ErrorCode, Liquidation, LiquidationState, LiquidatorConfig, MarginAccount,
     LIQUIDATION_MAX_EQUITY_LOSS_BPS,
 };
-use jet_metadata::LiquidatorMetadata;
 
 #[derive(Accounts)]
 pub struct LiquidateBegin<'info> {
     /// The account in need of liquidation
-    #[account(mut)]
+    #[account(
+        mut,
+        constraint = MarginAccount::has_current_version(&margin_account.to_account_info()) @ ErrorCode::WrongAccountVersion
+    )]
     pub margin_account: AccountLoader<'info, MarginAccount>,
 
     /// The address paying rent
@@ -38,9 +40,11 @@ pub struct LiquidateBegin<'info> {
     /// The liquidator account performing the liquidation actions
     pub liquidator: Signer<'info>,
 
-    /// The metadata describing the liquidator
-    #[account(has_one = liquidator)]
-    pub liquidator_metadata: Account<'info, LiquidatorMetadata>,
+    /// The config for the liquidator in the account's airspace, or the legacy
+    /// metadata describing the liquidator while compatibility with metadata is enabled.
+    ///
+    /// CHECK: validated in the handler
+    pub liquidator_metadata: AccountInfo<'info>,
 
     /// Account to persist the state of the liquidation
     #[account(
@@ -64,6 +68,12 @@ pub fn liquidate_begin_handler(ctx: Context<LiquidateBegin>) -> Result<()> {
     let liquidator = &ctx.accounts.liquidator;
     let mut account = ctx.accounts.margin_account.load_mut()?;
 
+    LiquidatorConfig::verify(
+        &ctx.accounts.liquidator_metadata,
+        &liquidator.key(),
+        &account,
+    )?;
+
     // verify the account is subject to liquidation
     account.verify_unhealthy_positions()?;
 
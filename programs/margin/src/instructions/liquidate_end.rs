@@ -28,7 +28,11 @@ pub struct LiquidateEnd<'info> {
     pub authority: Signer<'info>,
 
     /// The account in need of liquidation
-    #[account(mut, has_one = liquidation)]
+    #[account(
+        mut,
+        constraint = MarginAccount::has_current_version(&margin_account.to_account_info()) @ ErrorCode::WrongAccountVersion,
+        constraint = margin_account.load()?.liquidation == liquidation.key() @ anchor_lang::error::ErrorCode::ConstraintHasOne
+    )]
     pub margin_account: AccountLoader<'info, MarginAccount>,
 
     /// Account to persist the state of the liquidation
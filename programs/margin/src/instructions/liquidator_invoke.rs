@@ -17,10 +17,10 @@
 
 use anchor_lang::prelude::*;
 
-use jet_metadata::MarginAdapterMetadata;
-
 use crate::adapter::{self, InvokeAdapter};
-use crate::{events, ErrorCode, Liquidation, LiquidationState, MarginAccount, Valuation};
+use crate::{
+    events, AdapterConfig, ErrorCode, Liquidation, LiquidationState, MarginAccount, Valuation,
+};
 
 #[derive(Accounts)]
 pub struct LiquidatorInvoke<'info> {
@@ -32,18 +32,23 @@ pub struct LiquidatorInvoke<'info> {
     pub liquidation: AccountLoader<'info, LiquidationState>,
 
     /// The margin account to proxy an action for
-    #[account(mut,
-              has_one = liquidation,
-              has_one = liquidator)]
+    #[account(
+        mut,
+        constraint = MarginAccount::has_current_version(&margin_account.to_account_info()) @ ErrorCode::WrongAccountVersion,
+        constraint = margin_account.load()?.liquidation == liquidation.key() @ anchor_lang::error::ErrorCode::ConstraintHasOne,
+        constraint = margin_account.load()?.liquidator == liquidator.key() @ anchor_lang::error::ErrorCode::ConstraintHasOne
+    )]
     pub margin_account: AccountLoader<'info, MarginAccount>,
 
     /// The program to be invoked
     /// CHECK:
     pub adapter_program: AccountInfo<'info>,
 
-    /// The metadata about the proxy program
-    #[account(has_one = adapter_program)]
-    pub adapter_metadata: Account<'info, MarginAdapterMetadata>,
+    /// The config for the proxy program in the margin account's airspace, or the legacy
+    /// metadata while compatibility with metadata is enabled.
+    ///
+    /// CHECK: validated in the handler
+    pub adapter_metadata: AccountInfo<'info>,
 }
 
 pub fn liquidator_invoke_handler<'info>(
@@ -51,6 +56,13 @@ pub fn liquidator_invoke_handler<'info>(
     data: Vec<u8>,
 ) -> Result<()> {
     let margin_account = &ctx.accounts.margin_account;
+
+    AdapterConfig::verify(
+        &ctx.accounts.adapter_metadata,
+        ctx.accounts.adapter_program.key,
+        &*margin_account.load()?,
+    )?;
+
     let start_value = margin_account.load()?.valuation()?;
 
     emit!(events::LiquidatorInvokeBegin {
@@ -88,7 +100,7 @@ pub fn liquidator_invoke_handler<'info>(
     Ok(())
 }
 
-fn update_and_verify_liquidation(
+pub(crate) fn update_and_verify_liquidation(
     margin_account: &MarginAccount,
     liquidation: &mut Liquidation,
     start_value: Valuation,
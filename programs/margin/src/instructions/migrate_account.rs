@@ -0,0 +1,102 @@
+// SPDX-License-Identifier: AGPL-3.0-or-later
+//
+// Copyright (C) 2022 JET PROTOCOL HOLDINGS, LLC.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+use anchor_lang::{prelude::*, system_program};
+
+use jet_airspace::DEFAULT_AIRSPACE;
+
+use crate::{events, AnchorVerify, ErrorCode, MarginAccount, MARGIN_ACCOUNT_VERSION};
+
+#[derive(Accounts)]
+pub struct MigrateAccount<'info> {
+    /// The owner of the margin account
+    pub owner: Signer<'info>,
+
+    /// The address paying for the additional rent
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    /// The version 1 margin account to migrate
+    ///
+    /// CHECK: validated in the handler, since the account is too small to be loaded
+    #[account(mut)]
+    pub margin_account: AccountInfo<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn migrate_account_handler(ctx: Context<MigrateAccount>) -> Result<()> {
+    let info = &ctx.accounts.margin_account;
+    MarginAccount::anchor_verify(info)?;
+
+    if info.data_len() != MarginAccount::V1_SPACE {
+        msg!(
+            "account has size {}, and is not a version 1 account",
+            info.data_len()
+        );
+        return err!(ErrorCode::WrongAccountVersion);
+    }
+
+    let space = 8 + std::mem::size_of::<MarginAccount>();
+    let required_rent = Rent::get()?
+        .minimum_balance(space)
+        .saturating_sub(info.lamports());
+
+    if required_rent > 0 {
+        system_program::transfer(
+            CpiContext::new(
+                ctx.accounts.system_program.to_account_info(),
+                system_program::Transfer {
+                    from: ctx.accounts.payer.to_account_info(),
+                    to: info.clone(),
+                },
+            ),
+            required_rent,
+        )?;
+    }
+
+    info.realloc(space, true)?;
+
+    let margin_account = AccountLoader::<MarginAccount>::try_from(info)?;
+    let mut account = margin_account.load_mut()?;
+
+    if account.version != 1 {
+        msg!(
+            "expected a version 1 account but got version {}",
+            account.version
+        );
+        return err!(ErrorCode::WrongAccountVersion);
+    }
+
+    if account.owner != ctx.accounts.owner.key() {
+        msg!("the account is owned by {}", account.owner);
+        return err!(ErrorCode::UnauthorizedInvocation);
+    }
+
+    // version 1 accounts existed before airspaces, and their positions are all in
+    // the default airspace, so that is the only airspace they can be migrated into
+    account.version = MARGIN_ACCOUNT_VERSION;
+    account.airspace = DEFAULT_AIRSPACE;
+
+    emit!(events::AccountMigrated {
+        margin_account: info.key(),
+        airspace: account.airspace,
+        version: account.version,
+    });
+
+    Ok(())
+}
@@ -21,7 +21,9 @@ use anchor_spl::{
     token::{Mint, Token, TokenAccount},
 };
 
-use crate::{events, util::Require, Approver, MarginAccount, TokenConfig};
+use crate::{
+    events, util::Require, Approver, ErrorCode, MarginAccount, PositionParams, TokenConfig,
+};
 
 #[derive(Accounts)]
 pub struct CreateDepositPosition<'info> {
@@ -33,7 +35,10 @@ pub struct CreateDepositPosition<'info> {
     pub payer: Signer<'info>,
 
     /// The margin account to register this deposit account with
-    #[account(mut)]
+    #[account(
+        mut,
+        constraint = MarginAccount::has_current_version(&margin_account.to_account_info()) @ ErrorCode::WrongAccountVersion
+    )]
     pub margin_account: AccountLoader<'info, MarginAccount>,
 
     /// The mint for the token being stored in this account
@@ -61,6 +66,11 @@ pub fn create_deposit_position_handler(ctx: Context<CreateDepositPosition>) -> R
     let position_token = &ctx.accounts.mint;
     let address = ctx.accounts.token_account.key();
     account.verify_authority(ctx.accounts.authority.key())?;
+    account.verify_airspace(&config.airspace)?;
+
+    let mut params = PositionParams::from(&**config);
+    params.load_category(ctx.remaining_accounts)?;
+    params.verify_permit_tier(&account, ctx.remaining_accounts)?;
 
     let key = account.register_position(
         position_token.key(),
@@ -70,6 +80,9 @@ pub fn create_deposit_position_handler(ctx: Context<CreateDepositPosition>) -> R
         config.token_kind,
         config.value_modifier,
         config.max_staleness,
+        config.conservative_valuation,
+        params.category,
+        params.category_value_modifier,
         &[Approver::MarginAccountAuthority],
     )?;
 
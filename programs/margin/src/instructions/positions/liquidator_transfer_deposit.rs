@@ -0,0 +1,116 @@
+// SPDX-License-Identifier: AGPL-3.0-or-later
+//
+// Copyright (C) 2022 JET PROTOCOL HOLDINGS, LLC.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Token, TokenAccount, Transfer};
+
+use crate::{
+    events, update_and_verify_liquidation, ErrorCode, LiquidationState, MarginAccount, SignerSeeds,
+};
+
+#[derive(Accounts)]
+pub struct LiquidatorTransferDeposit<'info> {
+    /// The liquidator processing the margin account
+    pub liquidator: Signer<'info>,
+
+    /// Account to persist the state of the liquidation
+    #[account(mut)]
+    pub liquidation: AccountLoader<'info, LiquidationState>,
+
+    /// The margin account being liquidated
+    #[account(
+        mut,
+        constraint = MarginAccount::has_current_version(&margin_account.to_account_info()) @ ErrorCode::WrongAccountVersion,
+        constraint = margin_account.load()?.liquidation == liquidation.key() @ anchor_lang::error::ErrorCode::ConstraintHasOne,
+        constraint = margin_account.load()?.liquidator == liquidator.key() @ anchor_lang::error::ErrorCode::ConstraintHasOne
+    )]
+    pub margin_account: AccountLoader<'info, MarginAccount>,
+
+    /// The deposit account to transfer tokens out of
+    #[account(mut)]
+    pub source: Account<'info, TokenAccount>,
+
+    /// The account to receive the tokens
+    #[account(mut)]
+    pub destination: Account<'info, TokenAccount>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+pub fn liquidator_transfer_deposit_handler(
+    ctx: Context<LiquidatorTransferDeposit>,
+    amount: u64,
+) -> Result<()> {
+    let mut margin_account = ctx.accounts.margin_account.load_mut()?;
+    let start_value = margin_account.valuation()?;
+
+    let position = match margin_account.get_position(&ctx.accounts.source.mint) {
+        None => return err!(ErrorCode::PositionNotRegistered),
+        Some(pos) => pos,
+    };
+
+    if position.address != ctx.accounts.source.key() {
+        msg!("the source is not the deposit account for the position");
+        return err!(ErrorCode::PositionNotRegistered);
+    }
+
+    if position.adapter != Pubkey::default() {
+        msg!(
+            "the position is managed by the adapter {}",
+            position.adapter
+        );
+        return err!(ErrorCode::InvalidPositionAdapter);
+    }
+
+    let seeds = margin_account.signer_seeds_owned();
+    drop(margin_account);
+
+    token::transfer(
+        CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            Transfer {
+                from: ctx.accounts.source.to_account_info(),
+                to: ctx.accounts.destination.to_account_info(),
+                authority: ctx.accounts.margin_account.to_account_info(),
+            },
+            &[&seeds.signer_seeds()],
+        ),
+        amount,
+    )?;
+
+    let source = &mut ctx.accounts.source;
+    let mut margin_account = ctx.accounts.margin_account.load_mut()?;
+
+    source.reload()?;
+    let position =
+        margin_account.set_position_balance(&source.mint, &source.key(), source.amount)?;
+
+    let liquidation = &mut ctx.accounts.liquidation.load_mut()?.state;
+    let end_value = update_and_verify_liquidation(&margin_account, liquidation, start_value)?;
+
+    emit!(events::PositionBalanceUpdated { position });
+    emit!(events::LiquidatorDepositTransferred {
+        margin_account: ctx.accounts.margin_account.key(),
+        liquidator: ctx.accounts.liquidator.key(),
+        destination: ctx.accounts.destination.key(),
+        amount,
+        liquidation_data: *liquidation,
+        valuation_summary: end_value.into(),
+    });
+
+    Ok(())
+}
@@ -1,9 +1,11 @@
 mod create_deposit_position;
+mod liquidator_transfer_deposit;
 mod refresh_deposit_position;
 mod refresh_position_config;
 mod transfer_deposit;
 
 pub use create_deposit_position::*;
+pub use liquidator_transfer_deposit::*;
 pub use refresh_deposit_position::*;
 pub use refresh_position_config::*;
 pub use transfer_deposit::*;
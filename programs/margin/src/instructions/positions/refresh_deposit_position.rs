@@ -23,7 +23,10 @@ use crate::{ErrorCode, MarginAccount, PriceChangeInfo, TokenConfig, TokenOracle}
 #[derive(Accounts)]
 pub struct RefreshDepositPosition<'info> {
     /// The account to update
-    #[account(mut)]
+    #[account(
+        mut,
+        constraint = MarginAccount::has_current_version(&margin_account.to_account_info()) @ ErrorCode::WrongAccountVersion
+    )]
     pub margin_account: AccountLoader<'info, MarginAccount>,
 
     /// The margin config for the token
@@ -37,6 +40,8 @@ pub fn refresh_deposit_position_handler(ctx: Context<RefreshDepositPosition>) ->
     let mut margin_account = ctx.accounts.margin_account.load_mut()?;
     let config = &ctx.accounts.config;
 
+    margin_account.verify_airspace(&config.airspace)?;
+
     match config.oracle() {
         Some(TokenOracle::Pyth { price, .. }) => {
             let price_oracle_key = ctx.accounts.price_oracle.key();
@@ -73,7 +78,7 @@ pub fn refresh_deposit_position_handler(ctx: Context<RefreshDepositPosition>) ->
             };
 
             let position = margin_account.get_position_mut(&config.mint).unwrap();
-            position.set_price(&price_info.try_into()?)?;
+            position.set_price_and_twap(&price_info.try_into()?, price_info.twap)?;
         }
 
         None => {
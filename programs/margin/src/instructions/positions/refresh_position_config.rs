@@ -17,12 +17,15 @@
 
 use anchor_lang::prelude::*;
 
-use crate::{events, MarginAccount, TokenConfig};
+use crate::{events, ErrorCode, MarginAccount, PositionParams, TokenConfig};
 
 #[derive(Accounts)]
 pub struct RefreshPositionConfig<'info> {
     /// The margin account with the position to be refreshed
-    #[account(mut)]
+    #[account(
+        mut,
+        constraint = MarginAccount::has_current_version(&margin_account.to_account_info()) @ ErrorCode::WrongAccountVersion
+    )]
     pub margin_account: AccountLoader<'info, MarginAccount>,
 
     /// The config account for the token, which has been updated
@@ -30,19 +33,41 @@ pub struct RefreshPositionConfig<'info> {
 }
 
 /// Refresh the metadata for a position
+///
+/// If the token belongs to a category, its [TokenCategory](crate::TokenCategory) account
+/// must be passed as a remaining account.
 pub fn refresh_position_config_handler(ctx: Context<RefreshPositionConfig>) -> Result<()> {
-    let config = &ctx.accounts.config;
-    let mut account = ctx.accounts.margin_account.load_mut()?;
+    let mut params = PositionParams::from(&*ctx.accounts.config);
+    params.load_category(ctx.remaining_accounts)?;
+
+    refresh_position(
+        &ctx.accounts.margin_account,
+        &params,
+        ctx.remaining_accounts,
+    )
+}
+
+/// Update a registered position with the latest parameters for its token
+pub(crate) fn refresh_position(
+    margin_account: &AccountLoader<MarginAccount>,
+    params: &PositionParams,
+    accounts: &[AccountInfo],
+) -> Result<()> {
+    let mut account = margin_account.load_mut()?;
+    params.verify_airspace(&account, accounts)?;
 
     let position = account.refresh_position_metadata(
-        &config.mint,
-        config.token_kind,
-        config.value_modifier,
-        config.max_staleness,
+        &params.mint,
+        params.token_kind,
+        params.value_modifier,
+        params.max_staleness,
+        params.conservative_valuation,
+        params.category,
+        params.category_value_modifier,
     )?;
 
     emit!(events::PositionMetadataRefreshed {
-        margin_account: ctx.accounts.margin_account.key(),
+        margin_account: margin_account.key(),
         position,
     });
 
@@ -20,15 +20,17 @@ use anchor_spl::token::{self, Token, TokenAccount, Transfer};
 
 use crate::{events, ErrorCode, MarginAccount, SignerSeeds};
 
-// FIXME: no transfer support for liquidators
-
 #[derive(Accounts)]
 pub struct TransferDeposit<'info> {
     /// The authority that owns the margin account
     pub owner: Signer<'info>,
 
     /// The margin account that the deposit account is associated with
-    #[account(mut, has_one = owner)]
+    #[account(
+        mut,
+        constraint = MarginAccount::has_current_version(&margin_account.to_account_info()) @ ErrorCode::WrongAccountVersion,
+        constraint = margin_account.load()?.owner == owner.key() @ anchor_lang::error::ErrorCode::ConstraintHasOne
+    )]
     pub margin_account: AccountLoader<'info, MarginAccount>,
 
     /// The authority for the source account
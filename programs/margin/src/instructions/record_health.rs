@@ -0,0 +1,43 @@
+// SPDX-License-Identifier: AGPL-3.0-or-later
+//
+// Copyright (C) 2022 JET PROTOCOL HOLDINGS, LLC.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+use anchor_lang::prelude::*;
+
+use crate::{events, util::get_timestamp, ErrorCode, MarginAccount};
+
+#[derive(Accounts)]
+pub struct RecordHealth<'info> {
+    /// The account to record the health of
+    #[account(
+        constraint = MarginAccount::has_current_version(&margin_account.to_account_info()) @ ErrorCode::WrongAccountVersion
+    )]
+    pub margin_account: AccountLoader<'info, MarginAccount>,
+}
+
+pub fn record_health_handler(ctx: Context<RecordHealth>) -> Result<()> {
+    let account = ctx.accounts.margin_account.load()?;
+    let valuation = account.valuation()?;
+
+    emit!(events::HealthSnapshot {
+        margin_account: ctx.accounts.margin_account.key(),
+        timestamp: get_timestamp(),
+        health_ratio_bps: valuation.health_ratio_bps(),
+        valuation_summary: valuation.into(),
+    });
+
+    Ok(())
+}
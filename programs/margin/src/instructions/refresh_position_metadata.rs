@@ -17,36 +17,36 @@
 
 use anchor_lang::prelude::*;
 
-use jet_metadata::PositionTokenMetadata;
-
-use crate::{events, MarginAccount};
+use crate::{refresh_position, ErrorCode, MarginAccount, PositionParams};
 
 #[derive(Accounts)]
 pub struct RefreshPositionMetadata<'info> {
     /// The margin account with the position to be refreshed
-    #[account(mut)]
+    #[account(
+        mut,
+        constraint = MarginAccount::has_current_version(&margin_account.to_account_info()) @ ErrorCode::WrongAccountVersion
+    )]
     pub margin_account: AccountLoader<'info, MarginAccount>,
 
-    /// The metadata account for the token, which has been updated
-    pub metadata: Account<'info, PositionTokenMetadata>,
+    /// The config account for the token, or the legacy metadata account while
+    /// compatibility with metadata is enabled.
+    ///
+    /// CHECK: validated in the handler
+    pub metadata: AccountInfo<'info>,
 }
 
 /// Refresh the metadata for a position
+///
+/// Only accounts in the default airspace can use the legacy metadata, and only while
+/// there is no token config for the token. The address of the token config must be
+/// passed as a remaining account to show that it doesn't exist.
 pub fn refresh_position_metadata_handler(ctx: Context<RefreshPositionMetadata>) -> Result<()> {
-    let metadata = &ctx.accounts.metadata;
-    let mut account = ctx.accounts.margin_account.load_mut()?;
-
-    let position = account.refresh_position_metadata(
-        &metadata.position_token_mint,
-        metadata.token_kind.into(),
-        metadata.value_modifier,
-        metadata.max_staleness,
-    )?;
-
-    emit!(events::PositionMetadataRefreshed {
-        margin_account: ctx.accounts.margin_account.key(),
-        position,
-    });
-
-    Ok(())
+    let mut params = PositionParams::load(&ctx.accounts.metadata)?;
+    params.load_category(ctx.remaining_accounts)?;
+
+    refresh_position(
+        &ctx.accounts.margin_account,
+        &params,
+        ctx.remaining_accounts,
+    )
 }
@@ -18,9 +18,7 @@
 use anchor_lang::prelude::*;
 use anchor_spl::token::{Mint, Token, TokenAccount};
 
-use jet_metadata::PositionTokenMetadata;
-
-use crate::{events, util::Require, Approver, MarginAccount};
+use crate::{events, util::Require, Approver, ErrorCode, MarginAccount, PositionParams};
 
 #[derive(Accounts)]
 pub struct RegisterPosition<'info> {
@@ -32,15 +30,20 @@ pub struct RegisterPosition<'info> {
     pub payer: Signer<'info>,
 
     /// The margin account to register position type with
-    #[account(mut)]
+    #[account(
+        mut,
+        constraint = MarginAccount::has_current_version(&margin_account.to_account_info()) @ ErrorCode::WrongAccountVersion
+    )]
     pub margin_account: AccountLoader<'info, MarginAccount>,
 
     /// The mint for the position token being registered
     pub position_token_mint: Account<'info, Mint>,
 
-    /// The metadata account that references the correct oracle for the token
-    #[account(has_one = position_token_mint)]
-    pub metadata: Account<'info, PositionTokenMetadata>,
+    /// The config account for the token, or the legacy metadata account while
+    /// compatibility with metadata is enabled.
+    ///
+    /// CHECK: validated in the handler
+    pub metadata: AccountInfo<'info>,
 
     /// The token account to store hold the position assets in the custody of the
     /// margin account.
@@ -62,20 +65,32 @@ pub struct RegisterPosition<'info> {
 }
 
 pub fn register_position_handler(ctx: Context<RegisterPosition>) -> Result<()> {
-    let metadata = &ctx.accounts.metadata;
+    let mut params = PositionParams::load(&ctx.accounts.metadata)?;
+    params.load_category(ctx.remaining_accounts)?;
+
     let mut account = ctx.accounts.margin_account.load_mut()?;
     let position_token = &ctx.accounts.position_token_mint;
     let address = ctx.accounts.token_account.key();
     account.verify_authority(ctx.accounts.authority.key())?;
+    params.verify_airspace(&account, ctx.remaining_accounts)?;
+    params.verify_permit_tier(&account, ctx.remaining_accounts)?;
+
+    if params.mint != position_token.key() {
+        msg!("the token config is for a different mint");
+        return err!(ErrorCode::InvalidConfig);
+    }
 
     let key = account.register_position(
         position_token.key(),
         position_token.decimals,
         address,
-        metadata.adapter_program,
-        metadata.token_kind.into(),
-        metadata.value_modifier,
-        metadata.max_staleness,
+        params.adapter_program,
+        params.token_kind,
+        params.value_modifier,
+        params.max_staleness,
+        params.conservative_valuation,
+        params.category,
+        params.category_value_modifier,
         &[Approver::MarginAccountAuthority],
     )?;
 
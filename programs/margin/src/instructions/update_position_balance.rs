@@ -18,12 +18,15 @@
 use anchor_lang::prelude::*;
 use anchor_spl::token::TokenAccount;
 
-use crate::{events, MarginAccount};
+use crate::{events, ErrorCode, MarginAccount};
 
 #[derive(Accounts)]
 pub struct UpdatePositionBalance<'info> {
     /// The account to update
-    #[account(mut)]
+    #[account(
+        mut,
+        constraint = MarginAccount::has_current_version(&margin_account.to_account_info()) @ ErrorCode::WrongAccountVersion
+    )]
     pub margin_account: AccountLoader<'info, MarginAccount>,
 
     /// The token account to update the balance for
@@ -17,11 +17,14 @@
 
 use anchor_lang::prelude::*;
 
-use crate::{events, MarginAccount};
+use crate::{events, ErrorCode, MarginAccount};
 
 #[derive(Accounts)]
 pub struct VerifyHealthy<'info> {
     /// The account verify the health of
+    #[account(
+        constraint = MarginAccount::has_current_version(&margin_account.to_account_info()) @ ErrorCode::WrongAccountVersion
+    )]
     pub margin_account: AccountLoader<'info, MarginAccount>,
 }
 
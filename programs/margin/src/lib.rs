@@ -23,6 +23,7 @@ declare_id!("JPMRGNgRk3w2pzBM1RLNBnpGxQYsFQ3yXKpuk4tTXVZ");
 
 pub mod events;
 pub mod seeds;
+pub mod views;
 
 mod adapter;
 mod instructions;
@@ -36,7 +37,7 @@ pub use state::*;
 pub use util::Invocation;
 
 pub use adapter::{AdapterResult, PositionChange, PriceChangeInfo};
-pub use instructions::TokenConfigUpdate;
+pub use instructions::{AccountHealth, AdapterInstruction, TokenCategoryUpdate, TokenConfigUpdate};
 
 /// The maximum confidence deviation allowed for an oracle price.
 ///
@@ -106,6 +107,8 @@ pub mod jet_margin {
     /// | `owner` | `signer` | The owner of the new margin account. |
     /// | `payer` | `signer` | The pubkey paying rent for the new margin account opening. |
     /// | `margin_account` | `writable` | The margin account to initialize for the owner. |
+    /// | `airspace` | `read_only` | The airspace the new account will belong to. |
+    /// | `permit` | `read_only` | The owner's permit for the airspace, required only if the airspace is restricted. |
     /// | `system_program` | `read_only` | The [system native program](https://docs.solana.com/developing/runtime-facilities/programs#system-program). |
     ///
     /// # Events
@@ -119,6 +122,32 @@ pub mod jet_margin {
         create_account_handler(ctx, seed)
     }
 
+    /// Migrate a version 1 margin account, which was created before accounts were bound
+    /// to an airspace.
+    ///
+    /// The account is resized to fit the current layout and assigned to the default
+    /// airspace, which is where the pools and configs it already uses belong.
+    ///
+    /// # [Accounts](jet_margin::accounts::MigrateAccount)
+    ///
+    /// |     |     |     |
+    /// | --- | --- | --- |
+    /// | **Name** | **Type** | **Description** |
+    /// | `owner` | `signer` | The owner of the margin account. |
+    /// | `payer` | `signer` | The pubkey paying rent for the additional account space. |
+    /// | `margin_account` | `writable` | The margin account to migrate. |
+    /// | `system_program` | `read_only` | The [system native program](https://docs.solana.com/developing/runtime-facilities/programs#system-program). |
+    ///
+    /// # Events
+    ///
+    /// |     |     |
+    /// | --- | --- |
+    /// | **Event Name** | **Description** |
+    /// | [`events::AccountMigrated`] | The migrated account. |
+    pub fn migrate_account(ctx: Context<MigrateAccount>) -> Result<()> {
+        migrate_account_handler(ctx)
+    }
+
     /// Close a user's margin account
     ///
     /// The margin account must have zero positions remaining to be closed.
@@ -150,6 +179,11 @@ pub mod jet_margin {
     ///
     /// This instruction may fail if the account has reached it's maximum number of positions.
     ///
+    /// If the token requires a minimum permit tier, the owner's permit must be passed as a
+    /// remaining account.
+    /// Likewise, if the token belongs to a category, the category's config must be passed
+    /// as a remaining account.
+    ///
     /// # [Accounts](jet_margin::accounts::RegisterPosition)
     ///
     /// |     |     |     |
@@ -159,7 +193,7 @@ pub mod jet_margin {
     /// | `payer` | `signer` | The address paying for rent. |
     /// | `margin_account` | `writable` |  The margin account to register position type with. |
     /// | `position_token_mint` | `read_only` | The mint for the position token being registered. |
-    /// | `metadata` | `read_only` | The metadata account that references the correct oracle for the token. |
+    /// | `metadata` | `read_only` | The token config for the position token, or its legacy metadata account. |
     /// | `token_account` | `writable` | The token account to store hold the position assets in the custody of the margin account. |
     /// | `token_program` | `read_only` | The [spl token program](https://spl.solana.com/token). |
     /// | `rent` | `read_only` | The [rent sysvar](https://docs.solana.com/developing/runtime-facilities/sysvars#rent). The rent to open the account. |
@@ -206,13 +240,17 @@ pub mod jet_margin {
     /// in the case where the metadata has changed after the position was
     /// created.
     ///
+    /// Accepts either the token config or the legacy metadata for the token. New
+    /// clients should prefer `refresh_position_config`.
+    ///
     /// # [Accounts](jet_margin::accounts::RefreshPositionMetadata)
     ///
     /// |     |     |     |
     /// | --- | --- | --- |
     /// | **Name** | **Type** | **Description** |
     /// | `margin_account` | `writable` | The margin account with the position to be refreshed. |
-    /// | `metadata` | `read_only` | The metadata account for the token, which has been updated. |
+    /// | `metadata` | `read_only` | The token config or legacy metadata account for the token, which has been updated. |
+    /// | `token_category` | `read_only` | _(optional remaining account)_ The category config, required if the token belongs to a category. |
     ///
     /// # Events
     ///
@@ -269,6 +307,47 @@ pub mod jet_margin {
         verify_healthy_handler(ctx)
     }
 
+    /// Record the current health of a margin account.
+    ///
+    /// This instruction is permissionless, and can be used to build a history of
+    /// an account's valuation over time. The account does not need to be healthy,
+    /// but its claim positions must not be stale.
+    ///
+    /// # [Accounts](jet_margin::accounts::RecordHealth)
+    ///
+    /// |     |     |     |
+    /// | --- | --- | --- |
+    /// | **Name** | **Type** | **Description** |
+    /// | `margin_account` | `read_only` | The account to record the health of. |
+    ///
+    /// # Events
+    ///
+    /// |     |     |
+    /// | --- | --- |
+    /// | **Event Name** | **Description** |
+    /// | [`events::HealthSnapshot`] | The valuation and health ratio of the account. |
+    pub fn record_health(ctx: Context<RecordHealth>) -> Result<()> {
+        record_health_handler(ctx)
+    }
+
+    /// Get the current health of a margin account.
+    ///
+    /// This instruction does not modify any accounts, and returns an [AccountHealth]
+    /// through the return data, so other programs can check an account's health
+    /// through CPI. See [views::account_health]. The account's claim positions
+    /// must not be stale.
+    ///
+    /// # [Accounts](jet_margin::accounts::AccountHealthView)
+    ///
+    /// |     |     |     |
+    /// | --- | --- | --- |
+    /// | **Name** | **Type** | **Description** |
+    /// | `margin_account` | `read_only` | The account to get the health of. |
+    ///
+    pub fn account_health(ctx: Context<AccountHealthView>) -> Result<()> {
+        account_health_handler(ctx)
+    }
+
     /// Perform an action by invoking other programs, allowing them to alter
     /// the balances of the token accounts belonging to this margin account.
     ///
@@ -276,8 +355,9 @@ pub mod jet_margin {
     /// grants the adapter authority over any tokens held by the margin account.
     ///
     /// This validates the invoked program by expecting an `adapter_metadata` account,
-    /// which must exist for the instruction to be considered valid. The configuration
-    /// for allowing adapter programs is controlled by protocol governance.
+    /// which must be the adapter's config in the margin account's airspace for the
+    /// instruction to be considered valid. The configuration for allowing adapter
+    /// programs is controlled by the airspace authority.
     ///
     /// All extra accounts passed in are used as the input accounts when invoking
     /// the provided adapter porgram.
@@ -294,7 +374,7 @@ pub mod jet_margin {
     /// | `owner` | `signer` | The authority that owns the margin account. |
     /// | `margin_account` | `writable` | The margin account to proxy an action for. |
     /// | `adapter_program` | `read_only` | The program to be invoked. |
-    /// | `adapter_metadata` | `read_only` | The metadata about the proxy program. |
+    /// | `adapter_metadata` | `read_only` | The config for the proxy program in the account's airspace. |
     ///
     /// # Events
     ///
@@ -311,6 +391,45 @@ pub mod jet_margin {
         adapter_invoke_handler(ctx, data)
     }
 
+    /// Perform a sequence of actions by invoking other programs, allowing them to alter
+    /// the balances of the token accounts belonging to this margin account.
+    ///
+    /// This behaves like a series of `adapter_invoke` instructions, except that the
+    /// account health is only verified once, after the results from all the adapters have
+    /// been applied. If any of the invocations fail, the entire batch fails.
+    ///
+    /// The remaining accounts are split up between the instructions in order. Each
+    /// instruction uses the adapter program, then the adapter's config for the account's
+    /// airspace, and then the number of accounts given by `num_accounts` to be passed
+    /// through to the adapter.
+    ///
+    /// # Parameters
+    ///
+    /// * `instructions` - The instructions to invoke, in order
+    ///
+    /// # [Accounts](jet_margin::accounts::AdapterInvokeBatch)
+    ///
+    /// |     |     |     |
+    /// | --- | --- | --- |
+    /// | **Name** | **Type** | **Description** |
+    /// | `owner` | `signer` | The authority that owns the margin account. |
+    /// | `margin_account` | `writable` | The margin account to proxy actions for. |
+    ///
+    /// # Events
+    ///
+    /// |     |     |
+    /// | --- | --- |
+    /// | **Event Name** | **Description** |
+    /// | [`events::AdapterInvokeBegin`] | Marks the start of each adapter invocation. |
+    /// | [`events::PositionEvent`] _(Note that each single event represents a different adapter position)_ | The [PositionEvent](events::PositionEvent) describing the change in position. |
+    /// | [`events::AdapterInvokeEnd`] | Marks the ending of each adapter invocation. |
+    pub fn adapter_invoke_batch<'info>(
+        ctx: Context<'_, '_, '_, 'info, AdapterInvokeBatch<'info>>,
+        instructions: Vec<AdapterInstruction>,
+    ) -> Result<()> {
+        adapter_invoke_batch_handler(ctx, instructions)
+    }
+
     /// Perform an action by invoking other programs, allowing them only to
     /// refresh the state of the margin account to be consistent with the actual
     /// underlying prices or positions, but not permitting new position changes.
@@ -334,7 +453,7 @@ pub mod jet_margin {
     /// | **Name** | **Type** |  **Description** |
     /// | `margin_account` | `writable` | The margin account to proxy an action for. |
     /// | `adapter_program` | `read_only` | The program to be invoked. |
-    /// | `adapter_metadata` | `read_only` | The metadata about the proxy program. |
+    /// | `adapter_metadata` | `read_only` | The config for the proxy program in the account's airspace. |
     ///
     /// # Events
     ///
@@ -357,7 +476,7 @@ pub mod jet_margin {
     /// until the liquidator process is complete.
     ///
     /// Requires the `liquidator_metadata` account, which restricts the signer to
-    /// those approved by protocol governance.
+    /// those approved by the governance of the account's airspace.
     ///
     /// # [Accounts](jet_margin::accounts::LiquidateBegin)
     ///
@@ -367,7 +486,7 @@ pub mod jet_margin {
     /// | `margin_account` | `writable` | The account in need of liquidation. |
     /// | `payer` | `signer` | The address paying rent. |
     /// | `liquidator` | `signer` | The liquidator account performing the liquidation. |
-    /// | `liquidator_metadata` | `read_only` | The metadata describing the liquidator. |
+    /// | `liquidator_metadata` | `read_only` | The config for the liquidator in the account's airspace, or the legacy metadata. |
     /// | `liquidation` | `writable` | The account to persist the state of liquidation. |
     /// | `system_program` | `read_only` | The [system native program](https://docs.solana.com/developing/runtime-facilities/programs#system-program). |
     ///
@@ -420,7 +539,7 @@ pub mod jet_margin {
     /// | `liquidation` | `writable` | The account to persist the state of liquidation. |
     /// | `margin_account` | `writable` | The margin account to proxy an action for. |
     /// | `adapter_program` | `read_only` | The program to be invoked. |
-    /// | `adapter_metadata` | `read_only` | The metadata about the proxy program. |
+    /// | `adapter_metadata` | `read_only` | The config for the proxy program in the account's airspace. |
     ///
     /// # Events
     ///
@@ -440,6 +559,9 @@ pub mod jet_margin {
     /// Update the config for a token position stored in the margin account,
     /// in the case where the token config has changed after the position was
     /// created.
+    ///
+    /// If the token belongs to a category, the category's config must be passed as
+    /// a remaining account.
     pub fn refresh_position_config(ctx: Context<RefreshPositionConfig>) -> Result<()> {
         refresh_position_config_handler(ctx)
     }
@@ -450,6 +572,11 @@ pub mod jet_margin {
     }
 
     /// Create a new account for holding SPL token deposits directly by a margin account.
+    ///
+    /// If the token requires a minimum permit tier, the owner's permit must be passed as a
+    /// remaining account.
+    /// Likewise, if the token belongs to a category, the category's config must be passed
+    /// as a remaining account.
     pub fn create_deposit_position(ctx: Context<CreateDepositPosition>) -> Result<()> {
         create_deposit_position_handler(ctx)
     }
@@ -459,6 +586,19 @@ pub mod jet_margin {
         transfer_deposit_handler(ctx, amount)
     }
 
+    /// Transfer tokens out of a deposit position in an account being liquidated, into an
+    /// account chosen by the liquidator.
+    ///
+    /// The loss of equity is recorded on the liquidation, and the instruction fails if the
+    /// total change in equity during the liquidation falls below the allowed minimum. Only
+    /// positions not managed by an adapter can be transferred this way.
+    pub fn liquidator_transfer_deposit(
+        ctx: Context<LiquidatorTransferDeposit>,
+        amount: u64,
+    ) -> Result<()> {
+        liquidator_transfer_deposit_handler(ctx, amount)
+    }
+
     /// Set the configuration for a token, which allows it to be used as a position in a margin
     /// account.
     ///
@@ -474,6 +614,23 @@ pub mod jet_margin {
         configure_token_handler(ctx, update)
     }
 
+    /// Set the configuration for a category of correlated tokens.
+    ///
+    /// When all the claims in a margin account are in the same category, positions in that
+    /// category are valued with the category's collateral weight or max leverage, if higher
+    /// than the ones in their token configs. Positions pick up the category's values when
+    /// refreshed with `refresh_position_config`.
+    ///
+    /// The account storing the configuration will be funded if not already. If a `None` is provided as
+    /// the updated configuration, then the account will be defunded.
+    pub fn configure_token_category(
+        ctx: Context<ConfigureTokenCategory>,
+        category: u16,
+        update: Option<TokenCategoryUpdate>,
+    ) -> Result<()> {
+        configure_token_category_handler(ctx, category, update)
+    }
+
     /// Set the configuration for an adapter.
     ///
     /// The configuration for a token only applies for the associated airspace, and changing any
@@ -517,6 +674,14 @@ pub enum ErrorCode {
     #[msg("the current instruction was not directly invoked by the margin program")]
     IndirectInvocation,
 
+    /// 141004 - A view instruction did not return anything
+    #[msg("the program did not return a result")]
+    NoReturnData = 135_004,
+
+    /// 141005 - The return data was set by a program other than the one invoked
+    #[msg("the program that set the return data was not the one invoked")]
+    WrongProgramReturnData = 135_005,
+
     /// 141010 - Account cannot record any additional positions
     #[msg("account cannot record any additional positions")]
     MaxPositions = 135_010,
@@ -607,6 +772,14 @@ pub enum ErrorCode {
     /// 141051 - Attempting to use or set an oracle that is not valid
     #[msg("attempting to use or set invalid configuration")]
     InvalidOracle = 135_052,
+
+    /// 141053 - The permit does not allow the owner to use the airspace
+    #[msg("the permit does not allow the owner to use the airspace")]
+    WrongPermit = 135_053,
+
+    /// 141054 - The account does not have the expected version
+    #[msg("the account does not have the expected version")]
+    WrongAccountVersion = 135_054,
 }
 
 /// Writes the result of position changes from an adapter invocation.
@@ -25,3 +25,6 @@ pub const ADAPTER_CONFIG_SEED: &[u8] = b"adapter-config";
 
 #[constant]
 pub const LIQUIDATOR_CONFIG_SEED: &[u8] = b"liquidator-config";
+
+#[constant]
+pub const TOKEN_CATEGORY_SEED: &[u8] = b"token-category";
@@ -36,7 +36,7 @@ mod positions;
 pub use positions::*;
 
 /// The current version for the margin account state
-pub const MARGIN_ACCOUNT_VERSION: u8 = 1;
+pub const MARGIN_ACCOUNT_VERSION: u8 = 2;
 
 #[account(zero_copy)]
 #[repr(C)]
@@ -64,6 +64,11 @@ pub struct MarginAccount {
 
     /// The storage for tracking account balances
     pub positions: [u8; 7432],
+
+    /// The airspace this account belongs to
+    ///
+    /// Not present in version 1 accounts, which must be migrated before they can be used.
+    pub airspace: Pubkey,
 }
 
 #[cfg(any(test, feature = "cli"))]
@@ -72,9 +77,10 @@ impl Serialize for MarginAccount {
     where
         S: Serializer,
     {
-        let mut s = serializer.serialize_struct("MarginAccount", 5)?;
+        let mut s = serializer.serialize_struct("MarginAccount", 6)?;
         s.serialize_field("version", &self.version)?;
         s.serialize_field("owner", &self.owner.to_string())?;
+        s.serialize_field("airspace", &self.airspace.to_string())?;
         s.serialize_field("liquidation", &self.liquidation.to_string())?;
         s.serialize_field("liquidator", &self.liquidator.to_string())?;
         s.serialize_field("positions", &self.positions().collect::<Vec<_>>())?;
@@ -91,6 +97,7 @@ impl std::fmt::Debug for MarginAccount {
             .field("reserved0", &self.reserved0)
             .field("invocation", &self.invocation)
             .field("owner", &self.owner)
+            .field("airspace", &self.airspace)
             .field("liquidation", &self.liquidation)
             .field("liquidator", &self.liquidator);
 
@@ -133,6 +140,22 @@ pub trait AnchorVerify: Discriminator + Owner {
 impl AnchorVerify for MarginAccount {}
 
 impl MarginAccount {
+    /// The size of the account data for a version 1 account, which did not record an airspace
+    pub const V1_SPACE: usize = 8 + std::mem::size_of::<Self>() - std::mem::size_of::<Pubkey>();
+
+    /// Check that an account has the size and version of a current margin account.
+    ///
+    /// Anchor loads zero copy accounts without checking their length, so this has to be
+    /// checked before loading an account that may not have been migrated yet.
+    pub fn has_current_version(info: &AccountInfo) -> bool {
+        match info.try_borrow_data() {
+            Ok(data) => {
+                data.len() >= 8 + std::mem::size_of::<Self>() && data[8] == MARGIN_ACCOUNT_VERSION
+            }
+            Err(_) => false,
+        }
+    }
+
     pub fn start_liquidation(&mut self, liquidation: Pubkey, liquidator: Pubkey) {
         self.liquidation = liquidation;
         self.liquidator = liquidator;
@@ -156,14 +179,29 @@ impl MarginAccount {
         self.liquidation != Pubkey::default()
     }
 
-    pub fn initialize(&mut self, owner: Pubkey, seed: u16, bump_seed: u8) {
+    pub fn initialize(&mut self, airspace: Pubkey, owner: Pubkey, seed: u16, bump_seed: u8) {
         self.version = MARGIN_ACCOUNT_VERSION;
+        self.airspace = airspace;
         self.owner = owner;
         self.bump_seed = [bump_seed];
         self.user_seed = seed.to_le_bytes();
         self.liquidator = Pubkey::default();
     }
 
+    /// Verify that an entity configured for the given airspace can be used with this account
+    pub fn verify_airspace(&self, airspace: &Pubkey) -> AnchorResult<()> {
+        if self.airspace != *airspace {
+            msg!(
+                "account is in airspace {} but got {}",
+                self.airspace,
+                airspace
+            );
+            return err!(ErrorCode::WrongAirspace);
+        }
+
+        Ok(())
+    }
+
     /// Get the list of positions on this account
     pub fn positions(&self) -> impl Iterator<Item = &AccountPosition> {
         self.position_list()
@@ -183,6 +221,9 @@ impl MarginAccount {
         kind: TokenKind,
         value_modifier: u16,
         max_staleness: u64,
+        conservative_valuation: bool,
+        category: u16,
+        category_value_modifier: u16,
         approvals: &[Approver],
     ) -> AnchorResult<AccountPositionKey> {
         if !self.is_liquidating() && self.position_list().length >= MAX_USER_POSITIONS {
@@ -198,6 +239,9 @@ impl MarginAccount {
         free_position.balance = 0;
         free_position.value_modifier = value_modifier;
         free_position.max_staleness = max_staleness;
+        free_position.conservative_valuation = conservative_valuation as u8;
+        free_position.category = category;
+        free_position.category_value_modifier = category_value_modifier;
 
         if !free_position.may_be_registered_or_closed(approvals) {
             msg!(
@@ -234,12 +278,16 @@ impl MarginAccount {
         Ok(())
     }
 
+    #[allow(clippy::too_many_arguments)]
     pub fn refresh_position_metadata(
         &mut self,
         mint: &Pubkey,
         kind: TokenKind,
         value_modifier: u16,
         max_staleness: u64,
+        conservative_valuation: bool,
+        category: u16,
+        category_value_modifier: u16,
     ) -> Result<AccountPosition, ErrorCode> {
         let position = match self.position_list_mut().get_mut(mint) {
             None => return Err(ErrorCode::PositionNotRegistered),
@@ -249,6 +297,10 @@ impl MarginAccount {
         position.kind = kind.into_integer();
         position.value_modifier = value_modifier;
         position.max_staleness = max_staleness;
+        position.conservative_valuation = conservative_valuation as u8;
+        position.category = category;
+        position.category_value_modifier = category_value_modifier;
+        position.calculate_value();
 
         Ok(*position)
     }
@@ -381,8 +433,23 @@ impl MarginAccount {
         Ok(())
     }
 
+    /// The token category shared by every claim with a balance in this account, or zero if
+    /// the claims are in different categories, or there are none.
+    pub fn claims_category(&self) -> u16 {
+        let mut categories = self
+            .positions()
+            .filter(|p| p.kind() == TokenKind::Claim && p.balance > 0)
+            .map(|p| p.category);
+
+        match categories.next() {
+            Some(first) if categories.all(|c| c == first) => first,
+            _ => 0,
+        }
+    }
+
     pub fn valuation(&self) -> AnchorResult<Valuation> {
         let timestamp = crate::util::get_timestamp();
+        let claims_category = self.claims_category();
 
         let mut past_due = false;
         let mut liabilities = Number128::ZERO;
@@ -424,7 +491,7 @@ impl MarginAccount {
 
                     equity -= position.value();
                     liabilities += position.value();
-                    required_collateral += position.required_collateral_value();
+                    required_collateral += position.required_collateral_value(claims_category);
                 }
                 (TokenKind::Claim, Some(error)) => {
                     msg!("claim position is stale: {:?}", position);
@@ -433,7 +500,7 @@ impl MarginAccount {
 
                 (TokenKind::AdapterCollateral | TokenKind::Collateral, None) => {
                     equity += position.value();
-                    weighted_collateral += position.collateral_value();
+                    weighted_collateral += position.collateral_value(claims_category);
                 }
                 (TokenKind::AdapterCollateral | TokenKind::Collateral, Some(e)) => {
                     stale_collateral_list.push((position.token, e));
@@ -584,6 +651,22 @@ impl Valuation {
     pub fn past_due(&self) -> bool {
         self.past_due
     }
+
+    /// The ratio of effective to required collateral, in basis points.
+    ///
+    /// An account is healthy while this is at least 10_000. Accounts with no
+    /// required collateral report `u64::MAX`, and negative effective collateral
+    /// is reported as zero.
+    pub fn health_ratio_bps(&self) -> u64 {
+        if self.required_collateral <= Number128::ZERO {
+            return u64::MAX;
+        }
+
+        let ratio = self.effective_collateral / self.required_collateral;
+        let bps = (ratio * Number128::from_decimal(10_000, 0)).to_i128();
+
+        bps.clamp(0, u64::MAX as i128) as u64
+    }
 }
 
 #[cfg(test)]
@@ -621,6 +704,7 @@ mod tests {
             liquidator: Pubkey::default(),
             invocation,
             positions: [0; 7432],
+            airspace: Pubkey::default(),
         };
         let output = "MarginAccount {
             version: 1,
@@ -631,6 +715,7 @@ mod tests {
                 caller_heights: BitSet(0b10010111)
             },
             owner: 11111111111111111111111111111111,
+            airspace: 11111111111111111111111111111111,
             liquidation: 11111111111111111111111111111111,
             liquidator: 11111111111111111111111111111111,
             positions: []
@@ -650,6 +735,9 @@ mod tests {
             TokenKind::Collateral,
             5000,
             1000,
+            false,
+            0,
+            0,
             approvals,
         )
         .unwrap();
@@ -690,6 +778,7 @@ mod tests {
             liquidator: Pubkey::default(),
             invocation: Invocation::default(),
             positions: [0; 7432],
+            airspace: Pubkey::default(),
         };
 
         assert_ser_tokens(
@@ -697,12 +786,14 @@ mod tests {
             &[
                 Token::Struct {
                     name: "MarginAccount",
-                    len: 5,
+                    len: 6,
                 },
                 Token::Str("version"),
                 Token::U8(1),
                 Token::Str("owner"),
                 Token::Str("11111111111111111111111111111111"),
+                Token::Str("airspace"),
+                Token::Str("11111111111111111111111111111111"),
                 Token::Str("liquidation"),
                 Token::Str("11111111111111111111111111111111"),
                 Token::Str("liquidator"),
@@ -777,6 +868,7 @@ mod tests {
             liquidator: Pubkey::default(),
             invocation: Invocation::default(),
             positions: [0; 7432],
+            airspace: Pubkey::default(),
         };
         let pos = register_position(&mut margin_account, 0, TokenKind::Claim);
         margin_account.set_position_balance(&pos, &pos, 1).unwrap();
@@ -796,6 +888,7 @@ mod tests {
             liquidator: Pubkey::default(),
             invocation: Invocation::default(),
             positions: [0; 7432],
+            airspace: Pubkey::default(),
         };
 
         let pos = register_position(&mut margin_account, 0, TokenKind::AdapterCollateral);
@@ -836,6 +929,7 @@ mod tests {
             liquidator: Pubkey::default(),
             invocation: Invocation::default(),
             positions: [0; 7432],
+            airspace: Pubkey::default(),
         };
         let user_approval = &[Approver::MarginAccountAuthority];
         let adapter_approval = &[Approver::MarginAccountAuthority, Approver::Adapter(adapter)];
@@ -856,6 +950,9 @@ mod tests {
                 TokenKind::Collateral,
                 0,
                 0,
+                false,
+                0,
+                0,
                 user_approval,
             )
             .unwrap();
@@ -869,6 +966,9 @@ mod tests {
                 TokenKind::Claim,
                 0,
                 0,
+                false,
+                0,
+                0,
                 adapter_approval,
             )
             .unwrap();
@@ -882,6 +982,9 @@ mod tests {
                 TokenKind::Collateral,
                 0,
                 0,
+                false,
+                0,
+                0,
                 user_approval,
             )
             .unwrap();
@@ -913,6 +1016,9 @@ mod tests {
                 TokenKind::Collateral,
                 0,
                 100,
+                false,
+                0,
+                0,
                 user_approval,
             )
             .unwrap();
@@ -927,6 +1033,9 @@ mod tests {
                 TokenKind::Collateral,
                 0,
                 100,
+                false,
+                0,
+                0,
                 user_approval,
             )
             .unwrap();
@@ -966,6 +1075,7 @@ mod tests {
             liquidator: Pubkey::default(),
             invocation: Invocation::default(),
             positions: [0; 7432],
+            airspace: Pubkey::default(),
         };
         let (token_a, address_a) = create_position_input(&margin_address);
         let (token_b, address_b) = create_position_input(&margin_address);
@@ -981,6 +1091,9 @@ mod tests {
                 TokenKind::AdapterCollateral,
                 0,
                 0,
+                false,
+                0,
+                0,
                 &[],
             )
             .unwrap_err();
@@ -993,6 +1106,9 @@ mod tests {
                 TokenKind::AdapterCollateral,
                 0,
                 0,
+                false,
+                0,
+                0,
                 &[Approver::MarginAccountAuthority],
             )
             .unwrap_err();
@@ -1005,6 +1121,9 @@ mod tests {
                 TokenKind::AdapterCollateral,
                 0,
                 0,
+                false,
+                0,
+                0,
                 &[Approver::Adapter(adapter)],
             )
             .unwrap_err();
@@ -1017,6 +1136,9 @@ mod tests {
                 TokenKind::AdapterCollateral,
                 0,
                 0,
+                false,
+                0,
+                0,
                 &[Approver::MarginAccountAuthority, Approver::Adapter(adapter)],
             )
             .unwrap();
@@ -1036,6 +1158,7 @@ mod tests {
             liquidator: Pubkey::default(),
             invocation: Invocation::default(),
             positions: [0; 7432],
+            airspace: Pubkey::default(),
         };
         let (token, address) = create_position_input(&margin_address);
 
@@ -1048,6 +1171,9 @@ mod tests {
                 TokenKind::AdapterCollateral,
                 0,
                 0,
+                false,
+                0,
+                0,
                 &[Approver::MarginAccountAuthority, Approver::Adapter(adapter)],
             )
             .unwrap();
@@ -1065,6 +1191,7 @@ mod tests {
             liquidator: Pubkey::default(),
             invocation: Invocation::default(),
             positions: [0; 7432],
+            airspace: Pubkey::default(),
         };
         let collateral = register_position(&mut acc, 0, TokenKind::Collateral);
         let claim = register_position(&mut acc, 1, TokenKind::Claim);
@@ -1081,6 +1208,46 @@ mod tests {
         assert_unhealthy(&acc);
     }
 
+    #[test]
+    fn margin_account_version_is_checked_before_loading() {
+        let key = Pubkey::new_unique();
+        let owner = crate::id();
+        let mut lamports = 0;
+        let mut data = vec![0u8; MarginAccount::V1_SPACE];
+        data[8] = 1;
+
+        let v1 = AccountInfo::new(
+            &key,
+            false,
+            true,
+            &mut lamports,
+            &mut data,
+            &owner,
+            false,
+            0,
+        );
+        assert!(!MarginAccount::has_current_version(&v1));
+
+        let mut lamports = 0;
+        let mut data = vec![0u8; 8 + std::mem::size_of::<MarginAccount>()];
+        data[8] = 1;
+
+        let unmigrated = AccountInfo::new(
+            &key,
+            false,
+            true,
+            &mut lamports,
+            &mut data,
+            &owner,
+            false,
+            0,
+        );
+        assert!(!MarginAccount::has_current_version(&unmigrated));
+
+        unmigrated.try_borrow_mut_data().unwrap()[8] = MARGIN_ACCOUNT_VERSION;
+        assert!(MarginAccount::has_current_version(&unmigrated));
+    }
+
     fn register_position(acc: &mut MarginAccount, index: u8, kind: TokenKind) -> Pubkey {
         try_register_position(acc, index, kind).unwrap()
     }
@@ -1100,7 +1267,7 @@ mod tests {
             _ => (),
         }
 
-        acc.register_position(key, 2, key, key, kind, 10000, 0, &approvals)?;
+        acc.register_position(key, 2, key, key, kind, 10000, 0, false, 0, 0, &approvals)?;
 
         Ok(key)
     }
@@ -1115,6 +1282,16 @@ mod tests {
         acc.verify_unhealthy_positions().unwrap_err();
     }
 
+    fn set_price_and_twap(acc: &mut MarginAccount, key: Pubkey, price: i64, twap: i64) {
+        acc.get_position_mut(&key)
+            .unwrap()
+            .set_price_and_twap(
+                &PriceInfo::new_valid(1, price, crate::util::get_timestamp()),
+                twap,
+            )
+            .unwrap()
+    }
+
     fn set_price(acc: &mut MarginAccount, key: Pubkey, price: i64) {
         acc.set_position_price(
             &key,
@@ -1231,6 +1408,140 @@ mod tests {
         account.verify_authority(Pubkey::default()).unwrap_err();
     }
 
+    #[test]
+    fn margin_account_rejects_other_airspaces() {
+        let mut account = blank_account();
+        account.airspace = pda(0);
+        account.verify_airspace(&pda(0)).unwrap();
+        account.verify_airspace(&pda(1)).unwrap_err();
+        account.verify_airspace(&Pubkey::default()).unwrap_err();
+    }
+
+    #[test]
+    fn conservative_valuation_uses_less_favorable_price() {
+        let mut acc = blank_account();
+        let collateral = register_position(&mut acc, 0, TokenKind::Collateral);
+        let claim = register_position(&mut acc, 1, TokenKind::Claim);
+        set_price_and_twap(&mut acc, collateral, 100, 80);
+        set_price_and_twap(&mut acc, claim, 100, 120);
+        acc.set_position_balance(&collateral, &collateral, 100)
+            .unwrap();
+        acc.set_position_balance(&claim, &claim, 90).unwrap();
+
+        // valued at spot by default
+        let valuation = acc.valuation().unwrap();
+        assert_eq!(Number128::from_decimal(100, 0), valuation.equity);
+        assert_eq!(Number128::from_decimal(900, 0), valuation.liabilities);
+
+        // collateral at the lower twap, claims at the higher twap
+        for (key, kind) in [
+            (collateral, TokenKind::Collateral),
+            (claim, TokenKind::Claim),
+        ] {
+            acc.refresh_position_metadata(&key, kind, 10000, 0, true, 0, 0)
+                .unwrap();
+        }
+        let valuation = acc.valuation().unwrap();
+        assert_eq!(
+            Number128::ZERO - Number128::from_decimal(280, 0),
+            valuation.equity
+        );
+        assert_eq!(Number128::from_decimal(1080, 0), valuation.liabilities);
+
+        // spot is used when it is the less favorable price
+        set_price_and_twap(&mut acc, collateral, 80, 100);
+        set_price_and_twap(&mut acc, claim, 120, 100);
+        let valuation = acc.valuation().unwrap();
+        assert_eq!(
+            Number128::ZERO - Number128::from_decimal(280, 0),
+            valuation.equity
+        );
+
+        // prices without an average fall back to spot
+        set_price(&mut acc, collateral, 100);
+        set_price(&mut acc, claim, 100);
+        let valuation = acc.valuation().unwrap();
+        assert_eq!(Number128::from_decimal(100, 0), valuation.equity);
+    }
+
+    #[test]
+    fn claims_in_one_category_use_category_modifiers() {
+        let mut acc = blank_account();
+        let collateral = register_position(&mut acc, 0, TokenKind::Collateral);
+        let claim = register_position(&mut acc, 1, TokenKind::Claim);
+        let other_claim = register_position(&mut acc, 2, TokenKind::Claim);
+        acc.refresh_position_metadata(&collateral, TokenKind::Collateral, 50, 0, false, 1, 90)
+            .unwrap();
+        acc.refresh_position_metadata(&claim, TokenKind::Claim, 200, 0, false, 1, 1000)
+            .unwrap();
+        acc.refresh_position_metadata(&other_claim, TokenKind::Claim, 200, 0, false, 2, 1000)
+            .unwrap();
+        for key in [collateral, claim, other_claim] {
+            set_price(&mut acc, key, 100);
+        }
+        acc.set_position_balance(&collateral, &collateral, 100)
+            .unwrap();
+        acc.set_position_balance(&claim, &claim, 50).unwrap();
+
+        // all claims are in category 1, so the category modifiers apply
+        assert_eq!(1, acc.claims_category());
+        let valuation = acc.valuation().unwrap();
+        assert_eq!(
+            Number128::from_decimal(900, 0),
+            valuation.weighted_collateral
+        );
+        assert_eq!(
+            Number128::from_decimal(50, 0),
+            valuation.required_collateral
+        );
+        assert_healthy(&acc);
+
+        // mixing in a claim from another category falls back to the token modifiers
+        acc.set_position_balance(&other_claim, &other_claim, 1)
+            .unwrap();
+        assert_eq!(0, acc.claims_category());
+        let valuation = acc.valuation().unwrap();
+        assert_eq!(
+            Number128::from_decimal(500, 0),
+            valuation.weighted_collateral
+        );
+        assert_eq!(
+            Number128::from_decimal(255, 0),
+            valuation.required_collateral
+        );
+        assert_unhealthy(&acc);
+    }
+
+    #[test]
+    fn valuation_health_ratio() {
+        let valuation = |effective: i64, required: u64| Valuation {
+            equity: Number128::ZERO,
+            liabilities: Number128::ZERO,
+            required_collateral: Number128::from_decimal(required, 0),
+            weighted_collateral: Number128::ZERO,
+            effective_collateral: Number128::from_i128(
+                Number128::from_decimal(effective.unsigned_abs(), 0).to_i128()
+                    * effective.signum() as i128,
+            ),
+            stale_collateral_list: vec![],
+            past_due: false,
+        };
+
+        assert_eq!(15_000, valuation(150, 100).health_ratio_bps());
+        assert_eq!(10_000, valuation(100, 100).health_ratio_bps());
+        assert_eq!(5_000, valuation(50, 100).health_ratio_bps());
+        assert_eq!(0, valuation(-50, 100).health_ratio_bps());
+        assert_eq!(u64::MAX, valuation(50, 0).health_ratio_bps());
+
+        // values below a dollar still count towards the ratio
+        let fractional = Valuation {
+            required_collateral: Number128::from_decimal(4, -1),
+            effective_collateral: Number128::from_decimal(5, -1),
+            ..valuation(0, 0)
+        };
+        assert_eq!(12_500, fractional.health_ratio_bps());
+    }
+
     fn pda(index: u8) -> Pubkey {
         Pubkey::find_program_address(&[&[index]], &crate::id()).0
     }
@@ -1246,6 +1557,7 @@ mod tests {
             liquidator: Pubkey::default(),
             invocation: Invocation::default(),
             positions: [0; 7432],
+            airspace: Pubkey::default(),
         }
     }
 }
@@ -159,8 +159,24 @@ pub struct AccountPosition {
     /// Flags that are set by the adapter
     pub flags: AdapterPositionFlags,
 
+    /// Non-zero if the position is valued using the less favorable of its spot
+    /// and average prices
+    pub conservative_valuation: u8,
+
     /// Unused
-    pub _reserved: [u8; 23],
+    pub _reserved0: [u8; 6],
+
+    /// The average price reported with the current price, using the same exponent
+    pub twap: i64,
+
+    /// The category of correlated tokens this position belongs to, or zero if none
+    pub category: u16,
+
+    /// The value modifier used when all claims in the account are in the same category
+    pub category_value_modifier: u16,
+
+    /// Unused
+    pub _reserved: [u8; 4],
 }
 
 bitflags::bitflags! {
@@ -197,26 +213,52 @@ impl AccountPosition {
 
     pub fn calculate_value(&mut self) {
         self.value = (Number128::from_decimal(self.balance, self.exponent)
-            * Number128::from_decimal(self.price.value, self.price.exponent))
+            * Number128::from_decimal(self.valuation_price(), self.price.exponent))
         .into_bits();
     }
 
+    /// The price used to value the position.
+    ///
+    /// When conservative valuation is enabled, collateral uses the lower of the spot
+    /// and average prices, while claims use the higher.
+    pub fn valuation_price(&self) -> i64 {
+        // an average price of zero is never accepted, so it was not provided
+        if self.conservative_valuation == 0 || self.twap == 0 {
+            return self.price.value;
+        }
+
+        match self.kind() {
+            TokenKind::Claim => self.price.value.max(self.twap),
+            TokenKind::Collateral | TokenKind::AdapterCollateral => self.price.value.min(self.twap),
+        }
+    }
+
     pub fn value(&self) -> Number128 {
         Number128::from_bits(self.value)
     }
 
-    pub fn collateral_value(&self) -> Number128 {
+    /// The value modifier to apply to this position, given the category shared by all the
+    /// claims in the account (zero if they are not all in one category).
+    pub fn effective_value_modifier(&self, claims_category: u16) -> u16 {
+        match claims_category {
+            0 => self.value_modifier,
+            c if c == self.category => self.value_modifier.max(self.category_value_modifier),
+            _ => self.value_modifier,
+        }
+    }
+
+    pub fn collateral_value(&self, claims_category: u16) -> Number128 {
         assert!(
             self.kind() == TokenKind::Collateral || self.kind() == TokenKind::AdapterCollateral
         );
 
-        Number128::from_decimal(self.value_modifier, -2) * self.value()
+        Number128::from_decimal(self.effective_value_modifier(claims_category), -2) * self.value()
     }
 
-    pub fn required_collateral_value(&self) -> Number128 {
+    pub fn required_collateral_value(&self, claims_category: u16) -> Number128 {
         assert_eq!(self.kind(), TokenKind::Claim);
 
-        let modifier = Number128::from_decimal(self.value_modifier, -2);
+        let modifier = Number128::from_decimal(self.effective_value_modifier(claims_category), -2);
 
         if modifier == Number128::ZERO {
             msg!("no leverage configured for claim {}", &self.token);
@@ -235,7 +277,14 @@ impl AccountPosition {
 
     /// Update the price for this position
     pub fn set_price(&mut self, price: &PriceInfo) -> Result<(), ErrorCode> {
+        self.set_price_and_twap(price, price.value)
+    }
+
+    /// Update the price for this position, along with the average price reported
+    /// with it
+    pub fn set_price_and_twap(&mut self, price: &PriceInfo, twap: i64) -> Result<(), ErrorCode> {
         self.price = *price;
+        self.twap = twap;
         self.calculate_value();
 
         Ok(())
@@ -17,8 +17,9 @@
 
 use anchor_lang::prelude::*;
 use bytemuck::Contiguous;
+use jet_airspace::{state::AirspacePermit, DEFAULT_AIRSPACE};
 
-use crate::ErrorCode;
+use crate::{seeds::TOKEN_CONFIG_SEED, ErrorCode, MarginAccount};
 
 /// Description of the token's usage
 #[derive(AnchorSerialize, AnchorDeserialize, Contiguous, Eq, PartialEq, Clone, Copy, Debug)]
@@ -42,6 +43,7 @@ impl Default for TokenKind {
     }
 }
 
+#[cfg(feature = "metadata-compat")]
 impl From<jet_metadata::TokenKind> for TokenKind {
     fn from(kind: jet_metadata::TokenKind) -> Self {
         match kind {
@@ -82,6 +84,16 @@ pub struct TokenConfig {
 
     /// The maximum staleness (seconds) that's acceptable for balances of this token
     pub max_staleness: u64,
+
+    /// Value positions using the less favorable of the spot and average prices
+    pub conservative_valuation: bool,
+
+    /// The [TokenCategory] this token belongs to, or zero if it has none
+    pub category: u16,
+
+    /// The minimum [AirspacePermit] tier the owner of a margin account needs to register
+    /// a position for this token
+    pub min_permit_tier: u8,
 }
 
 impl TokenConfig {
@@ -111,6 +123,205 @@ impl TokenConfig {
     }
 }
 
+/// The parameters for a position in a margin account, as read from the configuration
+/// for the position's token.
+///
+/// These normally come from the airspace's [TokenConfig]. While the `metadata-compat`
+/// feature is enabled, they may also be read from a legacy
+/// [PositionTokenMetadata](jet_metadata::PositionTokenMetadata) account for tokens that
+/// have not yet been migrated.
+#[derive(Debug, Eq, PartialEq, Clone, Copy)]
+pub struct PositionParams {
+    /// The mint for the position token
+    pub mint: Pubkey,
+
+    /// The airspace the configuration is valid within, or `None` for legacy metadata
+    /// which is not scoped to an airspace.
+    pub airspace: Option<Pubkey>,
+
+    /// The adapter program in control of the position, if any
+    pub adapter_program: Pubkey,
+
+    /// Description of the token
+    pub token_kind: TokenKind,
+
+    /// A modifier to adjust the token value, based on the kind of token
+    pub value_modifier: u16,
+
+    /// The maximum staleness (seconds) that's acceptable for balances of this token
+    pub max_staleness: u64,
+
+    /// Value positions using the less favorable of the spot and average prices
+    pub conservative_valuation: bool,
+
+    /// The category of correlated tokens this token belongs to, or zero if it has none
+    pub category: u16,
+
+    /// The value modifier to use instead of `value_modifier`, when all of the claims in
+    /// an account are in the same category as this token. Zero if unknown.
+    pub category_value_modifier: u16,
+
+    /// The minimum permit tier required to register a position for the token
+    pub min_permit_tier: u8,
+}
+
+impl PositionParams {
+    /// Read the position parameters from either a [TokenConfig], or legacy metadata
+    /// when compatibility is enabled.
+    pub fn load(info: &AccountInfo) -> Result<Self> {
+        if info.owner == &TokenConfig::owner() {
+            let config = Account::<TokenConfig>::try_from(info)?;
+            return Ok(Self::from(&*config));
+        }
+
+        #[cfg(feature = "metadata-compat")]
+        if info.owner == &jet_metadata::PositionTokenMetadata::owner() {
+            let metadata = Account::<jet_metadata::PositionTokenMetadata>::try_from(info)?;
+            return Ok(Self::from(&*metadata));
+        }
+
+        msg!("account {} is not a valid token config", info.key);
+        err!(ErrorCode::InvalidConfig)
+    }
+
+    /// Read the modifier for the token's category from its [TokenCategory] account, which
+    /// must be among the given accounts if the token belongs to a category.
+    pub fn load_category(&mut self, accounts: &[AccountInfo]) -> Result<()> {
+        if self.category == 0 {
+            return Ok(());
+        }
+
+        let category = accounts
+            .iter()
+            .filter(|info| info.owner == &TokenCategory::owner())
+            .filter_map(|info| Account::<TokenCategory>::try_from(info).ok())
+            .find(|c| c.category == self.category && Some(c.airspace) == self.airspace);
+
+        let category = match category {
+            Some(category) => category,
+            None => {
+                msg!(
+                    "the config for token category {} is required",
+                    self.category
+                );
+                return err!(ErrorCode::InvalidConfig);
+            }
+        };
+
+        self.category_value_modifier = match self.token_kind {
+            TokenKind::Claim => category.max_leverage,
+            TokenKind::Collateral | TokenKind::AdapterCollateral => category.collateral_weight,
+        };
+
+        Ok(())
+    }
+
+    /// Verify that the owner of the margin account has a permit with the tier required for
+    /// this token. Tokens that require more than the lowest tier need the owner's permit
+    /// to be among the given accounts.
+    pub fn verify_permit_tier(
+        &self,
+        margin_account: &MarginAccount,
+        accounts: &[AccountInfo],
+    ) -> Result<()> {
+        if self.min_permit_tier == AirspacePermit::TIER_RETAIL {
+            return Ok(());
+        }
+
+        let permit = accounts
+            .iter()
+            .filter(|info| info.owner == &AirspacePermit::owner())
+            .filter_map(|info| Account::<AirspacePermit>::try_from(info).ok())
+            .find(|permit| {
+                permit.owner == margin_account.owner && permit.airspace == margin_account.airspace
+            });
+
+        match permit {
+            Some(permit) => permit.verify(self.min_permit_tier),
+            None => {
+                msg!(
+                    "a permit with tier {} is required for this token",
+                    self.min_permit_tier
+                );
+                err!(ErrorCode::WrongPermit)
+            }
+        }
+    }
+
+    /// Verify that these parameters may be used for a position in the margin account.
+    ///
+    /// Legacy metadata is only accepted for accounts in the default airspace, and only
+    /// while the airspace has no [TokenConfig] for the token, which must be shown by
+    /// including the empty config address among the given accounts.
+    pub fn verify_airspace(
+        &self,
+        margin_account: &MarginAccount,
+        accounts: &[AccountInfo],
+    ) -> Result<()> {
+        if let Some(airspace) = self.airspace {
+            return margin_account.verify_airspace(&airspace);
+        }
+
+        margin_account.verify_airspace(&DEFAULT_AIRSPACE)?;
+
+        let (config_address, _) = Pubkey::find_program_address(
+            &[
+                TOKEN_CONFIG_SEED,
+                DEFAULT_AIRSPACE.as_ref(),
+                self.mint.as_ref(),
+            ],
+            &crate::ID,
+        );
+
+        match accounts.iter().find(|info| info.key == &config_address) {
+            Some(info) if info.data_is_empty() => Ok(()),
+            Some(_) => {
+                msg!("the token config must be used instead of the legacy metadata");
+                err!(ErrorCode::InvalidConfig)
+            }
+            None => {
+                msg!("the token config address {} is required", config_address);
+                err!(ErrorCode::InvalidConfig)
+            }
+        }
+    }
+}
+
+impl From<&TokenConfig> for PositionParams {
+    fn from(config: &TokenConfig) -> Self {
+        Self {
+            mint: config.mint,
+            airspace: Some(config.airspace),
+            adapter_program: config.adapter_program().unwrap_or_default(),
+            token_kind: config.token_kind,
+            value_modifier: config.value_modifier,
+            max_staleness: config.max_staleness,
+            conservative_valuation: config.conservative_valuation,
+            category: config.category,
+            category_value_modifier: 0,
+            min_permit_tier: config.min_permit_tier,
+        }
+    }
+}
+
+#[cfg(feature = "metadata-compat")]
+impl From<&jet_metadata::PositionTokenMetadata> for PositionParams {
+    fn from(metadata: &jet_metadata::PositionTokenMetadata) -> Self {
+        Self {
+            mint: metadata.position_token_mint,
+            airspace: None,
+            adapter_program: metadata.adapter_program,
+            token_kind: metadata.token_kind.into(),
+            value_modifier: metadata.value_modifier,
+            max_staleness: metadata.max_staleness,
+            conservative_valuation: false,
+            category: 0,
+            category_value_modifier: 0,
+            min_permit_tier: AirspacePermit::TIER_RETAIL,
+        }
+    }
+}
+
 /// Information about where to find the oracle data for a token
 #[derive(AnchorSerialize, AnchorDeserialize, Debug, Eq, PartialEq, Clone, Copy)]
 pub enum TokenOracle {
@@ -138,6 +349,32 @@ pub enum TokenAdmin {
     Adapter(Pubkey),
 }
 
+/// Configuration for a category of correlated tokens within an airspace, such as
+/// stablecoins, or a token and its staked derivatives.
+///
+/// When every claim in a margin account belongs to the same category, positions in
+/// that category are valued with the modifiers configured here instead of the ones
+/// in their [TokenConfig], if these are higher.
+#[account]
+#[derive(Default, Debug, Eq, PartialEq)]
+pub struct TokenCategory {
+    /// The airspace the category is defined within
+    pub airspace: Pubkey,
+
+    /// The identifier for the category, referenced by [TokenConfig::category]
+    pub category: u16,
+
+    /// The collateral weight for collateral tokens in the category
+    pub collateral_weight: u16,
+
+    /// The maximum leverage for claims in the category
+    pub max_leverage: u16,
+}
+
+impl TokenCategory {
+    pub const SPACE: usize = 8 + std::mem::size_of::<Self>();
+}
+
 /// Configuration for allowed liquidators
 #[account]
 #[derive(Default, Debug, Eq, PartialEq)]
@@ -151,6 +388,42 @@ pub struct LiquidatorConfig {
 
 impl LiquidatorConfig {
     pub const SPACE: usize = 8 + std::mem::size_of::<Self>();
+
+    /// Verify that a liquidator may act on a margin account, using either the
+    /// [LiquidatorConfig] for the account's airspace, or the legacy
+    /// [LiquidatorMetadata](jet_metadata::LiquidatorMetadata) for accounts in the
+    /// default airspace while the `metadata-compat` feature is enabled.
+    pub fn verify(
+        info: &AccountInfo,
+        liquidator: &Pubkey,
+        margin_account: &MarginAccount,
+    ) -> Result<()> {
+        if info.owner == &LiquidatorConfig::owner() {
+            let config = Account::<LiquidatorConfig>::try_from(info)?;
+
+            if config.liquidator != *liquidator {
+                msg!("the liquidator config is for a different liquidator");
+                return err!(ErrorCode::InvalidConfig);
+            }
+
+            return margin_account.verify_airspace(&config.airspace);
+        }
+
+        #[cfg(feature = "metadata-compat")]
+        if info.owner == &jet_metadata::LiquidatorMetadata::owner() {
+            let metadata = Account::<jet_metadata::LiquidatorMetadata>::try_from(info)?;
+
+            if metadata.liquidator != *liquidator {
+                msg!("the liquidator metadata is for a different liquidator");
+                return err!(ErrorCode::InvalidConfig);
+            }
+
+            return margin_account.verify_airspace(&DEFAULT_AIRSPACE);
+        }
+
+        msg!("account {} is not a valid liquidator config", info.key);
+        err!(ErrorCode::InvalidConfig)
+    }
 }
 
 /// Configuration for allowed adapters
@@ -166,4 +439,40 @@ pub struct AdapterConfig {
 
 impl AdapterConfig {
     pub const SPACE: usize = 8 + std::mem::size_of::<Self>();
+
+    /// Verify that an adapter may be invoked for a margin account, using either the
+    /// [AdapterConfig] for the account's airspace, or the legacy
+    /// [MarginAdapterMetadata](jet_metadata::MarginAdapterMetadata) while the
+    /// `metadata-compat` feature is enabled.
+    pub fn verify(
+        info: &AccountInfo,
+        adapter_program: &Pubkey,
+        margin_account: &MarginAccount,
+    ) -> Result<()> {
+        if info.owner == &AdapterConfig::owner() {
+            let config = Account::<AdapterConfig>::try_from(info)?;
+
+            if config.adapter_program != *adapter_program {
+                msg!("the adapter config is for a different program");
+                return err!(ErrorCode::InvalidConfig);
+            }
+
+            return margin_account.verify_airspace(&config.airspace);
+        }
+
+        #[cfg(feature = "metadata-compat")]
+        if info.owner == &jet_metadata::MarginAdapterMetadata::owner() {
+            let metadata = Account::<jet_metadata::MarginAdapterMetadata>::try_from(info)?;
+
+            if metadata.adapter_program != *adapter_program {
+                msg!("the adapter metadata is for a different program");
+                return err!(ErrorCode::InvalidConfig);
+            }
+
+            return margin_account.verify_airspace(&DEFAULT_AIRSPACE);
+        }
+
+        msg!("account {} is not a valid adapter config", info.key);
+        err!(ErrorCode::InvalidConfig)
+    }
 }
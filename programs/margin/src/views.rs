@@ -0,0 +1,48 @@
+// SPDX-License-Identifier: AGPL-3.0-or-later
+//
+// Copyright (C) 2022 JET PROTOCOL HOLDINGS, LLC.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+//! Helpers for other programs to read the results of view instructions, which
+//! return their results through the return data instead of modifying accounts.
+
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::program::get_return_data;
+
+use crate::ErrorCode;
+
+#[cfg(feature = "cpi")]
+use crate::AccountHealth;
+
+/// Read the result of a view instruction from the return data, verifying that
+/// it was set by the expected program.
+pub fn read_return_data<T: AnchorDeserialize>(program_id: &Pubkey) -> Result<T> {
+    let (returning_program, data) = get_return_data().ok_or(ErrorCode::NoReturnData)?;
+
+    if returning_program != *program_id {
+        return err!(ErrorCode::WrongProgramReturnData);
+    }
+
+    Ok(T::deserialize(&mut &data[..])?)
+}
+
+/// Get the health of a margin account
+#[cfg(feature = "cpi")]
+pub fn account_health<'info>(
+    ctx: CpiContext<'_, '_, '_, 'info, crate::cpi::accounts::AccountHealthView<'info>>,
+) -> Result<AccountHealth> {
+    crate::cpi::account_health(ctx)?;
+    read_return_data(&crate::ID)
+}
@@ -38,6 +38,8 @@ pub fn token_update_pyth_price_handler(
     ctx: Context<TokenUpdatePythPrice>,
     price: i64,
     conf: i64,
+    ema_price: i64,
+    ema_conf: i64,
     expo: i32,
 ) -> Result<()> {
     let mut pyth_price = load_pyth_account::<PriceAccount>(&ctx.accounts.pyth_price)?;
@@ -50,14 +52,14 @@ pub fn token_update_pyth_price_handler(
     pyth_price.agg.pub_slot = clock.slot;
 
     pyth_price.ema_price = Rational {
-        val: price,
-        numer: price,
+        val: ema_price,
+        numer: ema_price,
         denom: 1,
     };
 
     pyth_price.ema_conf = Rational {
-        val: conf,
-        numer: conf,
+        val: ema_conf,
+        numer: ema_conf,
         denom: 1,
     };
 
@@ -81,9 +81,11 @@ pub mod jet_test_service {
         ctx: Context<TokenUpdatePythPrice>,
         price: i64,
         conf: i64,
+        ema_price: i64,
+        ema_conf: i64,
         expo: i32,
     ) -> Result<()> {
-        token_update_pyth_price_handler(ctx, price, conf, expo)
+        token_update_pyth_price_handler(ctx, price, conf, ema_price, ema_conf, expo)
     }
 }
 
@@ -245,6 +245,7 @@ fn main() -> Result<()> {
         post_only: false,
         post_allowed: true,
         auto_stake: true,
+        good_til_time: None,
     };
 
     let mut rng = thread_rng();
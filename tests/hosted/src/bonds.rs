@@ -14,15 +14,19 @@ use anyhow::Result;
 use async_trait::async_trait;
 use jet_bonds::{
     control::state::BondManager,
-    margin::state::MarginUser,
-    orderbook::state::{event_queue_len, orderbook_slab_len, CallbackInfo, OrderParams},
+    margin::{instructions::MarginReplaceOrdersParams, state::MarginUser},
+    orderbook::{
+        instructions::ReplaceOrdersParams,
+        state::{event_queue_len, orderbook_slab_len, CallbackInfo, OrderParams},
+    },
     tickets::state::{ClaimTicket, SplitTicket},
 };
 
 use jet_margin_sdk::{
     bonds::{event_builder::build_consume_events_info, BondsIxBuilder},
     ix_builder::{
-        get_control_authority_address, get_metadata_address, ControlIxBuilder, MarginIxBuilder,
+        derive_adapter_config, derive_airspace, get_control_authority_address,
+        get_metadata_address, AirspaceIxBuilder, MarginConfigIxBuilder, MarginIxBuilder,
     },
     margin_integrator::{NoProxy, Proxy},
     solana::{
@@ -93,6 +97,7 @@ pub struct TestManager {
     pub ix_builder: BondsIxBuilder,
     pub kps: Keys<Keypair>,
     pub keys: Keys<Pubkey>,
+    pub airspace_seed: String,
 }
 
 impl Clone for TestManager {
@@ -108,6 +113,7 @@ impl Clone for TestManager {
                     .collect(),
             ),
             keys: self.keys.clone(),
+            airspace_seed: self.airspace_seed.clone(),
         }
     }
 }
@@ -149,8 +155,13 @@ impl TestManager {
         let transaction = initialize_test_mint_transaction(mint, payer, 6, rent, recent_blockhash);
         client.send_and_confirm_transaction(&transaction).await?;
 
+        // each market gets its own airspace, with the payer as the authority
+        let airspace_seed = mint.pubkey().to_string()[..24].to_owned();
+        let init_airspace =
+            AirspaceIxBuilder::new(&airspace_seed, payer.pubkey(), payer.pubkey()).create(false);
+
         let ix_builder = BondsIxBuilder::new_from_seed(
-            &Pubkey::default(),
+            &derive_airspace(&airspace_seed),
             &mint.pubkey(),
             BOND_MANAGER_SEED,
             payer.pubkey(),
@@ -162,8 +173,10 @@ impl TestManager {
             ix_builder,
             kps: Keys::new(),
             keys: Keys::new(),
+            airspace_seed,
         };
         this.insert_kp("token_mint", clone(mint));
+        this.sign_send_transaction(&[init_airspace], None).await?;
 
         let init_eq = {
             let rent = this
@@ -301,6 +314,17 @@ impl TestManager {
 
         self.sign_send_transaction(&[consume], None).await
     }
+    pub async fn prune_expired_orders(
+        &self,
+        order_ids: &[u128],
+        out_accounts: &[Pubkey],
+    ) -> Result<Signature> {
+        let prune = self
+            .ix_builder
+            .prune_expired_orders(order_ids, out_accounts)?;
+
+        self.sign_send_transaction(&[prune], None).await
+    }
     pub async fn pause_ticket_redemption(&self) -> Result<Signature> {
         let pause = self.ix_builder.pause_ticket_redemption()?;
 
@@ -331,6 +355,42 @@ impl TestManager {
         Ok(())
     }
 
+    pub async fn set_duration(&self, duration: i64) -> Result<Signature> {
+        let set_duration = self.ix_builder.set_duration(duration)?;
+
+        self.sign_send_transaction(&[set_duration], None).await
+    }
+
+    pub async fn set_min_permit_tier(&self, tier: u8) -> Result<Signature> {
+        let set_tier = self.ix_builder.set_min_permit_tier(tier)?;
+
+        self.sign_send_transaction(&[set_tier], None).await
+    }
+
+    /// issue a permit for the market's airspace, or change the existing permit
+    pub async fn issue_permit(&self, owner: Pubkey, expires_at: i64, tier: u8) -> Result<()> {
+        let payer = self.client.payer().pubkey();
+        let airspace = AirspaceIxBuilder::new(&self.airspace_seed, payer, payer);
+
+        let ix = match self
+            .client
+            .get_account(&airspace.derive_permit(&owner))
+            .await?
+        {
+            None => airspace.permit_create(owner, expires_at, tier),
+            // permits issued by the airspace authority are recorded with the airspace as issuer
+            Some(_) => airspace.permit_update(
+                owner,
+                derive_airspace(&self.airspace_seed),
+                expires_at,
+                tier,
+            ),
+        };
+        self.sign_send_transaction(&[ix], None).await?;
+
+        Ok(())
+    }
+
     pub fn insert_kp(&mut self, k: &str, kp: Keypair) {
         self.keys.insert(k, kp.pubkey());
         self.kps.insert(k, kp);
@@ -364,7 +424,7 @@ impl TestManager {
     pub async fn register_adapter_if_unregistered(&self, adapter: &Pubkey) -> Result<()> {
         if self
             .client
-            .get_account(&get_metadata_address(adapter))
+            .get_account(&derive_adapter_config(&self.airspace(), adapter))
             .await?
             .is_none()
         {
@@ -425,12 +485,19 @@ impl TestManager {
     }
 
     pub async fn register_adapter(&self, adapter: &Pubkey) -> Result<()> {
-        let ix = ControlIxBuilder::new(self.client.payer().pubkey()).register_adapter(adapter);
+        let payer = self.client.payer().pubkey();
+        let ix =
+            MarginConfigIxBuilder::new(self.airspace(), payer).configure_adapter(*adapter, true);
 
         send_and_confirm(&self.client, &[ix], &[]).await?;
         Ok(())
     }
 
+    /// The airspace the market belongs to
+    pub fn airspace(&self) -> Pubkey {
+        derive_airspace(&self.airspace_seed)
+    }
+
     pub async fn simulate_new_order(
         &self,
         params: OrderParams,
@@ -553,7 +620,13 @@ impl GenerateProxy for NoProxy {
 #[async_trait]
 impl GenerateProxy for MarginIxBuilder {
     async fn generate(manager: Arc<TestManager>, owner: &Keypair) -> Result<Self> {
-        let margin = MarginIxBuilder::new(owner.pubkey(), 0);
+        let margin = MarginIxBuilder::new_with_payer_and_airspace(
+            owner.pubkey(),
+            0,
+            owner.pubkey(),
+            manager.airspace(),
+            None,
+        );
         manager
             .sign_send_transaction(&[margin.create_account()], Some(&[owner]))
             .await?;
@@ -641,7 +714,7 @@ impl<P: Proxy> BondsUser<P> {
         let ix = self
             .manager
             .ix_builder
-            .initialize_margin_user(self.proxy.pubkey())?;
+            .initialize_margin_user(self.proxy.pubkey(), self.owner.pubkey())?;
         self.client
             .send_and_confirm_1tx(&[self.proxy.invoke_signed(ix)], &[&self.owner])
             .await
@@ -724,6 +797,42 @@ impl<P: Proxy> BondsUser<P> {
             .send_and_confirm_1tx(&[self.proxy.invoke_signed(cancel)], &[&self.owner])
             .await
     }
+
+    pub async fn cancel_all_orders(
+        &self,
+        out_accounts: &[Pubkey],
+    ) -> Result<Vec<TransactionBuilder>> {
+        let cancel = self
+            .manager
+            .ix_builder
+            .cancel_all_orders(self.proxy.pubkey(), out_accounts)?;
+        self.proxy
+            .refresh_and_invoke_signed(cancel, clone(&self.owner))
+            .await
+    }
+
+    pub async fn replace_orders(&self, params: ReplaceOrdersParams) -> Result<Signature> {
+        let replace =
+            self.manager
+                .ix_builder
+                .replace_orders(self.proxy.pubkey(), None, None, params)?;
+        self.client
+            .send_and_confirm_1tx(&[self.proxy.invoke_signed(replace)], &[&self.owner])
+            .await
+    }
+
+    pub async fn margin_replace_orders(
+        &self,
+        params: MarginReplaceOrdersParams,
+    ) -> Result<Vec<TransactionBuilder>> {
+        let replace = self
+            .manager
+            .ix_builder
+            .margin_replace_orders(self.proxy.pubkey(), params)?;
+        self.proxy
+            .refresh_and_invoke_signed(replace, clone(&self.owner))
+            .await
+    }
 }
 
 impl<P: Proxy> BondsUser<P> {
@@ -773,6 +882,21 @@ impl<P: Proxy> BondsUser<P> {
             .map(|a| a.amount)
     }
 
+    /// loads the balance of the claims tracking the user's debt
+    pub async fn claims(&self) -> Result<u64> {
+        let key = BondsIxBuilder::margin_user_claims(
+            &self
+                .manager
+                .ix_builder
+                .margin_user_account(self.proxy.pubkey()),
+        );
+
+        self.manager
+            .load_anchor::<TokenAccount>(&key)
+            .await
+            .map(|a| a.amount)
+    }
+
     pub async fn load_margin_user(&self) -> Result<MarginUser> {
         let key = self
             .manager
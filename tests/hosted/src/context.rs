@@ -30,6 +30,25 @@ pub async fn test_context() -> &'static MarginTestContext {
         .await
 }
 
+/// Send a view instruction and decode the value it returned
+#[cfg(not(feature = "localnet"))]
+pub async fn view<T: anchor_lang::AnchorDeserialize>(
+    ctx: &MarginTestContext,
+    ix: solana_sdk::instruction::Instruction,
+) -> Result<T, Error> {
+    use anyhow::Context;
+
+    let program = ix.program_id;
+    jet_simulation::send_and_confirm(&ctx.rpc, &[ix], &[]).await?;
+
+    // the simulation runs programs in this process, so their return data can be read directly
+    let (returned_by, data) =
+        anchor_lang::solana_program::program::get_return_data().context("no return data")?;
+    assert_eq!(program, returned_by);
+
+    Ok(T::deserialize(&mut &data[..])?)
+}
+
 pub struct MarginPoolSetupInfo {
     pub token: Pubkey,
     pub fee_vault: Pubkey,
@@ -174,6 +193,7 @@ impl MarginTestContext {
             utilization_rate_1: 10,
             utilization_rate_2: 20,
             management_fee_rate: 10,
+            max_price_deviation: 0,
             flags: PoolFlags::ALLOW_LENDING.bits(),
         };
 
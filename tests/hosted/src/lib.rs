@@ -3,6 +3,7 @@ pub mod context;
 pub mod load;
 pub mod margin;
 pub mod pricing;
+pub mod serum;
 pub mod setup_helper;
 pub mod swap;
 pub mod test_user;
@@ -26,15 +26,16 @@ use anchor_lang::{
 use anchor_spl::associated_token::get_associated_token_address;
 use anyhow::{bail, Error};
 
+use jet_airspace::state::AirspacePermit;
 use jet_margin::{AccountPosition, MarginAccount, TokenKind};
 use jet_margin_sdk::ix_builder::{
-    derive_airspace, derive_permit, get_control_authority_address, get_metadata_address,
+    derive_adapter_config, derive_airspace, derive_permit, get_control_authority_address,
     AirspaceIxBuilder, ControlIxBuilder, MarginConfigIxBuilder, MarginPoolConfiguration,
-    MarginPoolIxBuilder,
+    MarginPoolIxBuilder, MarginSerumIxBuilder,
 };
 use jet_margin_sdk::solana::keypair::clone;
 use jet_margin_sdk::solana::transaction::{SendTransactionBuilder, TransactionBuilder};
-use jet_margin_sdk::spl_swap::SplSwapPool;
+use jet_margin_sdk::spl_swap::{SplSwapPool, SwapRoute};
 use jet_margin_sdk::tokens::TokenOracle;
 use solana_sdk::instruction::Instruction;
 use solana_sdk::signature::{Keypair, Signature, Signer};
@@ -46,6 +47,7 @@ use jet_margin_pool::{Amount, MarginPool, MarginPoolConfig, TokenChange};
 use jet_margin_sdk::tx_builder::{
     global_initialize_instructions, AirspaceAdmin, MarginTxBuilder, TokenDepositsConfig,
 };
+use jet_margin_swap::SwapEndpoint;
 use jet_metadata::{LiquidatorMetadata, MarginAdapterMetadata, TokenMetadata};
 use jet_simulation::{send_and_confirm, solana_rpc_api::SolanaRpcClient};
 
@@ -130,7 +132,7 @@ impl MarginClient {
     }
 
     pub async fn get_pool(&self, token: &Pubkey) -> Result<MarginPool, Error> {
-        let pool_builder = MarginPoolIxBuilder::new(*token);
+        let pool_builder = MarginPoolIxBuilder::new(self.airspace(), *token);
         let account = self.rpc.get_account(&pool_builder.address).await?;
 
         if account.is_none() {
@@ -188,7 +190,7 @@ impl MarginClient {
     pub async fn register_adapter_if_unregistered(&self, adapter: &Pubkey) -> Result<(), Error> {
         if self
             .rpc
-            .get_account(&get_metadata_address(adapter))
+            .get_account(&derive_adapter_config(&self.airspace(), adapter))
             .await?
             .is_none()
         {
@@ -205,6 +207,29 @@ impl MarginClient {
         Ok(())
     }
 
+    /// The address of the airspace used by this client
+    pub fn airspace(&self) -> Pubkey {
+        self.tx_admin.airspace
+    }
+
+    /// Register a serum market with the margin serum adapter, so its open orders can
+    /// be used as collateral
+    pub async fn register_serum_market(
+        &self,
+        market: &MarginSerumIxBuilder,
+        base_collateral_weight: u16,
+        quote_collateral_weight: u16,
+    ) -> Result<(), Error> {
+        self.rpc
+            .send_and_confirm(self.tx_admin.register_serum_market(
+                market,
+                base_collateral_weight,
+                quote_collateral_weight,
+            ))
+            .await?;
+        Ok(())
+    }
+
     pub async fn configure_token_deposits(
         &self,
         token_mint: &Pubkey,
@@ -224,18 +249,17 @@ impl MarginClient {
         token: &Pubkey,
         config: &MarginPoolConfiguration,
     ) -> Result<(), Error> {
-        let ix =
-            ControlIxBuilder::new(self.rpc.payer().pubkey()).configure_margin_pool(token, config);
-
-        send_and_confirm(&self.rpc, &[ix], &[]).await?;
+        self.rpc
+            .send_and_confirm(self.tx_admin.configure_margin_pool(*token, config))
+            .await?;
 
         Ok(())
     }
 
     /// Create a new margin pool for a token
     pub async fn create_pool(&self, setup_info: &MarginPoolSetupInfo) -> Result<(), Error> {
-        let ix =
-            ControlIxBuilder::new(self.rpc.payer().pubkey()).create_margin_pool(&setup_info.token);
+        let ix = ControlIxBuilder::new(self.rpc.payer().pubkey())
+            .create_margin_pool(&self.airspace(), &setup_info.token);
 
         send_and_confirm(&self.rpc, &[ix], &[]).await?;
 
@@ -352,9 +376,14 @@ impl MarginUser {
         let permit_account = derive_permit(self.tx.airspace(), &self.signer());
 
         if self.rpc.get_account(&permit_account).await?.is_none() {
-            let airspace = AirspaceIxBuilder::new("default", self.signer(), self.signer());
+            let payer = self.rpc.payer().pubkey();
+            let airspace = AirspaceIxBuilder::new("default", payer, payer);
             self.rpc
-                .send_and_confirm(vec![airspace.permit_create(self.signer())].into());
+                .send_and_confirm(
+                    vec![airspace.permit_create(self.signer(), 0, AirspacePermit::TIER_RETAIL)]
+                        .into(),
+                )
+                .await?;
         }
         self.send_confirm_tx(self.tx.create_account().await?).await
     }
@@ -469,6 +498,141 @@ impl MarginUser {
         .await
     }
 
+    /// Borrow the source token and swap it into a deposit of the destination token
+    #[allow(clippy::too_many_arguments)]
+    pub async fn open_leveraged_position(
+        &self,
+        program_id: &Pubkey,
+        source_mint: &Pubkey,
+        destination_mint: &Pubkey,
+        transit_source_account: &Pubkey,
+        transit_destination_account: &Pubkey,
+        swap_pool: &SplSwapPool,
+        borrow_amount: u64,
+        minimum_amount_out: u64,
+    ) -> Result<(), Error> {
+        let (source_token, destination_token) = if source_mint == &swap_pool.mint_a {
+            (&swap_pool.token_a, &swap_pool.token_b)
+        } else {
+            (&swap_pool.token_b, &swap_pool.token_a)
+        };
+        self.send_confirm_tx(
+            self.tx
+                .open_leveraged_position(
+                    source_mint,
+                    destination_mint,
+                    transit_source_account,
+                    transit_destination_account,
+                    &swap_pool.pool,
+                    &swap_pool.pool_mint,
+                    &swap_pool.fee_account,
+                    source_token,
+                    destination_token,
+                    program_id,
+                    borrow_amount,
+                    minimum_amount_out,
+                )
+                .await?,
+        )
+        .await
+    }
+
+    /// Swap a deposit of the source token into the destination token, repaying
+    /// the loan of the destination token
+    #[allow(clippy::too_many_arguments)]
+    pub async fn close_leveraged_position(
+        &self,
+        program_id: &Pubkey,
+        source_mint: &Pubkey,
+        destination_mint: &Pubkey,
+        transit_source_account: &Pubkey,
+        transit_destination_account: &Pubkey,
+        swap_pool: &SplSwapPool,
+        change: TokenChange,
+        minimum_amount_out: u64,
+    ) -> Result<(), Error> {
+        let (source_token, destination_token) = if source_mint == &swap_pool.mint_a {
+            (&swap_pool.token_a, &swap_pool.token_b)
+        } else {
+            (&swap_pool.token_b, &swap_pool.token_a)
+        };
+        self.send_confirm_tx(
+            self.tx
+                .close_leveraged_position(
+                    source_mint,
+                    destination_mint,
+                    transit_source_account,
+                    transit_destination_account,
+                    &swap_pool.pool,
+                    &swap_pool.pool_mint,
+                    &swap_pool.fee_account,
+                    source_token,
+                    destination_token,
+                    program_id,
+                    change,
+                    minimum_amount_out,
+                )
+                .await?,
+        )
+        .await
+    }
+
+    /// Swap between two tokens using a swap pool, where each side of the swap
+    /// can be a margin pool deposit or a deposit position
+    #[allow(clippy::too_many_arguments)]
+    pub async fn swap_positions(
+        &self,
+        program_id: &Pubkey,
+        source_mint: &Pubkey,
+        destination_mint: &Pubkey,
+        source: SwapEndpoint,
+        destination: SwapEndpoint,
+        swap_pool: &SplSwapPool,
+        change: TokenChange,
+        minimum_amount_out: u64,
+    ) -> Result<(), Error> {
+        // Determine the order of token_a and token_b based on direction of swap
+        let (source_token, destination_token) = if source_mint == &swap_pool.mint_a {
+            (&swap_pool.token_a, &swap_pool.token_b)
+        } else {
+            (&swap_pool.token_b, &swap_pool.token_a)
+        };
+        self.send_confirm_tx(
+            self.tx
+                .swap_positions(
+                    source_mint,
+                    destination_mint,
+                    source,
+                    destination,
+                    &swap_pool.pool,
+                    &swap_pool.pool_mint,
+                    &swap_pool.fee_account,
+                    source_token,
+                    destination_token,
+                    program_id,
+                    change,
+                    minimum_amount_out,
+                )
+                .await?,
+        )
+        .await
+    }
+
+    /// Swap between two tokens through a route of one or more swap pools
+    pub async fn route_swap(
+        &self,
+        route: &SwapRoute,
+        change: TokenChange,
+        minimum_amount_out: u64,
+    ) -> Result<(), Error> {
+        self.send_confirm_tx(
+            self.tx
+                .route_swap(route, change, minimum_amount_out)
+                .await?,
+        )
+        .await
+    }
+
     pub async fn positions(&self) -> Result<Vec<AccountPosition>, Error> {
         Ok(self
             .tx
@@ -0,0 +1,266 @@
+// SPDX-License-Identifier: AGPL-3.0-or-later
+//
+// Copyright (C) 2022 JET PROTOCOL HOLDINGS, LLC.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+//! Utilities for creating serum markets on a local validator, and trading on
+//! them with margin accounts.
+
+use std::sync::Arc;
+
+use anyhow::{Error, Result};
+use jet_margin_sdk::ix_builder::{MarginSerumIxBuilder, SerumMarketAccounts};
+use jet_margin_sdk::jet_margin_serum::MarketInfo;
+use jet_margin_sdk::solana::transaction::{SendTransactionBuilder, TransactionBuilder};
+use jet_simulation::{generate_keypair, send_and_confirm, solana_rpc_api::SolanaRpcClient};
+use serum_dex::instruction::SelfTradeBehavior;
+use serum_dex::matching::{OrderType, Side};
+use solana_sdk::instruction::Instruction;
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signature::{Keypair, Signer};
+use solana_sdk::system_instruction;
+
+use crate::margin::MarginUser;
+use crate::tokens::TokenManager;
+
+/// The address of the serum dex deployed to the local validator
+pub const SERUM_PROGRAM_ID: Pubkey = anchor_spl::dex::ID;
+
+const MARKET_SPACE: usize = 388;
+const REQUEST_QUEUE_SPACE: usize = 5120 + 12;
+const EVENT_QUEUE_SPACE: usize = 262144 + 12;
+const ORDER_BOOK_SPACE: usize = 65536 + 12;
+
+/// A serum market that margin accounts can trade on
+pub struct SerumMarket {
+    rpc: Arc<dyn SolanaRpcClient>,
+    pub ix: MarginSerumIxBuilder,
+}
+
+impl SerumMarket {
+    /// Create a new serum market on the dex, which still needs to be registered
+    /// with the margin serum program
+    pub async fn create(
+        rpc: &Arc<dyn SolanaRpcClient>,
+        airspace: Pubkey,
+        base: (Pubkey, Pubkey),
+        quote: (Pubkey, Pubkey),
+        base_lot_size: u64,
+        quote_lot_size: u64,
+    ) -> Result<Self, Error> {
+        let (base_mint, base_oracle) = base;
+        let (quote_mint, quote_oracle) = quote;
+        let tokens = TokenManager::new(rpc.clone());
+
+        let market = generate_keypair();
+        let request_queue = generate_keypair();
+        let event_queue = generate_keypair();
+        let bids = generate_keypair();
+        let asks = generate_keypair();
+
+        let (vault_signer, vault_signer_nonce) = (0u64..)
+            .find_map(|nonce| {
+                serum_dex::state::gen_vault_signer_key(nonce, &market.pubkey(), &SERUM_PROGRAM_ID)
+                    .ok()
+                    .map(|key| (key, nonce))
+            })
+            .unwrap();
+
+        let base_vault = tokens.create_account(&base_mint, &vault_signer).await?;
+        let quote_vault = tokens.create_account(&quote_mint, &vault_signer).await?;
+
+        let create_accounts = [
+            (&market, MARKET_SPACE),
+            (&request_queue, REQUEST_QUEUE_SPACE),
+            (&event_queue, EVENT_QUEUE_SPACE),
+            (&bids, ORDER_BOOK_SPACE),
+            (&asks, ORDER_BOOK_SPACE),
+        ];
+        for (account, space) in create_accounts {
+            let lamports = rpc.get_minimum_balance_for_rent_exemption(space).await?;
+            let ix = system_instruction::create_account(
+                &rpc.payer().pubkey(),
+                &account.pubkey(),
+                lamports,
+                space as u64,
+                &SERUM_PROGRAM_ID,
+            );
+
+            send_and_confirm(rpc, &[ix], &[account]).await?;
+        }
+
+        let init_market = serum_dex::instruction::initialize_market(
+            &market.pubkey(),
+            &SERUM_PROGRAM_ID,
+            &base_mint,
+            &quote_mint,
+            &base_vault,
+            &quote_vault,
+            None,
+            None,
+            None,
+            &bids.pubkey(),
+            &asks.pubkey(),
+            &request_queue.pubkey(),
+            &event_queue.pubkey(),
+            base_lot_size,
+            quote_lot_size,
+            vault_signer_nonce,
+            100,
+        )?;
+        rpc.send_and_confirm(vec![init_market].into()).await?;
+
+        let accounts = SerumMarketAccounts {
+            market: market.pubkey(),
+            base_mint,
+            quote_mint,
+            base_vault,
+            quote_vault,
+            request_queue: request_queue.pubkey(),
+            event_queue: event_queue.pubkey(),
+            bids: bids.pubkey(),
+            asks: asks.pubkey(),
+            vault_signer,
+        };
+
+        Ok(Self {
+            rpc: rpc.clone(),
+            ix: MarginSerumIxBuilder::new(airspace, accounts, base_oracle, quote_oracle),
+        })
+    }
+
+    /// Get the registration of the market with the margin serum program
+    pub async fn market_info(&self) -> Result<MarketInfo, Error> {
+        let account = self
+            .rpc
+            .get_account(&self.ix.market_info)
+            .await?
+            .ok_or_else(|| anyhow::anyhow!("market {} is not registered", self.ix.market_info))?;
+
+        Ok(anchor_lang::AccountDeserialize::try_deserialize(
+            &mut &account.data[..],
+        )?)
+    }
+
+    /// Create the open orders account for a margin account
+    pub async fn init_open_orders(&self, user: &MarginUser) -> Result<(), Error> {
+        let ix = self
+            .ix
+            .init_open_orders(*user.address(), self.rpc.payer().pubkey());
+
+        self.send_as(user, ix).await
+    }
+
+    /// Place a limit order with the margin account
+    pub async fn place_order(
+        &self,
+        user: &MarginUser,
+        payer: Pubkey,
+        side: Side,
+        limit_price: u64,
+        max_base_qty: u64,
+        max_native_quote_qty: u64,
+        client_order_id: u64,
+    ) -> Result<(), Error> {
+        let ix = self.ix.new_order(
+            *user.address(),
+            payer,
+            side,
+            limit_price,
+            max_base_qty,
+            max_native_quote_qty,
+            OrderType::Limit,
+            SelfTradeBehavior::DecrementTake,
+            client_order_id,
+        );
+
+        self.send_as(user, ix).await
+    }
+
+    /// Cancel an order placed by the margin account
+    pub async fn cancel_order_by_client_id(
+        &self,
+        user: &MarginUser,
+        client_order_id: u64,
+    ) -> Result<(), Error> {
+        let ix = self
+            .ix
+            .cancel_order_by_client_id(*user.address(), client_order_id);
+
+        self.send_as(user, ix).await
+    }
+
+    /// Settle the free tokens of the margin account's open orders
+    pub async fn settle_funds(
+        &self,
+        user: &MarginUser,
+        base_wallet: Pubkey,
+        quote_wallet: Pubkey,
+    ) -> Result<(), Error> {
+        let ix = self
+            .ix
+            .settle_funds(*user.address(), base_wallet, quote_wallet);
+
+        self.send_as(user, ix).await
+    }
+
+    /// Update the positions of the margin account after its orders were filled
+    pub async fn refresh_open_orders(&self, user: &MarginUser) -> Result<(), Error> {
+        let ix = user
+            .tx
+            .ix
+            .accounting_invoke(self.ix.refresh_open_orders(*user.address()));
+
+        self.rpc.send_and_confirm(vec![ix].into()).await?;
+        Ok(())
+    }
+
+    /// Close the margin account's open orders account and positions
+    pub async fn close_open_orders(&self, user: &MarginUser) -> Result<(), Error> {
+        let ix = self
+            .ix
+            .close_open_orders(*user.address(), self.rpc.payer().pubkey());
+
+        self.send_as(user, ix).await
+    }
+
+    /// Match the orders in the event queue
+    pub async fn consume_events(&self, open_orders: &[Pubkey]) -> Result<(), Error> {
+        let fee_receiver = self.ix.market.quote_vault;
+        let ix = serum_dex::instruction::consume_events(
+            &SERUM_PROGRAM_ID,
+            open_orders.iter().collect(),
+            &self.ix.market.market,
+            &self.ix.market.event_queue,
+            &fee_receiver,
+            &fee_receiver,
+            u16::MAX,
+        )?;
+
+        self.rpc.send_and_confirm(vec![ix].into()).await?;
+        Ok(())
+    }
+
+    async fn send_as(&self, user: &MarginUser, ix: Instruction) -> Result<(), Error> {
+        self.rpc
+            .send_and_confirm(TransactionBuilder {
+                instructions: vec![user.tx.ix.adapter_invoke(ix)],
+                signers: vec![Keypair::from_bytes(&user.signer.to_bytes())?],
+            })
+            .await?;
+
+        Ok(())
+    }
+}
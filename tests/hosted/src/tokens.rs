@@ -366,6 +366,8 @@ impl TokenManager {
                 mint,
                 price.price,
                 price.confidence as i64,
+                price.twap as i64,
+                price.confidence as i64,
                 price.exponent,
             ));
         }
@@ -0,0 +1,228 @@
+use anyhow::Error;
+
+use jet_margin_sdk::ix_builder::{MarginIxBuilder, MarginPoolIxBuilder};
+use jet_margin_sdk::tokens::TokenPrice;
+use jet_simulation::{assert_custom_program_error, create_wallet, send_and_confirm};
+use solana_sdk::native_token::LAMPORTS_PER_SOL;
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signature::{Keypair, Signer};
+
+use hosted_tests::{
+    context::{test_context, MarginTestContext},
+    margin::MarginPoolSetupInfo,
+};
+
+use jet_margin::TokenKind;
+use jet_margin_pool::{MarginPoolConfig, PoolFlags, TokenChange};
+
+const ONE_USDC: u64 = 1_000_000;
+const ONE_TSOL: u64 = LAMPORTS_PER_SOL;
+
+const DEFAULT_POOL_CONFIG: MarginPoolConfig = MarginPoolConfig {
+    borrow_rate_0: 10,
+    borrow_rate_1: 20,
+    borrow_rate_2: 30,
+    borrow_rate_3: 40,
+    utilization_rate_1: 10,
+    utilization_rate_2: 20,
+    management_fee_rate: 10,
+    flags: PoolFlags::ALLOW_LENDING.bits(),
+    max_price_deviation: 0,
+    reserved: [0; 6],
+};
+
+struct TestEnv {
+    tsol: Pubkey,
+    wallet: Keypair,
+    margin: MarginIxBuilder,
+    pool: MarginPoolIxBuilder,
+    deposit_position: Pubkey,
+    loan_position: Pubkey,
+}
+
+/// Create a user with 1,000 USDC of collateral and a small TSOL loan, borrowing
+/// from a pool with 100 TSOL deposited by another user.
+async fn setup_user(ctx: &MarginTestContext) -> Result<TestEnv, Error> {
+    let usdc = ctx.tokens.create_token(6, None, None).await?;
+    let usdc_oracle = ctx.tokens.create_oracle(&usdc).await?;
+    let tsol = ctx.tokens.create_token(9, None, None).await?;
+    let tsol_oracle = ctx.tokens.create_oracle(&tsol).await?;
+
+    for (token, oracle) in [(usdc, usdc_oracle), (tsol, tsol_oracle)] {
+        ctx.margin
+            .create_pool(&MarginPoolSetupInfo {
+                token,
+                token_kind: TokenKind::Collateral,
+                collateral_weight: 1_00,
+                max_leverage: 4_00,
+                oracle,
+                config: DEFAULT_POOL_CONFIG,
+            })
+            .await?;
+    }
+
+    for (token, price) in [(usdc, 100_000_000), (tsol, 10_000_000_000)] {
+        ctx.tokens
+            .set_price(
+                &token,
+                &TokenPrice {
+                    exponent: -8,
+                    price,
+                    confidence: price / 100,
+                    twap: price,
+                },
+            )
+            .await?;
+    }
+
+    let wallet_a = create_wallet(&ctx.rpc, 10 * LAMPORTS_PER_SOL).await?;
+    let wallet_b = create_wallet(&ctx.rpc, 10 * LAMPORTS_PER_SOL).await?;
+    let user_a = ctx.margin.user(&wallet_a, 0)?;
+    let user_b = ctx.margin.user(&wallet_b, 0)?;
+
+    user_a.create_account().await?;
+    user_b.create_account().await?;
+
+    let user_a_usdc_account = ctx
+        .tokens
+        .create_account_funded(&usdc, &wallet_a.pubkey(), 1_000 * ONE_USDC)
+        .await?;
+    let user_b_tsol_account = ctx
+        .tokens
+        .create_account_funded(&tsol, &wallet_b.pubkey(), 100 * ONE_TSOL)
+        .await?;
+
+    user_a
+        .deposit(
+            &usdc,
+            &user_a_usdc_account,
+            TokenChange::shift(1_000 * ONE_USDC),
+        )
+        .await?;
+    user_b
+        .deposit(
+            &tsol,
+            &user_b_tsol_account,
+            TokenChange::shift(100 * ONE_TSOL),
+        )
+        .await?;
+
+    user_a.refresh_all_pool_positions().await?;
+    user_b.refresh_all_pool_positions().await?;
+
+    // registers the deposit and loan positions for the pool
+    user_a.borrow(&tsol, TokenChange::shift(ONE_TSOL)).await?;
+
+    let payer = ctx.rpc.payer().pubkey();
+    let margin = MarginIxBuilder::new_with_payer_and_airspace(
+        wallet_a.pubkey(),
+        0,
+        payer,
+        ctx.margin.airspace(),
+        None,
+    );
+    let pool = MarginPoolIxBuilder::new(ctx.margin.airspace(), tsol);
+    let (deposit_position, _) = margin.register_position(pool.deposit_note_mint, 0);
+    let (loan_position, _) = pool.register_loan(margin.address, payer);
+
+    Ok(TestEnv {
+        tsol,
+        wallet: wallet_a,
+        margin,
+        pool,
+        deposit_position,
+        loan_position,
+    })
+}
+
+/// A batch is only required to leave the account healthy once all of its
+/// instructions have completed.
+#[tokio::test(flavor = "multi_thread")]
+#[cfg_attr(not(feature = "localnet"), serial_test::serial)]
+async fn batch_verifies_health_once() -> Result<(), Error> {
+    let ctx = test_context().await;
+    let env = setup_user(ctx).await?;
+    let oracle = ctx.margin.get_pool(&env.tsol).await?.token_price_oracle;
+    let refresh_ix = env.pool.margin_refresh_position(env.margin.address, oracle);
+    let borrow_ix = env.pool.margin_borrow(
+        env.margin.address,
+        env.deposit_position,
+        env.loan_position,
+        TokenChange::shift(90 * ONE_TSOL),
+    );
+    let repay_ix = env.pool.margin_repay(
+        env.margin.address,
+        env.deposit_position,
+        env.loan_position,
+        TokenChange::shift(90 * ONE_TSOL),
+    );
+
+    // borrowing 90 TSOL against 1,000 USDC leaves the account unhealthy
+    let result = send_and_confirm(
+        &ctx.rpc,
+        &[
+            env.margin.adapter_invoke(refresh_ix.clone()),
+            env.margin.adapter_invoke(borrow_ix.clone()),
+        ],
+        &[&env.wallet],
+    )
+    .await;
+
+    assert_custom_program_error(jet_margin::ErrorCode::Unhealthy, result);
+
+    // but not once the loan is repaid within the same batch
+    send_and_confirm(
+        &ctx.rpc,
+        &[env
+            .margin
+            .adapter_invoke_batch(vec![refresh_ix, borrow_ix, repay_ix])],
+        &[&env.wallet],
+    )
+    .await?;
+
+    send_and_confirm(&ctx.rpc, &[env.margin.verify_healthy()], &[]).await?;
+
+    Ok(())
+}
+
+/// When any instruction in a batch fails, none of the changes from the
+/// earlier instructions are kept.
+#[tokio::test(flavor = "multi_thread")]
+#[cfg_attr(not(feature = "localnet"), serial_test::serial)]
+async fn failing_batch_instruction_reverts_batch() -> Result<(), Error> {
+    let ctx = test_context().await;
+    let env = setup_user(ctx).await?;
+    let loan_notes = ctx.margin.get_pool(&env.tsol).await?.loan_notes;
+
+    let borrow = |tokens| {
+        env.pool.margin_borrow(
+            env.margin.address,
+            env.deposit_position,
+            env.loan_position,
+            TokenChange::shift(tokens),
+        )
+    };
+    let repay_ix = env.pool.margin_repay(
+        env.margin.address,
+        env.deposit_position,
+        env.loan_position,
+        TokenChange::shift(ONE_TSOL),
+    );
+
+    // the pool only has 100 TSOL to lend
+    let result = send_and_confirm(
+        &ctx.rpc,
+        &[env.margin.adapter_invoke_batch(vec![
+            borrow(ONE_TSOL),
+            borrow(1_000 * ONE_TSOL),
+            repay_ix,
+        ])],
+        &[&env.wallet],
+    )
+    .await;
+
+    assert_custom_program_error(jet_margin_pool::ErrorCode::InsufficientLiquidity, result);
+    assert_eq!(loan_notes, ctx.margin.get_pool(&env.tsol).await?.loan_notes);
+
+    Ok(())
+}
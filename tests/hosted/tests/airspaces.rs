@@ -0,0 +1,188 @@
+use anyhow::Error;
+
+use jet_margin_sdk::{
+    ix_builder::{
+        derive_airspace, AirspaceIxBuilder, MarginConfigIxBuilder, MarginIxBuilder,
+        MarginPoolIxBuilder,
+    },
+    solana::{keypair::clone, transaction::SendTransactionBuilder},
+    tx_builder::MarginTxBuilder,
+};
+use jet_simulation::{assert_custom_program_error, create_wallet, send_and_confirm};
+
+use solana_sdk::signature::Signer;
+use solana_sdk::{instruction::AccountMeta, native_token::LAMPORTS_PER_SOL};
+
+use hosted_tests::{context::test_context, margin::MarginPoolSetupInfo};
+
+use jet_margin::TokenKind;
+use jet_margin_pool::{MarginPoolConfig, PoolFlags, TokenChange};
+
+const DEFAULT_POOL_CONFIG: MarginPoolConfig = MarginPoolConfig {
+    borrow_rate_0: 10,
+    borrow_rate_1: 20,
+    borrow_rate_2: 30,
+    borrow_rate_3: 40,
+    utilization_rate_1: 10,
+    utilization_rate_2: 20,
+    management_fee_rate: 10,
+    flags: PoolFlags::ALLOW_LENDING.bits(),
+    max_price_deviation: 0,
+    reserved: [0; 6],
+};
+
+/// A margin account can't use the token configs, adapter configs or pools of
+/// another airspace.
+#[tokio::test(flavor = "multi_thread")]
+#[cfg_attr(not(feature = "localnet"), serial_test::serial)]
+async fn foreign_airspace_is_rejected() -> Result<(), Error> {
+    let ctx = test_context().await;
+    let payer = ctx.rpc.payer().pubkey();
+
+    let usdc = ctx.tokens.create_token(6, None, None).await?;
+    let usdc_oracle = ctx.tokens.create_oracle(&usdc).await?;
+
+    ctx.margin
+        .create_pool(&MarginPoolSetupInfo {
+            token: usdc,
+            token_kind: TokenKind::Collateral,
+            collateral_weight: 1_00,
+            max_leverage: 4_00,
+            oracle: usdc_oracle,
+            config: DEFAULT_POOL_CONFIG,
+        })
+        .await?;
+
+    // a second airspace, which allows the pool program as an adapter but has no pools
+    let foreign = AirspaceIxBuilder::new("foreign-pools", payer, payer);
+    let foreign_address = derive_airspace("foreign-pools");
+
+    ctx.rpc
+        .send_and_confirm(
+            vec![
+                foreign.create(false),
+                MarginConfigIxBuilder::new(foreign_address, payer)
+                    .configure_adapter(jet_margin_pool::ID, true),
+            ]
+            .into(),
+        )
+        .await?;
+
+    let wallet = create_wallet(&ctx.rpc, 10 * LAMPORTS_PER_SOL).await?;
+    let owner = wallet.pubkey();
+    let user = MarginTxBuilder::new_with_airspace(
+        ctx.rpc.clone(),
+        Some(clone(&wallet)),
+        owner,
+        0,
+        foreign_address,
+    );
+
+    ctx.rpc
+        .send_and_confirm_transaction(&user.create_account().await?)
+        .await?;
+
+    let pool = MarginPoolIxBuilder::new(ctx.margin.airspace(), usdc);
+    let default_ix =
+        MarginIxBuilder::new_with_payer_and_airspace(owner, 0, payer, ctx.margin.airspace(), None);
+    let foreign_ix =
+        MarginIxBuilder::new_with_payer_and_airspace(owner, 0, payer, foreign_address, None);
+
+    // the token config of the default airspace
+    let (_, register_ix) = default_ix.register_position(pool.deposit_note_mint, 0);
+    let result = send_and_confirm(&ctx.rpc, &[register_ix], &[&wallet]).await;
+
+    assert_custom_program_error(jet_margin::ErrorCode::WrongAirspace, result);
+
+    // the adapter config of the default airspace
+    let withdraw_ix = pool.withdraw(*user.address(), owner, owner, TokenChange::shift(1));
+    let result = send_and_confirm(
+        &ctx.rpc,
+        &[default_ix.adapter_invoke(withdraw_ix.clone())],
+        &[&wallet],
+    )
+    .await;
+
+    assert_custom_program_error(jet_margin::ErrorCode::WrongAirspace, result);
+
+    // the pool of the default airspace, through an adapter allowed in the account's airspace
+    let result = send_and_confirm(
+        &ctx.rpc,
+        &[foreign_ix.adapter_invoke(withdraw_ix)],
+        &[&wallet],
+    )
+    .await;
+
+    assert_custom_program_error(jet_margin_pool::ErrorCode::WrongAirspace, result);
+
+    Ok(())
+}
+
+/// Only version 1 accounts can be migrated into an airspace.
+#[tokio::test(flavor = "multi_thread")]
+#[cfg_attr(not(feature = "localnet"), serial_test::serial)]
+async fn current_account_is_not_migrated() -> Result<(), Error> {
+    let ctx = test_context().await;
+    let wallet = create_wallet(&ctx.rpc, 10 * LAMPORTS_PER_SOL).await?;
+    let user = ctx.margin.user(&wallet, 0)?;
+
+    user.create_account().await?;
+
+    let tx = MarginTxBuilder::new_with_airspace(
+        ctx.rpc.clone(),
+        Some(clone(&wallet)),
+        wallet.pubkey(),
+        0,
+        ctx.margin.airspace(),
+    );
+    let result = ctx
+        .rpc
+        .send_and_confirm_transaction(&tx.migrate_account().await?)
+        .await;
+
+    assert_custom_program_error(jet_margin::ErrorCode::WrongAccountVersion, result);
+
+    Ok(())
+}
+
+/// Migration can't be used to move an account into another airspace, since it
+/// always assigns the default airspace and ignores any airspace that is passed.
+#[tokio::test(flavor = "multi_thread")]
+#[cfg_attr(not(feature = "localnet"), serial_test::serial)]
+async fn account_is_not_migrated_into_another_airspace() -> Result<(), Error> {
+    let ctx = test_context().await;
+    let payer = ctx.rpc.payer().pubkey();
+
+    let foreign = AirspaceIxBuilder::new("foreign-migration", payer, payer);
+    let foreign_address = derive_airspace("foreign-migration");
+
+    ctx.rpc
+        .send_and_confirm(vec![foreign.create(false)].into())
+        .await?;
+
+    let wallet = create_wallet(&ctx.rpc, 10 * LAMPORTS_PER_SOL).await?;
+    let user = ctx.margin.user(&wallet, 0)?;
+
+    user.create_account().await?;
+
+    let foreign_ix = MarginIxBuilder::new_with_payer_and_airspace(
+        wallet.pubkey(),
+        0,
+        payer,
+        foreign_address,
+        None,
+    );
+    let mut migrate_ix = foreign_ix.migrate_account();
+    migrate_ix
+        .accounts
+        .push(AccountMeta::new_readonly(foreign_address, false));
+
+    let result = send_and_confirm(&ctx.rpc, &[migrate_ix], &[&wallet]).await;
+
+    assert_custom_program_error(jet_margin::ErrorCode::WrongAccountVersion, result);
+
+    let account = ctx.margin.get_account(user.address()).await?;
+    assert_eq!(account.airspace, ctx.margin.airspace());
+
+    Ok(())
+}
@@ -5,11 +5,23 @@ use hosted_tests::{
     bonds::{
         BondsUser, GenerateProxy, OrderAmount, TestManager as BondsTestManager, STARTING_TOKENS,
     },
-    context::test_context,
+    context::{test_context, MarginTestContext},
+    pricing::TokenPricer,
     setup_helper::{setup_user, tokens},
 };
-use jet_bonds::orderbook::state::OrderParams;
+use jet_airspace::state::AirspacePermit;
+use jet_bonds::{
+    margin::instructions::MarginReplaceOrdersParams,
+    orderbook::{instructions::ReplaceOrdersParams, state::OrderParams},
+    BondsError,
+};
 use jet_margin_sdk::{
+    bonds::{
+        crank_authorization,
+        market_order::{InterestRate, MarketOrder},
+        orderbook::{OrderSize, Side},
+        BondsIxBuilder,
+    },
     ix_builder::MarginIxBuilder,
     margin_integrator::{NoProxy, Proxy},
     tx_builder::bonds::BondsPositionRefresher,
@@ -19,8 +31,12 @@ use jet_margin_sdk::{
     tx_builder::MarginTxBuilder,
 };
 use jet_proto_math::fixed_point::Fp32;
+use jet_simulation::{assert_custom_program_error, create_wallet, send_and_confirm};
 
-use solana_sdk::signer::Signer;
+use solana_sdk::{
+    native_token::LAMPORTS_PER_SOL, pubkey::Pubkey, signature::Keypair, signer::Signer,
+};
+use spl_associated_token_account::get_associated_token_address;
 
 #[tokio::test(flavor = "multi_thread")]
 #[cfg_attr(not(feature = "localnet"), serial_test::serial)]
@@ -77,6 +93,19 @@ async fn margin() -> Result<()> {
     let user = BondsUser::new_with_proxy_funded(manager.clone(), wallet, proxy.clone())
         .await
         .unwrap();
+
+    // the market only admits margin users whose owner holds a permit of the required tier
+    manager
+        .set_min_permit_tier(AirspacePermit::TIER_INSTITUTIONAL)
+        .await?;
+    manager
+        .issue_permit(user.owner.pubkey(), 0, AirspacePermit::TIER_RETAIL)
+        .await?;
+    assert!(user.initialize_margin_user().await.is_err());
+
+    manager
+        .issue_permit(user.owner.pubkey(), 0, AirspacePermit::TIER_INSTITUTIONAL)
+        .await?;
     user.initialize_margin_user().await.unwrap();
 
     let borrower_account = user.load_margin_user().await.unwrap();
@@ -92,6 +121,7 @@ async fn margin() -> Result<()> {
         post_only: false,
         post_allowed: true,
         auto_stake: true,
+        good_til_time: None,
     };
     let mut ixs = vec![
         pricer.set_oracle_price_tx(&collateral, 1.0).await.unwrap(),
@@ -155,6 +185,12 @@ async fn _full_workflow<P: Proxy + GenerateProxy>(manager: Arc<BondsTestManager>
     let bond_manager = manager.load_manager().await?;
     assert!(!bond_manager.tickets_paused);
 
+    let invalid_duration = manager.ix_builder.set_duration(0)?;
+    assert!(manager
+        .sign_send_transaction(&[invalid_duration], None)
+        .await
+        .is_err());
+
     // Scenario a: post a borrow order to an empty book
     let a_amount = OrderAmount::from_amount_rate(1_000, 2_000);
     let a_params = OrderParams {
@@ -165,6 +201,7 @@ async fn _full_workflow<P: Proxy + GenerateProxy>(manager: Arc<BondsTestManager>
         post_only: false,
         post_allowed: true,
         auto_stake: true,
+        good_til_time: None,
     };
 
     // simulate
@@ -217,6 +254,7 @@ async fn _full_workflow<P: Proxy + GenerateProxy>(manager: Arc<BondsTestManager>
         post_only: false,
         post_allowed: true,
         auto_stake: true,
+        good_til_time: None,
     };
     assert!(alice.lend_order(crossing_params, vec![]).await.is_err());
 
@@ -230,6 +268,7 @@ async fn _full_workflow<P: Proxy + GenerateProxy>(manager: Arc<BondsTestManager>
         post_only: false,
         post_allowed: true,
         auto_stake: true,
+        good_til_time: None,
     };
 
     // simulate
@@ -256,6 +295,18 @@ async fn _full_workflow<P: Proxy + GenerateProxy>(manager: Arc<BondsTestManager>
 
     // send to validator
     let bob = BondsUser::<P>::new_funded(manager.clone()).await?;
+
+    // a market order that cannot fill within its rate limit is rejected
+    let tenor = manager.load_manager().await?.duration as u64;
+    let market_params =
+        MarketOrder::new(OrderSize::Underlying(500), InterestRate::Apy(5_000), tenor)
+            .params(Side::Bid)?;
+    assert_custom_program_error(
+        BondsError::SlippageExceeded,
+        bob.lend_order(market_params, vec![]).await,
+    );
+    assert_eq!(bob.tokens().await?, STARTING_TOKENS);
+
     bob.lend_order(b_params, vec![0]).await?;
 
     let split_ticket_b = bob.load_split_ticket(vec![0]).await?;
@@ -276,6 +327,7 @@ async fn _full_workflow<P: Proxy + GenerateProxy>(manager: Arc<BondsTestManager>
         post_only: false,
         post_allowed: true,
         auto_stake: true,
+        good_til_time: None,
     };
 
     // simulate
@@ -377,3 +429,544 @@ async fn _full_workflow<P: Proxy + GenerateProxy>(manager: Arc<BondsTestManager>
 
     Ok(())
 }
+
+/// Creates a margin account with collateral and a `MarginUser` in the bond market
+async fn margin_borrower(
+    ctx: &MarginTestContext,
+    manager: Arc<BondsTestManager>,
+) -> Result<(
+    BondsUser<RefreshingProxy<MarginIxBuilder>>,
+    TokenPricer,
+    Pubkey,
+)> {
+    let client = manager.client.clone();
+    let ([collateral], _, pricer) = tokens(ctx).await?;
+
+    let user = setup_user(ctx, vec![(collateral, 0, u64::MAX / 2)]).await?;
+    let margin = user.user.tx.ix.clone();
+    let wallet = user.user.signer;
+
+    let proxy = RefreshingProxy {
+        proxy: margin.clone(),
+        refreshers: vec![
+            Arc::new(MarginTxBuilder::new(
+                client.clone(),
+                None,
+                wallet.pubkey(),
+                0,
+            )),
+            Arc::new(
+                BondsPositionRefresher::new(
+                    margin.pubkey(),
+                    client.clone(),
+                    &[manager.ix_builder.manager()],
+                )
+                .await?,
+            ),
+        ],
+    };
+
+    let user = BondsUser::new_with_proxy_funded(manager.clone(), wallet, proxy).await?;
+    manager
+        .issue_permit(user.owner.pubkey(), 0, AirspacePermit::TIER_RETAIL)
+        .await?;
+    user.initialize_margin_user().await?;
+
+    Ok((user, pricer, collateral))
+}
+
+#[tokio::test(flavor = "multi_thread")]
+#[serial_test::serial]
+#[cfg(not(feature = "localnet"))]
+async fn prune_expired_orders() -> Result<()> {
+    let ctx = test_context().await;
+    let manager = Arc::new(BondsTestManager::full(ctx.rpc.clone()).await?);
+    let (borrower, pricer, collateral) = margin_borrower(ctx, manager.clone()).await?;
+    let lender = BondsUser::<NoProxy>::new_funded(manager.clone()).await?;
+
+    let expires_at = ctx.rpc.get_clock().await.unwrap().unix_timestamp + 60;
+
+    // a margin borrow order that expires
+    let borrow_amount = OrderAmount::from_amount_rate(1_000, 2_000);
+    let borrow_params = OrderParams {
+        max_bond_ticket_qty: borrow_amount.base,
+        max_underlying_token_qty: borrow_amount.quote,
+        limit_price: borrow_amount.price,
+        match_limit: 1,
+        post_only: true,
+        post_allowed: true,
+        auto_stake: true,
+        good_til_time: Some(expires_at),
+    };
+    let mut ixs = vec![
+        pricer.set_oracle_price_tx(&collateral, 1.0).await?,
+        pricer
+            .set_oracle_price_tx(&manager.ix_builder.token_mint(), 1.0)
+            .await?,
+    ];
+    ixs.extend(borrower.margin_borrow_order(borrow_params).await?);
+    manager
+        .client
+        .send_and_confirm_condensed_in_order(ixs)
+        .await?;
+    assert!(borrower.load_margin_user().await?.debt.pending() > 0);
+
+    // a lend order that expires, at a rate that does not cross the borrow order
+    let lend_amount = OrderAmount::from_amount_rate(1_000, 3_000);
+    let lend_params = OrderParams {
+        max_bond_ticket_qty: lend_amount.base,
+        max_underlying_token_qty: lend_amount.quote,
+        limit_price: lend_amount.price,
+        match_limit: 1,
+        post_only: true,
+        post_allowed: true,
+        auto_stake: true,
+        good_til_time: Some(expires_at),
+    };
+    lender.lend_order(lend_params, vec![]).await?;
+    assert!(lender.tokens().await? < STARTING_TOKENS);
+
+    let mut book = manager.load_orderbook().await?;
+    let borrow_order = book.asks()?[0].key;
+    let lend_order = book.bids()?[0].key;
+    let borrower_account = manager
+        .ix_builder
+        .margin_user_account(borrower.proxy.pubkey());
+    let orders = [borrow_order, lend_order];
+    let out_accounts = [
+        borrower_account,
+        BondsIxBuilder::margin_user_claims(&borrower_account),
+        lender.token_acc,
+    ];
+
+    // orders can't be pruned before their good-til-time
+    assert_custom_program_error(
+        BondsError::OrderNotExpired,
+        manager.prune_expired_orders(&orders, &out_accounts).await,
+    );
+
+    let mut clock = ctx.rpc.get_clock().await.unwrap();
+    clock.unix_timestamp = expires_at + 1;
+    ctx.rpc.set_clock(clock);
+
+    manager.prune_expired_orders(&orders, &out_accounts).await?;
+
+    let mut book = manager.load_orderbook().await?;
+    assert!(book.asks()?.is_empty());
+    assert!(book.bids()?.is_empty());
+
+    // the borrower's pending debt and claims are released, and the lender's tokens are returned
+    assert_eq!(borrower.load_margin_user().await?.debt.pending(), 0);
+    assert_eq!(borrower.claims().await?, 0);
+    assert_eq!(lender.tokens().await?, STARTING_TOKENS);
+
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread")]
+#[serial_test::serial]
+#[cfg(not(feature = "localnet"))]
+async fn expired_orders_are_not_filled() -> Result<()> {
+    let ctx = test_context().await;
+    let manager = Arc::new(BondsTestManager::full(ctx.rpc.clone()).await?);
+    let (borrower, pricer, collateral) = margin_borrower(ctx, manager.clone()).await?;
+    let lender = BondsUser::<NoProxy>::new_funded(manager.clone()).await?;
+
+    let expires_at = ctx.rpc.get_clock().await.unwrap().unix_timestamp + 60;
+
+    let mut ixs = vec![
+        pricer.set_oracle_price_tx(&collateral, 1.0).await?,
+        pricer
+            .set_oracle_price_tx(&manager.ix_builder.token_mint(), 1.0)
+            .await?,
+    ];
+    ixs.extend(
+        borrower
+            .margin_borrow_order(OrderParams {
+                good_til_time: Some(expires_at),
+                ..post_only(OrderAmount::from_amount_rate(1_000, 2_000))
+            })
+            .await?,
+    );
+    manager
+        .client
+        .send_and_confirm_condensed_in_order(ixs)
+        .await?;
+    assert!(borrower.load_margin_user().await?.debt.pending() > 0);
+
+    let mut clock = ctx.rpc.get_clock().await.unwrap();
+    clock.unix_timestamp = expires_at + 1;
+    ctx.rpc.set_clock(clock);
+
+    // a lend order at a rate that crosses the expired borrow order
+    lender
+        .lend_order(
+            OrderParams {
+                match_limit: 100,
+                post_only: false,
+                ..post_only(OrderAmount::from_amount_rate(1_000, 1_500))
+            },
+            vec![],
+        )
+        .await?;
+
+    // the borrow order is removed instead of being filled, and the lend order is posted
+    let mut book = manager.load_orderbook().await?;
+    assert!(book.asks()?.is_empty());
+    assert_eq!(book.bids()?.len(), 1);
+
+    // the removed order is returned to the borrower by the crank
+    manager.consume_events().await?;
+    assert_eq!(borrower.load_margin_user().await?.debt.pending(), 0);
+
+    Ok(())
+}
+
+fn post_only(amount: OrderAmount) -> OrderParams {
+    OrderParams {
+        max_bond_ticket_qty: amount.base,
+        max_underlying_token_qty: amount.quote,
+        limit_price: amount.price,
+        match_limit: 1,
+        post_only: true,
+        post_allowed: true,
+        auto_stake: true,
+        good_til_time: None,
+    }
+}
+
+#[tokio::test(flavor = "multi_thread")]
+#[serial_test::serial]
+async fn cancel_all_orders() -> Result<()> {
+    let ctx = test_context().await;
+    let manager = Arc::new(BondsTestManager::full(ctx.rpc.clone()).await?);
+    let user = BondsUser::<NoProxy>::new_funded(manager.clone()).await?;
+
+    const TICKETS: u64 = 10_000;
+    user.convert_tokens(TICKETS).await?;
+
+    // a lend order and a ticket sale that don't cross
+    user.lend_order(
+        post_only(OrderAmount::from_amount_rate(1_000, 3_000)),
+        vec![],
+    )
+    .await?;
+    user.sell_tickets_order(post_only(OrderAmount::from_amount_rate(1_000, 1_000)))
+        .await?;
+    assert!(user.tokens().await? < STARTING_TOKENS - TICKETS);
+    assert!(user.tickets().await? < TICKETS);
+
+    let ticket_acc =
+        get_associated_token_address(&user.proxy.pubkey(), &manager.ix_builder.ticket_mint());
+    manager
+        .client
+        .send_and_confirm_condensed_in_order(
+            user.cancel_all_orders(&[user.token_acc, ticket_acc])
+                .await?,
+        )
+        .await?;
+
+    let mut book = manager.load_orderbook().await?;
+    assert!(book.bids()?.is_empty());
+    assert!(book.asks()?.is_empty());
+    assert_eq!(user.tokens().await?, STARTING_TOKENS - TICKETS);
+    assert_eq!(user.tickets().await?, TICKETS);
+
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread")]
+#[serial_test::serial]
+async fn margin_cancel_all_orders() -> Result<()> {
+    let ctx = test_context().await;
+    let manager = Arc::new(BondsTestManager::full(ctx.rpc.clone()).await?);
+    let (borrower, pricer, collateral) = margin_borrower(ctx, manager.clone()).await?;
+
+    let mut ixs = vec![
+        pricer.set_oracle_price_tx(&collateral, 1.0).await?,
+        pricer
+            .set_oracle_price_tx(&manager.ix_builder.token_mint(), 1.0)
+            .await?,
+    ];
+    ixs.extend(
+        borrower
+            .margin_borrow_order(post_only(OrderAmount::from_amount_rate(1_000, 2_000)))
+            .await?,
+    );
+    ixs.extend(
+        borrower
+            .margin_borrow_order(post_only(OrderAmount::from_amount_rate(500, 2_500)))
+            .await?,
+    );
+    manager
+        .client
+        .send_and_confirm_condensed_in_order(ixs)
+        .await?;
+
+    let pending = borrower.load_margin_user().await?.debt.pending();
+    assert!(pending > 0);
+    assert_eq!(borrower.claims().await?, pending);
+
+    let borrower_account = manager
+        .ix_builder
+        .margin_user_account(borrower.proxy.pubkey());
+    let mut ixs = vec![
+        pricer.set_oracle_price_tx(&collateral, 1.0).await?,
+        pricer
+            .set_oracle_price_tx(&manager.ix_builder.token_mint(), 1.0)
+            .await?,
+    ];
+    ixs.extend(
+        borrower
+            .cancel_all_orders(&[
+                borrower_account,
+                BondsIxBuilder::margin_user_claims(&borrower_account),
+            ])
+            .await?,
+    );
+    manager
+        .client
+        .send_and_confirm_condensed_in_order(ixs)
+        .await?;
+
+    // the pending debt is released and the claims that collateralized it are burned
+    assert!(manager.load_orderbook().await?.asks()?.is_empty());
+    assert_eq!(borrower.load_margin_user().await?.debt.pending(), 0);
+    assert_eq!(borrower.claims().await?, 0);
+
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread")]
+#[serial_test::serial]
+#[cfg(not(feature = "localnet"))]
+async fn view_instructions_return_data() -> Result<()> {
+    use anyhow::Context;
+    use hosted_tests::context::view;
+    use jet_bonds::{
+        margin::instructions::MarginUserDebt, orderbook::instructions::OrderbookQuote,
+    };
+
+    let ctx = test_context().await;
+    let manager = Arc::new(BondsTestManager::full(ctx.rpc.clone()).await?);
+    let (borrower, pricer, collateral) = margin_borrower(ctx, manager.clone()).await?;
+
+    let mut ixs = vec![
+        pricer.set_oracle_price_tx(&collateral, 1.0).await?,
+        pricer
+            .set_oracle_price_tx(&manager.ix_builder.token_mint(), 1.0)
+            .await?,
+    ];
+    ixs.extend(
+        borrower
+            .margin_borrow_order(post_only(OrderAmount::from_amount_rate(1_000, 2_000)))
+            .await?,
+    );
+    manager
+        .client
+        .send_and_confirm_condensed_in_order(ixs)
+        .await?;
+
+    let ask = manager.load_orderbook().await?.asks()?[0];
+    let quote: OrderbookQuote = view(ctx, manager.ix_builder.orderbook_quote()?).await?;
+    assert!(quote.best_bid.is_none());
+    let best_ask = quote.best_ask.context("no best ask")?;
+    assert_eq!(best_ask.price, ask.price());
+    assert_eq!(best_ask.quantity, ask.base_quantity);
+
+    let debt: MarginUserDebt = view(
+        ctx,
+        manager
+            .ix_builder
+            .margin_user_debt(borrower.proxy.pubkey())?,
+    )
+    .await?;
+    assert_eq!(
+        debt.pending,
+        borrower.load_margin_user().await?.debt.pending()
+    );
+    assert!(debt.pending > 0);
+    assert_eq!(debt.committed, 0);
+    assert_eq!(debt.outstanding_obligations, 0);
+
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread")]
+#[serial_test::serial]
+async fn replace_orders() -> Result<()> {
+    let ctx = test_context().await;
+    let manager = Arc::new(BondsTestManager::full(ctx.rpc.clone()).await?);
+    let user = BondsUser::<NoProxy>::new_funded(manager.clone()).await?;
+
+    user.lend_order(
+        post_only(OrderAmount::from_amount_rate(1_000, 3_000)),
+        vec![],
+    )
+    .await?;
+    let old_order = manager.load_orderbook().await?.bids()?[0].key;
+
+    let new_bids = [
+        OrderAmount::from_amount_rate(500, 2_500),
+        OrderAmount::from_amount_rate(700, 2_000),
+    ];
+    user.replace_orders(ReplaceOrdersParams {
+        cancel_order_ids: vec![old_order],
+        bids: new_bids.into_iter().map(post_only).collect(),
+        asks: vec![],
+    })
+    .await?;
+
+    let bids = manager.load_orderbook().await?.bids()?;
+    assert_eq!(bids.len(), 2);
+    assert!(bids.iter().all(|order| order.key != old_order));
+    // the funds of the cancelled order are returned before the new orders are paid for
+    let book_quote: u64 = bids
+        .iter()
+        .map(|order| {
+            Fp32::upcast_fp32(order.price())
+                .decimal_u64_mul(order.base_quantity)
+                .unwrap()
+        })
+        .sum();
+    assert_eq!(user.tokens().await?, STARTING_TOKENS - book_quote);
+
+    // replacement orders must be post only
+    let mut crossing = post_only(OrderAmount::from_amount_rate(500, 2_500));
+    crossing.post_only = false;
+    assert_custom_program_error(
+        BondsError::ReplacementNotPostOnly,
+        user.replace_orders(ReplaceOrdersParams {
+            cancel_order_ids: vec![],
+            bids: vec![crossing],
+            asks: vec![],
+        })
+        .await,
+    );
+
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread")]
+#[serial_test::serial]
+async fn margin_replace_orders() -> Result<()> {
+    let ctx = test_context().await;
+    let manager = Arc::new(BondsTestManager::full(ctx.rpc.clone()).await?);
+    let (borrower, pricer, collateral) = margin_borrower(ctx, manager.clone()).await?;
+
+    let mut ixs = vec![
+        pricer.set_oracle_price_tx(&collateral, 1.0).await?,
+        pricer
+            .set_oracle_price_tx(&manager.ix_builder.token_mint(), 1.0)
+            .await?,
+    ];
+    ixs.extend(
+        borrower
+            .margin_borrow_order(post_only(OrderAmount::from_amount_rate(1_000, 2_000)))
+            .await?,
+    );
+    manager
+        .client
+        .send_and_confirm_condensed_in_order(ixs)
+        .await?;
+    let old_order = manager.load_orderbook().await?.asks()?[0].key;
+
+    let mut ixs = vec![
+        pricer.set_oracle_price_tx(&collateral, 1.0).await?,
+        pricer
+            .set_oracle_price_tx(&manager.ix_builder.token_mint(), 1.0)
+            .await?,
+    ];
+    ixs.extend(
+        borrower
+            .margin_replace_orders(MarginReplaceOrdersParams {
+                cancel_order_ids: vec![old_order],
+                asks: vec![post_only(OrderAmount::from_amount_rate(400, 2_500))],
+            })
+            .await?,
+    );
+    manager
+        .client
+        .send_and_confirm_condensed_in_order(ixs)
+        .await?;
+
+    let asks = manager.load_orderbook().await?.asks()?;
+    assert_eq!(asks.len(), 1);
+    assert_ne!(asks[0].key, old_order);
+
+    // the pending debt and claims only reflect the new order
+    let pending = borrower.load_margin_user().await?.debt.pending();
+    assert_eq!(
+        pending,
+        Fp32::upcast_fp32(asks[0].price())
+            .decimal_u64_mul(asks[0].base_quantity)
+            .unwrap()
+    );
+    assert_eq!(borrower.claims().await?, pending);
+
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread")]
+#[serial_test::serial]
+async fn set_duration_only_affects_new_tickets() -> Result<()> {
+    let ctx = test_context().await;
+    let manager = Arc::new(BondsTestManager::full(ctx.rpc.clone()).await?);
+    let user = BondsUser::<NoProxy>::new_funded(manager.clone()).await?;
+    user.convert_tokens(2_000).await?;
+
+    let old_duration = manager.load_manager().await?.duration;
+    user.stake_tokens(1_000, vec![0]).await?;
+    let old_ticket = user.load_claim_ticket(vec![0]).await?;
+
+    for invalid in [0, -1] {
+        assert_custom_program_error(
+            BondsError::InvalidDuration,
+            manager.set_duration(invalid).await,
+        );
+    }
+
+    let new_duration = old_duration + 1_000;
+    manager.set_duration(new_duration).await?;
+    assert_eq!(manager.load_manager().await?.duration, new_duration);
+
+    // tickets staked before the change keep their maturity
+    assert_eq!(
+        user.load_claim_ticket(vec![0]).await?.maturation_timestamp,
+        old_ticket.maturation_timestamp
+    );
+
+    user.stake_tokens(1_000, vec![1]).await?;
+    let new_ticket = user.load_claim_ticket(vec![1]).await?;
+    assert!(
+        new_ticket.maturation_timestamp
+            >= old_ticket.maturation_timestamp - old_duration + new_duration
+    );
+
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread")]
+#[serial_test::serial]
+async fn crank_is_only_authorized_by_the_airspace_authority() -> Result<()> {
+    let ctx = test_context().await;
+    let manager = BondsTestManager::full(ctx.rpc.clone()).await?;
+    let impostor = create_wallet(&ctx.rpc, 10 * LAMPORTS_PER_SOL).await?;
+    let crank = Keypair::new();
+
+    let authorize_crank = manager
+        .ix_builder
+        .clone()
+        .with_authority(&impostor.pubkey())
+        .authorize_crank(impostor.pubkey(), crank.pubkey())?;
+    let result = send_and_confirm(&ctx.rpc, &[authorize_crank], &[&impostor]).await;
+
+    assert_custom_program_error(BondsError::WrongAirspaceAuthorization, result);
+    assert!(ctx
+        .rpc
+        .get_account(&crank_authorization(&manager.airspace(), &crank.pubkey()))
+        .await?
+        .is_none());
+
+    Ok(())
+}
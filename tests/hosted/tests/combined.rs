@@ -11,6 +11,7 @@ feature_groups! {
         mod pool_overpayment;
         mod rounding;
         mod sanity;
+        mod serum;
         mod swap;
     }
 }
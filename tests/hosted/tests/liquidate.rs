@@ -1,3 +1,5 @@
+use anchor_lang::AccountDeserialize;
+use anchor_spl::associated_token::get_associated_token_address;
 use anyhow::Result;
 
 use hosted_tests::{
@@ -6,8 +8,11 @@ use hosted_tests::{
     setup_helper::{liquidators, setup_token, setup_user, tokens, users},
     test_user::TestLiquidator,
 };
-use jet_margin::ErrorCode;
-use jet_margin_sdk::{solana::transaction::InverseSendTransactionBuilder, tokens::TokenPrice};
+use jet_margin::{ErrorCode, LiquidationState, TokenOracle};
+use jet_margin_sdk::{
+    solana::transaction::InverseSendTransactionBuilder, tokens::TokenPrice,
+    tx_builder::TokenDepositsConfig,
+};
 use solana_sdk::native_token::LAMPORTS_PER_SOL;
 use solana_sdk::pubkey::Pubkey;
 use solana_sdk::signature::Signer;
@@ -271,6 +276,102 @@ async fn can_withdraw_some_during_liquidation() -> Result<()> {
     Ok(())
 }
 
+/// User B also deposits 20'000 USDC directly, which leaves the account 95'000 short
+/// of its required collateral, so the liquidation may lose up to 9'500 of equity
+#[tokio::test(flavor = "multi_thread")]
+#[cfg_attr(not(feature = "localnet"), serial_test::serial)]
+async fn liquidator_can_transfer_some_deposits() -> Result<()> {
+    let ctx = test_context().await;
+    let scen = scenario1().await.unwrap();
+
+    let usdc_oracle = ctx.margin.get_pool(&scen.usdc).await?.token_price_oracle;
+    let (usdc_product, _) = Pubkey::find_program_address(
+        &[scen.usdc.as_ref(), b"oracle:product".as_ref()],
+        &jet_metadata::ID,
+    );
+
+    ctx.margin
+        .configure_token_deposits(
+            &scen.usdc,
+            Some(&TokenDepositsConfig {
+                oracle: TokenOracle::Pyth {
+                    price: usdc_oracle,
+                    product: usdc_product,
+                },
+                collateral_weight: 1_00,
+            }),
+        )
+        .await?;
+
+    let user_b_wallet_usdc = ctx
+        .tokens
+        .create_account_funded(&scen.usdc, scen.user_b.owner(), 20_000 * ONE_USDC)
+        .await?;
+    let user_b_deposit = get_associated_token_address(scen.user_b.address(), &scen.usdc);
+
+    scen.user_b
+        .transfer_deposit(
+            &scen.usdc,
+            scen.user_b.owner(),
+            &user_b_wallet_usdc,
+            &user_b_deposit,
+            20_000 * ONE_USDC,
+        )
+        .await?;
+
+    let user_b_liq = scen.liquidator.begin(&scen.user_b, true).await.unwrap();
+    let liquidator_usdc = ctx
+        .tokens
+        .create_account(&scen.usdc, &scen.liquidator.wallet.pubkey())
+        .await?;
+
+    ctx.rpc
+        .send_and_confirm_transaction(
+            &user_b_liq
+                .user
+                .tx
+                .liquidator_transfer_deposit(scen.usdc, liquidator_usdc, 1_000 * ONE_USDC)
+                .await?,
+        )
+        .await?;
+
+    assert_eq!(
+        1_000 * ONE_USDC,
+        ctx.tokens.get_balance(&liquidator_usdc).await?
+    );
+
+    let (liquidation, _) = Pubkey::find_program_address(
+        &[
+            b"liquidation",
+            scen.user_b.address().as_ref(),
+            scen.liquidator.wallet.pubkey().as_ref(),
+        ],
+        &jet_margin::ID,
+    );
+    let liquidation_data = ctx.rpc.get_account(&liquidation).await?.unwrap().data;
+    let state = LiquidationState::try_deserialize(&mut &liquidation_data[..])?.state;
+
+    // the transfer counts against the equity the liquidation may lose
+    assert!(state.equity_change < 0);
+    assert!(state.equity_change > state.min_equity_change);
+
+    // and taking another 10'000 would lose more than allowed
+    let result = ctx
+        .rpc
+        .send_and_confirm_transaction(
+            &user_b_liq
+                .user
+                .tx
+                .liquidator_transfer_deposit(scen.usdc, liquidator_usdc, 10_000 * ONE_USDC)
+                .await?,
+        )
+        .await;
+
+    assert_custom_program_error(ErrorCode::LiquidationLostValue, result);
+
+    Ok(())
+}
+
 #[tokio::test(flavor = "multi_thread")]
 #[cfg_attr(not(feature = "localnet"), serial_test::serial)]
 #[ignore = "ignored while there is no constraint on borrowing"]
@@ -0,0 +1,217 @@
+use anyhow::Error;
+
+use jet_airspace::{state::AirspacePermit, AirspaceErrorCode};
+use jet_margin::{TokenAdmin, TokenConfigUpdate, TokenKind, TokenOracle};
+use jet_margin_sdk::{
+    ix_builder::{derive_airspace, AirspaceIxBuilder, MarginConfigIxBuilder},
+    solana::transaction::SendTransactionBuilder,
+    tx_builder::MarginTxBuilder,
+};
+use jet_simulation::{assert_custom_program_error, create_wallet};
+
+use solana_sdk::native_token::LAMPORTS_PER_SOL;
+use solana_sdk::signature::Signer;
+
+use hosted_tests::context::test_context;
+
+/// A margin account can't be created in a restricted airspace with an expired permit,
+/// until the permit is renewed.
+#[tokio::test(flavor = "multi_thread")]
+#[serial_test::serial]
+#[cfg(not(feature = "localnet"))]
+async fn expired_permit_is_rejected() -> Result<(), Error> {
+    let ctx = test_context().await;
+    let payer = ctx.rpc.payer().pubkey();
+    let airspace = AirspaceIxBuilder::new("permit-expiry", payer, payer);
+
+    ctx.rpc
+        .send_and_confirm(vec![airspace.create(true)].into())
+        .await?;
+
+    let wallet = create_wallet(&ctx.rpc, 10 * LAMPORTS_PER_SOL).await?;
+    let owner = wallet.pubkey();
+    let expires_at = ctx.rpc.get_clock().await.unwrap().unix_timestamp + 60;
+
+    ctx.rpc
+        .send_and_confirm(
+            vec![airspace.permit_create(owner, expires_at, AirspacePermit::TIER_RETAIL)].into(),
+        )
+        .await?;
+
+    let mut clock = ctx.rpc.get_clock().await.unwrap();
+    clock.unix_timestamp = expires_at + 1;
+    ctx.rpc.set_clock(clock.clone());
+
+    let user = MarginTxBuilder::new_with_airspace(
+        ctx.rpc.clone(),
+        Some(wallet),
+        owner,
+        0,
+        derive_airspace("permit-expiry"),
+    );
+    let result = ctx
+        .rpc
+        .send_and_confirm_transaction(&user.create_account().await?)
+        .await;
+
+    assert_custom_program_error(AirspaceErrorCode::PermitExpired, result);
+
+    // the airspace authority renews the permit it issued
+    ctx.rpc
+        .send_and_confirm(
+            vec![airspace.permit_update(
+                owner,
+                derive_airspace("permit-expiry"),
+                clock.unix_timestamp + 60,
+                AirspacePermit::TIER_RETAIL,
+            )]
+            .into(),
+        )
+        .await?;
+
+    ctx.rpc
+        .send_and_confirm_transaction(&user.create_account().await?)
+        .await?;
+
+    Ok(())
+}
+
+/// Positions for a token that requires a minimum permit tier can only be registered
+/// by owners holding a permit with at least that tier.
+#[tokio::test(flavor = "multi_thread")]
+#[cfg_attr(not(feature = "localnet"), serial_test::serial)]
+async fn token_requires_permit_tier() -> Result<(), Error> {
+    let ctx = test_context().await;
+    let payer = ctx.rpc.payer().pubkey();
+    let airspace = AirspaceIxBuilder::new("permit-tiers", payer, payer);
+    let airspace_address = derive_airspace("permit-tiers");
+
+    ctx.rpc
+        .send_and_confirm(vec![airspace.create(false)].into())
+        .await?;
+
+    let token = ctx.tokens.create_token(6, None, None).await?;
+    let oracle = ctx.tokens.create_oracle(&token).await?;
+    let config = MarginConfigIxBuilder::new(airspace_address, payer);
+
+    ctx.rpc
+        .send_and_confirm(
+            vec![config.configure_token(
+                token,
+                Some(TokenConfigUpdate {
+                    underlying_mint: token,
+                    admin: TokenAdmin::Margin {
+                        oracle: TokenOracle::Pyth {
+                            price: oracle.price,
+                            product: oracle.product,
+                        },
+                    },
+                    token_kind: TokenKind::Collateral,
+                    value_modifier: 1_00,
+                    max_staleness: 0,
+                    conservative_valuation: false,
+                    category: 0,
+                    min_permit_tier: AirspacePermit::TIER_INSTITUTIONAL,
+                }),
+            )]
+            .into(),
+        )
+        .await?;
+
+    // accounts in an unrestricted airspace don't need a permit
+    let wallet = create_wallet(&ctx.rpc, 10 * LAMPORTS_PER_SOL).await?;
+    let owner = wallet.pubkey();
+    let user = MarginTxBuilder::new_with_airspace(
+        ctx.rpc.clone(),
+        Some(wallet),
+        owner,
+        0,
+        airspace_address,
+    );
+
+    ctx.rpc
+        .send_and_confirm_transaction(&user.create_account().await?)
+        .await?;
+
+    // without a permit
+    let result = ctx
+        .rpc
+        .send_and_confirm_transaction(&user.create_deposit_position(&token).await?)
+        .await;
+
+    assert_custom_program_error(jet_margin::ErrorCode::WrongPermit, result);
+
+    // with a permit below the required tier
+    ctx.rpc
+        .send_and_confirm(
+            vec![airspace.permit_create(owner, 0, AirspacePermit::TIER_RETAIL)].into(),
+        )
+        .await?;
+
+    let result = ctx
+        .rpc
+        .send_and_confirm_transaction(&user.create_deposit_position(&token).await?)
+        .await;
+
+    assert_custom_program_error(AirspaceErrorCode::PermitTierTooLow, result);
+
+    // with the required tier
+    ctx.rpc
+        .send_and_confirm(
+            vec![airspace.permit_update(
+                owner,
+                airspace_address,
+                0,
+                AirspacePermit::TIER_INSTITUTIONAL,
+            )]
+            .into(),
+        )
+        .await?;
+
+    ctx.rpc
+        .send_and_confirm_transaction(&user.create_deposit_position(&token).await?)
+        .await?;
+
+    Ok(())
+}
+
+/// A margin account can't be created in a restricted airspace without a permit.
+#[tokio::test(flavor = "multi_thread")]
+#[cfg_attr(not(feature = "localnet"), serial_test::serial)]
+async fn restricted_airspace_requires_permit() -> Result<(), Error> {
+    let ctx = test_context().await;
+    let payer = ctx.rpc.payer().pubkey();
+    let airspace = AirspaceIxBuilder::new("permit-required", payer, payer);
+
+    ctx.rpc
+        .send_and_confirm(vec![airspace.create(true)].into())
+        .await?;
+
+    let wallet = create_wallet(&ctx.rpc, 10 * LAMPORTS_PER_SOL).await?;
+    let owner = wallet.pubkey();
+    let user = MarginTxBuilder::new_with_airspace(
+        ctx.rpc.clone(),
+        Some(wallet),
+        owner,
+        0,
+        derive_airspace("permit-required"),
+    );
+    let result = ctx
+        .rpc
+        .send_and_confirm_transaction(&user.create_account().await?)
+        .await;
+
+    assert_custom_program_error(anchor_lang::error::ErrorCode::AccountNotInitialized, result);
+
+    ctx.rpc
+        .send_and_confirm(
+            vec![airspace.permit_create(owner, 0, AirspacePermit::TIER_RETAIL)].into(),
+        )
+        .await?;
+
+    ctx.rpc
+        .send_and_confirm_transaction(&user.create_account().await?)
+        .await?;
+
+    Ok(())
+}
@@ -0,0 +1,127 @@
+use anyhow::Error;
+
+use jet_margin_sdk::ix_builder::MarginPoolIxBuilder;
+use jet_margin_sdk::solana::transaction::SendTransactionBuilder;
+use jet_margin_sdk::tokens::TokenPrice;
+use solana_sdk::native_token::LAMPORTS_PER_SOL;
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signature::Signer;
+
+use hosted_tests::{context::test_context, margin::MarginPoolSetupInfo};
+
+use jet_margin::TokenKind;
+use jet_margin_pool::{MarginPoolConfig, PoolFlags, TokenChange};
+use jet_simulation::{assert_custom_program_error, create_wallet};
+
+const ONE_USDC: u64 = 1_000_000;
+const ONE_TSOL: u64 = LAMPORTS_PER_SOL;
+
+const DEFAULT_POOL_CONFIG: MarginPoolConfig = MarginPoolConfig {
+    borrow_rate_0: 10,
+    borrow_rate_1: 20,
+    borrow_rate_2: 30,
+    borrow_rate_3: 40,
+    utilization_rate_1: 10,
+    utilization_rate_2: 20,
+    management_fee_rate: 10,
+    flags: PoolFlags::ALLOW_LENDING.bits(),
+    max_price_deviation: 0,
+    reserved: [0; 6],
+};
+
+/// Pools in the default airspace keep the address of the pools created before airspaces,
+/// so migrated pools stay usable, and current pools can't be migrated again.
+#[tokio::test(flavor = "multi_thread")]
+#[cfg_attr(not(feature = "localnet"), serial_test::serial)]
+async fn default_airspace_pools_keep_legacy_address() -> Result<(), Error> {
+    let ctx = test_context().await;
+
+    let usdc = ctx.tokens.create_token(6, None, None).await?;
+    let usdc_oracle = ctx.tokens.create_oracle(&usdc).await?;
+    let tsol = ctx.tokens.create_token(9, None, None).await?;
+    let tsol_oracle = ctx.tokens.create_oracle(&tsol).await?;
+
+    for (token, oracle) in [(usdc, usdc_oracle), (tsol, tsol_oracle)] {
+        ctx.margin
+            .create_pool(&MarginPoolSetupInfo {
+                token,
+                token_kind: TokenKind::Collateral,
+                collateral_weight: 1_00,
+                max_leverage: 4_00,
+                oracle,
+                config: DEFAULT_POOL_CONFIG,
+            })
+            .await?;
+    }
+
+    // version 0 pools were derived from just the token mint
+    let pool = MarginPoolIxBuilder::new(ctx.margin.airspace(), usdc);
+    let (legacy_address, _) = Pubkey::find_program_address(&[usdc.as_ref()], &jet_margin_pool::ID);
+
+    assert_eq!(legacy_address, pool.address);
+
+    let result = ctx
+        .rpc
+        .send_and_confirm(vec![pool.migrate(ctx.rpc.payer().pubkey())].into())
+        .await;
+
+    assert_custom_program_error(jet_margin_pool::ErrorCode::InvalidPoolVersion, result);
+
+    for (token, price) in [(usdc, 100_000_000), (tsol, 10_000_000_000)] {
+        ctx.tokens
+            .set_price(
+                &token,
+                &TokenPrice {
+                    exponent: -8,
+                    price,
+                    confidence: price / 100,
+                    twap: price,
+                },
+            )
+            .await?;
+    }
+
+    let wallet_a = create_wallet(&ctx.rpc, 10 * LAMPORTS_PER_SOL).await?;
+    let wallet_b = create_wallet(&ctx.rpc, 10 * LAMPORTS_PER_SOL).await?;
+    let user_a = ctx.margin.user(&wallet_a, 0)?;
+    let user_b = ctx.margin.user(&wallet_b, 0)?;
+
+    user_a.create_account().await?;
+    user_b.create_account().await?;
+
+    let user_a_usdc_account = ctx
+        .tokens
+        .create_account_funded(&usdc, &wallet_a.pubkey(), 10_000 * ONE_USDC)
+        .await?;
+    let user_b_tsol_account = ctx
+        .tokens
+        .create_account_funded(&tsol, &wallet_b.pubkey(), 100 * ONE_TSOL)
+        .await?;
+
+    user_a
+        .deposit(
+            &usdc,
+            &user_a_usdc_account,
+            TokenChange::shift(10_000 * ONE_USDC),
+        )
+        .await?;
+    user_b
+        .deposit(
+            &tsol,
+            &user_b_tsol_account,
+            TokenChange::shift(100 * ONE_TSOL),
+        )
+        .await?;
+
+    user_a.refresh_all_pool_positions().await?;
+    user_b.refresh_all_pool_positions().await?;
+
+    user_a
+        .borrow(&tsol, TokenChange::shift(10 * ONE_TSOL))
+        .await?;
+    user_b
+        .borrow(&usdc, TokenChange::shift(1_000 * ONE_USDC))
+        .await?;
+
+    Ok(())
+}
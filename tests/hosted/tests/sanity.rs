@@ -34,7 +34,8 @@ const DEFAULT_POOL_CONFIG: MarginPoolConfig = MarginPoolConfig {
     utilization_rate_2: 20,
     management_fee_rate: 10,
     flags: PoolFlags::ALLOW_LENDING.bits(),
-    reserved: 0,
+    max_price_deviation: 0,
+    reserved: [0; 6],
 };
 
 struct TestEnv {
@@ -332,8 +333,14 @@ async fn sanity_test() -> Result<(), anyhow::Error> {
 
     // Close all User A empty accounts
     let mut loan_to_token: HashMap<Pubkey, Pubkey> = HashMap::new();
-    loan_to_token.insert(MarginPoolIxBuilder::new(env.tsol).loan_note_mint, env.tsol);
-    loan_to_token.insert(MarginPoolIxBuilder::new(env.usdc).loan_note_mint, env.usdc);
+    loan_to_token.insert(
+        MarginPoolIxBuilder::new(ctx.margin.airspace(), env.tsol).loan_note_mint,
+        env.tsol,
+    );
+    loan_to_token.insert(
+        MarginPoolIxBuilder::new(ctx.margin.airspace(), env.usdc).loan_note_mint,
+        env.usdc,
+    );
     user_a.close_empty_positions(&loan_to_token).await?;
 
     // Close User A's margin account
@@ -0,0 +1,245 @@
+#![cfg(feature = "localnet")]
+
+use anyhow::Error;
+
+use jet_margin_sdk::{tokens::TokenPrice, tx_builder::TokenDepositsConfig};
+use serum_dex::matching::Side;
+use solana_sdk::native_token::LAMPORTS_PER_SOL;
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signature::Signer;
+
+use hosted_tests::{
+    context::{test_context, MarginTestContext},
+    serum::SerumMarket,
+};
+
+use jet_simulation::create_wallet;
+
+const ONE_USDC: u64 = 1_000_000;
+const ONE_TSOL: u64 = 1_000_000;
+
+// one lot of TSOL is 0.001 TSOL, one lot of USDC is 0.00001 USDC
+const BASE_LOT_SIZE: u64 = 1_000;
+const QUOTE_LOT_SIZE: u64 = 10;
+
+// 100 USDC per TSOL, in quote lots per base lot
+const PRICE_100: u64 = 10_000;
+
+struct TestEnv {
+    usdc: Pubkey,
+    tsol: Pubkey,
+    market: SerumMarket,
+}
+
+async fn setup_environment(ctx: &MarginTestContext) -> Result<TestEnv, Error> {
+    let usdc = ctx.tokens.create_token(6, None, None).await?;
+    let usdc_oracle = ctx.tokens.create_oracle(&usdc).await?;
+    let tsol = ctx.tokens.create_token(6, None, None).await?;
+    let tsol_oracle = ctx.tokens.create_oracle(&tsol).await?;
+
+    for (token, oracle) in [(usdc, &usdc_oracle), (tsol, &tsol_oracle)] {
+        ctx.margin
+            .configure_token_deposits(
+                &token,
+                Some(&TokenDepositsConfig {
+                    oracle: jet_margin::TokenOracle::Pyth {
+                        price: oracle.price,
+                        product: oracle.product,
+                    },
+                    collateral_weight: 90,
+                }),
+            )
+            .await?;
+    }
+
+    ctx.tokens
+        .set_price(
+            &usdc,
+            &TokenPrice {
+                exponent: -8,
+                price: 100_000_000,
+                confidence: 1_000_000,
+                twap: 100_000_000,
+            },
+        )
+        .await?;
+    ctx.tokens
+        .set_price(
+            &tsol,
+            &TokenPrice {
+                exponent: -8,
+                price: 10_000_000_000,
+                confidence: 100_000_000,
+                twap: 10_000_000_000,
+            },
+        )
+        .await?;
+
+    let market = SerumMarket::create(
+        &ctx.rpc,
+        ctx.margin.airspace(),
+        (tsol, tsol_oracle.price),
+        (usdc, usdc_oracle.price),
+        BASE_LOT_SIZE,
+        QUOTE_LOT_SIZE,
+    )
+    .await?;
+
+    ctx.margin
+        .register_adapter_if_unregistered(&jet_margin_serum::ID)
+        .await?;
+    ctx.margin.register_serum_market(&market.ix, 90, 90).await?;
+
+    Ok(TestEnv { usdc, tsol, market })
+}
+
+/// Trade on a serum market with funds held in margin accounts
+#[tokio::test(flavor = "multi_thread")]
+async fn serum_limit_orders() -> Result<(), anyhow::Error> {
+    let ctx = test_context().await;
+    let env = setup_environment(ctx).await?;
+    let market = &env.market;
+
+    let info = market.market_info().await?;
+    assert_eq!(info.market, market.ix.market.market);
+    assert_eq!(info.base_note_mint, market.ix.base_note_mint);
+    assert_eq!(info.quote_note_mint, market.ix.quote_note_mint);
+
+    let wallet_a = create_wallet(&ctx.rpc, 10 * LAMPORTS_PER_SOL).await?;
+    let wallet_b = create_wallet(&ctx.rpc, 10 * LAMPORTS_PER_SOL).await?;
+    let user_a = ctx.margin.user(&wallet_a, 0)?;
+    let user_b = ctx.margin.user(&wallet_b, 0)?;
+    user_a.create_account().await?;
+    user_b.create_account().await?;
+
+    // user a buys TSOL with USDC, user b sells TSOL for USDC
+    let user_a_usdc = user_a.create_deposit_position(&env.usdc).await?;
+    let user_a_tsol = user_a.create_deposit_position(&env.tsol).await?;
+    let user_b_usdc = user_b.create_deposit_position(&env.usdc).await?;
+    let user_b_tsol = user_b.create_deposit_position(&env.tsol).await?;
+
+    let wallet_a_usdc = ctx
+        .tokens
+        .create_account_funded(&env.usdc, &wallet_a.pubkey(), 1_000 * ONE_USDC)
+        .await?;
+    let wallet_b_tsol = ctx
+        .tokens
+        .create_account_funded(&env.tsol, &wallet_b.pubkey(), 10 * ONE_TSOL)
+        .await?;
+
+    user_a
+        .transfer_deposit(
+            &env.usdc,
+            &wallet_a.pubkey(),
+            &wallet_a_usdc,
+            &user_a_usdc,
+            1_000 * ONE_USDC,
+        )
+        .await?;
+    user_b
+        .transfer_deposit(
+            &env.tsol,
+            &wallet_b.pubkey(),
+            &wallet_b_tsol,
+            &user_b_tsol,
+            10 * ONE_TSOL,
+        )
+        .await?;
+
+    market.init_open_orders(&user_a).await?;
+    market.init_open_orders(&user_b).await?;
+
+    let user_a_quote_notes = market.ix.quote_notes(user_a.address());
+    let user_a_base_notes = market.ix.base_notes(user_a.address());
+    let user_b_base_notes = market.ix.base_notes(user_b.address());
+    assert_eq!(0, ctx.tokens.get_balance(&user_a_quote_notes).await?);
+
+    // the funds locked in a resting bid are still counted as collateral through the notes
+    market
+        .place_order(
+            &user_a,
+            user_a_usdc,
+            Side::Bid,
+            PRICE_100,
+            1_000,
+            101 * ONE_USDC,
+            1,
+        )
+        .await?;
+
+    let locked = 1_000 * ONE_USDC - ctx.tokens.get_balance(&user_a_usdc).await?;
+    assert!(locked >= 100 * ONE_USDC);
+    assert_eq!(locked, ctx.tokens.get_balance(&user_a_quote_notes).await?);
+
+    // a second bid that is cancelled returns the notes to the first bid's amount
+    market
+        .place_order(
+            &user_a,
+            user_a_usdc,
+            Side::Bid,
+            PRICE_100 / 2,
+            1_000,
+            51 * ONE_USDC,
+            2,
+        )
+        .await?;
+    assert!(locked < ctx.tokens.get_balance(&user_a_quote_notes).await?);
+
+    market.cancel_order_by_client_id(&user_a, 2).await?;
+    market
+        .settle_funds(&user_a, user_a_tsol, user_a_usdc)
+        .await?;
+
+    let locked = 1_000 * ONE_USDC - ctx.tokens.get_balance(&user_a_usdc).await?;
+    assert!(locked >= 100 * ONE_USDC);
+    assert_eq!(locked, ctx.tokens.get_balance(&user_a_quote_notes).await?);
+
+    // cross the first bid
+    market
+        .place_order(
+            &user_b,
+            user_b_tsol,
+            Side::Ask,
+            PRICE_100,
+            1_000,
+            u64::MAX,
+            1,
+        )
+        .await?;
+    assert_eq!(9 * ONE_TSOL, ctx.tokens.get_balance(&user_b_tsol).await?);
+
+    market
+        .consume_events(&[
+            market.ix.open_orders(user_a.address()),
+            market.ix.open_orders(user_b.address()),
+        ])
+        .await?;
+    market.refresh_open_orders(&user_a).await?;
+    market.refresh_open_orders(&user_b).await?;
+    assert_eq!(ONE_TSOL, ctx.tokens.get_balance(&user_a_base_notes).await?);
+
+    market
+        .settle_funds(&user_a, user_a_tsol, user_a_usdc)
+        .await?;
+    market
+        .settle_funds(&user_b, user_b_tsol, user_b_usdc)
+        .await?;
+
+    assert_eq!(ONE_TSOL, ctx.tokens.get_balance(&user_a_tsol).await?);
+    assert_eq!(0, ctx.tokens.get_balance(&user_a_base_notes).await?);
+    assert_eq!(0, ctx.tokens.get_balance(&user_a_quote_notes).await?);
+    assert_eq!(0, ctx.tokens.get_balance(&user_b_base_notes).await?);
+    assert!(ctx.tokens.get_balance(&user_b_usdc).await? > 99 * ONE_USDC);
+
+    // with nothing left in the book, the open orders can be closed
+    market.close_open_orders(&user_a).await?;
+    market.close_open_orders(&user_b).await?;
+
+    assert!(ctx
+        .rpc
+        .get_account(&market.ix.open_orders(user_a.address()))
+        .await?
+        .is_none());
+
+    Ok(())
+}
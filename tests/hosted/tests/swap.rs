@@ -16,10 +16,12 @@ use hosted_tests::{
 
 use jet_margin::TokenKind;
 use jet_margin_pool::{MarginPoolConfig, PoolFlags, TokenChange};
+use jet_margin_swap::SwapEndpoint;
 use jet_simulation::{assert_custom_program_error, create_wallet};
 
 const ONE_USDC: u64 = 1_000_000;
 const ONE_TSOL: u64 = LAMPORTS_PER_SOL;
+const ONE_TBTC: u64 = 1_000_000;
 
 const DEFAULT_POOL_CONFIG: MarginPoolConfig = MarginPoolConfig {
     borrow_rate_0: 10,
@@ -30,7 +32,8 @@ const DEFAULT_POOL_CONFIG: MarginPoolConfig = MarginPoolConfig {
     utilization_rate_2: 20,
     management_fee_rate: 10,
     flags: PoolFlags::ALLOW_LENDING.bits(),
-    reserved: 0,
+    max_price_deviation: 0,
+    reserved: [0; 6],
 };
 
 /// Test token swaps for the official SPL token swap
@@ -331,3 +334,392 @@ async fn swap_test_impl(swap_program_id: Pubkey) -> Result<(), anyhow::Error> {
 
     Ok(())
 }
+
+/// Test a swap routed through two pools, with no pool for the direct pair
+#[tokio::test(flavor = "multi_thread")]
+#[cfg_attr(not(feature = "localnet"), serial_test::serial)]
+async fn spl_swap_v2_route() -> Result<(), anyhow::Error> {
+    let swap_program_id = spl_token_swap_v2::id();
+    let ctx = test_context().await;
+    let env = setup_environment(ctx).await?;
+
+    // A third token, which can only be swapped for TSOL
+    let tbtc = ctx.tokens.create_token(6, None, None).await?;
+    let tbtc_oracle = ctx.tokens.create_oracle(&tbtc).await?;
+    let tbtc_pool = MarginPoolSetupInfo {
+        token: tbtc,
+        token_kind: TokenKind::Collateral,
+        collateral_weight: 95,
+        max_leverage: 4_00,
+        config: DEFAULT_POOL_CONFIG,
+        oracle: tbtc_oracle,
+    };
+    ctx.margin
+        .configure_token_deposits(
+            &tbtc,
+            Some(&TokenDepositsConfig {
+                oracle: jet_margin::TokenOracle::Pyth {
+                    price: tbtc_oracle.price,
+                    product: tbtc_oracle.product,
+                },
+                collateral_weight: 95,
+            }),
+        )
+        .await?;
+    ctx.margin.create_pool(&tbtc_pool).await?;
+
+    for (mint, price) in [
+        (env.usdc, 100_000_000),
+        (env.tsol, 10_000_000_000),
+        (tbtc, 2_000_000_000_000),
+    ] {
+        ctx.tokens
+            .set_price(
+                &mint,
+                &TokenPrice {
+                    exponent: -8,
+                    price,
+                    confidence: price / 100,
+                    twap: price,
+                },
+            )
+            .await?;
+    }
+
+    // 1 TBTC = 200 TSOL = 20,000 USDC
+    SplSwapPool::configure(
+        &ctx.rpc,
+        &swap_program_id,
+        &env.usdc,
+        &env.tsol,
+        1_000_000 * ONE_USDC,
+        10_000 * ONE_TSOL,
+    )
+    .await?;
+    SplSwapPool::configure(
+        &ctx.rpc,
+        &swap_program_id,
+        &tbtc,
+        &env.tsol,
+        50 * ONE_TBTC,
+        10_000 * ONE_TSOL,
+    )
+    .await?;
+
+    let supported_mints = HashSet::from([env.usdc, env.tsol, tbtc]);
+    let swap_pools = SplSwapPool::get_pools(&ctx.rpc, &supported_mints, swap_program_id).await?;
+
+    let route = SplSwapPool::find_route(&ctx.rpc, &swap_pools, tbtc, env.usdc, ONE_TBTC / 10, 3)
+        .await?
+        .unwrap();
+    assert_eq!(2, route.hops.len());
+    assert_eq!(env.tsol, route.hops[0].destination_mint);
+    assert!(route.expected_amount_out > 1_900 * ONE_USDC);
+
+    // There is no pool for the direct pair, so there is no single hop route
+    assert!(
+        SplSwapPool::find_route(&ctx.rpc, &swap_pools, tbtc, env.usdc, ONE_TBTC / 10, 1)
+            .await?
+            .is_none()
+    );
+
+    let wallet = create_wallet(&ctx.rpc, 10 * LAMPORTS_PER_SOL).await?;
+    let user = ctx.margin.user(&wallet, 0)?;
+    user.create_account().await?;
+
+    // transit accounts for each token of the route
+    let usdc_transit = user.create_deposit_position(&env.usdc).await?;
+    let tsol_transit = user.create_deposit_position(&env.tsol).await?;
+    user.create_deposit_position(&tbtc).await?;
+
+    let tbtc_account = ctx
+        .tokens
+        .create_account_funded(&tbtc, &wallet.pubkey(), ONE_TBTC)
+        .await?;
+    user.deposit(&tbtc, &tbtc_account, TokenChange::shift(ONE_TBTC))
+        .await?;
+    user.refresh_all_pool_positions().await?;
+
+    // Asking for more than the route can return fails without moving any funds
+    let result = user
+        .route_swap(
+            &route,
+            TokenChange::shift(ONE_TBTC / 10),
+            route.expected_amount_out + 1,
+        )
+        .await;
+    assert_custom_program_error(jet_margin_swap::ErrorCode::SwapOutputBelowMinimum, result);
+
+    user.route_swap(
+        &route,
+        TokenChange::shift(ONE_TBTC / 10),
+        route.expected_amount_out,
+    )
+    .await?;
+
+    // Only the final output is deposited, the intermediate transit account is untouched
+    assert_eq!(0, ctx.tokens.get_balance(&tsol_transit).await?);
+    assert_eq!(0, ctx.tokens.get_balance(&usdc_transit).await?);
+
+    let usdc_pool = ctx.margin.get_pool(&env.usdc).await?;
+    assert!(usdc_pool.deposit_tokens >= route.expected_amount_out);
+
+    Ok(())
+}
+
+/// Test swaps between deposit positions and margin pool deposits
+#[tokio::test(flavor = "multi_thread")]
+#[cfg_attr(not(feature = "localnet"), serial_test::serial)]
+async fn spl_swap_v2_deposit_positions() -> Result<(), anyhow::Error> {
+    let swap_program_id = spl_token_swap_v2::id();
+    let ctx = test_context().await;
+    let env = setup_environment(ctx).await?;
+
+    let wallet = create_wallet(&ctx.rpc, 10 * LAMPORTS_PER_SOL).await?;
+    let user = ctx.margin.user(&wallet, 0)?;
+    user.create_account().await?;
+
+    let swap_pool = SplSwapPool::configure(
+        &ctx.rpc,
+        &swap_program_id,
+        &env.usdc,
+        &env.tsol,
+        1_000_000 * ONE_USDC,
+        10_000 * ONE_TSOL,
+    )
+    .await?;
+
+    ctx.tokens
+        .set_price(
+            &env.usdc,
+            &TokenPrice {
+                exponent: -8,
+                price: 100_000_000,
+                confidence: 1_000_000,
+                twap: 100_000_000,
+            },
+        )
+        .await?;
+    ctx.tokens
+        .set_price(
+            &env.tsol,
+            &TokenPrice {
+                exponent: -8,
+                price: 10_000_000_000,
+                confidence: 100_000_000,
+                twap: 10_000_000_000,
+            },
+        )
+        .await?;
+
+    // The user holds USDC in a deposit position, rather than in the margin pool
+    let usdc_deposit = user.create_deposit_position(&env.usdc).await?;
+    let tsol_deposit = user.create_deposit_position(&env.tsol).await?;
+    let usdc_account = ctx
+        .tokens
+        .create_account_funded(&env.usdc, &wallet.pubkey(), 1_000 * ONE_USDC)
+        .await?;
+    user.transfer_deposit(
+        &env.usdc,
+        &wallet.pubkey(),
+        &usdc_account,
+        &usdc_deposit,
+        1_000 * ONE_USDC,
+    )
+    .await?;
+
+    // deposit position to deposit position
+    user.swap_positions(
+        &swap_program_id,
+        &env.usdc,
+        &env.tsol,
+        SwapEndpoint::Deposit,
+        SwapEndpoint::Deposit,
+        &swap_pool,
+        TokenChange::shift(100 * ONE_USDC),
+        ONE_TSOL / 10 * 9,
+    )
+    .await?;
+
+    assert_eq!(900 * ONE_USDC, ctx.tokens.get_balance(&usdc_deposit).await?);
+    let tsol_balance = ctx.tokens.get_balance(&tsol_deposit).await?;
+    assert!(tsol_balance >= ONE_TSOL / 10 * 9);
+
+    // the margin account records the new balances of both deposit positions
+    let positions = user.positions().await?;
+    let usdc_position = positions.iter().find(|p| p.token == env.usdc).unwrap();
+    let tsol_position = positions.iter().find(|p| p.token == env.tsol).unwrap();
+    assert_eq!(900 * ONE_USDC, usdc_position.balance);
+    assert_eq!(tsol_balance, tsol_position.balance);
+
+    // deposit position to a margin pool, leaving 800 USDC in the deposit
+    user.swap_positions(
+        &swap_program_id,
+        &env.usdc,
+        &env.tsol,
+        SwapEndpoint::Deposit,
+        SwapEndpoint::MarginPool,
+        &swap_pool,
+        TokenChange::set(800 * ONE_USDC),
+        ONE_TSOL / 10 * 9,
+    )
+    .await?;
+
+    assert_eq!(800 * ONE_USDC, ctx.tokens.get_balance(&usdc_deposit).await?);
+    assert_eq!(tsol_balance, ctx.tokens.get_balance(&tsol_deposit).await?);
+    let tsol_pool = ctx.margin.get_pool(&env.tsol).await?;
+    assert!(tsol_pool.deposit_tokens >= ONE_TSOL / 10 * 9);
+
+    // margin pool back to a deposit position
+    user.swap_positions(
+        &swap_program_id,
+        &env.tsol,
+        &env.usdc,
+        SwapEndpoint::MarginPool,
+        SwapEndpoint::Deposit,
+        &swap_pool,
+        TokenChange::set(0),
+        90 * ONE_USDC,
+    )
+    .await?;
+
+    assert!(ctx.tokens.get_balance(&usdc_deposit).await? >= 890 * ONE_USDC);
+
+    // A deposit swap can't take more than the deposit holds
+    let result = user
+        .swap_positions(
+            &swap_program_id,
+            &env.usdc,
+            &env.tsol,
+            SwapEndpoint::Deposit,
+            SwapEndpoint::Deposit,
+            &swap_pool,
+            TokenChange::set(2_000 * ONE_USDC),
+            ONE_TSOL,
+        )
+        .await;
+    assert_custom_program_error(jet_margin_swap::ErrorCode::InvalidSetTo, result);
+
+    Ok(())
+}
+
+/// Test opening and closing a leveraged position with a single swap instruction each
+#[tokio::test(flavor = "multi_thread")]
+#[cfg_attr(not(feature = "localnet"), serial_test::serial)]
+async fn spl_swap_v2_leveraged_position() -> Result<(), anyhow::Error> {
+    let swap_program_id = spl_token_swap_v2::id();
+    let ctx = test_context().await;
+    let env = setup_environment(ctx).await?;
+
+    let wallet_a = create_wallet(&ctx.rpc, 10 * LAMPORTS_PER_SOL).await?;
+    let wallet_b = create_wallet(&ctx.rpc, 10 * LAMPORTS_PER_SOL).await?;
+    let user_a = ctx.margin.user(&wallet_a, 0)?;
+    let user_b = ctx.margin.user(&wallet_b, 0)?;
+    user_a.create_account().await?;
+    user_b.create_account().await?;
+
+    let swap_pool = SplSwapPool::configure(
+        &ctx.rpc,
+        &swap_program_id,
+        &env.usdc,
+        &env.tsol,
+        1_000_000 * ONE_USDC,
+        10_000 * ONE_TSOL,
+    )
+    .await?;
+
+    ctx.tokens
+        .set_price(
+            &env.usdc,
+            &TokenPrice {
+                exponent: -8,
+                price: 100_000_000,
+                confidence: 1_000_000,
+                twap: 100_000_000,
+            },
+        )
+        .await?;
+    ctx.tokens
+        .set_price(
+            &env.tsol,
+            &TokenPrice {
+                exponent: -8,
+                price: 10_000_000_000,
+                confidence: 100_000_000,
+                twap: 10_000_000_000,
+            },
+        )
+        .await?;
+
+    let usdc_transit = user_a.create_deposit_position(&env.usdc).await?;
+    let tsol_transit = user_a.create_deposit_position(&env.tsol).await?;
+
+    // user a has USDC collateral, user b lends USDC
+    let user_a_usdc_account = ctx
+        .tokens
+        .create_account_funded(&env.usdc, &wallet_a.pubkey(), 1_000 * ONE_USDC)
+        .await?;
+    let user_b_usdc_account = ctx
+        .tokens
+        .create_account_funded(&env.usdc, &wallet_b.pubkey(), 10_000 * ONE_USDC)
+        .await?;
+    user_a
+        .deposit(
+            &env.usdc,
+            &user_a_usdc_account,
+            TokenChange::shift(1_000 * ONE_USDC),
+        )
+        .await?;
+    user_b
+        .deposit(
+            &env.usdc,
+            &user_b_usdc_account,
+            TokenChange::shift(10_000 * ONE_USDC),
+        )
+        .await?;
+    user_a.refresh_all_pool_positions().await?;
+
+    // borrow 1,000 USDC and swap it into TSOL, for a 2x long
+    user_a
+        .open_leveraged_position(
+            &swap_program_id,
+            &env.usdc,
+            &env.tsol,
+            &usdc_transit,
+            &tsol_transit,
+            &swap_pool,
+            1_000 * ONE_USDC,
+            ONE_TSOL * 9,
+        )
+        .await?;
+
+    let usdc_pool = ctx.margin.get_pool(&env.usdc).await?;
+    let tsol_pool = ctx.margin.get_pool(&env.tsol).await?;
+    assert_eq!(10_000 * ONE_USDC, usdc_pool.deposit_tokens);
+    assert!(usdc_pool.loan_notes > 0);
+    assert!(tsol_pool.deposit_tokens >= ONE_TSOL * 9);
+    assert_eq!(0, ctx.tokens.get_balance(&usdc_transit).await?);
+    assert_eq!(0, ctx.tokens.get_balance(&tsol_transit).await?);
+
+    // swap all of the TSOL back, which covers most of the loan
+    user_a
+        .close_leveraged_position(
+            &swap_program_id,
+            &env.tsol,
+            &env.usdc,
+            &tsol_transit,
+            &usdc_transit,
+            &swap_pool,
+            TokenChange::set(0),
+            990 * ONE_USDC,
+        )
+        .await?;
+
+    let usdc_pool = ctx.margin.get_pool(&env.usdc).await?;
+    let tsol_pool = ctx.margin.get_pool(&env.tsol).await?;
+    assert!(usdc_pool.loan_notes < 10 * ONE_USDC);
+    assert!(usdc_pool.deposit_tokens >= 10_990 * ONE_USDC);
+    assert_eq!(0, tsol_pool.deposit_tokens);
+
+    Ok(())
+}
@@ -0,0 +1,187 @@
+#![cfg(not(feature = "localnet"))]
+
+use anyhow::Error;
+
+use jet_margin::{AccountHealth, TokenKind};
+use jet_margin_pool::{
+    ExchangeRate, MarginPoolConfig, PoolFlags, PoolRates, TokenChange, EXCHANGE_RATE_EXPONENT,
+};
+use jet_margin_sdk::{
+    ix_builder::MarginPoolIxBuilder, tokens::TokenPrice, tx_builder::TokenDepositsConfig,
+};
+use jet_simulation::create_wallet;
+
+use solana_sdk::native_token::LAMPORTS_PER_SOL;
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signature::Signer;
+
+use hosted_tests::{
+    context::{test_context, view, MarginTestContext},
+    margin::MarginPoolSetupInfo,
+};
+
+const ONE_USDC: u64 = 1_000_000;
+const ONE_TSOL: u64 = LAMPORTS_PER_SOL;
+
+/// An exchange rate of one token per note
+const ONE_PER_NOTE: u64 = 10u64.pow(-EXCHANGE_RATE_EXPONENT as u32);
+
+const DEFAULT_POOL_CONFIG: MarginPoolConfig = MarginPoolConfig {
+    borrow_rate_0: 10,
+    borrow_rate_1: 20,
+    borrow_rate_2: 30,
+    borrow_rate_3: 40,
+    utilization_rate_1: 10,
+    utilization_rate_2: 20,
+    management_fee_rate: 10,
+    flags: PoolFlags::ALLOW_LENDING.bits(),
+    max_price_deviation: 0,
+    reserved: [0; 6],
+};
+
+struct TestEnv {
+    usdc: Pubkey,
+    tsol: Pubkey,
+}
+
+async fn setup_environment(ctx: &MarginTestContext) -> Result<TestEnv, Error> {
+    let usdc = ctx.tokens.create_token(6, None, None).await?;
+    let usdc_oracle = ctx.tokens.create_oracle(&usdc).await?;
+    let tsol = ctx.tokens.create_token(9, None, None).await?;
+    let tsol_oracle = ctx.tokens.create_oracle(&tsol).await?;
+
+    let pools = [
+        MarginPoolSetupInfo {
+            token: usdc,
+            token_kind: TokenKind::Collateral,
+            collateral_weight: 1_00,
+            max_leverage: 10_00,
+            config: DEFAULT_POOL_CONFIG,
+            oracle: usdc_oracle,
+        },
+        MarginPoolSetupInfo {
+            token: tsol,
+            token_kind: TokenKind::Collateral,
+            collateral_weight: 95,
+            max_leverage: 4_00,
+            config: DEFAULT_POOL_CONFIG,
+            oracle: tsol_oracle,
+        },
+    ];
+
+    for pool_info in pools {
+        ctx.margin
+            .configure_token_deposits(
+                &pool_info.token,
+                Some(&TokenDepositsConfig {
+                    oracle: jet_margin::TokenOracle::Pyth {
+                        price: pool_info.oracle.price,
+                        product: pool_info.oracle.product,
+                    },
+                    collateral_weight: pool_info.collateral_weight,
+                }),
+            )
+            .await?;
+        ctx.margin.create_pool(&pool_info).await?;
+    }
+
+    for (token, price) in [(usdc, 100_000_000), (tsol, 10_000_000_000)] {
+        ctx.tokens
+            .set_price(
+                &token,
+                &TokenPrice {
+                    exponent: -8,
+                    price,
+                    confidence: price / 100,
+                    twap: price,
+                },
+            )
+            .await?;
+    }
+
+    Ok(TestEnv { usdc, tsol })
+}
+
+/// The margin and pool view instructions return the state of an account with a loan,
+/// without modifying any accounts
+#[tokio::test(flavor = "multi_thread")]
+#[serial_test::serial]
+async fn margin_and_pool_views_return_data() -> Result<(), Error> {
+    let ctx = test_context().await;
+    let env = setup_environment(ctx).await?;
+
+    let wallet_a = create_wallet(&ctx.rpc, 10 * LAMPORTS_PER_SOL).await?;
+    let wallet_b = create_wallet(&ctx.rpc, 10 * LAMPORTS_PER_SOL).await?;
+    let user_a = ctx.margin.user(&wallet_a, 0)?;
+    let user_b = ctx.margin.user(&wallet_b, 0)?;
+    user_a.create_account().await?;
+    user_b.create_account().await?;
+
+    let user_a_usdc_account = ctx
+        .tokens
+        .create_account_funded(&env.usdc, &wallet_a.pubkey(), 10_000 * ONE_USDC)
+        .await?;
+    let user_b_tsol_account = ctx
+        .tokens
+        .create_account_funded(&env.tsol, &wallet_b.pubkey(), 100 * ONE_TSOL)
+        .await?;
+
+    user_a
+        .deposit(
+            &env.usdc,
+            &user_a_usdc_account,
+            TokenChange::shift(10_000 * ONE_USDC),
+        )
+        .await?;
+    user_b
+        .deposit(
+            &env.tsol,
+            &user_b_tsol_account,
+            TokenChange::shift(100 * ONE_TSOL),
+        )
+        .await?;
+    user_b.refresh_all_pool_positions().await?;
+    user_b
+        .borrow(&env.usdc, TokenChange::shift(1_000 * ONE_USDC))
+        .await?;
+
+    let health: AccountHealth = view(ctx, user_b.tx.ix.account_health()).await?;
+    assert!(health.valuation_summary.required_collateral > 0);
+    assert!(health.valuation_summary.liabilities > 0);
+    assert!(health.health_ratio_bps > 10_000);
+
+    // nothing is borrowed from the tsol pool, so its notes are still worth one token
+    let tsol_pool = MarginPoolIxBuilder::new(ctx.margin.airspace(), env.tsol);
+    let rates: PoolRates = view(ctx, tsol_pool.pool_rates()).await?;
+    assert_eq!(rates.deposit_note_exchange_rate, ONE_PER_NOTE);
+    assert_eq!(rates.utilization_rate, 0);
+
+    let usdc_pool = MarginPoolIxBuilder::new(ctx.margin.airspace(), env.usdc);
+    let rates: PoolRates = view(ctx, usdc_pool.pool_rates()).await?;
+    assert!(rates.utilization_rate > 0);
+    assert!(rates.borrow_rate > rates.deposit_rate);
+
+    let exchange_rate: ExchangeRate = view(ctx, usdc_pool.deposit_note_exchange_rate()).await?;
+    assert_eq!(
+        exchange_rate.tokens_per_note,
+        rates.deposit_note_exchange_rate
+    );
+    assert_eq!(exchange_rate.timestamp, rates.timestamp);
+    assert!(exchange_rate.tokens_per_note >= ONE_PER_NOTE);
+
+    // interest is included up to the current time, but not stored in the pool
+    let accrued_until = ctx.margin.get_pool(&env.usdc).await?.accrued_until;
+    let mut clock = ctx.rpc.get_clock().await.unwrap();
+    clock.unix_timestamp = accrued_until + 365 * 24 * 60 * 60;
+    ctx.rpc.set_clock(clock);
+
+    let later: ExchangeRate = view(ctx, usdc_pool.deposit_note_exchange_rate()).await?;
+    assert!(later.tokens_per_note > exchange_rate.tokens_per_note);
+    assert_eq!(later.timestamp, accrued_until + 365 * 24 * 60 * 60);
+    assert_eq!(
+        ctx.margin.get_pool(&env.usdc).await?.accrued_until,
+        accrued_until
+    );
+
+    Ok(())
+}
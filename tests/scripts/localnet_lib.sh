@@ -12,9 +12,11 @@ BOND_PID=JBond79m9K6HqYwngCjiJHb311GTXggo46kGcT2GijUc
 ASM_PID=JPASMkxARMmbeahk37H8PAAP1UzPNC4wGhvwLnBsfHi
 JTS_PID=JPTSApMSqCHBww7vDhpaSmzipTV3qPg6vxub4qneKoy
 MGNSWAP_PID=JPMAa5dnWLFRvUsumawFcGhnwikqZziLLfqn9SLNXPN
+MGNSERUM_PID=JPSRM5EALmzGY2o37MZNv4amyXQf1bqs9a1YorvDvKD
 SPLSWAP_PID=SwaPpA9LAaLfeLi3a68M4DjnLqgtticKg6CnyNwgAC8
 ORCAv1_PID=DjVE6JNiYqPL2QXyCUUh8rNjHrbz9hXHNYt99MQ59qw1
 ORCAv2_PID=9W959DqEETiGZocYWCQPaJ6sBmUzgfxXfqGeTEdp3aQP
+SERUM_PID=9xQeWvG816bUx9EPjHmaT23yvVM2ZWbrrpZb9PusVFin
 
 CTRL_SO=target/deploy/jet_control.so
 MRGN_SO=target/deploy/jet_margin.so
@@ -24,9 +26,11 @@ BOND_SO=target/deploy/jet_bonds.so
 ASM_SO=target/deploy/jet_airspace.so
 JTS_SO=target/deploy/jet_test_service.so
 MGNSWAP_SO=target/deploy/jet_margin_swap.so
+MGNSERUM_SO=target/deploy/jet_margin_serum.so
 SPLSWAP_SO=$SPL_V20_FROM_CRATES
 ORCAv1_SO=$ORCA_V1_MAINNET
 ORCAv2_SO=$ORCA_V2_MAINNET
+SERUM_SO=deps/serum_dex_v3.so
 
 PROGRAM_FEATURES='testing'
 TEST_FEATURES="${BATCH:-batch_all},localnet"
@@ -65,9 +69,11 @@ start-validator() {
         --bpf-program $BOND_PID $BOND_SO \
         --bpf-program $ASM_PID $ASM_SO \
         --bpf-program $MGNSWAP_PID $MGNSWAP_SO \
+        --bpf-program $MGNSERUM_PID $MGNSERUM_SO \
         --bpf-program $SPLSWAP_PID $SPLSWAP_SO \
         --bpf-program $ORCAv1_PID $ORCAv1_SO \
         --bpf-program $ORCAv2_PID $ORCAv2_SO \
+        --bpf-program $SERUM_PID $SERUM_SO \
         --quiet \
         $@
 }
@@ -50,13 +50,17 @@ async fn process_apply_file(client: &Client, config_file: PathBuf) -> Result<Pla
 async fn process_apply_token_def(client: &Client, token_def: TokenDefinition) -> Result<Plan> {
     let mut plan = Plan::new();
 
-    plan.extend(super::margin_pool::process_create_pool(client, token_def.config.mint).await?);
+    plan.extend(
+        super::margin_pool::process_create_pool(client, &token_def.airspace, token_def.config.mint)
+            .await?,
+    );
     plan.extend(
         super::margin_pool::process_configure_pool(
             client,
             ConfigurePoolCliOptions {
                 token_config: token_def.config,
                 margin_pool: token_def.margin_pool,
+                airspace: token_def.airspace,
             },
         )
         .await?,
@@ -1,6 +1,11 @@
-use anyhow::Result;
+use anyhow::{bail, Result};
 use clap::Parser;
-use jet_margin_sdk::bonds::BondsIxBuilder;
+use comfy_table::{presets::UTF8_FULL, Table};
+use jet_margin_sdk::bonds::{
+    interest_pricing::fp32_to_f64,
+    orderbook::{ExpectedFill, OrderSize, OrderbookSnapshot, PriceLevel, Side},
+    BondManager, BondsIxBuilder, CrankPolicy,
+};
 use serde::{Deserialize, Serialize};
 use solana_clap_utils::keypair::signer_from_path;
 use solana_sdk::{pubkey::Pubkey, signer::Signer};
@@ -42,6 +47,33 @@ pub struct BondMarketParameters {
     pub asks: String,
 }
 
+#[derive(Debug, Clone, Parser, Serialize, Deserialize)]
+pub struct ConfigureBondMarketParameters {
+    /// The address of the bond manager for the market
+    #[clap(long)]
+    pub manager: Pubkey,
+
+    /// Pause or resume the redemption of staked tickets
+    #[clap(long)]
+    pub tickets_paused: Option<bool>,
+
+    /// The oracle for the underlying token
+    #[clap(long)]
+    pub underlying_oracle: Option<Pubkey>,
+
+    /// The oracle for the bond tickets
+    #[clap(long)]
+    pub ticket_oracle: Option<Pubkey>,
+
+    /// The tenor in seconds, for orders filled after the change
+    #[clap(long)]
+    pub duration: Option<i64>,
+
+    /// Allow any signer to consume events from the orderbook
+    #[clap(long)]
+    pub permissionless_crank: Option<bool>,
+}
+
 fn map_seed(seed: Vec<u8>) -> [u8; 32] {
     let mut buf = [0u8; 32];
     let mut iter = seed.into_iter();
@@ -143,3 +175,174 @@ pub async fn process_create_bond_market<'a>(
         .instructions(signers, steps, instructions)
         .build())
 }
+
+pub async fn process_configure_bond_market(
+    client: &Client,
+    params: ConfigureBondMarketParameters,
+) -> Result<Plan> {
+    if !client.account_exists(&params.manager).await? {
+        bail!("bond manager {} does not exist", params.manager);
+    }
+
+    let bond_manager = client
+        .read_anchor_account::<BondManager>(&params.manager)
+        .await?;
+    let bonds = BondsIxBuilder::from(bond_manager).with_authority(&resolve_payer(client)?);
+
+    let mut steps = vec![];
+    let mut instructions = vec![];
+
+    if let Some(paused) = params.tickets_paused {
+        if paused != bond_manager.tickets_paused {
+            steps.push(format!(
+                "set-tickets-paused: {} -> {paused}",
+                bond_manager.tickets_paused
+            ));
+            instructions.push(bonds.set_tickets_paused(paused)?);
+        }
+    }
+    if let Some(oracle) = params.underlying_oracle {
+        if oracle != bond_manager.underlying_oracle {
+            steps.push(format!(
+                "set-underlying-oracle: {} -> {oracle}",
+                bond_manager.underlying_oracle
+            ));
+            instructions.push(bonds.set_underlying_oracle(oracle)?);
+        }
+    }
+    if let Some(oracle) = params.ticket_oracle {
+        if oracle != bond_manager.ticket_oracle {
+            steps.push(format!(
+                "set-ticket-oracle: {} -> {oracle}",
+                bond_manager.ticket_oracle
+            ));
+            instructions.push(bonds.set_ticket_oracle(oracle)?);
+        }
+    }
+    if let Some(duration) = params.duration {
+        if duration != bond_manager.duration {
+            steps.push(format!(
+                "set-duration: {} -> {duration}",
+                bond_manager.duration
+            ));
+            instructions.push(bonds.set_duration(duration)?);
+        }
+    }
+    if let Some(permissionless) = params.permissionless_crank {
+        let policy = match permissionless {
+            true => CrankPolicy::Permissionless,
+            false => CrankPolicy::Authorized,
+        };
+
+        if policy != bond_manager.crank_policy() {
+            steps.push(format!(
+                "set-crank-policy: {:?} -> {policy:?}",
+                bond_manager.crank_policy()
+            ));
+            instructions.push(bonds.set_crank_policy(policy)?);
+        }
+    }
+
+    if instructions.is_empty() {
+        println!("no changes to bond market {}", params.manager);
+        return Ok(Plan::default());
+    }
+
+    Ok(client.plan()?.instructions([], steps, instructions).build())
+}
+
+pub async fn process_show_orderbook(
+    client: &Client,
+    manager: Pubkey,
+    size: Option<u64>,
+) -> Result<Plan> {
+    if !client.account_exists(&manager).await? {
+        bail!("bond manager {manager} does not exist");
+    }
+
+    let bond_manager = client.read_anchor_account::<BondManager>(&manager).await?;
+    let mut bids_data = client.get_account_data(&bond_manager.bids).await?;
+    let mut asks_data = client.get_account_data(&bond_manager.asks).await?;
+
+    let snapshot = OrderbookSnapshot::from_slabs(
+        manager,
+        bond_manager.duration,
+        &mut bids_data,
+        &mut asks_data,
+    )?;
+    let depth = snapshot.depth();
+
+    for (name, levels) in [("Bids (lend)", &depth.bids), ("Asks (borrow)", &depth.asks)] {
+        let mut output_table = Table::new();
+
+        output_table
+            .load_preset(UTF8_FULL)
+            .set_content_arrangement(comfy_table::ContentArrangement::Dynamic)
+            .set_header(vec![
+                "Price",
+                "Rate (bps)",
+                "Tickets",
+                "Underlying",
+                "Orders",
+            ]);
+
+        for level in levels {
+            output_table.add_row(level_row(level));
+        }
+
+        println!("{name}");
+        println!("{output_table}");
+    }
+
+    match (depth.best_bid(), depth.best_ask()) {
+        (Some(bid), Some(ask)) => println!(
+            "best bid: {:.6} ({} bps), best ask: {:.6} ({} bps)",
+            fp32_to_f64(bid.price),
+            bid.rate,
+            fp32_to_f64(ask.price),
+            ask.rate
+        ),
+        (Some(bid), None) => println!("best bid: {:.6} ({} bps)", fp32_to_f64(bid.price), bid.rate),
+        (None, Some(ask)) => println!("best ask: {:.6} ({} bps)", fp32_to_f64(ask.price), ask.rate),
+        (None, None) => println!("the order book is empty"),
+    }
+
+    if let Some(size) = size {
+        print_expected_fill(
+            "lend",
+            &depth.expected_fill(Side::Bid, OrderSize::Underlying(size)),
+        );
+        print_expected_fill(
+            "borrow",
+            &depth.expected_fill(Side::Ask, OrderSize::Underlying(size)),
+        );
+    }
+
+    Ok(Plan::default())
+}
+
+fn level_row(level: &PriceLevel) -> Vec<String> {
+    vec![
+        format!("{:.6}", fp32_to_f64(level.price)),
+        level.rate.to_string(),
+        level.base_quantity.to_string(),
+        level.quote_quantity.to_string(),
+        level.order_count.to_string(),
+    ]
+}
+
+fn print_expected_fill(action: &str, fill: &ExpectedFill) {
+    if fill.base_filled == 0 {
+        println!("{action}: no liquidity available");
+        return;
+    }
+
+    println!(
+        "{action}: {} underlying for {} tickets at {:.6} ({} bps){}",
+        fill.quote_filled,
+        fill.base_filled,
+        fp32_to_f64(fill.average_price),
+        fill.average_rate,
+        if fill.complete { "" } else { ", partial fill" }
+    );
+}
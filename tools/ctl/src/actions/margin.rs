@@ -1,4 +1,5 @@
 use std::{
+    collections::HashMap,
     fmt::Display,
     str::FromStr,
     time::{SystemTime, UNIX_EPOCH},
@@ -10,14 +11,17 @@ use clap::Parser;
 use comfy_table::{presets::UTF8_FULL, Table};
 use futures::FutureExt;
 use jet_margin_sdk::{
-    ix_builder::{get_metadata_address, ControlIxBuilder},
-    jet_margin::{self, MarginAccount, PriceInfo, Valuation},
+    ix_builder::{derive_airspace, get_metadata_address, ControlIxBuilder, MarginConfigIxBuilder},
+    jet_margin::{
+        self, MarginAccount, PriceInfo, TokenAdmin, TokenConfig, TokenConfigUpdate, TokenOracle,
+        Valuation,
+    },
     jet_margin_pool::{self, MarginPool},
-    jet_metadata,
+    jet_metadata::{self, PositionTokenMetadata, TokenMetadata},
 };
 use serde::{Deserialize, Serialize};
 use serde_with::{serde_as, DisplayFromStr};
-use solana_sdk::{pubkey, pubkey::Pubkey};
+use solana_sdk::{instruction::Instruction, pubkey, pubkey::Pubkey};
 
 use crate::{
     client::{Client, Plan},
@@ -146,6 +150,100 @@ pub async fn process_set_liquidator(
         .build())
 }
 
+pub async fn process_migrate_metadata(client: &Client, airspace: &str) -> Result<Plan> {
+    let airspace = derive_airspace(airspace);
+    let config_builder = MarginConfigIxBuilder::new(airspace, resolve_payer(client)?);
+    let metadata_accounts = client.rpc().get_program_accounts(&jet_metadata::ID).await?;
+
+    let mut position_metadata = vec![];
+    let mut token_metadata = HashMap::new();
+
+    for (_, account) in metadata_accounts {
+        if let Ok(ptm) = PositionTokenMetadata::try_deserialize(&mut &account.data[..]) {
+            position_metadata.push(ptm);
+        } else if let Ok(tm) = TokenMetadata::try_deserialize(&mut &account.data[..]) {
+            token_metadata.insert(tm.token_mint, tm);
+        }
+    }
+
+    println!(
+        "found {} position metadata entries",
+        position_metadata.len()
+    );
+
+    let mut steps = vec![];
+    let mut instructions = vec![];
+
+    for ptm in position_metadata {
+        let admin = match ptm.adapter_program {
+            adapter if adapter != Pubkey::default() => TokenAdmin::Adapter(adapter),
+            _ => match token_metadata.get(&ptm.position_token_mint) {
+                Some(tm) => TokenAdmin::Margin {
+                    oracle: TokenOracle::Pyth {
+                        price: tm.pyth_price,
+                        product: tm.pyth_product,
+                    },
+                },
+                None => {
+                    eprintln!(
+                        "skipping {}: no adapter or oracle in its metadata",
+                        ptm.position_token_mint
+                    );
+                    continue;
+                }
+            },
+        };
+
+        let update = TokenConfigUpdate {
+            underlying_mint: ptm.underlying_token_mint,
+            admin,
+            token_kind: ptm.token_kind.into(),
+            value_modifier: ptm.value_modifier,
+            max_staleness: ptm.max_staleness,
+            conservative_valuation: false,
+            category: 0,
+            min_permit_tier: 0,
+        };
+
+        let config_address = config_builder.derive_token_config(&ptm.position_token_mint);
+
+        if client.account_exists(&config_address).await? {
+            let config = client
+                .read_anchor_account::<TokenConfig>(&config_address)
+                .await?;
+
+            if config.underlying_mint == update.underlying_mint
+                && config.admin == update.admin
+                && config.token_kind == update.token_kind
+                && config.value_modifier == update.value_modifier
+                && config.max_staleness == update.max_staleness
+            {
+                continue;
+            }
+        }
+
+        steps.push(format!(
+            "migrate-metadata for token {}",
+            ptm.position_token_mint
+        ));
+        instructions.push(config_builder.configure_token(ptm.position_token_mint, Some(update)));
+    }
+
+    println!("migrating {} entries to token configs", instructions.len());
+
+    Ok(steps
+        .into_iter()
+        .zip(instructions)
+        .collect::<Vec<_>>()
+        .chunks(6)
+        .fold(client.plan()?, |plan, chunk| {
+            let (steps, ix_list): (Vec<String>, Vec<Instruction>) = chunk.iter().cloned().unzip();
+
+            plan.instructions([], steps, ix_list)
+        })
+        .build())
+}
+
 pub async fn process_list_top_accounts(client: &Client, limit: usize) -> Result<Plan> {
     let all_margin_accounts = client.rpc().get_program_accounts(&jet_margin::ID).await?;
     let margin_user_account_size = 8 + std::mem::size_of::<MarginAccount>();
@@ -4,7 +4,8 @@ use clap::Parser;
 use comfy_table::{presets::UTF8_FULL, Table};
 use jet_margin_sdk::{
     ix_builder::{
-        get_metadata_address, ControlIxBuilder, MarginPoolConfiguration, MarginPoolIxBuilder,
+        derive_airspace, get_metadata_address, ControlIxBuilder, MarginPoolConfiguration,
+        MarginPoolIxBuilder,
     },
     jet_control::TokenMetadataParams,
     jet_margin_pool::{self, MarginPool},
@@ -44,6 +45,9 @@ pub struct MarginPoolParameters {
 
     #[clap(long)]
     pub management_fee_rate: Option<u16>,
+
+    #[clap(long)]
+    pub max_price_deviation: Option<u16>,
 }
 
 #[derive(Debug, Parser, Deserialize)]
@@ -55,6 +59,15 @@ pub struct ConfigurePoolCliOptions {
     #[clap(flatten)]
     #[serde(flatten)]
     pub margin_pool: MarginPoolParameters,
+
+    /// The name of the airspace the pool belongs to
+    #[clap(long, default_value = "default")]
+    #[serde(default = "default_airspace")]
+    pub airspace: String,
+}
+
+pub fn default_airspace() -> String {
+    "default".to_owned()
 }
 
 pub async fn process_list_pools(client: &Client) -> Result<Plan> {
@@ -101,7 +114,7 @@ pub async fn process_collect_pool_fees(client: &Client) -> Result<Plan> {
     let instructions = pools
         .into_iter()
         .filter_map(|(address, pool)| {
-            let ix_build = MarginPoolIxBuilder::new(pool.token_mint);
+            let ix_build = MarginPoolIxBuilder::new(pool.airspace, pool.token_mint);
             let (fee_vault_address, _) = Pubkey::find_program_address(
                 &[
                     jet_margin_sdk::jet_control::seeds::FEE_DESTINATION,
@@ -131,8 +144,9 @@ pub async fn process_collect_pool_fees(client: &Client) -> Result<Plan> {
         .build())
 }
 
-pub async fn process_create_pool(client: &Client, token: Pubkey) -> Result<Plan> {
-    let margin_pool = MarginPoolIxBuilder::new(token);
+pub async fn process_create_pool(client: &Client, airspace: &str, token: Pubkey) -> Result<Plan> {
+    let airspace = derive_airspace(airspace);
+    let margin_pool = MarginPoolIxBuilder::new(airspace, token);
     let ctrl = ControlIxBuilder::new(resolve_payer(client)?);
 
     if client.account_exists(&margin_pool.address).await? {
@@ -150,7 +164,7 @@ pub async fn process_create_pool(client: &Client, token: Pubkey) -> Result<Plan>
         .instructions(
             [],
             [format!("create-margin-pool for token {token}")],
-            [ctrl.create_margin_pool(&token)],
+            [ctrl.create_margin_pool(&airspace, &token)],
         )
         .build())
 }
@@ -159,7 +173,8 @@ pub async fn process_configure_pool(
     client: &Client,
     options: ConfigurePoolCliOptions,
 ) -> Result<Plan> {
-    let margin_pool = MarginPoolIxBuilder::new(options.token_config.mint);
+    let airspace = derive_airspace(&options.airspace);
+    let margin_pool = MarginPoolIxBuilder::new(airspace, options.token_config.mint);
     let ctrl = ControlIxBuilder::new(resolve_payer(client)?);
     let mut configuration = match client.account_exists(&margin_pool.address).await? {
         false => MarginPoolConfiguration {
@@ -202,7 +217,13 @@ pub async fn process_configure_pool(
                     "configure-margin-pool for token {}",
                     options.token_config.mint
                 )],
-                [ctrl.configure_margin_pool(&options.token_config.mint, &configuration)],
+                [
+                    ctrl.configure_margin_pool(
+                        &airspace,
+                        &options.token_config.mint,
+                        &configuration,
+                    ),
+                ],
             )
             .build())
     } else {
@@ -210,8 +231,8 @@ pub async fn process_configure_pool(
     }
 }
 
-pub async fn process_show_pool(client: &Client, token: Pubkey) -> Result<Plan> {
-    let margin_pool = MarginPoolIxBuilder::new(token);
+pub async fn process_show_pool(client: &Client, airspace: &str, token: Pubkey) -> Result<Plan> {
+    let margin_pool = MarginPoolIxBuilder::new(derive_airspace(airspace), token);
 
     if !client.account_exists(&margin_pool.address).await? {
         bail!("pool for token {} does not exist", token);
@@ -268,6 +289,7 @@ fn override_pool_config_with_options(
         borrow_rate_2,
         borrow_rate_3,
         management_fee_rate,
+        max_price_deviation,
     } = margin_pool;
 
     let orig_params = config.parameters.unwrap();
@@ -281,6 +303,7 @@ fn override_pool_config_with_options(
     override_field!(overridden, params, borrow_rate_2);
     override_field!(overridden, params, borrow_rate_3);
     override_field!(overridden, params, management_fee_rate);
+    override_field!(overridden, params, max_price_deviation);
 
     if orig_params == *params {
         config.parameters = None;
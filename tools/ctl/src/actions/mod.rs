@@ -5,4 +5,5 @@ pub mod governance;
 pub mod margin;
 pub mod margin_pool;
 pub mod program;
+pub mod snapshot;
 pub mod test;
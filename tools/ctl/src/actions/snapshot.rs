@@ -0,0 +1,498 @@
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    path::Path,
+};
+
+use anchor_lang::{AccountDeserialize, Discriminator};
+use anyhow::{Context, Result};
+use jet_margin_sdk::{
+    bonds::BondManager,
+    jet_airspace::{
+        self,
+        state::{Airspace, AirspacePermit, AirspacePermitIssuerId, GovernorId},
+    },
+    jet_bonds,
+    jet_margin::{
+        self, AdapterConfig, LiquidatorConfig, MarginAccount, TokenCategory, TokenConfig,
+    },
+    jet_margin_pool::{self, MarginPool},
+    jet_metadata::{
+        self, LiquidatorMetadata, MarginAdapterMetadata, PositionTokenMetadata, TokenMetadata,
+    },
+};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+
+use crate::client::{Client, Plan};
+
+/// The state of all accounts owned by the Jet programs at a point in time
+#[derive(Debug, Serialize, Deserialize)]
+pub struct Snapshot {
+    /// The slot the snapshot was taken at
+    pub slot: u64,
+
+    /// The decoded accounts, keyed by their address
+    pub accounts: BTreeMap<String, AccountSnapshot>,
+}
+
+/// The decoded state of a single account
+#[derive(Debug, Serialize, Deserialize, PartialEq, Eq)]
+pub struct AccountSnapshot {
+    /// The name of the account type
+    #[serde(rename = "type")]
+    pub kind: String,
+
+    /// The account data
+    pub data: Value,
+}
+
+pub async fn process_snapshot(client: &Client, output: &Path) -> Result<Plan> {
+    let slot = client.rpc().get_slot().await?;
+    let programs = [
+        jet_airspace::ID,
+        jet_margin::ID,
+        jet_margin_pool::ID,
+        jet_bonds::ID,
+        jet_metadata::ID,
+    ];
+
+    let mut accounts = BTreeMap::new();
+
+    for program in programs {
+        let program_accounts = client.rpc().get_program_accounts(&program).await?;
+        let mut count = 0;
+
+        for (address, account) in program_accounts {
+            if let Some(snapshot) = decode_account(&account.data) {
+                accounts.insert(address.to_string(), snapshot);
+                count += 1;
+            }
+        }
+
+        println!("found {count} accounts for program {program}");
+    }
+
+    let snapshot = Snapshot { slot, accounts };
+    let snapshot_json = serde_json::to_string_pretty(&snapshot)?;
+
+    tokio::fs::write(output, &snapshot_json)
+        .await
+        .with_context(|| format!("while trying to write to file {output:?}"))?;
+
+    println!(
+        "wrote {} accounts at slot {slot} to {output:?}",
+        snapshot.accounts.len()
+    );
+
+    Ok(Plan::default())
+}
+
+pub async fn process_diff(before: &Path, after: &Path) -> Result<Plan> {
+    let before = read_snapshot(before).await?;
+    let after = read_snapshot(after).await?;
+
+    println!("comparing slot {} to slot {}", before.slot, after.slot);
+
+    let addresses = before
+        .accounts
+        .keys()
+        .chain(after.accounts.keys())
+        .collect::<BTreeSet<_>>();
+    let mut changed_count = 0;
+
+    for address in addresses {
+        match (before.accounts.get(address), after.accounts.get(address)) {
+            (None, Some(added)) => {
+                println!("+ {address} ({})", added.kind);
+                changed_count += 1;
+            }
+            (Some(removed), None) => {
+                println!("- {address} ({})", removed.kind);
+                changed_count += 1;
+            }
+            (Some(old), Some(new)) if old != new => {
+                println!("~ {address} ({})", new.kind);
+
+                let mut changes = vec![];
+                diff_values("", &old.data, &new.data, &mut changes);
+
+                for change in changes {
+                    println!("    {change}");
+                }
+
+                changed_count += 1;
+            }
+            _ => (),
+        }
+    }
+
+    println!("{changed_count} accounts changed");
+
+    Ok(Plan::default())
+}
+
+async fn read_snapshot(path: &Path) -> Result<Snapshot> {
+    let content = tokio::fs::read_to_string(path)
+        .await
+        .with_context(|| format!("while trying to read file {path:?}"))?;
+
+    serde_json::from_str(&content).with_context(|| format!("invalid snapshot file {path:?}"))
+}
+
+pub fn diff_values(path: &str, before: &Value, after: &Value, changes: &mut Vec<String>) {
+    match (before, after) {
+        (Value::Object(old), Value::Object(new)) => {
+            let keys = old.keys().chain(new.keys()).collect::<BTreeSet<_>>();
+
+            for key in keys {
+                let field = match path {
+                    "" => key.to_string(),
+                    _ => format!("{path}.{key}"),
+                };
+
+                diff_values(
+                    &field,
+                    old.get(key).unwrap_or(&Value::Null),
+                    new.get(key).unwrap_or(&Value::Null),
+                    changes,
+                );
+            }
+        }
+        (Value::Array(old), Value::Array(new)) if old.len() == new.len() => {
+            for (index, (old_item, new_item)) in old.iter().zip(new).enumerate() {
+                diff_values(&format!("{path}[{index}]"), old_item, new_item, changes);
+            }
+        }
+        _ if before != after => {
+            changes.push(format!("{path}: {before} -> {after}"));
+        }
+        _ => (),
+    }
+}
+
+fn decode<T: AccountDeserialize + Discriminator>(data: &[u8]) -> Option<T> {
+    if data.len() < 8 || data[..8] != T::discriminator() {
+        return None;
+    }
+
+    T::try_deserialize(&mut &data[..]).ok()
+}
+
+/// Zero copy accounts are read without checking their length, so shorter accounts
+/// have to be skipped before decoding them.
+fn decode_zero_copy<T: AccountDeserialize + Discriminator>(data: &[u8]) -> Option<T> {
+    if data.len() < 8 + std::mem::size_of::<T>() {
+        return None;
+    }
+
+    decode(data)
+}
+
+/// Version 1 margin accounts are missing the airspace at the end, so they're padded
+/// to the current size before being decoded.
+fn decode_margin_account(data: &[u8]) -> Option<MarginAccount> {
+    if data.len() == MarginAccount::V1_SPACE {
+        let mut padded = data.to_vec();
+        padded.resize(8 + std::mem::size_of::<MarginAccount>(), 0);
+
+        return decode_zero_copy(&padded);
+    }
+
+    decode_zero_copy(data)
+}
+
+fn snapshot(kind: &str, data: Value) -> Option<AccountSnapshot> {
+    Some(AccountSnapshot {
+        kind: kind.to_owned(),
+        data,
+    })
+}
+
+pub fn decode_account(data: &[u8]) -> Option<AccountSnapshot> {
+    if let Some(airspace) = decode::<Airspace>(data) {
+        return snapshot(
+            "Airspace",
+            json!({
+                "authority": airspace.authority.to_string(),
+                "isRestricted": airspace.is_restricted,
+            }),
+        );
+    }
+
+    if let Some(issuer) = decode::<AirspacePermitIssuerId>(data) {
+        return snapshot(
+            "AirspacePermitIssuerId",
+            json!({
+                "airspace": issuer.airspace.to_string(),
+                "issuer": issuer.issuer.to_string(),
+            }),
+        );
+    }
+
+    if let Some(permit) = decode::<AirspacePermit>(data) {
+        return snapshot(
+            "AirspacePermit",
+            json!({
+                "airspace": permit.airspace.to_string(),
+                "owner": permit.owner.to_string(),
+                "issuer": permit.issuer.to_string(),
+                "expiresAt": permit.expires_at,
+                "tier": permit.tier,
+            }),
+        );
+    }
+
+    if let Some(governor) = decode::<GovernorId>(data) {
+        return snapshot(
+            "GovernorId",
+            json!({ "governor": governor.governor.to_string() }),
+        );
+    }
+
+    if let Some(config) = decode::<TokenConfig>(data) {
+        return snapshot(
+            "TokenConfig",
+            json!({
+                "mint": config.mint.to_string(),
+                "underlyingMint": config.underlying_mint.to_string(),
+                "airspace": config.airspace.to_string(),
+                "admin": format!("{:?}", config.admin),
+                "tokenKind": config.token_kind,
+                "valueModifier": config.value_modifier,
+                "maxStaleness": config.max_staleness,
+                "conservativeValuation": config.conservative_valuation,
+                "category": config.category,
+                "minPermitTier": config.min_permit_tier,
+            }),
+        );
+    }
+
+    if let Some(category) = decode::<TokenCategory>(data) {
+        return snapshot(
+            "TokenCategory",
+            json!({
+                "airspace": category.airspace.to_string(),
+                "category": category.category,
+                "collateralWeight": category.collateral_weight,
+                "maxLeverage": category.max_leverage,
+            }),
+        );
+    }
+
+    if let Some(config) = decode::<AdapterConfig>(data) {
+        return snapshot(
+            "AdapterConfig",
+            json!({
+                "airspace": config.airspace.to_string(),
+                "adapterProgram": config.adapter_program.to_string(),
+            }),
+        );
+    }
+
+    if let Some(config) = decode::<LiquidatorConfig>(data) {
+        return snapshot(
+            "LiquidatorConfig",
+            json!({
+                "airspace": config.airspace.to_string(),
+                "liquidator": config.liquidator.to_string(),
+            }),
+        );
+    }
+
+    if let Some(account) = decode_margin_account(data) {
+        return snapshot("MarginAccount", serde_json::to_value(&account).ok()?);
+    }
+
+    if let Some(pool) = decode::<MarginPool>(data) {
+        let mut value = serde_json::to_value(&pool).ok()?;
+
+        value.as_object_mut()?.insert(
+            "config".to_owned(),
+            json!({
+                "flags": pool.config.flags,
+                "utilizationRate1": pool.config.utilization_rate_1,
+                "utilizationRate2": pool.config.utilization_rate_2,
+                "borrowRate0": pool.config.borrow_rate_0,
+                "borrowRate1": pool.config.borrow_rate_1,
+                "borrowRate2": pool.config.borrow_rate_2,
+                "borrowRate3": pool.config.borrow_rate_3,
+                "managementFeeRate": pool.config.management_fee_rate,
+                "maxPriceDeviation": pool.config.max_price_deviation,
+            }),
+        );
+
+        return snapshot("MarginPool", value);
+    }
+
+    if let Some(manager) = decode_zero_copy::<BondManager>(data) {
+        return snapshot("BondManager", serde_json::to_value(&manager).ok()?);
+    }
+
+    if let Some(metadata) = decode::<TokenMetadata>(data) {
+        return snapshot(
+            "TokenMetadata",
+            json!({
+                "tokenMint": metadata.token_mint.to_string(),
+                "pythPrice": metadata.pyth_price.to_string(),
+                "pythProduct": metadata.pyth_product.to_string(),
+            }),
+        );
+    }
+
+    if let Some(metadata) = decode::<PositionTokenMetadata>(data) {
+        return snapshot(
+            "PositionTokenMetadata",
+            json!({
+                "positionTokenMint": metadata.position_token_mint.to_string(),
+                "underlyingTokenMint": metadata.underlying_token_mint.to_string(),
+                "adapterProgram": metadata.adapter_program.to_string(),
+                "tokenKind": format!("{:?}", metadata.token_kind),
+                "valueModifier": metadata.value_modifier,
+                "maxStaleness": metadata.max_staleness,
+            }),
+        );
+    }
+
+    if let Some(metadata) = decode::<MarginAdapterMetadata>(data) {
+        return snapshot(
+            "MarginAdapterMetadata",
+            json!({ "adapterProgram": metadata.adapter_program.to_string() }),
+        );
+    }
+
+    if let Some(metadata) = decode::<LiquidatorMetadata>(data) {
+        return snapshot(
+            "LiquidatorMetadata",
+            json!({ "liquidator": metadata.liquidator.to_string() }),
+        );
+    }
+
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use anchor_lang::{prelude::Pubkey, AccountSerialize};
+    use jet_margin_sdk::jet_margin::{TokenAdmin, TokenKind};
+
+    use super::*;
+
+    fn account_data<T: Discriminator + bytemuck::Pod>(value: &T) -> Vec<u8> {
+        let mut data = T::discriminator().to_vec();
+        data.extend_from_slice(bytemuck::bytes_of(value));
+        data
+    }
+
+    #[test]
+    fn diff_reports_changed_fields() {
+        let before = json!({
+            "owner": "a",
+            "config": { "flags": 1, "rate": 2 },
+            "positions": [1, 2],
+            "removed": true,
+        });
+        let after = json!({
+            "owner": "a",
+            "config": { "flags": 3, "rate": 2 },
+            "positions": [1, 4],
+            "added": 5,
+        });
+
+        let mut changes = vec![];
+        diff_values("", &before, &after, &mut changes);
+
+        assert_eq!(
+            changes,
+            vec![
+                "added: null -> 5",
+                "config.flags: 1 -> 3",
+                "positions[1]: 2 -> 4",
+                "removed: true -> null",
+            ]
+        );
+    }
+
+    #[test]
+    fn diff_replaces_arrays_with_different_lengths() {
+        let mut changes = vec![];
+        diff_values("list", &json!([1]), &json!([1, 2]), &mut changes);
+
+        assert_eq!(changes, vec!["list: [1] -> [1,2]"]);
+    }
+
+    #[test]
+    fn decode_token_config() {
+        let config = TokenConfig {
+            mint: Pubkey::new_unique(),
+            underlying_mint: Pubkey::new_unique(),
+            airspace: Pubkey::new_unique(),
+            admin: TokenAdmin::Adapter(Pubkey::new_unique()),
+            token_kind: TokenKind::Collateral,
+            value_modifier: 95,
+            max_staleness: 30,
+            conservative_valuation: true,
+            category: 3,
+            min_permit_tier: 1,
+        };
+        let mut data = vec![];
+        config.try_serialize(&mut data).unwrap();
+
+        let snapshot = decode_account(&data).unwrap();
+
+        assert_eq!(snapshot.kind, "TokenConfig");
+        assert_eq!(snapshot.data["mint"], config.mint.to_string());
+        assert_eq!(snapshot.data["valueModifier"], 95);
+        assert_eq!(snapshot.data["conservativeValuation"], true);
+        assert_eq!(snapshot.data["category"], 3);
+        assert_eq!(snapshot.data["minPermitTier"], 1);
+    }
+
+    #[test]
+    fn decode_token_category() {
+        let category = TokenCategory {
+            airspace: Pubkey::new_unique(),
+            category: 2,
+            collateral_weight: 90,
+            max_leverage: 10_00,
+        };
+        let mut data = vec![];
+        category.try_serialize(&mut data).unwrap();
+
+        let snapshot = decode_account(&data).unwrap();
+
+        assert_eq!(snapshot.kind, "TokenCategory");
+        assert_eq!(snapshot.data["category"], 2);
+        assert_eq!(snapshot.data["collateralWeight"], 90);
+    }
+
+    #[test]
+    fn decode_version_1_margin_account() {
+        let mut account: MarginAccount = bytemuck::Zeroable::zeroed();
+        account.version = 1;
+        account.owner = Pubkey::new_unique();
+
+        let mut data = account_data(&account);
+        data.truncate(MarginAccount::V1_SPACE);
+
+        let snapshot = decode_account(&data).unwrap();
+
+        assert_eq!(snapshot.kind, "MarginAccount");
+        assert_eq!(snapshot.data["version"], 1);
+        assert_eq!(snapshot.data["owner"], account.owner.to_string());
+    }
+
+    #[test]
+    fn skip_truncated_zero_copy_accounts() {
+        let account: MarginAccount = bytemuck::Zeroable::zeroed();
+        let mut data = account_data(&account);
+        data.truncate(100);
+
+        assert_eq!(decode_account(&data), None);
+    }
+
+    #[test]
+    fn skip_unknown_accounts() {
+        assert_eq!(decode_account(&[]), None);
+        assert_eq!(decode_account(&[0; 64]), None);
+    }
+}
@@ -92,7 +92,7 @@ async fn generate_app_config(client: &Client, env: &EnvironmentConfig) -> Result
         margin_pool_program_id: jet_margin_sdk::jet_margin_pool::ID,
         margin_swap_program_id: jet_margin_sdk::jet_margin_swap::ID,
         metadata_program_id: jet_margin_sdk::jet_metadata::ID,
-        margin_serum_program_id: Pubkey::default(),
+        margin_serum_program_id: jet_margin_sdk::jet_margin_serum::ID,
         orca_swap_program_id: pubkey!("9W959DqEETiGZocYWCQPaJ6sBmUzgfxXfqGeTEdp3aQP"),
         serum_program_id: pubkey!("9xQeWvG816bUx9EPjHmaT23yvVM2ZWbrrpZb9PusVFin"),
         faucet_program_id: None,
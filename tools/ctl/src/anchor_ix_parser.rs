@@ -4,7 +4,8 @@ use anchor_lang::{idl::IdlAccount, AnchorDeserialize};
 use anchor_syn::{
     codegen::program::common::SIGHASH_GLOBAL_NAMESPACE,
     idl::{
-        EnumFields, Idl, IdlAccountItem, IdlField, IdlInstruction, IdlType, IdlTypeDefinitionTy,
+        EnumFields, Idl, IdlAccountItem, IdlEvent, IdlField, IdlInstruction, IdlType,
+        IdlTypeDefinitionTy,
     },
 };
 use anyhow::{anyhow, bail, Context, Result};
@@ -18,6 +19,12 @@ pub struct ParsedInstruction {
     pub accounts: Vec<ParsedAccountInput>,
     pub data: DataValue,
 }
+
+pub struct ParsedEvent {
+    pub name: String,
+    pub data: DataValue,
+}
+
 pub enum ParsedAccountInput {
     Account(String, Pubkey),
     Group(String, Vec<ParsedAccountInput>),
@@ -76,6 +83,46 @@ impl<'a> AnchorParser<'a> {
         })
     }
 
+    pub fn try_parse_event(&self, program_id: &Pubkey, data: &[u8]) -> Result<ParsedEvent> {
+        if data.len() < 8 {
+            bail!("event is not in the standard anchor format");
+        }
+
+        let idl = self
+            .idls
+            .get(program_id)
+            .ok_or_else(|| anyhow!("did not load idl for {}", program_id))?;
+        let reader = IdlReader::new(&self.idls, idl);
+
+        let event_def = reader
+            .find_event_by_discriminator(&data[..8])
+            .ok_or_else(|| {
+                anyhow!(
+                    "unknown event for program {}: {}",
+                    program_id,
+                    bs58::encode(&data[..8]).into_string(),
+                )
+            })?;
+
+        let data = &mut &data[8..];
+        let fields = event_def
+            .fields
+            .iter()
+            .map(|field| {
+                let value = reader
+                    .parse_data_value(data, &field.ty)
+                    .with_context(|| format!("parsing field {}", &field.name))?;
+
+                Ok((field.name.clone(), value))
+            })
+            .collect::<Result<_>>()?;
+
+        Ok(ParsedEvent {
+            name: event_def.name.clone(),
+            data: DataValue::Struct(fields),
+        })
+    }
+
     pub async fn load_idl(&mut self, program_id: &Pubkey) -> Result<()> {
         if self.idls.contains_key(program_id) {
             return Ok(());
@@ -187,6 +234,15 @@ impl<'a> IdlReader<'a> {
         })
     }
 
+    fn find_event_by_discriminator(&self, discriminator: &[u8]) -> Option<&IdlEvent> {
+        self.idl.events.as_ref()?.iter().find(|event_def| {
+            let preimage = format!("event:{}", event_def.name);
+            let expected_sig = anchor_lang::solana_program::hash::hash(preimage.as_bytes());
+
+            &expected_sig.to_bytes()[..8] == discriminator
+        })
+    }
+
     fn parse_accounts(
         &self,
         to_parse: &mut impl Iterator<Item = Pubkey>,
@@ -348,6 +404,12 @@ impl Debug for ParsedInstruction {
     }
 }
 
+impl Debug for ParsedEvent {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} {:?}", self.name, self.data)
+    }
+}
+
 struct DisplayParsedAccounts<'a>(&'a Vec<ParsedAccountInput>);
 
 impl<'a> Debug for DisplayParsedAccounts<'a> {
@@ -371,3 +433,90 @@ fn find_type_in_idl<'a>(idl: &'a Idl, name: &str) -> Option<&'a IdlTypeDefinitio
         .find(|tydef| tydef.name == name)
         .map(|tydef| &tydef.ty)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parser_with_idl(rpc: &RpcClient, program: Pubkey) -> AnchorParser<'_> {
+        let idl = serde_json::from_value(serde_json::json!({
+            "version": "0.1.0",
+            "name": "test_program",
+            "instructions": [],
+            "events": [{
+                "name": "PoolMigrated",
+                "fields": [
+                    { "name": "marginPool", "type": "publicKey", "index": false },
+                    { "name": "version", "type": "u8", "index": false }
+                ]
+            }]
+        }))
+        .unwrap();
+
+        let mut parser = AnchorParser::new(rpc);
+        parser.idls.insert(program, idl);
+        parser
+    }
+
+    fn event_data(name: &str, fields: &[u8]) -> Vec<u8> {
+        let preimage = format!("event:{name}");
+        let mut data =
+            anchor_lang::solana_program::hash::hash(preimage.as_bytes()).to_bytes()[..8].to_vec();
+        data.extend_from_slice(fields);
+        data
+    }
+
+    #[test]
+    fn parse_event_fields() {
+        let rpc = RpcClient::new("http://localhost:8899".to_owned());
+        let program = Pubkey::new_unique();
+        let pool = Pubkey::new_unique();
+        let parser = parser_with_idl(&rpc, program);
+
+        let mut fields = pool.to_bytes().to_vec();
+        fields.push(1);
+
+        let event = parser
+            .try_parse_event(&program, &event_data("PoolMigrated", &fields))
+            .unwrap();
+
+        assert_eq!(event.name, "PoolMigrated");
+
+        let fields = match event.data {
+            DataValue::Struct(fields) => fields,
+            other => panic!("expected a struct, got {other:?}"),
+        };
+
+        assert_eq!(fields.len(), 2);
+        assert!(
+            matches!(&fields[0], (name, DataValue::PublicKey(key)) if name == "marginPool" && *key == pool)
+        );
+        assert!(matches!(&fields[1], (name, DataValue::IntegerUnsigned(1)) if name == "version"));
+    }
+
+    #[test]
+    fn reject_unknown_events() {
+        let rpc = RpcClient::new("http://localhost:8899".to_owned());
+        let program = Pubkey::new_unique();
+        let parser = parser_with_idl(&rpc, program);
+
+        assert!(parser
+            .try_parse_event(&program, &event_data("Unknown", &[]))
+            .is_err());
+        assert!(parser.try_parse_event(&program, &[0; 4]).is_err());
+        assert!(parser
+            .try_parse_event(&Pubkey::new_unique(), &event_data("PoolMigrated", &[]))
+            .is_err());
+    }
+
+    #[test]
+    fn reject_truncated_events() {
+        let rpc = RpcClient::new("http://localhost:8899".to_owned());
+        let program = Pubkey::new_unique();
+        let parser = parser_with_idl(&rpc, program);
+
+        assert!(parser
+            .try_parse_event(&program, &event_data("PoolMigrated", &[0; 16]))
+            .is_err());
+    }
+}
@@ -151,7 +151,7 @@ impl JetAppConfig {
             margin_pool_program_id: jet_margin_sdk::jet_margin_pool::ID,
             margin_swap_program_id: jet_margin_sdk::jet_margin_swap::ID,
             metadata_program_id: jet_margin_sdk::jet_metadata::ID,
-            margin_serum_program_id: Pubkey::default(),
+            margin_serum_program_id: jet_margin_sdk::jet_margin_serum::ID,
             orca_swap_program_id: deps.orca_program_id,
             serum_program_id: deps.serum_program_id,
             faucet_program_id: deps.faucet_program_id,
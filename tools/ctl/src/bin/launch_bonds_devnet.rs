@@ -144,6 +144,7 @@ async fn main() -> Result<()> {
     let client_config = ClientConfig::new(
         OPTS.dry_run,
         false,
+        false,
         OPTS.signer_path.clone(),
         Some(ENDPOINT.to_string()),
         OPTS.compute_budget,
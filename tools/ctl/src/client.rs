@@ -41,6 +41,10 @@ pub struct ClientConfig {
     /// If true, transactions will be simulated but not actually submitted.
     dry_run: bool,
 
+    /// If true, transactions will be simulated and their effects shown, without
+    /// being submitted.
+    simulate: bool,
+
     /// If true, will not ask user to confirm before submitting transactions
     no_confirm: bool,
 
@@ -57,6 +61,7 @@ pub struct ClientConfig {
 impl ClientConfig {
     pub fn new(
         dry_run: bool,
+        simulate: bool,
         no_confirm: bool,
         signer_path: Option<String>,
         rpc_endpoint: Option<String>,
@@ -79,6 +84,7 @@ impl ClientConfig {
 
         Ok(ClientConfig {
             dry_run,
+            simulate,
             no_confirm,
             rpc_client,
             signer,
@@ -198,6 +204,10 @@ impl Client {
                 .partial_sign(&[&**signer], self.recent_blockhash);
         }
 
+        if self.config.simulate {
+            return crate::simulate::simulate_plan(self, &plan).await;
+        }
+
         for (i, entry) in plan.iter().enumerate() {
             let tx_size = entry.transaction.message().serialize().len();
             println!("\t transaction #{i} (size {tx_size}):");
@@ -344,7 +354,8 @@ impl Client {
         }
     }
 
-    async fn get_account_data(&self, address: &Pubkey) -> Result<Vec<u8>> {
+    /// Read the raw data of an account
+    pub async fn get_account_data(&self, address: &Pubkey) -> Result<Vec<u8>> {
         Ok(self
             .rpc()
             .get_account_with_commitment(
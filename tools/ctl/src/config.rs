@@ -5,13 +5,20 @@ use serde::{Deserialize, Serialize};
 use serde_with::{serde_as, DisplayFromStr};
 use solana_sdk::pubkey::Pubkey;
 
-use crate::actions::{margin::TokenConfig, margin_pool::MarginPoolParameters};
+use crate::actions::{
+    margin::TokenConfig,
+    margin_pool::{default_airspace, MarginPoolParameters},
+};
 
 #[derive(Debug, Serialize, Deserialize)]
 pub struct TokenDefinition {
     pub token: TokenDescription,
     pub config: TokenConfig,
     pub margin_pool: MarginPoolParameters,
+
+    /// The name of the airspace the token's pool belongs to
+    #[serde(default = "default_airspace")]
+    pub airspace: String,
 }
 
 #[serde_as]
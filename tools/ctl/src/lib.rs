@@ -1,6 +1,9 @@
 use std::path::PathBuf;
 
-use actions::{bonds::BondMarketParameters, margin_pool::ConfigurePoolCliOptions};
+use actions::{
+    bonds::{BondMarketParameters, ConfigureBondMarketParameters},
+    margin_pool::ConfigurePoolCliOptions,
+};
 use anchor_lang::prelude::Pubkey;
 use anyhow::Result;
 use clap::{AppSettings, Parser, Subcommand};
@@ -16,6 +19,7 @@ mod anchor_ix_parser;
 mod app_config;
 mod governance;
 mod serum;
+mod simulate;
 
 #[derive(Debug, Parser)]
 #[clap(version)]
@@ -44,6 +48,10 @@ pub struct CliOpts {
     #[clap(global = true, long)]
     pub dry_run: bool,
 
+    /// Simulate transactions, showing their logs, events and changes to program accounts
+    #[clap(global = true, long, conflicts_with = "dry-run")]
+    pub simulate: bool,
+
     /// Don't ask for confirmation
     #[clap(global = true, long)]
     pub no_confirm: bool,
@@ -141,6 +149,14 @@ pub enum MarginCommand {
         #[clap(long, default_value_t = 10)]
         limit: usize,
     },
+
+    /// Copy all position metadata into token configs for an airspace
+    MigrateMetadata {
+        /// The name of the airspace to create the token configs in
+        #[clap(long, default_value = "default")]
+        #[serde(default = "actions::margin_pool::default_airspace")]
+        airspace: String,
+    },
 }
 
 #[serde_as]
@@ -152,6 +168,11 @@ pub enum MarginPoolCommand {
         /// The target token to create the pool for
         #[serde_as(as = "DisplayFromStr")]
         token: Pubkey,
+
+        /// The name of the airspace the pool belongs to
+        #[clap(long, default_value = "default")]
+        #[serde(default = "actions::margin_pool::default_airspace")]
+        airspace: String,
     },
 
     /// Modify the parameters for an existing margin pool
@@ -167,6 +188,11 @@ pub enum MarginPoolCommand {
     Show {
         /// The token to show the pool for
         token: Pubkey,
+
+        /// The name of the airspace the pool belongs to
+        #[clap(long, default_value = "default")]
+        #[serde(default = "actions::margin_pool::default_airspace")]
+        airspace: String,
     },
 }
 
@@ -176,6 +202,20 @@ pub enum MarginPoolCommand {
 pub enum BondsCommand {
     /// Create a new bond market
     CreateMarket(BondMarketParameters),
+
+    /// Modify the configuration of an existing bond market
+    Configure(ConfigureBondMarketParameters),
+
+    /// Show the order book depth for a bond market
+    ShowOrderbook {
+        /// The address of the bond manager for the market
+        #[serde_as(as = "DisplayFromStr")]
+        manager: Pubkey,
+
+        /// Show the expected fill for lending and borrowing this amount of underlying tokens
+        #[clap(long)]
+        size: Option<u64>,
+    },
 }
 
 #[serde_as]
@@ -268,6 +308,22 @@ pub enum Command {
         #[clap(subcommand)]
         subcmd: TestCommand,
     },
+
+    /// Save the state of all accounts owned by the Jet programs to a file
+    Snapshot {
+        /// The output file path for the snapshot
+        #[clap(long, short = 'o')]
+        output: PathBuf,
+    },
+
+    /// Show the differences between two snapshots
+    Diff {
+        /// The path to the earlier snapshot
+        before: PathBuf,
+
+        /// The path to the later snapshot
+        after: PathBuf,
+    },
 }
 
 pub async fn run(opts: CliOpts) -> Result<()> {
@@ -276,6 +332,7 @@ pub async fn run(opts: CliOpts) -> Result<()> {
         .map(solana_clap_utils::input_validators::normalize_to_url_if_moniker);
     let client_config = ClientConfig::new(
         opts.dry_run,
+        opts.simulate,
         opts.no_confirm,
         opts.signer_path,
         rpc_endpoint,
@@ -308,6 +365,10 @@ pub async fn run(opts: CliOpts) -> Result<()> {
         Command::MarginPool { subcmd } => run_margin_pool_command(&client, subcmd).await?,
         Command::Bonds { subcmd } => run_bonds_command(&client, subcmd).await?,
         Command::Test { subcmd } => run_test_command(&client, subcmd).await?,
+        Command::Snapshot { output } => {
+            actions::snapshot::process_snapshot(&client, &output).await?
+        }
+        Command::Diff { before, after } => actions::snapshot::process_diff(&before, &after).await?,
     };
 
     if let Some(proposal_id) = opts.target_proposal {
@@ -375,13 +436,16 @@ async fn run_margin_command(client: &Client, command: MarginCommand) -> Result<P
         MarginCommand::ListTopAccounts { limit } => {
             actions::margin::process_list_top_accounts(client, limit).await
         }
+        MarginCommand::MigrateMetadata { airspace } => {
+            actions::margin::process_migrate_metadata(client, &airspace).await
+        }
     }
 }
 
 async fn run_margin_pool_command(client: &Client, command: MarginPoolCommand) -> Result<Plan> {
     match command {
-        MarginPoolCommand::Create { token } => {
-            actions::margin_pool::process_create_pool(client, token).await
+        MarginPoolCommand::Create { token, airspace } => {
+            actions::margin_pool::process_create_pool(client, &airspace, token).await
         }
         MarginPoolCommand::Configure(options) => {
             actions::margin_pool::process_configure_pool(client, options).await
@@ -390,8 +454,8 @@ async fn run_margin_pool_command(client: &Client, command: MarginPoolCommand) ->
             actions::margin_pool::process_collect_pool_fees(client).await
         }
         MarginPoolCommand::List => actions::margin_pool::process_list_pools(client).await,
-        MarginPoolCommand::Show { token } => {
-            actions::margin_pool::process_show_pool(client, token).await
+        MarginPoolCommand::Show { token, airspace } => {
+            actions::margin_pool::process_show_pool(client, &airspace, token).await
         }
     }
 }
@@ -401,6 +465,12 @@ async fn run_bonds_command(client: &Client, command: BondsCommand) -> Result<Pla
         BondsCommand::CreateMarket(params) => {
             actions::bonds::process_create_bond_market(client, params).await
         }
+        BondsCommand::Configure(params) => {
+            actions::bonds::process_configure_bond_market(client, params).await
+        }
+        BondsCommand::ShowOrderbook { manager, size } => {
+            actions::bonds::process_show_orderbook(client, manager, size).await
+        }
     }
 }
 
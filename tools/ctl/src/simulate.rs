@@ -0,0 +1,161 @@
+use anyhow::{bail, Result};
+use solana_account_decoder::UiAccountEncoding;
+use solana_client::rpc_config::{
+    RpcSimulateTransactionAccountsConfig, RpcSimulateTransactionConfig,
+};
+use solana_sdk::{
+    account::Account,
+    commitment_config::{CommitmentConfig, CommitmentLevel},
+};
+
+use jet_margin_sdk::solana::logs::find_event_data;
+
+use crate::{
+    actions::snapshot::{decode_account, diff_values},
+    anchor_ix_parser::AnchorParser,
+    client::{Client, TransactionEntry},
+    governance::DEFAULT_IDLS,
+};
+
+/// Simulate each transaction in a plan, showing the logs, emitted events and changes
+/// to any accounts owned by the Jet programs.
+///
+/// Transactions are simulated independently against the current network state, so
+/// effects of earlier transactions in the plan are not visible to later ones.
+pub async fn simulate_plan(client: &Client, plan: &[TransactionEntry]) -> Result<()> {
+    let mut anchor_parser = AnchorParser::new(client.rpc());
+
+    for program in DEFAULT_IDLS.iter().chain(&[
+        jet_margin_sdk::jet_airspace::ID,
+        jet_margin_sdk::jet_bonds::ID,
+        jet_margin_sdk::jet_control::ID,
+    ]) {
+        if let Err(e) = anchor_parser.load_idl(program).await {
+            eprintln!("events from {program} will not be decoded: {e:?}");
+        }
+    }
+
+    let mut failed_count = 0;
+
+    for (i, entry) in plan.iter().enumerate() {
+        println!("transaction #{i}:");
+
+        for (j, step) in entry.steps.iter().enumerate() {
+            println!("\t [{j}] {step}");
+        }
+
+        if !simulate_entry(client, &anchor_parser, entry).await? {
+            failed_count += 1;
+        }
+
+        println!();
+    }
+
+    println!(
+        "simulated {} transactions, {failed_count} failed",
+        plan.len()
+    );
+
+    if failed_count > 0 {
+        bail!(
+            "{failed_count} of {} transactions failed in simulation",
+            plan.len()
+        );
+    }
+
+    Ok(())
+}
+
+async fn simulate_entry(
+    client: &Client,
+    anchor_parser: &AnchorParser<'_>,
+    entry: &TransactionEntry,
+) -> Result<bool> {
+    let message = entry.transaction.message();
+    let writable = message
+        .account_keys
+        .iter()
+        .enumerate()
+        .filter(|(index, _)| message.is_writable(*index))
+        .map(|(_, address)| *address)
+        .collect::<Vec<_>>();
+
+    let accounts_before = client.rpc().get_multiple_accounts(&writable).await?;
+    let result = client
+        .rpc()
+        .simulate_transaction_with_config(
+            &entry.transaction,
+            RpcSimulateTransactionConfig {
+                sig_verify: false,
+                replace_recent_blockhash: true,
+                commitment: Some(CommitmentConfig {
+                    commitment: CommitmentLevel::Processed,
+                }),
+                accounts: Some(RpcSimulateTransactionAccountsConfig {
+                    encoding: Some(UiAccountEncoding::Base64),
+                    addresses: writable.iter().map(|a| a.to_string()).collect(),
+                }),
+                ..Default::default()
+            },
+        )
+        .await?
+        .value;
+
+    let logs = result.logs.unwrap_or_default();
+
+    println!("\t logs:");
+    for line in &logs {
+        println!("\t\t {line}");
+    }
+
+    if let Some(e) = result.err {
+        println!("\t failed: {e}");
+        return Ok(false);
+    }
+
+    println!("\t events:");
+    for (program, data) in find_event_data(&logs) {
+        match anchor_parser.try_parse_event(&program, &data) {
+            Ok(event) => println!("\t\t {event:?}"),
+            Err(_) => println!(
+                "\t\t unknown event from {program}: {}",
+                base64::encode(&data)
+            ),
+        }
+    }
+
+    println!("\t account changes:");
+    let accounts_after = result.accounts.unwrap_or_default();
+
+    for (index, address) in writable.iter().enumerate() {
+        let before = accounts_before
+            .get(index)
+            .cloned()
+            .flatten()
+            .and_then(|account| decode_account(&account.data));
+        let after = accounts_after
+            .get(index)
+            .cloned()
+            .flatten()
+            .and_then(|account| account.decode::<Account>())
+            .and_then(|account| decode_account(&account.data));
+
+        match (before, after) {
+            (None, Some(created)) => println!("\t\t + {address} ({})", created.kind),
+            (Some(closed), None) => println!("\t\t - {address} ({})", closed.kind),
+            (Some(old), Some(new)) if old != new => {
+                println!("\t\t ~ {address} ({})", new.kind);
+
+                let mut changes = vec![];
+                diff_values("", &old.data, &new.data, &mut changes);
+
+                for change in changes {
+                    println!("\t\t\t {change}");
+                }
+            }
+            _ => (),
+        }
+    }
+
+    Ok(true)
+}
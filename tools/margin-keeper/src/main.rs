@@ -0,0 +1,284 @@
+use std::{
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+
+use anchor_lang::AccountDeserialize;
+use anyhow::{bail, Context, Result};
+use clap::Parser;
+use serde::{Deserialize, Serialize};
+use serde_with::{serde_as, DisplayFromStr};
+
+use jet_margin_sdk::{
+    ix_builder::{derive_airspace, MarginIxBuilder},
+    jet_margin::{self, MarginAccount},
+    margin_integrator::{PositionRefresher, RefreshingProxy},
+    solana::transaction::SendTransactionBuilder,
+    tx_builder::{bonds::BondsPositionRefresher, MarginTxBuilder},
+};
+use jet_proto_math::Number128;
+use jet_simulation::solana_rpc_api::{RpcConnection, SolanaRpcClient};
+use solana_clap_utils::input_validators::normalize_to_url_if_moniker;
+use solana_cli_config::{Config as SolanaConfig, CONFIG_FILE as SOLANA_CONFIG_FILE};
+use solana_sdk::{pubkey::Pubkey, signature::Keypair};
+
+#[derive(Parser, Debug)]
+pub struct CliOpts {
+    /// The path to the keeper configuration file
+    pub config_path: PathBuf,
+
+    /// The network endpoint to refresh accounts on
+    #[clap(long, short = 'u')]
+    pub rpc_endpoint: Option<String>,
+
+    /// The keypair to use for paying for refresh transactions
+    #[clap(long, short = 'k')]
+    pub keypair_path: Option<String>,
+
+    /// The interval between refresh cycles
+    #[clap(long,
+           short = 'i',
+           parse(try_from_str = parse_interval_duration),
+           default_value_t = default_interval_duration()
+    )]
+    pub interval: humantime::Duration,
+}
+
+/// The configuration for the keeper, read from a TOML file
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct KeeperConfig {
+    /// Accounts at or above this risk level get their positions refreshed
+    #[serde(default = "default_min_risk")]
+    pub min_risk: f64,
+
+    /// The maximum number of accounts to refresh in a single cycle
+    #[serde(default = "default_max_accounts")]
+    pub max_accounts: usize,
+
+    /// The airspaces with positions to be refreshed
+    #[serde(rename = "airspace")]
+    pub airspaces: Vec<AirspaceDefinition>,
+}
+
+#[serde_as]
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct AirspaceDefinition {
+    /// The seed used to derive the airspace address
+    pub name: String,
+
+    /// The bond markets within the airspace that margin accounts may hold positions in
+    #[serde(default)]
+    #[serde_as(as = "Vec<DisplayFromStr>")]
+    pub bond_markets: Vec<Pubkey>,
+}
+
+impl AirspaceDefinition {
+    fn address(&self) -> Pubkey {
+        derive_airspace(&self.name)
+    }
+}
+
+#[tokio::main]
+async fn main() {
+    let opts = CliOpts::parse();
+
+    if let Err(e) = run(opts).await {
+        eprintln!("error: ");
+
+        for err in e.chain() {
+            eprintln!("{err}");
+        }
+
+        eprintln!("{}", e.backtrace());
+        std::process::exit(1);
+    }
+}
+
+async fn run(opts: CliOpts) -> Result<()> {
+    let solana_config =
+        SolanaConfig::load(SOLANA_CONFIG_FILE.as_ref().unwrap()).unwrap_or_default();
+
+    let rpc_endpoint = normalize_to_url_if_moniker(
+        opts.rpc_endpoint
+            .unwrap_or_else(|| solana_config.json_rpc_url.clone()),
+    );
+    let keypair_path = PathBuf::from(
+        opts.keypair_path
+            .unwrap_or_else(|| solana_config.keypair_path.clone()),
+    );
+
+    if !keypair_path.exists() {
+        bail!("no keypair to use at {}", keypair_path.display())
+    }
+
+    let signer_data_json = std::fs::read_to_string(keypair_path)?;
+    let signer_data: Vec<u8> = serde_json::from_str(&signer_data_json)?;
+    let signer = Keypair::from_bytes(&signer_data)?;
+
+    let config = read_config_file(&opts.config_path).await?;
+    let rpc: Arc<dyn SolanaRpcClient> =
+        Arc::new(RpcConnection::new_optimistic(signer, &rpc_endpoint));
+
+    loop {
+        if let Err(e) = refresh_accounts(&rpc, &config).await {
+            eprintln!("failed refresh cycle: {e:?}");
+        }
+
+        tokio::time::sleep(opts.interval.into()).await;
+    }
+}
+
+async fn read_config_file(path: impl AsRef<Path>) -> Result<KeeperConfig> {
+    let file_content = tokio::fs::read_to_string(path.as_ref())
+        .await
+        .with_context(|| format!("trying to read {:?}", path.as_ref()))?;
+
+    Ok(toml::from_str(&file_content)?)
+}
+
+/// Find the margin accounts closest to being unhealthy, and refresh their positions
+async fn refresh_accounts(rpc: &Arc<dyn SolanaRpcClient>, config: &KeeperConfig) -> Result<()> {
+    let mut accounts = find_margin_accounts(rpc)
+        .await?
+        .into_iter()
+        .map(|(address, account)| (address, account_risk(&account), account))
+        .filter(|(_, risk, _)| *risk >= config.min_risk)
+        .collect::<Vec<_>>();
+
+    // most at-risk accounts first
+    accounts.sort_by(|(_, a, _), (_, b, _)| b.total_cmp(a));
+    accounts.truncate(config.max_accounts);
+
+    println!("refreshing {} accounts at risk", accounts.len());
+
+    for (address, risk, account) in accounts {
+        let refreshing = match account_refresher(rpc, config, &account).await {
+            Ok(refreshing) => refreshing,
+            Err(e) => {
+                eprintln!("could not create refresher for account {address}: {e:?}");
+                continue;
+            }
+        };
+
+        let transactions = match refreshing.refresh().await {
+            Ok(txs) => txs,
+            Err(e) => {
+                eprintln!("could not build refresh for account {address}: {e:?}");
+                continue;
+            }
+        };
+
+        if transactions.is_empty() {
+            continue;
+        }
+
+        match rpc.send_and_confirm_condensed(transactions).await {
+            Ok(_) => println!("refreshed account {address} (risk {risk:.4})"),
+            Err(e) => eprintln!("failed refreshing account {address}: {e:?}"),
+        }
+    }
+
+    Ok(())
+}
+
+async fn find_margin_accounts(
+    rpc: &Arc<dyn SolanaRpcClient>,
+) -> Result<Vec<(Pubkey, MarginAccount)>> {
+    let margin_account_size = 8 + std::mem::size_of::<MarginAccount>();
+    let all_accounts = rpc
+        .get_program_accounts(&jet_margin::ID, Some(margin_account_size))
+        .await?;
+
+    Ok(all_accounts
+        .into_iter()
+        .filter_map(|(address, account)| {
+            match MarginAccount::try_deserialize(&mut &account.data[..]) {
+                Ok(deserialized) => Some((address, deserialized)),
+                Err(_) => {
+                    eprintln!("could not deserialize margin account {address}");
+                    None
+                }
+            }
+        })
+        .collect())
+}
+
+/// Build a proxy that can refresh all the pool, deposit and bond positions of an account.
+///
+/// Pool and deposit positions are refreshed through the account's own airspace, while
+/// bond positions may be held in markets from any of the configured airspaces.
+async fn account_refresher(
+    rpc: &Arc<dyn SolanaRpcClient>,
+    config: &KeeperConfig,
+    account: &MarginAccount,
+) -> Result<RefreshingProxy<MarginIxBuilder>> {
+    let seed = u16::from_le_bytes(account.user_seed);
+    let proxy = MarginIxBuilder::new_with_payer_and_airspace(
+        account.owner,
+        seed,
+        account.owner,
+        account.airspace,
+        None,
+    );
+    let mut refreshers: Vec<Arc<dyn PositionRefresher>> =
+        vec![Arc::new(MarginTxBuilder::new_with_airspace(
+            rpc.clone(),
+            None,
+            account.owner,
+            seed,
+            account.airspace,
+        ))];
+
+    for airspace in &config.airspaces {
+        if !airspace.bond_markets.is_empty() {
+            refreshers.push(Arc::new(
+                BondsPositionRefresher::new(proxy.address, rpc.clone(), &airspace.bond_markets)
+                    .await?,
+            ));
+        }
+    }
+
+    Ok(RefreshingProxy { proxy, refreshers })
+}
+
+/// Estimate how close an account is to being unhealthy, based on the last known
+/// prices for its positions. The account is unhealthy once the risk reaches 1.
+fn account_risk(account: &MarginAccount) -> f64 {
+    let valuation = match account.valuation() {
+        Ok(valuation) => valuation,
+
+        // claims with stale prices can't be valued until they are refreshed
+        Err(_) => return f64::INFINITY,
+    };
+
+    if valuation.required_collateral == Number128::ZERO {
+        return 0.0;
+    }
+
+    if valuation.effective_collateral <= Number128::ZERO {
+        return f64::INFINITY;
+    }
+
+    (valuation.required_collateral / valuation.effective_collateral)
+        .to_string()
+        .parse::<f64>()
+        .unwrap_or(f64::INFINITY)
+}
+
+fn parse_interval_duration(arg: &str) -> Result<humantime::Duration> {
+    Ok(arg.parse::<humantime::Duration>().map(Into::into)?)
+}
+
+fn default_interval_duration() -> humantime::Duration {
+    std::time::Duration::from_secs(10).into()
+}
+
+fn default_min_risk() -> f64 {
+    0.8
+}
+
+fn default_max_accounts() -> usize {
+    50
+}
@@ -1,4 +1,5 @@
 use std::{
+    fs::OpenOptions,
     path::{Path, PathBuf},
     str::FromStr,
 };
@@ -7,6 +8,7 @@ use anchor_lang::{AccountDeserialize, Discriminator};
 use anyhow::{bail, Result};
 use clap::Parser;
 
+use jet_margin_sdk::jet_test_service::state::TokenInfo;
 use pyth_sdk_solana::state::ProductAccount;
 use solana_clap_utils::input_validators::normalize_to_url_if_moniker;
 use solana_cli_config::{Config as SolanaConfig, CONFIG_FILE as SOLANA_CONFIG_FILE};
@@ -16,10 +18,14 @@ use solana_client::{
     rpc_request::{RpcError, RpcResponseErrorData},
 };
 use solana_sdk::{
-    commitment_config::CommitmentConfig, hash::Hash, pubkey, pubkey::Pubkey, signature::Keypair,
-    signer::Signer, transaction::Transaction,
+    commitment_config::CommitmentConfig, hash::Hash, instruction::Instruction, pubkey,
+    pubkey::Pubkey, signature::Keypair, signer::Signer, transaction::Transaction,
 };
 
+use replay::PricePoint;
+
+mod replay;
+
 const PYTH_DEVNET_PROGRAM: Pubkey = pubkey!("gSbePebfvPy7tRqimPoVecS2UsBvYv46ynrzWocc92s");
 const PYTH_MAINNET_PROGRAM: Pubkey = pubkey!("FsJ3A3u2vn5cTVofAjvy6y5kwABJAqYWpe4975bi2epH");
 
@@ -29,8 +35,8 @@ const DEVNET_HASH: &str = "EtWTRABZaYq6iMfeYKouRu166VU2xqa1wcaWoxPkrZBG";
 #[derive(Parser, Debug)]
 pub struct CliOpts {
     /// The network endpoint to use for reading price oracles
-    #[clap(long, short = 's')]
-    pub source_endpoint: String,
+    #[clap(long, short = 's', required_unless_present = "replay")]
+    pub source_endpoint: Option<String>,
 
     /// The network endpoint to publish prices onto
     #[clap(long, short = 't')]
@@ -47,6 +53,19 @@ pub struct CliOpts {
            default_value_t = default_interval_duration()
     )]
     pub interval: humantime::Duration,
+
+    /// Append the mirrored prices to a CSV file, which can be replayed later
+    #[clap(long, conflicts_with = "replay")]
+    pub record: Option<PathBuf>,
+
+    /// Instead of mirroring a source network, replay a recorded price path
+    /// from a CSV or JSON file
+    #[clap(long)]
+    pub replay: Option<PathBuf>,
+
+    /// The speed to replay prices at, relative to the recorded timestamps
+    #[clap(long, default_value_t = 1.0)]
+    pub speed: f64,
 }
 
 #[tokio::main]
@@ -65,7 +84,6 @@ async fn main() {
 }
 
 async fn run(opts: CliOpts) -> Result<()> {
-    let source_endpoint = normalize_to_url_if_moniker(opts.source_endpoint);
     let target_endpoint = normalize_to_url_if_moniker(opts.target_endpoint);
     let keypair_path = opts.keypair_path.unwrap_or_else(|| {
         let solana_config =
@@ -84,17 +102,28 @@ async fn run(opts: CliOpts) -> Result<()> {
     let signer_data: Vec<u8> = serde_json::from_str(&signer_data_json)?;
     let signer = Keypair::from_bytes(&signer_data)?;
 
-    let source_client =
-        RpcClient::new_with_commitment(source_endpoint, CommitmentConfig::processed());
     let target_client =
         RpcClient::new_with_commitment(target_endpoint, CommitmentConfig::processed());
 
+    if let Some(replay_path) = opts.replay {
+        return replay::replay_prices(&target_client, &signer, &replay_path, opts.speed).await;
+    }
+
+    let source_endpoint = normalize_to_url_if_moniker(opts.source_endpoint.unwrap());
+    let source_client =
+        RpcClient::new_with_commitment(source_endpoint, CommitmentConfig::processed());
+
     let oracle_list = discover_oracles(&source_client, &target_client).await?;
 
+    let mut recording = opts.record.map(PriceRecording::open).transpose()?;
     let mut id_file = None;
 
     loop {
-        sync_oracles(&source_client, &target_client, &signer, &oracle_list).await?;
+        let prices = sync_oracles(&source_client, &target_client, &signer, &oracle_list).await?;
+
+        if let Some(recording) = &mut recording {
+            recording.append(&prices)?;
+        }
 
         if id_file.is_none() {
             id_file = Some(RunningProcessIdFile::new());
@@ -107,6 +136,7 @@ async fn run(opts: CliOpts) -> Result<()> {
 struct OracleInfo {
     source_oracle: Pubkey,
     target_mint: Pubkey,
+    symbol: String,
 }
 
 async fn sync_oracles(
@@ -114,7 +144,9 @@ async fn sync_oracles(
     target: &RpcClient,
     signer: &Keypair,
     oracles: &[OracleInfo],
-) -> Result<()> {
+) -> Result<Vec<PricePoint>> {
+    let mut prices = Vec::with_capacity(oracles.len());
+
     for oracle in oracles {
         let source_account = source.get_account_data(&oracle.source_oracle).await?;
         let source_price = pyth_sdk_solana::state::load_price_account(&source_account)?;
@@ -124,29 +156,47 @@ async fn sync_oracles(
             &oracle.target_mint,
             source_price.agg.price,
             source_price.agg.conf as i64,
+            source_price.ema_price.val,
+            source_price.ema_conf.val,
             source_price.expo,
         );
 
-        let recent_blockhash = target.get_latest_blockhash().await?;
-        let update_price_tx = Transaction::new_signed_with_payer(
-            &[update_target_ix],
-            Some(&signer.pubkey()),
-            &[signer],
-            recent_blockhash,
-        );
+        send_price_update(target, signer, update_target_ix).await?;
+
+        prices.push(PricePoint {
+            timestamp: source_price.timestamp,
+            symbol: oracle.symbol.clone(),
+            price: source_price.agg.price,
+            conf: source_price.agg.conf as i64,
+            expo: source_price.expo,
+            ema_price: Some(source_price.ema_price.val),
+            ema_conf: Some(source_price.ema_conf.val),
+        });
+    }
 
-        match target.send_and_confirm_transaction(&update_price_tx).await {
-            Ok(_) => (),
-            Err(e) => {
-                eprintln!("{e}");
-
-                if let ClientErrorKind::RpcError(RpcError::RpcResponseError {
-                    data: RpcResponseErrorData::SendTransactionPreflightFailure(failure),
-                    ..
-                }) = e.kind()
-                {
-                    eprintln!("{:#?}", failure.logs);
-                }
+    Ok(prices)
+}
+
+async fn send_price_update(target: &RpcClient, signer: &Keypair, ix: Instruction) -> Result<()> {
+    let recent_blockhash = target.get_latest_blockhash().await?;
+    let update_price_tx = Transaction::new_signed_with_payer(
+        &[ix],
+        Some(&signer.pubkey()),
+        &[signer],
+        recent_blockhash,
+    );
+
+    match target.send_and_confirm_transaction(&update_price_tx).await {
+        Ok(_) => (),
+        Err(e) => {
+            eprintln!("{e}");
+
+            if let ClientErrorKind::RpcError(RpcError::RpcResponseError {
+                data: RpcResponseErrorData::SendTransactionPreflightFailure(failure),
+                ..
+            }) = e.kind()
+            {
+                eprintln!("{:#?}", failure.logs);
             }
         }
     }
@@ -154,20 +204,16 @@ async fn sync_oracles(
     Ok(())
 }
 
-async fn discover_oracles(source: &RpcClient, target: &RpcClient) -> Result<Vec<OracleInfo>> {
-    use jet_margin_sdk::jet_test_service::state::TokenInfo;
-
-    // Find all the tokens in the target in need of price data
+/// Find all the tokens in the target in need of price data
+async fn find_target_tokens(target: &RpcClient) -> Result<Vec<(Pubkey, TokenInfo)>> {
     let target_test_accounts = target
         .get_program_accounts(&jet_margin_sdk::jet_test_service::ID)
         .await?;
 
-    let target_token_infos = target_test_accounts
+    Ok(target_test_accounts
         .into_iter()
         .filter_map(|(address, account)| {
-            let discriminator = jet_margin_sdk::jet_test_service::state::TokenInfo::discriminator();
-
-            if account.data[..8] != discriminator {
+            if account.data[..8] != TokenInfo::discriminator() {
                 return None;
             }
 
@@ -175,7 +221,11 @@ async fn discover_oracles(source: &RpcClient, target: &RpcClient) -> Result<Vec<
 
             Some((address, info))
         })
-        .collect::<Vec<_>>();
+        .collect())
+}
+
+async fn discover_oracles(source: &RpcClient, target: &RpcClient) -> Result<Vec<OracleInfo>> {
+    let target_token_infos = find_target_tokens(target).await?;
 
     // Load all the pyth products available in the source network
     let pyth_program_id = get_pyth_program_id(source).await?;
@@ -208,6 +258,7 @@ async fn discover_oracles(source: &RpcClient, target: &RpcClient) -> Result<Vec<
                         Some(OracleInfo {
                             source_oracle: product.px_acc,
                             target_mint: info.mint,
+                            symbol: info.symbol.clone(),
                         })
                     }
 
@@ -272,6 +323,34 @@ impl PythAttributeGetter for ProductAccount {
     }
 }
 
+/// A CSV file that mirrored prices are appended to
+struct PriceRecording {
+    writer: csv::Writer<std::fs::File>,
+}
+
+impl PriceRecording {
+    fn open(path: PathBuf) -> Result<Self> {
+        let file = OpenOptions::new().create(true).append(true).open(&path)?;
+
+        // the header is written before the first row of a new file
+        let is_empty = file.metadata()?.len() == 0;
+        let writer = csv::WriterBuilder::new()
+            .has_headers(is_empty)
+            .from_writer(file);
+
+        Ok(Self { writer })
+    }
+
+    fn append(&mut self, prices: &[PricePoint]) -> Result<()> {
+        for price in prices {
+            self.writer.serialize(price)?;
+        }
+        self.writer.flush()?;
+
+        Ok(())
+    }
+}
+
 struct RunningProcessIdFile;
 
 impl RunningProcessIdFile {
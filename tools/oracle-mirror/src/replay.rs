@@ -0,0 +1,259 @@
+use std::{collections::HashMap, path::Path, time::Duration};
+
+use anyhow::{bail, Context, Result};
+use serde::{Deserialize, Serialize};
+
+use solana_client::nonblocking::rpc_client::RpcClient;
+use solana_sdk::{pubkey::Pubkey, signature::Keypair, signer::Signer};
+
+use crate::{find_target_tokens, send_price_update, RunningProcessIdFile};
+
+/// A single recorded price for a token
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct PricePoint {
+    /// The unix timestamp the price was observed at
+    pub timestamp: i64,
+
+    /// The symbol of the token in the test service
+    pub symbol: String,
+
+    pub price: i64,
+    pub conf: i64,
+    pub expo: i32,
+
+    /// The EMA price, which defaults to the price when not recorded
+    #[serde(default)]
+    pub ema_price: Option<i64>,
+
+    /// The EMA confidence, which defaults to the confidence when not recorded
+    #[serde(default)]
+    pub ema_conf: Option<i64>,
+}
+
+/// Load a recorded price path, as either a JSON array or a CSV file with a header row
+pub fn load_price_path(path: &Path) -> Result<Vec<PricePoint>> {
+    let content = std::fs::read_to_string(path)
+        .with_context(|| format!("could not read price path {}", path.display()))?;
+
+    let mut points = match path.extension().and_then(|ext| ext.to_str()) {
+        Some("json") => serde_json::from_str::<Vec<PricePoint>>(&content)?,
+        Some("csv") => parse_csv(&content)?,
+        _ => bail!(
+            "price path must be a .json or .csv file: {}",
+            path.display()
+        ),
+    };
+
+    points.sort_by_key(|point| point.timestamp);
+
+    Ok(points)
+}
+
+/// Read a CSV price path, where the columns are named by the header row and
+/// lines starting with `#` are ignored
+fn parse_csv(content: &str) -> Result<Vec<PricePoint>> {
+    csv::ReaderBuilder::new()
+        .comment(Some(b'#'))
+        .trim(csv::Trim::All)
+        .from_reader(content.as_bytes())
+        .deserialize::<PricePoint>()
+        .map(|row| row.context("invalid price"))
+        .collect()
+}
+
+/// The time to wait between two recorded prices when replaying at the `speed`
+fn replay_delay(elapsed: i64, speed: f64) -> Result<Duration> {
+    let seconds = elapsed.max(0) as f64 / speed;
+
+    // Duration::from_secs_f64 panics on values it can't represent
+    if !seconds.is_finite() || seconds >= u64::MAX as f64 {
+        bail!("replay speed {speed} is too slow to wait {elapsed} seconds between prices");
+    }
+
+    Ok(Duration::from_secs_f64(seconds))
+}
+
+/// Play a recorded price path onto the target network, waiting between each
+/// price for the recorded time difference divided by the `speed`.
+pub async fn replay_prices(
+    target: &RpcClient,
+    signer: &Keypair,
+    path: &Path,
+    speed: f64,
+) -> Result<()> {
+    if speed.is_nan() || speed <= 0.0 {
+        bail!("replay speed must be positive, got {speed}");
+    }
+
+    let points = load_price_path(path)?;
+    let mints = find_target_tokens(target)
+        .await?
+        .into_iter()
+        .map(|(_, info)| (info.symbol, info.mint))
+        .collect::<HashMap<String, Pubkey>>();
+
+    let mut unknown = points
+        .iter()
+        .map(|point| point.symbol.as_str())
+        .filter(|symbol| !mints.contains_key(*symbol))
+        .collect::<Vec<_>>();
+    unknown.sort_unstable();
+    unknown.dedup();
+
+    if !unknown.is_empty() {
+        bail!("no tokens in target for symbols: {}", unknown.join(", "));
+    }
+
+    println!("replaying {} prices from {}", points.len(), path.display());
+
+    let mut id_file = None;
+    let mut last_timestamp = None;
+
+    for point in points {
+        if let Some(last) = last_timestamp {
+            let delay = replay_delay(point.timestamp.saturating_sub(last), speed)?;
+            tokio::time::sleep(delay).await;
+        }
+
+        last_timestamp = Some(point.timestamp);
+
+        let update_target_ix = jet_margin_sdk::ix_builder::test_service::token_update_pyth_price(
+            &signer.pubkey(),
+            &mints[&point.symbol],
+            point.price,
+            point.conf,
+            point.ema_price.unwrap_or(point.price),
+            point.ema_conf.unwrap_or(point.conf),
+            point.expo,
+        );
+
+        send_price_update(target, signer, update_target_ix).await?;
+
+        if id_file.is_none() {
+            id_file = Some(RunningProcessIdFile::new());
+        }
+    }
+
+    println!("replay complete");
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn point(timestamp: i64, symbol: &str, ema: Option<(i64, i64)>) -> PricePoint {
+        PricePoint {
+            timestamp,
+            symbol: symbol.to_owned(),
+            price: 2_000,
+            conf: 10,
+            expo: -2,
+            ema_price: ema.map(|(price, _)| price),
+            ema_conf: ema.map(|(_, conf)| conf),
+        }
+    }
+
+    const HEADER: &str = "timestamp,symbol,price,conf,expo,ema_price,ema_conf";
+
+    fn write_csv(points: &[PricePoint]) -> String {
+        let mut writer = csv::Writer::from_writer(vec![]);
+        for point in points {
+            writer.serialize(point).unwrap();
+        }
+
+        String::from_utf8(writer.into_inner().unwrap()).unwrap()
+    }
+
+    #[test]
+    fn parse_csv_reads_optional_ema_columns() {
+        let content = "\
+# recorded from devnet
+timestamp,symbol,price,conf,expo,ema_price,ema_conf
+
+1,SOL,2000,10,-2,,
+# a comment between rows
+2, SOL , 2000, 10, -2, 1990, 12
+";
+
+        assert_eq!(
+            parse_csv(content).unwrap(),
+            vec![point(1, "SOL", None), point(2, "SOL", Some((1990, 12)))]
+        );
+    }
+
+    #[test]
+    fn parse_csv_allows_missing_ema_columns() {
+        let content = "timestamp,symbol,price,conf,expo\n1,SOL,2000,10,-2";
+
+        assert_eq!(parse_csv(content).unwrap(), vec![point(1, "SOL", None)]);
+    }
+
+    #[test]
+    fn parse_csv_without_rows_is_empty() {
+        assert!(parse_csv("").unwrap().is_empty());
+        assert!(parse_csv("# nothing here\n\n").unwrap().is_empty());
+        assert!(parse_csv(HEADER).unwrap().is_empty());
+    }
+
+    #[test]
+    fn parse_csv_rejects_bad_rows() {
+        let parse_row = |row: &str| parse_csv(&format!("{HEADER}\n{row}"));
+
+        assert!(parse_row("1,SOL,lots,10,-2,,").is_err());
+        assert!(parse_row("1,SOL,2000").is_err());
+        assert!(parse_row("1,SOL,2000,10,-2,,,extra").is_err());
+        assert!(parse_row("1,\"SOL,2000,10,-2,,").is_err());
+    }
+
+    #[test]
+    fn csv_rows_round_trip_with_escaped_symbols() {
+        let points = vec![
+            point(1, "SOL", None),
+            point(2, "a, \"quoted\" symbol", Some((1990, 12))),
+            point(3, "100", None),
+        ];
+        let content = write_csv(&points);
+
+        assert!(content.starts_with(HEADER));
+        assert_eq!(parse_csv(&content).unwrap(), points);
+    }
+
+    #[test]
+    fn load_price_path_sorts_by_timestamp() {
+        let path = std::env::temp_dir().join(format!("price-path-{}.csv", std::process::id()));
+        std::fs::write(
+            &path,
+            format!("{HEADER}\n2,SOL,2000,10,-2\n1,SOL,2000,10,-2"),
+        )
+        .unwrap();
+
+        let points = load_price_path(&path);
+        std::fs::remove_file(&path).unwrap();
+
+        assert_eq!(
+            points.unwrap(),
+            vec![point(1, "SOL", None), point(2, "SOL", None)]
+        );
+    }
+
+    #[test]
+    fn load_price_path_requires_a_known_extension() {
+        let path = std::env::temp_dir().join(format!("price-path-{}.txt", std::process::id()));
+        std::fs::write(&path, HEADER).unwrap();
+
+        let points = load_price_path(&path);
+        std::fs::remove_file(&path).unwrap();
+
+        assert!(points.is_err());
+    }
+
+    #[test]
+    fn replay_delay_is_checked() {
+        assert_eq!(replay_delay(10, 2.0).unwrap(), Duration::from_secs(5));
+        assert_eq!(replay_delay(-10, 2.0).unwrap(), Duration::ZERO);
+        assert!(replay_delay(10, f64::MIN_POSITIVE).is_err());
+        assert!(replay_delay(i64::MAX, 1e-3).is_err());
+    }
+}